@@ -0,0 +1,50 @@
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsField},
+    traits::{AsBytes, ByteConversion},
+};
+
+/// A transcript where every message and challenge is bound to a label, in the style of Merlin.
+/// Protocols composed together (e.g. PLONK and the KZG openings it makes) use distinct labels, so
+/// their messages can't be mistaken for each other.
+pub trait IsLabeledTranscript<F: IsField>: Sized {
+    /// Appends `message` to the transcript under `label`.
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]);
+    /// Fills `dest` with challenge bytes bound to `label` and to every previous operation.
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]);
+    /// Returns a transcript for a sub-protocol. Both the returned transcript and `self` are bound
+    /// to their common history and to `label`, but they diverge from each other from then on.
+    fn fork(&mut self, label: &'static [u8]) -> Self;
+
+    /// Appends a field element to the transcript under `label`.
+    fn append_field_element(&mut self, label: &'static [u8], element: &FieldElement<F>)
+    where
+        FieldElement<F>: ByteConversion,
+    {
+        self.append_message(label, &element.to_bytes_be());
+    }
+
+    /// Appends a curve point, or any other group element such as a commitment, under `label`.
+    fn append_point<P: AsBytes>(&mut self, label: &'static [u8], point: &P) {
+        self.append_message(label, &point.as_bytes());
+    }
+
+    /// Returns a field element sampled under `label`. It is reduced from 128 bits more than the
+    /// size of the field, so its distribution is statistically close to uniform.
+    fn challenge_scalar(&mut self, label: &'static [u8]) -> FieldElement<F>
+    where
+        FieldElement<F>: ByteConversion,
+    {
+        // At least 16 bytes more than a field element, in limbs of 8 bytes.
+        let num_limbs = FieldElement::<F>::zero().to_bytes_be().len() / 8 + 3;
+        let mut bytes = vec![0u8; num_limbs * 8];
+        self.challenge_bytes(label, &mut bytes);
+
+        let two_to_the_64 = FieldElement::<F>::from(u64::MAX) + FieldElement::<F>::one();
+        bytes
+            .chunks_exact(8)
+            .fold(FieldElement::<F>::zero(), |acc, limb| {
+                acc * &two_to_the_64
+                    + FieldElement::from(u64::from_be_bytes(limb.try_into().unwrap()))
+            })
+    }
+}
@@ -0,0 +1,200 @@
+use super::is_labeled_transcript::IsLabeledTranscript;
+use core::marker::PhantomData;
+use lambdaworks_math::field::traits::IsField;
+use sha3::{Digest, Keccak256};
+
+const DOMAIN_SEPARATOR: u8 = 0;
+const MESSAGE: u8 = 1;
+const CHALLENGE: u8 = 2;
+const FORK: u8 = 3;
+
+/// A labeled transcript over Keccak256. Each operation is absorbed together with its kind, its
+/// label and the length of its data, so no two different sequences of operations hash the same.
+/// Sampling a challenge absorbs its output back, making later challenges depend on it.
+pub struct LabeledTranscript<F: IsField> {
+    hasher: Keccak256,
+    phantom: PhantomData<F>,
+}
+
+impl<F: IsField> Clone for LabeledTranscript<F> {
+    fn clone(&self) -> Self {
+        Self {
+            hasher: self.hasher.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<F: IsField> LabeledTranscript<F> {
+    /// Starts a transcript for the protocol named `domain_separator`.
+    pub fn new(domain_separator: &'static [u8]) -> Self {
+        let mut transcript = Self {
+            hasher: Keccak256::new(),
+            phantom: PhantomData,
+        };
+        transcript.absorb(DOMAIN_SEPARATOR, domain_separator, &[]);
+        transcript
+    }
+
+    fn absorb(&mut self, operation: u8, label: &[u8], data: &[u8]) {
+        self.hasher.update([operation]);
+        self.hasher.update((label.len() as u32).to_le_bytes());
+        self.hasher.update(label);
+        self.hasher.update((data.len() as u64).to_le_bytes());
+        self.hasher.update(data);
+    }
+}
+
+impl<F: IsField> IsLabeledTranscript<F> for LabeledTranscript<F> {
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        self.absorb(MESSAGE, label, message);
+    }
+
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+        self.absorb(CHALLENGE, label, &(dest.len() as u64).to_le_bytes());
+        let seed = self.hasher.clone().finalize();
+        for (i, chunk) in dest.chunks_mut(32).enumerate() {
+            let block = Keccak256::new()
+                .chain_update(seed)
+                .chain_update((i as u64).to_le_bytes())
+                .finalize();
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+        self.hasher.update(seed);
+    }
+
+    fn fork(&mut self, label: &'static [u8]) -> Self {
+        let mut child = self.clone();
+        self.absorb(FORK, label, &[0]);
+        child.absorb(FORK, label, &[1]);
+        child
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lambdaworks_math::{
+        elliptic_curve::{
+            short_weierstrass::curves::bls12_381::{curve::BLS12381Curve, default_types::FrField},
+            traits::IsEllipticCurve,
+        },
+        field::{
+            element::FieldElement,
+            fields::fft_friendly::{
+                babybear::Babybear31PrimeField, stark_252_prime_field::Stark252PrimeField,
+            },
+        },
+    };
+
+    type Transcript = LabeledTranscript<FrField>;
+
+    fn challenge(transcript: &mut Transcript) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        transcript.challenge_bytes(b"challenge", &mut bytes);
+        bytes
+    }
+
+    #[test]
+    fn transcripts_with_the_same_operations_agree() {
+        let mut first = Transcript::new(b"protocol");
+        let mut second = Transcript::new(b"protocol");
+        first.append_message(b"message", b"hello");
+        second.append_message(b"message", b"hello");
+        assert_eq!(challenge(&mut first), challenge(&mut second));
+        assert_eq!(
+            first.challenge_scalar(b"scalar"),
+            second.challenge_scalar(b"scalar")
+        );
+    }
+
+    #[test]
+    fn domain_separators_labels_and_framing_change_the_challenges() {
+        let mut transcript = Transcript::new(b"protocol");
+        transcript.append_message(b"message", b"hello");
+        let expected = challenge(&mut transcript);
+
+        let mut other_protocol = Transcript::new(b"other protocol");
+        other_protocol.append_message(b"message", b"hello");
+        assert_ne!(challenge(&mut other_protocol), expected);
+
+        let mut other_label = Transcript::new(b"protocol");
+        other_label.append_message(b"other message", b"hello");
+        assert_ne!(challenge(&mut other_label), expected);
+
+        let mut split = Transcript::new(b"protocol");
+        split.append_message(b"message", b"hel");
+        split.append_message(b"message", b"lo");
+        assert_ne!(challenge(&mut split), expected);
+
+        let mut other_challenge_label = Transcript::new(b"protocol");
+        other_challenge_label.append_message(b"message", b"hello");
+        let mut bytes = [0u8; 32];
+        other_challenge_label.challenge_bytes(b"other challenge", &mut bytes);
+        assert_ne!(bytes, expected);
+    }
+
+    #[test]
+    fn consecutive_challenges_differ() {
+        let mut transcript = Transcript::new(b"protocol");
+        assert_ne!(challenge(&mut transcript), challenge(&mut transcript));
+    }
+
+    #[test]
+    fn challenge_bytes_of_any_length() {
+        let mut transcript = Transcript::new(b"protocol");
+        let mut bytes = [0u8; 100];
+        transcript.challenge_bytes(b"challenge", &mut bytes);
+        assert!(bytes[64..].iter().any(|byte| *byte != 0));
+        assert_ne!(bytes[..32], bytes[32..64]);
+    }
+
+    #[test]
+    fn forks_diverge_from_their_parent_and_from_each_other() {
+        let mut parent = Transcript::new(b"protocol");
+        parent.append_message(b"message", b"hello");
+        let mut reference = parent.clone();
+
+        let mut child = parent.fork(b"sub-protocol");
+        let mut other_child = reference.fork(b"other sub-protocol");
+
+        let parent_challenge = challenge(&mut parent);
+        let child_challenge = challenge(&mut child);
+        assert_ne!(parent_challenge, child_challenge);
+        assert_ne!(challenge(&mut other_child), child_challenge);
+        assert_ne!(challenge(&mut reference), parent_challenge);
+    }
+
+    #[test]
+    fn points_and_field_elements_are_appended_as_their_bytes() {
+        let point = BLS12381Curve::generator();
+        let element = FieldElement::<FrField>::from(42);
+
+        let mut transcript = Transcript::new(b"protocol");
+        transcript.append_point(b"point", &point);
+        transcript.append_field_element(b"element", &element);
+
+        let mut expected = Transcript::new(b"protocol");
+        expected.append_message(
+            b"point",
+            &lambdaworks_math::traits::AsBytes::as_bytes(&point),
+        );
+        expected.append_message(
+            b"element",
+            &lambdaworks_math::traits::ByteConversion::to_bytes_be(&element),
+        );
+
+        assert_eq!(challenge(&mut transcript), challenge(&mut expected));
+    }
+
+    #[test]
+    fn challenge_scalars_of_fields_of_any_size() {
+        let mut transcript = LabeledTranscript::<Babybear31PrimeField>::new(b"protocol");
+        let first = transcript.challenge_scalar(b"scalar");
+        assert_ne!(first, transcript.challenge_scalar(b"scalar"));
+
+        let mut transcript = LabeledTranscript::<Stark252PrimeField>::new(b"protocol");
+        let first = transcript.challenge_scalar(b"scalar");
+        assert_ne!(first, transcript.challenge_scalar(b"scalar"));
+    }
+}
@@ -2,5 +2,6 @@ pub mod hash_to_field;
 pub mod monolith;
 pub mod pedersen;
 pub mod poseidon;
+pub mod poseidon2;
 pub mod rescue_prime;
 pub mod sha3;
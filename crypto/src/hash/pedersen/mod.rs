@@ -8,8 +8,7 @@ use lambdaworks_math::{
 
 mod constants;
 mod parameters;
-use parameters::PedersenParameters;
-pub use parameters::PedersenStarkCurve;
+pub use parameters::{PedersenParameters, PedersenStarkCurve};
 
 mod private {
     use super::*;
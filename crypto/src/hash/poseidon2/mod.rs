@@ -0,0 +1,269 @@
+mod parameters;
+
+use lambdaworks_math::field::{
+    element::FieldElement, fields::fft_friendly::babybear::Babybear31PrimeField,
+};
+use parameters::{
+    FINAL_EXTERNAL_ROUND_CONSTANTS, INITIAL_EXTERNAL_ROUND_CONSTANTS, INTERNAL_DIAGONAL,
+    INTERNAL_ROUND_CONSTANTS,
+};
+
+pub type Fp = FieldElement<Babybear31PrimeField>;
+
+// Implementation of the Poseidon2 permutation over BabyBear with a state of 16 elements.
+// https://eprint.iacr.org/2023/323
+// The parameters are those of `default_babybear_poseidon2_16` in Plonky3: the S-box is x^7, there are
+// 8 external rounds, half of them before the 13 internal rounds, the external matrix is
+// circ(2 * M4, M4, M4, M4) with the matrix M4 of Plonky3, and the round constants are the ones of the
+// reference implementation of Horizen Labs.
+
+/// Number of elements of the state of the permutation.
+pub const WIDTH: usize = 16;
+
+pub struct Poseidon2BabyBear;
+
+impl Poseidon2BabyBear {
+    /// Applies the permutation to `state`.
+    pub fn permutation(state: &mut [Fp; WIDTH]) {
+        Self::external_linear_layer(state);
+        for constants in INITIAL_EXTERNAL_ROUND_CONSTANTS.chunks_exact(WIDTH) {
+            Self::external_round(state, constants);
+        }
+        for constant in INTERNAL_ROUND_CONSTANTS.iter() {
+            state[0] = Self::sbox(&(&state[0] + constant));
+            Self::internal_linear_layer(state);
+        }
+        for constants in FINAL_EXTERNAL_ROUND_CONSTANTS.chunks_exact(WIDTH) {
+            Self::external_round(state, constants);
+        }
+    }
+
+    fn sbox(x: &Fp) -> Fp {
+        let x2 = x.square();
+        let x3 = &x2 * x;
+        x3 * x2.square()
+    }
+
+    fn external_round(state: &mut [Fp; WIDTH], constants: &[Fp]) {
+        for (element, constant) in state.iter_mut().zip(constants) {
+            *element = Self::sbox(&(&*element + constant));
+        }
+        Self::external_linear_layer(state);
+    }
+
+    /// Multiplies every chunk of 4 elements by M4 and adds to each the sum of the chunks.
+    fn external_linear_layer(state: &mut [Fp; WIDTH]) {
+        for chunk in state.chunks_exact_mut(4) {
+            Self::apply_m4(chunk);
+        }
+        let sums: [Fp; 4] = core::array::from_fn(|i| {
+            state
+                .iter()
+                .skip(i)
+                .step_by(4)
+                .fold(Fp::zero(), |a, b| a + b)
+        });
+        for (i, element) in state.iter_mut().enumerate() {
+            *element = &*element + &sums[i % 4];
+        }
+    }
+
+    /// Multiplies `x` by the matrix
+    /// ```text
+    /// [2 3 1 1]
+    /// [1 2 3 1]
+    /// [1 1 2 3]
+    /// [3 1 1 2]
+    /// ```
+    /// with additions only.
+    fn apply_m4(x: &mut [Fp]) {
+        let t01 = &x[0] + &x[1];
+        let t23 = &x[2] + &x[3];
+        let t0123 = &t01 + &t23;
+        let t01123 = &t0123 + &x[1];
+        let t01233 = &t0123 + &x[3];
+        x[3] = &t01233 + x[0].double();
+        x[1] = &t01123 + x[2].double();
+        x[0] = t01123 + t01;
+        x[2] = t01233 + t23;
+    }
+
+    /// Multiplies the state by the matrix `1 + diag(INTERNAL_DIAGONAL)`.
+    fn internal_linear_layer(state: &mut [Fp; WIDTH]) {
+        let sum = state.iter().fold(Fp::zero(), |a, b| a + b);
+        for (element, diagonal) in state.iter_mut().zip(INTERNAL_DIAGONAL.iter()) {
+            *element = &sum + &*element * diagonal;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use lambdaworks_math::{traits::ByteConversion, unsigned_integer::element::U256};
+
+    /// The Grain LFSR the reference implementations use to generate the round constants, in its
+    /// self-shrinking mode.
+    struct Grain {
+        state: Vec<bool>,
+    }
+
+    impl Grain {
+        /// Seeds the LFSR for a prime field of `field_bits` bits, the S-box x^alpha, a width of
+        /// `width`, and `full_rounds` external and `partial_rounds` internal rounds.
+        fn new(field_bits: u64, width: u64, full_rounds: u64, partial_rounds: u64) -> Self {
+            let fields: [(u64, usize); 7] = [
+                (1, 2),
+                (0, 4),
+                (field_bits, 12),
+                (width, 12),
+                (full_rounds, 10),
+                (partial_rounds, 10),
+                ((1 << 30) - 1, 30),
+            ];
+            let state = fields
+                .iter()
+                .flat_map(|&(value, bits)| (0..bits).rev().map(move |i| (value >> i) & 1 == 1))
+                .collect();
+            let mut grain = Self { state };
+            for _ in 0..160 {
+                grain.step();
+            }
+            grain
+        }
+
+        fn step(&mut self) -> bool {
+            let bit = [62, 51, 38, 23, 13, 0]
+                .iter()
+                .fold(false, |acc, &i| acc ^ self.state[i]);
+            self.state.remove(0);
+            self.state.push(bit);
+            bit
+        }
+
+        fn next_bit(&mut self) -> bool {
+            loop {
+                let keep = self.step();
+                let bit = self.step();
+                if keep {
+                    return bit;
+                }
+            }
+        }
+
+        /// Samples a big-endian integer of `bits` bits.
+        fn next_integer(&mut self, bits: usize) -> U256 {
+            let mut bytes = [0u8; 32];
+            for i in (0..bits).rev() {
+                if self.next_bit() {
+                    bytes[31 - i / 8] |= 1 << (i % 8);
+                }
+            }
+            U256::from_bytes_be(&bytes).unwrap()
+        }
+
+        /// Samples an integer of `bits` bits smaller than `modulus`.
+        fn next_element(&mut self, bits: usize, modulus: &U256) -> U256 {
+            loop {
+                let value = self.next_integer(bits);
+                if value < *modulus {
+                    return value;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn grain_matches_the_poseidon_constants_over_bn254() {
+        // The first round constant of Poseidon over BN254 with a width of 3, 8 full and 57 partial
+        // rounds, as published with the reference implementation and used by circomlib.
+        let modulus = U256::from_hex_unchecked(
+            "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+        );
+        let mut grain = Grain::new(254, 3, 8, 57);
+        assert_eq!(
+            grain.next_element(254, &modulus),
+            U256::from_hex_unchecked(
+                "0ee9a592ba9a9518d05986d656f40c2114c4993c11bb29938d21d47304cd8e6e"
+            )
+        );
+    }
+
+    #[test]
+    fn round_constants_are_generated_with_grain() {
+        let modulus = U256::from_u64(2013265921);
+        let mut grain = Grain::new(31, WIDTH as u64, 8, 13);
+        let constants: Vec<Fp> = (0..8 * WIDTH + 13)
+            .map(|_| Fp::from(grain.next_element(31, &modulus).limbs[3]))
+            .collect();
+
+        let expected: Vec<Fp> = INITIAL_EXTERNAL_ROUND_CONSTANTS
+            .iter()
+            .chain(INTERNAL_ROUND_CONSTANTS.iter())
+            .chain(FINAL_EXTERNAL_ROUND_CONSTANTS.iter())
+            .cloned()
+            .collect();
+        assert_eq!(constants, expected);
+        // The first constants of `BABYBEAR_RC16_EXTERNAL_INITIAL` in Plonky3.
+        assert_eq!(
+            &constants[..4],
+            &[0x69cbb6af, 0x46ad93f9, 0x60a00f4e, 0x6b1297cd].map(|x: u64| Fp::from(x))
+        );
+    }
+
+    #[test]
+    fn internal_diagonal_matches_plonky3() {
+        let two = Fp::from(2);
+        let inverse_power_of_two = |exponent: u64| two.pow(exponent).inv().unwrap();
+        let expected = [
+            -Fp::from(2),
+            Fp::from(1),
+            Fp::from(2),
+            inverse_power_of_two(1),
+            Fp::from(3),
+            Fp::from(4),
+            -inverse_power_of_two(1),
+            -Fp::from(3),
+            -Fp::from(4),
+            inverse_power_of_two(8),
+            inverse_power_of_two(2),
+            inverse_power_of_two(3),
+            inverse_power_of_two(27),
+            -inverse_power_of_two(8),
+            -inverse_power_of_two(4),
+            -inverse_power_of_two(27),
+        ];
+        assert_eq!(INTERNAL_DIAGONAL, expected);
+    }
+
+    #[test]
+    fn m4_matches_its_matrix() {
+        let matrix = [[2, 3, 1, 1], [1, 2, 3, 1], [1, 1, 2, 3], [3, 1, 1, 2]];
+        let input: Vec<Fp> = (1..=4).map(|i| Fp::from(i * 1000 + 7)).collect();
+        let mut output = input.clone();
+        Poseidon2BabyBear::apply_m4(&mut output);
+
+        for (row, value) in matrix.iter().zip(output) {
+            let expected = row
+                .iter()
+                .zip(&input)
+                .fold(Fp::zero(), |acc, (m, x)| acc + Fp::from(*m) * x);
+            assert_eq!(value, expected);
+        }
+    }
+
+    #[test]
+    fn permutation_of_0_to_15() {
+        // Computed with an independent Python implementation of the permutation with these
+        // parameters.
+        let expected = [
+            1906786279, 1737026427, 1959749225, 700325316, 1638050605, 1021608788, 1726691001,
+            1761127344, 1552405120, 417318995, 36799261, 1215172152, 614923223, 1300746575,
+            957311597, 304856115,
+        ];
+        let mut state = core::array::from_fn(|i| Fp::from(i as u64));
+        Poseidon2BabyBear::permutation(&mut state);
+        assert_eq!(state, expected.map(|x: u64| Fp::from(x)));
+    }
+}
@@ -0,0 +1,178 @@
+use super::Fp;
+
+// Round constants of the reference implementation of Horizen Labs, which Plonky3 also uses for
+// BabyBear with a width of 16. They are generated with the Grain LFSR of `poseidon2_rust_params.sage`
+// for a prime field of 31 bits, the S-box x^7, a width of 16, 8 external and 13 internal rounds.
+// The first 64 constants are those of the initial external rounds, the following 13 those of the
+// internal rounds and the last 64 those of the final external rounds.
+
+pub(super) const INITIAL_EXTERNAL_ROUND_CONSTANTS: [Fp; 64] = [
+    Fp::from_hex_unchecked("69cbb6af"),
+    Fp::from_hex_unchecked("46ad93f9"),
+    Fp::from_hex_unchecked("60a00f4e"),
+    Fp::from_hex_unchecked("6b1297cd"),
+    Fp::from_hex_unchecked("23189afe"),
+    Fp::from_hex_unchecked("732e7bef"),
+    Fp::from_hex_unchecked("72c246de"),
+    Fp::from_hex_unchecked("2c941900"),
+    Fp::from_hex_unchecked("557eede"),
+    Fp::from_hex_unchecked("1580496f"),
+    Fp::from_hex_unchecked("3a3ea77b"),
+    Fp::from_hex_unchecked("54f3f271"),
+    Fp::from_hex_unchecked("f49b029"),
+    Fp::from_hex_unchecked("47872fe1"),
+    Fp::from_hex_unchecked("221e2e36"),
+    Fp::from_hex_unchecked("1ab7202e"),
+    Fp::from_hex_unchecked("487779a6"),
+    Fp::from_hex_unchecked("3851c9d8"),
+    Fp::from_hex_unchecked("38dc17c0"),
+    Fp::from_hex_unchecked("209f8849"),
+    Fp::from_hex_unchecked("268dcee8"),
+    Fp::from_hex_unchecked("350c48da"),
+    Fp::from_hex_unchecked("5b9ad32e"),
+    Fp::from_hex_unchecked("523272b"),
+    Fp::from_hex_unchecked("3f89055b"),
+    Fp::from_hex_unchecked("1e894b2"),
+    Fp::from_hex_unchecked("13ddedde"),
+    Fp::from_hex_unchecked("1b2ef334"),
+    Fp::from_hex_unchecked("7507d8b4"),
+    Fp::from_hex_unchecked("6ceeb94e"),
+    Fp::from_hex_unchecked("52eb6ba2"),
+    Fp::from_hex_unchecked("50642905"),
+    Fp::from_hex_unchecked("5453f3f"),
+    Fp::from_hex_unchecked("6349efc"),
+    Fp::from_hex_unchecked("6922787c"),
+    Fp::from_hex_unchecked("4bfff9c"),
+    Fp::from_hex_unchecked("768c714a"),
+    Fp::from_hex_unchecked("3e9ff21a"),
+    Fp::from_hex_unchecked("15737c9c"),
+    Fp::from_hex_unchecked("2229c807"),
+    Fp::from_hex_unchecked("d47f88c"),
+    Fp::from_hex_unchecked("97e0ecc"),
+    Fp::from_hex_unchecked("27eadba0"),
+    Fp::from_hex_unchecked("2d7d29e4"),
+    Fp::from_hex_unchecked("3502aaa0"),
+    Fp::from_hex_unchecked("f475fd7"),
+    Fp::from_hex_unchecked("29fbda49"),
+    Fp::from_hex_unchecked("18afffd"),
+    Fp::from_hex_unchecked("315b618"),
+    Fp::from_hex_unchecked("6d4497d1"),
+    Fp::from_hex_unchecked("1b171d9e"),
+    Fp::from_hex_unchecked("52861abd"),
+    Fp::from_hex_unchecked("2e5d0501"),
+    Fp::from_hex_unchecked("3ec8646c"),
+    Fp::from_hex_unchecked("6e5f250a"),
+    Fp::from_hex_unchecked("148ae8e6"),
+    Fp::from_hex_unchecked("17f5fa4a"),
+    Fp::from_hex_unchecked("3e66d284"),
+    Fp::from_hex_unchecked("51aa3b"),
+    Fp::from_hex_unchecked("483f7913"),
+    Fp::from_hex_unchecked("2cfe5f15"),
+    Fp::from_hex_unchecked("23427ca"),
+    Fp::from_hex_unchecked("2cc78315"),
+    Fp::from_hex_unchecked("1e36ea47"),
+];
+
+pub(super) const INTERNAL_ROUND_CONSTANTS: [Fp; 13] = [
+    Fp::from_hex_unchecked("5a8053c0"),
+    Fp::from_hex_unchecked("693be639"),
+    Fp::from_hex_unchecked("3858867d"),
+    Fp::from_hex_unchecked("19334f6b"),
+    Fp::from_hex_unchecked("128f0fd8"),
+    Fp::from_hex_unchecked("4e2b1ccb"),
+    Fp::from_hex_unchecked("61210ce0"),
+    Fp::from_hex_unchecked("3c318939"),
+    Fp::from_hex_unchecked("b5b2f22"),
+    Fp::from_hex_unchecked("2edb11d5"),
+    Fp::from_hex_unchecked("213effdf"),
+    Fp::from_hex_unchecked("cac4606"),
+    Fp::from_hex_unchecked("241af16d"),
+];
+
+pub(super) const FINAL_EXTERNAL_ROUND_CONSTANTS: [Fp; 64] = [
+    Fp::from_hex_unchecked("7290a80d"),
+    Fp::from_hex_unchecked("6f7e5329"),
+    Fp::from_hex_unchecked("598ec8a8"),
+    Fp::from_hex_unchecked("76a859a0"),
+    Fp::from_hex_unchecked("6559e868"),
+    Fp::from_hex_unchecked("657b83af"),
+    Fp::from_hex_unchecked("13271d3f"),
+    Fp::from_hex_unchecked("1f876063"),
+    Fp::from_hex_unchecked("aeeae37"),
+    Fp::from_hex_unchecked("706e9ca6"),
+    Fp::from_hex_unchecked("46400cee"),
+    Fp::from_hex_unchecked("72a05c26"),
+    Fp::from_hex_unchecked("2c589c9e"),
+    Fp::from_hex_unchecked("20bd37a7"),
+    Fp::from_hex_unchecked("6a2d3d10"),
+    Fp::from_hex_unchecked("20523767"),
+    Fp::from_hex_unchecked("5b8fe9c4"),
+    Fp::from_hex_unchecked("2aa501d6"),
+    Fp::from_hex_unchecked("1e01ac3e"),
+    Fp::from_hex_unchecked("1448bc54"),
+    Fp::from_hex_unchecked("5ce5ad1c"),
+    Fp::from_hex_unchecked("4918a14d"),
+    Fp::from_hex_unchecked("2c46a83f"),
+    Fp::from_hex_unchecked("4fcf6876"),
+    Fp::from_hex_unchecked("61d8d5c8"),
+    Fp::from_hex_unchecked("6ddf4ff9"),
+    Fp::from_hex_unchecked("11fda4d3"),
+    Fp::from_hex_unchecked("2933a8f"),
+    Fp::from_hex_unchecked("170eaf81"),
+    Fp::from_hex_unchecked("5a9c314f"),
+    Fp::from_hex_unchecked("49a12590"),
+    Fp::from_hex_unchecked("35ec52a1"),
+    Fp::from_hex_unchecked("58eb1611"),
+    Fp::from_hex_unchecked("5e481e65"),
+    Fp::from_hex_unchecked("367125c9"),
+    Fp::from_hex_unchecked("eba33ba"),
+    Fp::from_hex_unchecked("1fc28ded"),
+    Fp::from_hex_unchecked("66399ad"),
+    Fp::from_hex_unchecked("cbec0ea"),
+    Fp::from_hex_unchecked("75fd1af0"),
+    Fp::from_hex_unchecked("50f5bf4e"),
+    Fp::from_hex_unchecked("643d5f41"),
+    Fp::from_hex_unchecked("6f4fe718"),
+    Fp::from_hex_unchecked("5b3cbbde"),
+    Fp::from_hex_unchecked("1e3afb3e"),
+    Fp::from_hex_unchecked("296fb027"),
+    Fp::from_hex_unchecked("45e1547b"),
+    Fp::from_hex_unchecked("4a8db2ab"),
+    Fp::from_hex_unchecked("59986d19"),
+    Fp::from_hex_unchecked("30bcdfa3"),
+    Fp::from_hex_unchecked("1db63932"),
+    Fp::from_hex_unchecked("1d7c2824"),
+    Fp::from_hex_unchecked("53b33681"),
+    Fp::from_hex_unchecked("673b747"),
+    Fp::from_hex_unchecked("38a98a3"),
+    Fp::from_hex_unchecked("2c5bce60"),
+    Fp::from_hex_unchecked("351979cd"),
+    Fp::from_hex_unchecked("5008fb73"),
+    Fp::from_hex_unchecked("547bca78"),
+    Fp::from_hex_unchecked("711af481"),
+    Fp::from_hex_unchecked("3f93bf64"),
+    Fp::from_hex_unchecked("644d987b"),
+    Fp::from_hex_unchecked("3c8bcd87"),
+    Fp::from_hex_unchecked("608758b8"),
+];
+
+// Diagonal of the internal matrix `1 + diag(d)`, the one chosen by Plonky3 for BabyBear with a
+// width of 16: [-2, 1, 2, 1/2, 3, 4, -1/2, -3, -4, 1/2^8, 1/4, 1/8, 1/2^27, -1/2^8, -1/16, -1/2^27].
+pub(super) const INTERNAL_DIAGONAL: [Fp; 16] = [
+    Fp::from_hex_unchecked("77ffffff"),
+    Fp::from_hex_unchecked("1"),
+    Fp::from_hex_unchecked("2"),
+    Fp::from_hex_unchecked("3c000001"),
+    Fp::from_hex_unchecked("3"),
+    Fp::from_hex_unchecked("4"),
+    Fp::from_hex_unchecked("3c000000"),
+    Fp::from_hex_unchecked("77fffffe"),
+    Fp::from_hex_unchecked("77fffffd"),
+    Fp::from_hex_unchecked("77880001"),
+    Fp::from_hex_unchecked("5a000001"),
+    Fp::from_hex_unchecked("69000001"),
+    Fp::from_hex_unchecked("77fffff2"),
+    Fp::from_hex_unchecked("780000"),
+    Fp::from_hex_unchecked("7800000"),
+    Fp::from_hex_unchecked("f"),
+];
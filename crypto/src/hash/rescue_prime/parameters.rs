@@ -928,6 +928,7 @@ pub fn get_round_constants(level: &SecurityLevel) -> &'static [Fp] {
     }
 }
 
+#[derive(Clone)]
 pub enum MdsVector {
     Mds128([Fp; 12]),
     Mds160([Fp; 16]),
@@ -27,6 +27,7 @@ use lambdaworks_math::field::errors::FieldError;
 // The number of rounds can be increased to 8 or 9 to achieve a higher level of security at the cost of performance.
 const NUM_FULL_ROUNDS: usize = 7;
 
+#[derive(Clone)]
 pub struct RescuePrimeOptimized {
     /// State width of the hash function.
     m: usize,
@@ -35,7 +35,15 @@ where
             return None;
         }
 
-        let hashed_leaves: Vec<B::Node> = B::hash_leaves(unhashed_leaves);
+        Self::from_hashed_leaves(B::hash_leaves(unhashed_leaves))
+    }
+
+    /// Builds a tree whose leaves are the already hashed `hashed_leaves`, e.g. computed in
+    /// batches without keeping all the unhashed data in memory.
+    pub fn from_hashed_leaves(hashed_leaves: Vec<B::Node>) -> Option<Self> {
+        if hashed_leaves.is_empty() {
+            return None;
+        }
 
         //The leaf must be a power of 2 set
         let hashed_leaves = complete_until_power_of_two(hashed_leaves);
@@ -90,7 +98,9 @@ mod tests {
     use super::*;
     use lambdaworks_math::field::{element::FieldElement, fields::u64_prime_field::U64PrimeField};
 
-    use crate::merkle_tree::{merkle::MerkleTree, test_merkle::TestBackend};
+    use crate::merkle_tree::{
+        merkle::MerkleTree, test_merkle::TestBackend, traits::IsMerkleTreeBackend,
+    };
 
     const MODULUS: u64 = 13;
     type U64PF = U64PrimeField<MODULUS>;
@@ -130,4 +140,19 @@ mod tests {
     fn build_empty_tree_should_not_panic() {
         assert!(MerkleTree::<TestBackend<U64PF>>::build(&[]).is_none());
     }
+
+    #[test]
+    fn build_merkle_tree_from_hashed_leaves() {
+        let values: Vec<FE> = (1..6).map(FE::new).collect();
+        let hashed_leaves = TestBackend::<U64PF>::hash_leaves(&values);
+        let merkle_tree =
+            MerkleTree::<TestBackend<U64PF>>::from_hashed_leaves(hashed_leaves).unwrap();
+        assert_eq!(
+            merkle_tree.root,
+            MerkleTree::<TestBackend<U64PF>>::build(&values)
+                .unwrap()
+                .root
+        );
+        assert!(MerkleTree::<TestBackend<U64PF>>::from_hashed_leaves(Vec::new()).is_none());
+    }
 }
@@ -0,0 +1,123 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
+use stark_platinum_prover::proof::options::{ProofOptions, SecurityLevel};
+
+/// Smallest blowup factor the Miden VM constraints can be proven with: the
+/// composition polynomial of its degree 9 constraints needs 8 times the trace length.
+pub const MIDEN_MIN_BLOWUP_FACTOR: u8 = 8;
+
+#[derive(Parser, Debug)]
+#[command(author = "Lambdaworks", version, about)]
+pub struct ProveMidenArgs {
+    #[clap(subcommand)]
+    pub entity: ProveMidenEntity,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ProveMidenEntity {
+    #[clap(about = "Execute a Miden program and prove its execution")]
+    Prove(ProveArgs),
+    #[clap(about = "Verify a proof of a Miden program execution")]
+    Verify(VerifyArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ProveArgs {
+    /// Path to the Miden assembly (.masm) program
+    pub program_path: String,
+    /// JSON file with the operand stack and advice stack inputs
+    #[clap(short, long)]
+    pub inputs_path: Option<String>,
+    /// Where to write the proof. Defaults to the program path with a `.proof` extension
+    #[clap(short, long)]
+    pub proof_path: Option<String>,
+    /// Where to write the public inputs. Defaults to the program path with a `_public_inputs.json` suffix
+    #[clap(long)]
+    pub public_inputs_path: Option<String>,
+    #[clap(flatten)]
+    pub proof_options: ProofOptionsArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct VerifyArgs {
+    /// Path to the proof written by `prove`
+    pub proof_path: String,
+    /// Path to the public inputs written by `prove`
+    pub public_inputs_path: String,
+    /// Hex encoded hash of the program the proof claims to execute
+    #[clap(long)]
+    pub program_hash: String,
+    /// Weakest security level the proof may have been generated with
+    #[clap(long, value_enum, default_value_t = Security::Conjecturable100)]
+    pub min_security: Security,
+}
+
+/// The proof options. They are saved with the public inputs, and the verifier only
+/// accepts them if their security level is at least its `--min-security`.
+#[derive(Args, Serialize, Deserialize, Clone, Debug)]
+pub struct ProofOptionsArgs {
+    /// Security level the proof options are chosen for
+    #[clap(long, value_enum, default_value_t = Security::Conjecturable100)]
+    pub security: Security,
+    /// Raises the blowup factor of the chosen security level
+    #[clap(long)]
+    pub blowup_factor: Option<u8>,
+}
+
+/// The blowup factor is never below the one of the security level nor
+/// `MIDEN_MIN_BLOWUP_FACTOR`, so `--blowup-factor` can only make the proof more secure.
+impl From<&ProofOptionsArgs> for ProofOptions {
+    fn from(args: &ProofOptionsArgs) -> Self {
+        let mut proof_options = ProofOptions::new_secure(args.security.into(), 3);
+        proof_options.blowup_factor = proof_options
+            .blowup_factor
+            .max(args.blowup_factor.unwrap_or_default())
+            .max(MIDEN_MIN_BLOWUP_FACTOR);
+        proof_options
+    }
+}
+
+#[derive(ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Security {
+    Conjecturable80,
+    Conjecturable100,
+    Conjecturable128,
+    Provable80,
+    Provable100,
+    Provable128,
+}
+
+impl Security {
+    /// Bits of security and whether they are provable.
+    fn strength(self) -> (u32, bool) {
+        match self {
+            Security::Conjecturable80 => (80, false),
+            Security::Conjecturable100 => (100, false),
+            Security::Conjecturable128 => (128, false),
+            Security::Provable80 => (80, true),
+            Security::Provable100 => (100, true),
+            Security::Provable128 => (128, true),
+        }
+    }
+
+    /// Whether this level gives at least the security of `minimum`.
+    pub fn satisfies(self, minimum: Security) -> bool {
+        let (bits, provable) = self.strength();
+        let (minimum_bits, minimum_provable) = minimum.strength();
+        bits >= minimum_bits && (provable || !minimum_provable)
+    }
+}
+
+impl From<Security> for SecurityLevel {
+    fn from(value: Security) -> Self {
+        match value {
+            Security::Conjecturable80 => SecurityLevel::Conjecturable80Bits,
+            Security::Conjecturable100 => SecurityLevel::Conjecturable100Bits,
+            Security::Conjecturable128 => SecurityLevel::Conjecturable128Bits,
+            Security::Provable80 => SecurityLevel::Provable80Bits,
+            Security::Provable100 => SecurityLevel::Provable100Bits,
+            Security::Provable128 => SecurityLevel::Provable128Bits,
+        }
+    }
+}
@@ -1,107 +1,340 @@
-use std::time::Instant;
-
+mod commands;
+use clap::Parser;
+use commands::{ProofOptionsArgs, ProveArgs, ProveMidenArgs, ProveMidenEntity, VerifyArgs};
 use lambdaworks_winterfell_adapter::{
     adapter::{public_inputs::AirAdapterPublicInputs, QuadFeltTranscript},
-    examples::miden_vm::MidenVMQuadFeltAir,
+    examples::miden_vm::{MidenVMQuadFeltAir, MidenVMQuadFeltVerifierAir},
 };
 use miden_air::{ProvingOptions, PublicInputs};
 use miden_assembly::Assembler;
-use miden_core::{Felt, FieldElement, StackInputs, StarkField};
-use miden_processor::{self as processor};
-use processor::DefaultHost;
+use miden_core::{
+    crypto::hash::RpoDigest,
+    utils::{Deserializable, Serializable},
+    Kernel, ProgramInfo, StackInputs, StackOutputs,
+};
+use miden_processor::{self as processor, AdviceInputs, DefaultHost, MemAdviceProvider};
+use serde::{Deserialize, Serialize};
 use stark_platinum_prover::{
-    proof::options::{ProofOptions, SecurityLevel},
+    proof::{options::ProofOptions, stark::StarkProof},
     prover::{IsStarkProver, Prover},
+    traits::AIR,
     verifier::{IsStarkVerifier, Verifier},
 };
+use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::process::ExitCode;
+use std::time::Instant;
+use winter_air::{TraceInfo, TraceLayout};
 use winter_prover::Trace;
 
-fn compute_fibonacci(n: usize) -> Felt {
-    let mut t0 = Felt::ZERO;
-    let mut t1 = Felt::ONE;
+/// Number of steps at the end of the trace where Miden VM transitions do not apply,
+/// for each of its transition constraints.
+const MIDEN_TRANSITION_EXEMPTIONS: [usize; 182] = [2; 182];
+const MIDEN_TRANSITION_OFFSETS: [usize; 2] = [0, 1];
 
-    for _ in 0..n {
-        t1 = t0 + t1;
-        core::mem::swap(&mut t0, &mut t1);
-    }
-    t0
+type MidenProof =
+    StarkProof<<MidenVMQuadFeltAir as AIR>::Field, <MidenVMQuadFeltAir as AIR>::FieldExtension>;
+
+/// Inputs of a Miden program, in the same order Miden expects them.
+#[derive(Deserialize, Default)]
+struct ProgramInputs {
+    #[serde(default)]
+    operand_stack: Vec<u64>,
+    #[serde(default)]
+    advice_stack: Vec<u64>,
 }
 
-fn main() {
-    let fibonacci_number = 16;
+/// Everything a verifier needs besides the proof and the program hash.
+#[derive(Serialize, Deserialize, Clone)]
+struct ProofPublicInputs {
+    program_hash: String,
+    stack_inputs: Vec<u64>,
+    stack_outputs: Vec<u64>,
+    overflow_addrs: Vec<u64>,
+    trace_layout: String,
+    trace_length: usize,
+    trace_meta: String,
+    proof_options: ProofOptionsArgs,
+}
 
-    let program = format!(
-        "begin
-            repeat.{}
-                swap dup.1 add
-            end
-        end",
-        fibonacci_number - 1
-    );
+fn other_error<E: Debug>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, format!("{:?}", error))
+}
+
+fn default_output_path(program_path: &str, suffix: &str) -> String {
+    match program_path.strip_suffix(".masm") {
+        Some(stem) => format!("{stem}{suffix}"),
+        None => format!("{program_path}{suffix}"),
+    }
+}
 
-    println!("\nCompiling miden fibonacci program");
+/// Bytes both transcripts start from, so that the proof is bound to the program,
+/// its inputs and outputs and the shape of its trace.
+fn transcript_seed(public_inputs: &ProofPublicInputs) -> Result<Vec<u8>, io::Error> {
+    let mut seed = hex::decode(&public_inputs.program_hash).map_err(other_error)?;
+    for values in [
+        &public_inputs.stack_inputs,
+        &public_inputs.stack_outputs,
+        &public_inputs.overflow_addrs,
+    ] {
+        seed.extend_from_slice(&(values.len() as u64).to_le_bytes());
+        for value in values {
+            seed.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    seed.extend(hex::decode(&public_inputs.trace_layout).map_err(other_error)?);
+    Ok(seed)
+}
 
-    let program = Assembler::default().compile(program).unwrap();
-    let expected_result = vec![compute_fibonacci(fibonacci_number).as_int()];
-    let stack_inputs = StackInputs::try_from_values([0, 1]).unwrap();
+fn prove_program(
+    source: &str,
+    inputs: ProgramInputs,
+    proof_options: ProofOptionsArgs,
+) -> Result<(MidenProof, ProofPublicInputs), io::Error> {
+    let program = Assembler::default().compile(source).map_err(other_error)?;
 
-    let mut lambda_proof_options = ProofOptions::new_secure(SecurityLevel::Conjecturable100Bits, 3);
-    lambda_proof_options.blowup_factor = 8;
+    let stack_inputs =
+        StackInputs::try_from_values(inputs.operand_stack.iter().copied()).map_err(other_error)?;
+    let advice_inputs = AdviceInputs::default()
+        .with_stack_values(inputs.advice_stack.iter().copied())
+        .map_err(other_error)?;
 
-    println!("\nExecuting program in Miden VM");
+    println!("Executing program in Miden VM");
     let winter_trace = processor::execute(
         &program,
         stack_inputs.clone(),
-        DefaultHost::default(),
+        DefaultHost::new(MemAdviceProvider::from(advice_inputs)),
         *ProvingOptions::default().execution_options(),
     )
-    .unwrap();
+    .map_err(other_error)?;
     let program_info = winter_trace.program_info().clone();
     let stack_outputs = winter_trace.stack_outputs().clone();
+    println!("Program outputs: {:?}", stack_outputs.stack());
 
-    let pub_inputs = PublicInputs::new(program_info, stack_inputs, stack_outputs.clone());
-
-    assert_eq!(
-        expected_result,
-        stack_outputs.clone().stack_truncated(1),
-        "Program result was computed incorrectly"
-    );
+    let trace_info = winter_trace.get_info();
+    let public_inputs = ProofPublicInputs {
+        program_hash: hex::encode(program.hash().to_bytes()),
+        stack_inputs: inputs.operand_stack,
+        stack_outputs: stack_outputs.stack().to_vec(),
+        overflow_addrs: stack_outputs.overflow_addrs().to_vec(),
+        trace_layout: hex::encode(trace_info.layout().to_bytes()),
+        trace_length: trace_info.length(),
+        trace_meta: hex::encode(trace_info.meta()),
+        proof_options,
+    };
 
     let pub_inputs = AirAdapterPublicInputs::new(
-        pub_inputs,
-        vec![2; 182],
-        vec![0, 1],
-        winter_trace.get_info(),
+        PublicInputs::new(program_info, stack_inputs, stack_outputs),
+        MIDEN_TRANSITION_EXEMPTIONS.to_vec(),
+        MIDEN_TRANSITION_OFFSETS.to_vec(),
+        trace_info,
         winter_trace.clone().into(),
     );
-
-    println!("\nImporting trace to lambdaworks");
     let trace =
         MidenVMQuadFeltAir::convert_winterfell_trace_table(winter_trace.main_segment().clone());
 
-    println!("\nProving ");
-
-    let timer0 = Instant::now();
+    println!("Proving");
+    let timer = Instant::now();
     let proof = Prover::<MidenVMQuadFeltAir>::prove(
         &trace,
         &pub_inputs,
-        &lambda_proof_options,
-        QuadFeltTranscript::new(&[]),
+        &ProofOptions::from(&public_inputs.proof_options),
+        QuadFeltTranscript::new(&transcript_seed(&public_inputs)?),
+    )
+    .map_err(other_error)?;
+    println!("Total time spent proving: {:?}", timer.elapsed());
+
+    Ok((proof, public_inputs))
+}
+
+fn verify_proof(proof: &MidenProof, public_inputs: &ProofPublicInputs) -> Result<bool, io::Error> {
+    let program_hash =
+        RpoDigest::read_from_bytes(&hex::decode(&public_inputs.program_hash).map_err(other_error)?)
+            .map_err(other_error)?;
+
+    let stack_inputs = StackInputs::try_from_values(public_inputs.stack_inputs.iter().copied())
+        .map_err(other_error)?;
+    let stack_outputs = StackOutputs::new(
+        public_inputs.stack_outputs.clone(),
+        public_inputs.overflow_addrs.clone(),
+    )
+    .map_err(other_error)?;
+    let trace_layout = TraceLayout::read_from_bytes(
+        &hex::decode(&public_inputs.trace_layout).map_err(other_error)?,
     )
-    .unwrap();
-    let elapsed0 = timer0.elapsed();
-    println!("Total time spent proving: {:?}", elapsed0);
-
-    println!("\nVerifying ");
-    let timer0 = Instant::now();
-    assert!(Verifier::<MidenVMQuadFeltAir>::verify(
-        &proof,
+    .map_err(other_error)?;
+    let trace_info = TraceInfo::new_multi_segment(
+        trace_layout,
+        public_inputs.trace_length,
+        hex::decode(&public_inputs.trace_meta).map_err(other_error)?,
+    );
+
+    let pub_inputs = AirAdapterPublicInputs::new(
+        PublicInputs::new(
+            ProgramInfo::new(program_hash, Kernel::default()),
+            stack_inputs,
+            stack_outputs,
+        ),
+        MIDEN_TRANSITION_EXEMPTIONS.to_vec(),
+        MIDEN_TRANSITION_OFFSETS.to_vec(),
+        trace_info,
+        (),
+    );
+
+    let timer = Instant::now();
+    let verified = Verifier::<MidenVMQuadFeltVerifierAir>::verify(
+        proof,
         &pub_inputs,
-        &lambda_proof_options,
-        QuadFeltTranscript::new(&[]),
-    ));
-    let elapsed0 = timer0.elapsed();
-    println!("Total time spent verifying: {:?}", elapsed0);
+        &ProofOptions::from(&public_inputs.proof_options),
+        QuadFeltTranscript::new(&transcript_seed(public_inputs)?),
+    );
+    println!("Total time spent verifying: {:?}", timer.elapsed());
+
+    Ok(verified)
+}
 
-    println!("\nDone!");
+fn prove(args: ProveArgs) -> Result<(), io::Error> {
+    let inputs: ProgramInputs = match &args.inputs_path {
+        Some(path) => serde_json::from_str(&fs::read_to_string(path)?)?,
+        None => ProgramInputs::default(),
+    };
+
+    println!("Compiling {}", args.program_path);
+    let (proof, public_inputs) = prove_program(
+        &fs::read_to_string(&args.program_path)?,
+        inputs,
+        args.proof_options,
+    )?;
+
+    let proof_path = args
+        .proof_path
+        .unwrap_or_else(|| default_output_path(&args.program_path, ".proof"));
+    let proof_bytes =
+        bincode::serde::encode_to_vec(&proof, bincode::config::standard()).map_err(other_error)?;
+    fs::write(&proof_path, proof_bytes)?;
+
+    let public_inputs_path = args
+        .public_inputs_path
+        .unwrap_or_else(|| default_output_path(&args.program_path, "_public_inputs.json"));
+    fs::write(
+        &public_inputs_path,
+        serde_json::to_string_pretty(&public_inputs)?,
+    )?;
+
+    println!(
+        "Saved proof to {} and public inputs to {}",
+        proof_path, public_inputs_path
+    );
+
+    Ok(())
+}
+
+fn verify(args: VerifyArgs) -> Result<(), io::Error> {
+    let public_inputs: ProofPublicInputs =
+        serde_json::from_str(&fs::read_to_string(&args.public_inputs_path)?)?;
+
+    if public_inputs.program_hash != args.program_hash.trim_start_matches("0x") {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "The proof was generated for a different program",
+        ));
+    }
+    if !public_inputs
+        .proof_options
+        .security
+        .satisfies(args.min_security)
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "The proof was generated with security level {:?}, below the required {:?}",
+                public_inputs.proof_options.security, args.min_security
+            ),
+        ));
+    }
+
+    let (proof, _): (StarkProof<_, _>, usize) = bincode::serde::decode_from_slice(
+        &fs::read(&args.proof_path)?,
+        bincode::config::standard(),
+    )
+    .map_err(other_error)?;
+
+    if !verify_proof(&proof, &public_inputs)? {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Proof failed verifying",
+        ));
+    }
+    println!(
+        "\x1b[32mProof verified succesfully, program outputs: {:?}\x1b[0m",
+        public_inputs.stack_outputs
+    );
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: ProveMidenArgs = ProveMidenArgs::parse();
+    if let Err(e) = match args.entity {
+        ProveMidenEntity::Prove(args) => prove(args),
+        ProveMidenEntity::Verify(args) => verify(args),
+    } {
+        eprintln!("\x1b[31mError while running command: {}\x1b[0m", e);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commands::Security;
+
+    fn prove_addition() -> (MidenProof, ProofPublicInputs) {
+        prove_program(
+            "begin push.3 push.5 add end",
+            ProgramInputs::default(),
+            ProofOptionsArgs {
+                security: Security::Conjecturable80,
+                blowup_factor: None,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn proof_verifies_against_its_public_inputs() {
+        let (proof, public_inputs) = prove_addition();
+        assert!(verify_proof(&proof, &public_inputs).unwrap());
+    }
+
+    #[test]
+    fn proof_is_rejected_for_a_different_program() {
+        let (proof, mut public_inputs) = prove_addition();
+        let other_program = Assembler::default()
+            .compile("begin push.3 push.6 add end")
+            .unwrap();
+        public_inputs.program_hash = hex::encode(other_program.hash().to_bytes());
+        assert!(!verify_proof(&proof, &public_inputs).unwrap());
+    }
+
+    #[test]
+    fn proof_is_rejected_for_different_outputs() {
+        let (proof, mut public_inputs) = prove_addition();
+        public_inputs.stack_outputs[0] = 9;
+        assert!(!verify_proof(&proof, &public_inputs).unwrap());
+    }
+
+    #[test]
+    fn blowup_factor_is_never_below_the_miden_minimum() {
+        let proof_options = ProofOptions::from(&ProofOptionsArgs {
+            security: Security::Conjecturable80,
+            blowup_factor: Some(2),
+        });
+        assert_eq!(
+            proof_options.blowup_factor,
+            commands::MIDEN_MIN_BLOWUP_FACTOR
+        );
+    }
 }
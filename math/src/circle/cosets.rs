@@ -57,11 +57,11 @@ impl Coset {
     pub fn get_coset_points(coset: &Self) -> Vec<CirclePoint<Mersenne31Field>> {
         // g_n the generator of the subgroup of order n.
         let generator_n = CirclePoint::get_generator_of_subgroup(coset.log_2_size);
-        let size: u8 = 1 << coset.log_2_size;
+        let size: usize = 1 << coset.log_2_size;
         core::iter::successors(Some(coset.shift.clone()), move |prev| {
             Some(prev + &generator_n)
         })
-        .take(size.into())
+        .take(size)
         .collect()
     }
 }
@@ -77,6 +77,13 @@ mod tests {
         assert_eq!(1 << coset.log_2_size, points.len())
     }
 
+    #[test]
+    fn coset_points_vector_has_right_size_for_large_cosets() {
+        let coset = Coset::new_standard(10);
+        let points = Coset::get_coset_points(&coset);
+        assert_eq!(1 << coset.log_2_size, points.len())
+    }
+
     #[test]
     fn antipode_of_coset_point_is_in_coset() {
         let coset = Coset::new_standard(3);
@@ -5,6 +5,8 @@ use crate::field::{
     traits::{IsFFTField, IsField, IsSubFieldOf},
 };
 
+#[cfg(feature = "alloc")]
+use crate::traits::AsBytes;
 #[cfg(feature = "lambdaworks-serde-binary")]
 use crate::traits::ByteConversion;
 
@@ -262,6 +264,14 @@ impl ByteConversion for [FieldElement<Babybear31PrimeField>; 4] {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl AsBytes for FieldElement<Degree4BabyBearExtensionField> {
+    fn as_bytes(&self) -> alloc::vec::Vec<u8> {
+        let [a, b, c, d] = self.value();
+        [a.as_bytes(), b.as_bytes(), c.as_bytes(), d.as_bytes()].concat()
+    }
+}
+
 impl IsFFTField for Degree4BabyBearExtensionField {
     const TWO_ADICITY: u64 = 29;
     const TWO_ADIC_PRIMITVE_ROOT_OF_UNITY: Self::BaseType = [
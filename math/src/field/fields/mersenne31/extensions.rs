@@ -5,6 +5,8 @@ use crate::field::{
     traits::{IsField, IsSubFieldOf},
 };
 #[cfg(feature = "alloc")]
+use crate::traits::AsBytes;
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
 type FpE = FieldElement<Mersenne31Field>;
@@ -265,6 +267,22 @@ impl IsSubFieldOf<Degree4ExtensionField> for Mersenne31Field {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl AsBytes for FieldElement<Degree2ExtensionField> {
+    fn as_bytes(&self) -> Vec<u8> {
+        let [a, b] = self.value();
+        [a.as_bytes(), b.as_bytes()].concat()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsBytes for FieldElement<Degree4ExtensionField> {
+    fn as_bytes(&self) -> Vec<u8> {
+        let [a, b] = self.value();
+        [a.as_bytes(), b.as_bytes()].concat()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use core::ops::Neg;
@@ -1,3 +1,5 @@
+#[cfg(feature = "alloc")]
+use crate::traits::AsBytes;
 use crate::{
     errors::CreationError,
     field::{
@@ -197,6 +199,13 @@ impl FieldElement<Mersenne31Field> {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl AsBytes for FieldElement<Mersenne31Field> {
+    fn as_bytes(&self) -> alloc::vec::Vec<u8> {
+        self.to_bytes_be()
+    }
+}
+
 impl Display for FieldElement<Mersenne31Field> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:x}", self.representative())
@@ -36,12 +36,12 @@ impl Proof {
         Ok(Self { pi1, pi2, pi3 })
     }
 
-    fn serialize_commitment<Commitment: AsBytes>(cm: &Commitment) -> Vec<u8> {
+    pub(crate) fn serialize_commitment<Commitment: AsBytes>(cm: &Commitment) -> Vec<u8> {
         cm.as_bytes()
     }
 
     // Repetitive. Same as in plonk/src/prover.rs
-    fn deserialize_commitment<Commitment: Deserializable>(
+    pub(crate) fn deserialize_commitment<Commitment: Deserializable>(
         bytes: &[u8],
         offset: usize,
     ) -> Result<(usize, Commitment), DeserializationError> {
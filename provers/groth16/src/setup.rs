@@ -1,11 +1,18 @@
-use crate::{common::*, QuadraticArithmeticProgram};
+use crate::{common::*, Proof, QuadraticArithmeticProgram};
 use lambdaworks_math::{
     cyclic_group::IsGroup,
     elliptic_curve::{
-        short_weierstrass::{point::ShortWeierstrassProjectivePoint, traits::IsShortWeierstrass},
+        short_weierstrass::{
+            curves::bls12_381::field_extension::Degree2ExtensionField,
+            point::ShortWeierstrassProjectivePoint, traits::IsShortWeierstrass,
+        },
         traits::{IsEllipticCurve, IsPairing},
     },
+    errors::DeserializationError,
+    field::element::FieldElement,
+    traits::ByteConversion,
 };
+use std::mem::size_of;
 
 pub struct VerifyingKey {
     // e([alpha]_1, [beta]_2) computed during setup as it's a constant
@@ -18,6 +25,96 @@ pub struct VerifyingKey {
     pub verifier_k_tau_g1: Vec<G1Point>,
 }
 
+impl VerifyingKey {
+    /// Size of each of the 6 coefficients in Fp2 of the pairing output.
+    fn fp2_size() -> usize {
+        FieldElement::<Degree2ExtensionField>::zero()
+            .to_bytes_be()
+            .len()
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::new();
+        [
+            Self::serialize_pairing_output(&self.alpha_g1_times_beta_g2),
+            Proof::serialize_commitment(&self.delta_g2),
+            Proof::serialize_commitment(&self.gamma_g2),
+        ]
+        .into_iter()
+        .chain(
+            self.verifier_k_tau_g1
+                .iter()
+                .map(Proof::serialize_commitment),
+        )
+        .for_each(|serialized| {
+            bytes.extend_from_slice(&(serialized.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(&serialized);
+        });
+        bytes
+    }
+
+    pub fn deserialize(bytes: &[u8]) -> Result<Self, DeserializationError>
+    where
+        Self: Sized,
+    {
+        let (offset, alpha_g1_times_beta_g2) = Self::deserialize_pairing_output(bytes)?;
+        let (offset, delta_g2) = Proof::deserialize_commitment::<G2Point>(bytes, offset)?;
+        let (mut offset, gamma_g2) = Proof::deserialize_commitment::<G2Point>(bytes, offset)?;
+        let mut verifier_k_tau_g1 = Vec::new();
+        while offset < bytes.len() {
+            let (next_offset, point) = Proof::deserialize_commitment::<G1Point>(bytes, offset)?;
+            verifier_k_tau_g1.push(point);
+            offset = next_offset;
+        }
+        Ok(Self {
+            alpha_g1_times_beta_g2,
+            delta_g2,
+            gamma_g2,
+            verifier_k_tau_g1,
+        })
+    }
+
+    fn serialize_pairing_output(element: &PairingOutput) -> Vec<u8> {
+        element
+            .value()
+            .iter()
+            .flat_map(|fp6| fp6.value().iter().flat_map(|fp2| fp2.to_bytes_be()))
+            .collect()
+    }
+
+    fn deserialize_pairing_output(
+        bytes: &[u8],
+    ) -> Result<(usize, PairingOutput), DeserializationError> {
+        let element_size_bytes: [u8; size_of::<u32>()] = bytes
+            .get(..size_of::<u32>())
+            .ok_or(DeserializationError::InvalidAmountOfBytes)?
+            .try_into()
+            .map_err(|_| DeserializationError::InvalidAmountOfBytes)?;
+        let element_size = u32::from_be_bytes(element_size_bytes) as usize;
+        let fp2_size = Self::fp2_size();
+        if element_size != 6 * fp2_size {
+            return Err(DeserializationError::InvalidAmountOfBytes);
+        }
+        let offset = size_of::<u32>();
+        let element_bytes = bytes
+            .get(offset..offset + element_size)
+            .ok_or(DeserializationError::InvalidAmountOfBytes)?;
+
+        let fp2_coefficients = element_bytes
+            .chunks(fp2_size)
+            .map(FieldElement::<Degree2ExtensionField>::from_bytes_be)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| DeserializationError::FieldFromBytesError)?;
+        let fp6 = |c: &[FieldElement<Degree2ExtensionField>]| {
+            FieldElement::new([c[0].clone(), c[1].clone(), c[2].clone()])
+        };
+        let element =
+            PairingOutput::new([fp6(&fp2_coefficients[..3]), fp6(&fp2_coefficients[3..])]);
+
+        Ok((offset + element_size, element))
+    }
+}
+
 pub struct ProvingKey {
     pub alpha_g1: G1Point,
     pub beta_g1: G1Point,
@@ -1,4 +1,4 @@
-use lambdaworks_groth16::{common::*, setup, verify, Proof, Prover};
+use lambdaworks_groth16::{common::*, setup, verify, Proof, Prover, VerifyingKey};
 
 mod test_circuits;
 use test_circuits::*;
@@ -41,3 +41,26 @@ fn example() {
     let accept = verify(&vk, &deserialized_proof, &w[..qap.num_of_public_inputs]);
     assert!(accept);
 }
+
+#[test]
+fn serialized_verifying_key_verifies_proof() {
+    let qap = test_circuits::vitalik_qap(); // x^3 + x + 5 = 35
+
+    let (pk, vk) = setup(&qap);
+    let vk = VerifyingKey::deserialize(&vk.serialize()).unwrap();
+
+    let w = ["0x1", "0x3", "0x23", "0x9", "0x1b", "0x1e"]
+        .map(FrElement::from_hex_unchecked)
+        .to_vec();
+    let proof = Prover::prove(&w, &qap, &pk);
+
+    assert!(verify(&vk, &proof, &w[..qap.num_of_public_inputs]));
+}
+
+#[test]
+fn verifying_key_deserialization_rejects_truncated_bytes() {
+    let (_, vk) = setup(&test_circuits::vitalik_qap());
+    let serialized_vk = vk.serialize();
+
+    assert!(VerifyingKey::deserialize(&serialized_vk[..serialized_vk.len() - 1]).is_err());
+}
@@ -1,4 +1,4 @@
-use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_crypto::fiat_shamir::is_labeled_transcript::IsLabeledTranscript;
 use lambdaworks_math::errors::DeserializationError;
 use lambdaworks_math::field::traits::IsFFTField;
 use lambdaworks_math::traits::{AsBytes, Deserializable, IsRandomFieldElementGenerator};
@@ -127,7 +127,7 @@ where
 }
 
 // TODO: Remove this once FieldElements implement Serializable
-fn deserialize_field_element<F>(
+pub(crate) fn deserialize_field_element<F>(
     bytes: &[u8],
     offset: usize,
 ) -> Result<(usize, FieldElement<F>), DeserializationError>
@@ -152,7 +152,7 @@ where
     Ok((offset, field_element))
 }
 
-fn deserialize_commitment<Commitment>(
+pub(crate) fn deserialize_commitment<Commitment>(
     bytes: &[u8],
     offset: usize,
 ) -> Result<(usize, Commitment), DeserializationError>
@@ -638,20 +638,20 @@ where
 
         // Round 1
         let round_1 = self.round_1(witness, common_preprocessed_input);
-        transcript.append_bytes(&round_1.a_1.as_bytes());
-        transcript.append_bytes(&round_1.b_1.as_bytes());
-        transcript.append_bytes(&round_1.c_1.as_bytes());
+        transcript.append_point(b"a", &round_1.a_1);
+        transcript.append_point(b"b", &round_1.b_1);
+        transcript.append_point(b"c", &round_1.c_1);
 
         // Round 2
         // TODO: Handle error
-        let beta = transcript.sample_field_element();
-        let gamma = transcript.sample_field_element();
+        let beta = transcript.challenge_scalar(b"beta");
+        let gamma = transcript.challenge_scalar(b"gamma");
 
         let round_2 = self.round_2(witness, common_preprocessed_input, beta, gamma);
-        transcript.append_bytes(&round_2.z_1.as_bytes());
+        transcript.append_point(b"z", &round_2.z_1);
 
         // Round 3
-        let alpha = transcript.sample_field_element();
+        let alpha = transcript.challenge_scalar(b"alpha");
         let round_3 = self.round_3(
             common_preprocessed_input,
             public_input,
@@ -659,23 +659,23 @@ where
             &round_2,
             alpha,
         );
-        transcript.append_bytes(&round_3.t_lo_1.as_bytes());
-        transcript.append_bytes(&round_3.t_mid_1.as_bytes());
-        transcript.append_bytes(&round_3.t_hi_1.as_bytes());
+        transcript.append_point(b"t_lo", &round_3.t_lo_1);
+        transcript.append_point(b"t_mid", &round_3.t_mid_1);
+        transcript.append_point(b"t_hi", &round_3.t_hi_1);
 
         // Round 4
-        let zeta = transcript.sample_field_element();
+        let zeta = transcript.challenge_scalar(b"zeta");
         let round_4 = self.round_4(common_preprocessed_input, &round_1, &round_2, zeta);
 
-        transcript.append_field_element(&round_4.a_zeta);
-        transcript.append_field_element(&round_4.b_zeta);
-        transcript.append_field_element(&round_4.c_zeta);
-        transcript.append_field_element(&round_4.s1_zeta);
-        transcript.append_field_element(&round_4.s2_zeta);
-        transcript.append_field_element(&round_4.z_zeta_omega);
+        transcript.append_field_element(b"a_zeta", &round_4.a_zeta);
+        transcript.append_field_element(b"b_zeta", &round_4.b_zeta);
+        transcript.append_field_element(b"c_zeta", &round_4.c_zeta);
+        transcript.append_field_element(b"s1_zeta", &round_4.s1_zeta);
+        transcript.append_field_element(b"s2_zeta", &round_4.s2_zeta);
+        transcript.append_field_element(b"z_zeta_omega", &round_4.z_zeta_omega);
 
         // Round 5
-        let upsilon = transcript.sample_field_element();
+        let upsilon = transcript.challenge_scalar(b"upsilon");
         let round_5 = self.round_5(
             common_preprocessed_input,
             &round_1,
@@ -1,15 +1,17 @@
 use std::collections::HashMap;
 
 use crate::constraint_system::{get_permutation, ConstraintSystem, Variable};
+use crate::prover::{deserialize_commitment, deserialize_field_element};
 use crate::test_utils::utils::{generate_domain, generate_permutation_coefficients};
 use lambdaworks_crypto::commitments::traits::IsCommitmentScheme;
 use lambdaworks_crypto::fiat_shamir::{
-    default_transcript::DefaultTranscript, is_transcript::IsTranscript,
+    is_labeled_transcript::IsLabeledTranscript, labeled_transcript::LabeledTranscript,
 };
+use lambdaworks_math::errors::DeserializationError;
 use lambdaworks_math::field::traits::IsFFTField;
 use lambdaworks_math::field::{element::FieldElement, traits::IsField};
 use lambdaworks_math::polynomial::Polynomial;
-use lambdaworks_math::traits::{AsBytes, ByteConversion};
+use lambdaworks_math::traits::{AsBytes, ByteConversion, Deserializable};
 
 // TODO: implement getters
 pub struct Witness<F: IsField> {
@@ -101,6 +103,147 @@ impl<F: IsFFTField> CommonPreprocessedInput<F> {
     }
 }
 
+fn serialize_field_elements<F>(bytes: &mut Vec<u8>, elements: &[FieldElement<F>])
+where
+    F: IsField,
+    FieldElement<F>: ByteConversion,
+{
+    bytes.extend_from_slice(&(elements.len() as u32).to_be_bytes());
+    elements.iter().for_each(|element| {
+        let serialized_element = element.to_bytes_be();
+        bytes.extend_from_slice(&(serialized_element.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&serialized_element);
+    });
+}
+
+fn deserialize_field_elements<F>(
+    bytes: &[u8],
+    offset: usize,
+) -> Result<(usize, Vec<FieldElement<F>>), DeserializationError>
+where
+    F: IsField,
+    FieldElement<F>: ByteConversion,
+{
+    let length_bytes: [u8; 4] = bytes
+        .get(offset..offset + 4)
+        .ok_or(DeserializationError::InvalidAmountOfBytes)?
+        .try_into()
+        .map_err(|_| DeserializationError::InvalidAmountOfBytes)?;
+    let length = u32::from_be_bytes(length_bytes) as usize;
+    let mut offset = offset + 4;
+    let mut elements = Vec::new();
+    for _ in 0..length {
+        let (next_offset, element) = deserialize_field_element(bytes, offset)?;
+        elements.push(element);
+        offset = next_offset;
+    }
+    Ok((offset, elements))
+}
+
+impl<F> AsBytes for CommonPreprocessedInput<F>
+where
+    F: IsField,
+    FieldElement<F>: ByteConversion,
+{
+    /// Serializes the size of the circuit, `omega`, `k1`, the coefficients of the selector and
+    /// permutation polynomials and the evaluations of the permutation polynomials. The domain is
+    /// not serialized, since it is determined by `omega` and the size.
+    fn as_bytes(&self) -> Vec<u8> {
+        let mut serialized_input = (self.n as u64).to_be_bytes().to_vec();
+        serialize_field_elements(
+            &mut serialized_input,
+            &[self.omega.clone(), self.k1.clone()],
+        );
+        [
+            &self.ql, &self.qr, &self.qo, &self.qm, &self.qc, &self.s1, &self.s2, &self.s3,
+        ]
+        .iter()
+        .for_each(|polynomial| {
+            serialize_field_elements(&mut serialized_input, polynomial.coefficients())
+        });
+        [&self.s1_lagrange, &self.s2_lagrange, &self.s3_lagrange]
+            .iter()
+            .for_each(|evaluations| serialize_field_elements(&mut serialized_input, evaluations));
+        serialized_input
+    }
+}
+
+impl<F> Deserializable for CommonPreprocessedInput<F>
+where
+    F: IsField,
+    FieldElement<F>: ByteConversion,
+{
+    /// Deserializes a common preprocessed input serialized with `as_bytes`. It fails if the size
+    /// is not a power of two, if `omega` is not a primitive root of unity of that order or if
+    /// there are not as many evaluations of the permutation polynomials as rows.
+    fn deserialize(bytes: &[u8]) -> Result<Self, DeserializationError>
+    where
+        Self: Sized,
+    {
+        let n_bytes: [u8; 8] = bytes
+            .get(..8)
+            .ok_or(DeserializationError::InvalidAmountOfBytes)?
+            .try_into()
+            .map_err(|_| DeserializationError::InvalidAmountOfBytes)?;
+        let n = usize::try_from(u64::from_be_bytes(n_bytes))
+            .map_err(|_| DeserializationError::PointerSizeError)?;
+        if !n.is_power_of_two() {
+            return Err(DeserializationError::InvalidValue);
+        }
+
+        let (offset, roots) = deserialize_field_elements::<F>(bytes, 8)?;
+        let [omega, k1]: [FieldElement<F>; 2] = roots
+            .try_into()
+            .map_err(|_| DeserializationError::InvalidValue)?;
+        let one = FieldElement::<F>::one();
+        if omega.pow(n as u64) != one || (n > 1 && omega.pow(n as u64 / 2) == one) {
+            return Err(DeserializationError::InvalidValue);
+        }
+
+        let mut offset = offset;
+        let mut polynomials = Vec::with_capacity(8);
+        for _ in 0..8 {
+            let (next_offset, coefficients) = deserialize_field_elements(bytes, offset)?;
+            polynomials.push(Polynomial::new(&coefficients));
+            offset = next_offset;
+        }
+        let [ql, qr, qo, qm, qc, s1, s2, s3]: [Polynomial<FieldElement<F>>; 8] = polynomials
+            .try_into()
+            .map_err(|_| DeserializationError::InvalidValue)?;
+
+        let (offset, s1_lagrange) = deserialize_field_elements(bytes, offset)?;
+        let (offset, s2_lagrange) = deserialize_field_elements(bytes, offset)?;
+        let (offset, s3_lagrange) = deserialize_field_elements(bytes, offset)?;
+        if offset != bytes.len() {
+            return Err(DeserializationError::InvalidAmountOfBytes);
+        }
+        if [&s1_lagrange, &s2_lagrange, &s3_lagrange]
+            .iter()
+            .any(|evaluations| evaluations.len() != n)
+        {
+            return Err(DeserializationError::InvalidValue);
+        }
+
+        Ok(Self {
+            n,
+            domain: generate_domain(&omega, n),
+            omega,
+            k1,
+            ql,
+            qr,
+            qo,
+            qm,
+            qc,
+            s1,
+            s2,
+            s3,
+            s1_lagrange,
+            s2_lagrange,
+            s3_lagrange,
+        })
+    }
+}
+
 pub struct VerificationKey<G1Point> {
     pub qm_1: G1Point,
     pub ql_1: G1Point,
@@ -113,6 +256,50 @@ pub struct VerificationKey<G1Point> {
     pub s3_1: G1Point,
 }
 
+impl<G1Point: AsBytes> AsBytes for VerificationKey<G1Point> {
+    fn as_bytes(&self) -> Vec<u8> {
+        let commitments = [
+            &self.qm_1, &self.ql_1, &self.qr_1, &self.qo_1, &self.qc_1, &self.s1_1, &self.s2_1,
+            &self.s3_1,
+        ];
+
+        let mut serialized_vk: Vec<u8> = Vec::new();
+        commitments.iter().for_each(|commitment| {
+            let serialized_commitment = commitment.as_bytes();
+            serialized_vk.extend_from_slice(&(serialized_commitment.len() as u32).to_be_bytes());
+            serialized_vk.extend_from_slice(&serialized_commitment);
+        });
+        serialized_vk
+    }
+}
+
+impl<G1Point: Deserializable> Deserializable for VerificationKey<G1Point> {
+    fn deserialize(bytes: &[u8]) -> Result<Self, DeserializationError>
+    where
+        Self: Sized,
+    {
+        let (offset, qm_1) = deserialize_commitment(bytes, 0)?;
+        let (offset, ql_1) = deserialize_commitment(bytes, offset)?;
+        let (offset, qr_1) = deserialize_commitment(bytes, offset)?;
+        let (offset, qo_1) = deserialize_commitment(bytes, offset)?;
+        let (offset, qc_1) = deserialize_commitment(bytes, offset)?;
+        let (offset, s1_1) = deserialize_commitment(bytes, offset)?;
+        let (offset, s2_1) = deserialize_commitment(bytes, offset)?;
+        let (_, s3_1) = deserialize_commitment(bytes, offset)?;
+
+        Ok(VerificationKey {
+            qm_1,
+            ql_1,
+            qr_1,
+            qo_1,
+            qc_1,
+            s1_1,
+            s2_1,
+            s3_1,
+        })
+    }
+}
+
 pub fn setup<F: IsField, CS: IsCommitmentScheme<F>>(
     common_input: &CommonPreprocessedInput<F>,
     commitment_scheme: &CS,
@@ -133,26 +320,26 @@ pub fn setup<F: IsField, CS: IsCommitmentScheme<F>>(
 pub fn new_strong_fiat_shamir_transcript<F, CS>(
     vk: &VerificationKey<CS::Commitment>,
     public_input: &[FieldElement<F>],
-) -> DefaultTranscript<F>
+) -> LabeledTranscript<F>
 where
     F: IsField,
     FieldElement<F>: ByteConversion,
     CS: IsCommitmentScheme<F>,
     CS::Commitment: AsBytes,
 {
-    let mut transcript = DefaultTranscript::default();
+    let mut transcript = LabeledTranscript::new(b"lambdaworks-plonk");
 
-    transcript.append_bytes(&vk.s1_1.as_bytes());
-    transcript.append_bytes(&vk.s2_1.as_bytes());
-    transcript.append_bytes(&vk.s3_1.as_bytes());
-    transcript.append_bytes(&vk.ql_1.as_bytes());
-    transcript.append_bytes(&vk.qr_1.as_bytes());
-    transcript.append_bytes(&vk.qm_1.as_bytes());
-    transcript.append_bytes(&vk.qo_1.as_bytes());
-    transcript.append_bytes(&vk.qc_1.as_bytes());
+    transcript.append_point(b"s1", &vk.s1_1);
+    transcript.append_point(b"s2", &vk.s2_1);
+    transcript.append_point(b"s3", &vk.s3_1);
+    transcript.append_point(b"ql", &vk.ql_1);
+    transcript.append_point(b"qr", &vk.qr_1);
+    transcript.append_point(b"qm", &vk.qm_1);
+    transcript.append_point(b"qo", &vk.qo_1);
+    transcript.append_point(b"qc", &vk.qc_1);
 
     for value in public_input.iter() {
-        transcript.append_field_element(value);
+        transcript.append_field_element(b"public_input", value);
     }
 
     transcript
@@ -167,7 +354,7 @@ mod tests {
 
     use super::*;
     use crate::test_utils::circuit_1::test_common_preprocessed_input_1;
-    use crate::test_utils::utils::{test_srs, FpElement, KZG};
+    use crate::test_utils::utils::{test_srs, FpElement, G1Point, KZG};
 
     #[test]
     fn setup_works_for_simple_circuit() {
@@ -216,4 +403,57 @@ mod tests {
         assert_eq!(vk.s2_1, expected_s2);
         assert_eq!(vk.s3_1, expected_s3);
     }
+
+    #[test]
+    fn common_preprocessed_input_serialization_round_trip() {
+        let common_input = test_common_preprocessed_input_1();
+
+        let deserialized_input =
+            CommonPreprocessedInput::<FrField>::deserialize(&common_input.as_bytes()).unwrap();
+
+        assert_eq!(deserialized_input.n, common_input.n);
+        assert_eq!(deserialized_input.domain, common_input.domain);
+        assert_eq!(deserialized_input.omega, common_input.omega);
+        assert_eq!(deserialized_input.k1, common_input.k1);
+        assert_eq!(deserialized_input.ql, common_input.ql);
+        assert_eq!(deserialized_input.qc, common_input.qc);
+        assert_eq!(deserialized_input.s3, common_input.s3);
+        assert_eq!(deserialized_input.s1_lagrange, common_input.s1_lagrange);
+        assert_eq!(deserialized_input.s3_lagrange, common_input.s3_lagrange);
+    }
+
+    #[test]
+    fn common_preprocessed_input_with_a_wrong_root_of_unity_is_rejected() {
+        let mut common_input = test_common_preprocessed_input_1();
+        common_input.omega = common_input.omega.square();
+
+        assert!(matches!(
+            CommonPreprocessedInput::<FrField>::deserialize(&common_input.as_bytes()),
+            Err(DeserializationError::InvalidValue)
+        ));
+
+        let bytes = test_common_preprocessed_input_1().as_bytes();
+        assert!(
+            CommonPreprocessedInput::<FrField>::deserialize(&bytes[..bytes.len() - 1]).is_err()
+        );
+    }
+
+    #[test]
+    fn verification_key_serialization_round_trip() {
+        let common_input = test_common_preprocessed_input_1();
+        let srs = test_srs(common_input.n);
+        let kzg = KZG::new(srs);
+        let vk = setup::<FrField, KZG>(&common_input, &kzg);
+
+        let deserialized_vk = VerificationKey::<G1Point>::deserialize(&vk.as_bytes()).unwrap();
+
+        assert_eq!(deserialized_vk.qm_1, vk.qm_1);
+        assert_eq!(deserialized_vk.ql_1, vk.ql_1);
+        assert_eq!(deserialized_vk.qr_1, vk.qr_1);
+        assert_eq!(deserialized_vk.qo_1, vk.qo_1);
+        assert_eq!(deserialized_vk.qc_1, vk.qc_1);
+        assert_eq!(deserialized_vk.s1_1, vk.s1_1);
+        assert_eq!(deserialized_vk.s2_1, vk.s2_1);
+        assert_eq!(deserialized_vk.s3_1, vk.s3_1);
+    }
 }
@@ -1,5 +1,5 @@
 use lambdaworks_crypto::commitments::traits::IsCommitmentScheme;
-use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_crypto::fiat_shamir::is_labeled_transcript::IsLabeledTranscript;
 use lambdaworks_math::cyclic_group::IsGroup;
 use lambdaworks_math::field::element::FieldElement;
 use lambdaworks_math::field::traits::{IsFFTField, IsField, IsPrimeField};
@@ -36,27 +36,27 @@ impl<F: IsField + IsFFTField, CS: IsCommitmentScheme<F>> Verifier<F, CS> {
     {
         let mut transcript = new_strong_fiat_shamir_transcript::<F, CS>(vk, public_input);
 
-        transcript.append_bytes(&p.a_1.as_bytes());
-        transcript.append_bytes(&p.b_1.as_bytes());
-        transcript.append_bytes(&p.c_1.as_bytes());
-        let beta = transcript.sample_field_element();
-        let gamma = transcript.sample_field_element();
-
-        transcript.append_bytes(&p.z_1.as_bytes());
-        let alpha = transcript.sample_field_element();
-
-        transcript.append_bytes(&p.t_lo_1.as_bytes());
-        transcript.append_bytes(&p.t_mid_1.as_bytes());
-        transcript.append_bytes(&p.t_hi_1.as_bytes());
-        let zeta = transcript.sample_field_element();
-
-        transcript.append_field_element(&p.a_zeta);
-        transcript.append_field_element(&p.b_zeta);
-        transcript.append_field_element(&p.c_zeta);
-        transcript.append_field_element(&p.s1_zeta);
-        transcript.append_field_element(&p.s2_zeta);
-        transcript.append_field_element(&p.z_zeta_omega);
-        let upsilon = transcript.sample_field_element();
+        transcript.append_point(b"a", &p.a_1);
+        transcript.append_point(b"b", &p.b_1);
+        transcript.append_point(b"c", &p.c_1);
+        let beta = transcript.challenge_scalar(b"beta");
+        let gamma = transcript.challenge_scalar(b"gamma");
+
+        transcript.append_point(b"z", &p.z_1);
+        let alpha = transcript.challenge_scalar(b"alpha");
+
+        transcript.append_point(b"t_lo", &p.t_lo_1);
+        transcript.append_point(b"t_mid", &p.t_mid_1);
+        transcript.append_point(b"t_hi", &p.t_hi_1);
+        let zeta = transcript.challenge_scalar(b"zeta");
+
+        transcript.append_field_element(b"a_zeta", &p.a_zeta);
+        transcript.append_field_element(b"b_zeta", &p.b_zeta);
+        transcript.append_field_element(b"c_zeta", &p.c_zeta);
+        transcript.append_field_element(b"s1_zeta", &p.s1_zeta);
+        transcript.append_field_element(b"s2_zeta", &p.s2_zeta);
+        transcript.append_field_element(b"z_zeta_omega", &p.z_zeta_omega);
+        let upsilon = transcript.challenge_scalar(b"upsilon");
 
         [beta, gamma, alpha, zeta, upsilon]
     }
@@ -0,0 +1,194 @@
+use lambdaworks_math::field::{
+    element::FieldElement,
+    fields::fft_friendly::{
+        babybear::Babybear31PrimeField, quartic_babybear::Degree4BabyBearExtensionField,
+    },
+};
+use p3_baby_bear::BabyBear;
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_uni_stark::{Entry, SymbolicExpression};
+use stark_platinum_prover::{
+    constraints::{
+        boundary::BoundaryConstraints,
+        symbolic::{Expr, SymbolicConstraint, SymbolicConstraints},
+        transition::TransitionConstraint,
+    },
+    context::AirContext,
+    proof::options::ProofOptions,
+    trace::TraceTable,
+    traits::AIR,
+};
+
+use crate::utils::{p3_to_lambda, vec_p3_to_lambda};
+
+use super::{errors::Plonky3AdapterError, public_inputs::Plonky3PublicInputs};
+
+type F = Babybear31PrimeField;
+type E = Degree4BabyBearExtensionField;
+
+/// Periodic columns holding the row selectors of Plonky3. They have the same period as the
+/// trace, so, like a trace column, they are polynomials of degree `trace_length - 1`.
+const IS_FIRST_ROW: usize = 0;
+const IS_LAST_ROW: usize = 1;
+const IS_TRANSITION: usize = 2;
+
+/// Translates a constraint evaluated by Plonky3's `SymbolicAirBuilder` into a lambdaworks
+/// symbolic expression. The window of Plonky3 is the frame of steps 0 and 1, preprocessed
+/// columns come right after the `main_width` main columns, and public values are replaced
+/// by their values.
+pub(crate) fn translate_expression(
+    expression: &SymbolicExpression<BabyBear>,
+    main_width: usize,
+    public_values: &[FieldElement<F>],
+) -> Result<Expr<F>, Plonky3AdapterError> {
+    let translate = |expression| translate_expression(expression, main_width, public_values);
+    Ok(match expression {
+        SymbolicExpression::Variable(variable) => match variable.entry {
+            Entry::Main { offset } => Expr::main_at(offset, variable.index),
+            Entry::Preprocessed { offset } => Expr::main_at(offset, main_width + variable.index),
+            Entry::Public => Expr::constant(
+                public_values
+                    .get(variable.index)
+                    .ok_or(Plonky3AdapterError::MissingPublicValue(variable.index))?
+                    .clone(),
+            ),
+            Entry::Permutation { .. } => {
+                return Err(Plonky3AdapterError::UnsupportedEntry("permutation"))
+            }
+            Entry::Challenge => return Err(Plonky3AdapterError::UnsupportedEntry("challenge")),
+        },
+        SymbolicExpression::IsFirstRow => Expr::periodic(IS_FIRST_ROW),
+        SymbolicExpression::IsLastRow => Expr::periodic(IS_LAST_ROW),
+        SymbolicExpression::IsTransition => Expr::periodic(IS_TRANSITION),
+        SymbolicExpression::Constant(constant) => Expr::constant(p3_to_lambda(constant)),
+        SymbolicExpression::Add { x, y, .. } => translate(x)? + translate(y)?,
+        SymbolicExpression::Sub { x, y, .. } => translate(x)? - translate(y)?,
+        SymbolicExpression::Neg { x, .. } => -translate(x)?,
+        SymbolicExpression::Mul { x, y, .. } => translate(x)? * translate(y)?,
+    })
+}
+
+/// A lambdaworks AIR over BabyBear and its quartic extension proving the constraints of a
+/// Plonky3 AIR.
+///
+/// Plonky3 constraints must vanish on every row, and restrict themselves to some rows by
+/// multiplying by the selectors `is_first_row`, `is_last_row` and `is_transition`. They are
+/// translated into transition constraints without exemptions, with the selectors given as
+/// periodic columns, so there are no boundary constraints.
+///
+/// Every wrapped AIR shares the identifier `plonky3_adapter`. The wrapped AIR is bound to the
+/// statement through its translated constraints in the bytes of [`Plonky3PublicInputs`].
+pub struct Plonky3AirAdapter<A> {
+    public_inputs: Plonky3PublicInputs<A>,
+    context: AirContext,
+    trace_length: usize,
+    constraints: Vec<Box<dyn TransitionConstraint<F, E>>>,
+}
+
+impl<A> Plonky3AirAdapter<A> {
+    pub fn convert_plonky3_trace_table(trace: &RowMajorMatrix<BabyBear>) -> TraceTable<F, E> {
+        TraceTable::new_main(vec_p3_to_lambda(&trace.values), trace.width(), 1)
+    }
+}
+
+impl<A: Clone> AIR for Plonky3AirAdapter<A> {
+    type Field = F;
+    type FieldExtension = E;
+    type PublicInputs = Plonky3PublicInputs<A>;
+
+    const STEP_SIZE: usize = 1;
+    const AIR_ID: &'static str = "plonky3_adapter";
+    const EXTENSION_DEGREE: usize = 4;
+
+    fn new(
+        trace_length: usize,
+        pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Self {
+        let constraints = pub_inputs
+            .constraints
+            .iter()
+            .fold(SymbolicConstraints::new(), |constraints, expression| {
+                constraints.with(SymbolicConstraint::new(expression.clone()))
+            });
+        let context = AirContext {
+            proof_options: proof_options.clone(),
+            trace_columns: pub_inputs.main_width + pub_inputs.preprocessed_columns.len(),
+            transition_offsets: constraints.transition_offsets(),
+            num_transition_constraints: constraints.len(),
+        };
+
+        Self {
+            public_inputs: pub_inputs.clone(),
+            context,
+            trace_length,
+            constraints: constraints.build(),
+        }
+    }
+
+    fn trace_layout(&self) -> (usize, usize) {
+        (self.public_inputs.main_width, 0)
+    }
+
+    fn preprocessed_columns(&self) -> Vec<Vec<FieldElement<Self::Field>>> {
+        self.public_inputs.preprocessed_columns.clone()
+    }
+
+    fn num_preprocessed_columns(&self) -> usize {
+        self.public_inputs.preprocessed_columns.len()
+    }
+
+    /// Counting the selectors as variables, as Plonky3 does, a constraint of degree `d` has
+    /// degree at most `d * (trace_length - 1)`, so its quotient by the zerofier has degree
+    /// less than `(d - 1) * trace_length`.
+    fn composition_poly_degree_bound(&self) -> usize {
+        let max_degree = self.public_inputs.max_constraint_degree.max(2);
+        self.trace_length * (max_degree - 1).next_power_of_two()
+    }
+
+    fn boundary_constraints(
+        &self,
+        _rap_challenges: &[FieldElement<Self::FieldExtension>],
+    ) -> BoundaryConstraints<Self::FieldExtension> {
+        BoundaryConstraints::from_constraints(Vec::new())
+    }
+
+    fn transition_constraints(
+        &self,
+    ) -> &Vec<Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>> {
+        &self.constraints
+    }
+
+    fn context(&self) -> &AirContext {
+        &self.context
+    }
+
+    fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    fn pub_inputs(&self) -> &Self::PublicInputs {
+        &self.public_inputs
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<FieldElement<Self::Field>>> {
+        let selector = |selected_row| {
+            (0..self.trace_length)
+                .map(|row| {
+                    if row == selected_row {
+                        FieldElement::one()
+                    } else {
+                        FieldElement::zero()
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+        let is_first_row = selector(0);
+        let is_last_row = selector(self.trace_length - 1);
+        let is_transition = is_last_row
+            .iter()
+            .map(|value| FieldElement::<F>::one() - value)
+            .collect();
+        vec![is_first_row, is_last_row, is_transition]
+    }
+}
@@ -0,0 +1,9 @@
+/// Errors found while translating the constraints of a Plonky3 AIR.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Plonky3AdapterError {
+    /// The AIR reads the permutation trace or its challenges, which the adapter doesn't
+    /// support.
+    UnsupportedEntry(&'static str),
+    /// The AIR reads a public value that was not given.
+    MissingPublicValue(usize),
+}
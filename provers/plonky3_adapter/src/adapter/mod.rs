@@ -0,0 +1,74 @@
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::{
+    field::{
+        element::FieldElement,
+        fields::fft_friendly::{
+            babybear::Babybear31PrimeField, quartic_babybear::Degree4BabyBearExtensionField,
+        },
+    },
+    traits::AsBytes,
+};
+use p3_baby_bear::BabyBear;
+use p3_field::PrimeField32;
+use sha3::{Digest, Keccak256};
+
+pub mod air;
+pub mod errors;
+pub mod public_inputs;
+
+/// A Keccak transcript sampling elements of the quartic extension of BabyBear, the field
+/// of the challenges of `Plonky3AirAdapter`. Every sample is absorbed back into the
+/// transcript, so consecutive samples are independent.
+pub struct BabyBearTranscript {
+    hasher: Keccak256,
+}
+
+impl BabyBearTranscript {
+    pub fn new(data: &[u8]) -> Self {
+        let mut res = Self {
+            hasher: Keccak256::new(),
+        };
+        res.append_bytes(data);
+        res
+    }
+
+    fn sample_base_field_element(&mut self) -> FieldElement<Babybear31PrimeField> {
+        loop {
+            let bytes: [u8; 4] = self.state()[..4].try_into().unwrap();
+            self.append_bytes(&bytes);
+            let x = u32::from_be_bytes(bytes) & 0x7fff_ffff;
+            if x < BabyBear::ORDER_U32 {
+                return FieldElement::from(u64::from(x));
+            }
+        }
+    }
+}
+
+impl IsTranscript<Degree4BabyBearExtensionField> for BabyBearTranscript {
+    fn append_field_element(&mut self, element: &FieldElement<Degree4BabyBearExtensionField>) {
+        self.append_bytes(&element.as_bytes());
+    }
+
+    fn append_bytes(&mut self, new_bytes: &[u8]) {
+        self.hasher.update(new_bytes);
+    }
+
+    fn state(&self) -> [u8; 32] {
+        self.hasher.clone().finalize().into()
+    }
+
+    fn sample_field_element(&mut self) -> FieldElement<Degree4BabyBearExtensionField> {
+        FieldElement::new([
+            self.sample_base_field_element(),
+            self.sample_base_field_element(),
+            self.sample_base_field_element(),
+            self.sample_base_field_element(),
+        ])
+    }
+
+    fn sample_u64(&mut self, upper_bound: u64) -> u64 {
+        let bytes: [u8; 8] = self.state()[..8].try_into().unwrap();
+        self.append_bytes(&bytes);
+        u64::from_be_bytes(bytes) % upper_bound
+    }
+}
@@ -0,0 +1,164 @@
+use lambdaworks_math::{
+    field::{element::FieldElement, fields::fft_friendly::babybear::Babybear31PrimeField},
+    traits::AsBytes,
+};
+use p3_air::{Air, BaseAir};
+use p3_baby_bear::BabyBear;
+use p3_matrix::Matrix;
+use p3_uni_stark::{get_symbolic_constraints, SymbolicAirBuilder};
+use stark_platinum_prover::constraints::symbolic::{Expr, TraceCell};
+
+use crate::utils::vec_p3_to_lambda;
+
+use super::{air::translate_expression, errors::Plonky3AdapterError};
+
+/// The public inputs of a `Plonky3AirAdapter`: the Plonky3 AIR itself, its public values and
+/// its constraints translated to lambdaworks symbolic expressions.
+///
+/// Their bytes bind the whole statement: the width of the main trace, the preprocessed columns,
+/// the public values and the translated constraints.
+#[derive(Clone)]
+pub struct Plonky3PublicInputs<A> {
+    pub(crate) plonky3_air: A,
+    pub(crate) public_values: Vec<FieldElement<Babybear31PrimeField>>,
+    pub(crate) main_width: usize,
+    pub(crate) preprocessed_columns: Vec<Vec<FieldElement<Babybear31PrimeField>>>,
+    pub(crate) constraints: Vec<Expr<Babybear31PrimeField>>,
+    pub(crate) max_constraint_degree: usize,
+}
+
+impl<A> Plonky3PublicInputs<A>
+where
+    A: Air<SymbolicAirBuilder<BabyBear>> + Clone,
+{
+    /// Evaluates the constraints of `plonky3_air` symbolically and translates them. Fails if
+    /// the AIR uses features of Plonky3 the adapter doesn't support.
+    pub fn new(plonky3_air: A, public_values: &[BabyBear]) -> Result<Self, Plonky3AdapterError> {
+        let main_width = <A as BaseAir<BabyBear>>::width(&plonky3_air);
+        let preprocessed_columns: Vec<Vec<_>> = plonky3_air
+            .preprocessed_trace()
+            .map(|trace| {
+                let values = vec_p3_to_lambda(&trace.values);
+                (0..trace.width())
+                    .map(|column| {
+                        values
+                            .iter()
+                            .skip(column)
+                            .step_by(trace.width())
+                            .cloned()
+                            .collect()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let symbolic_constraints = get_symbolic_constraints::<BabyBear, A>(
+            &plonky3_air,
+            preprocessed_columns.len(),
+            public_values.len(),
+        );
+        let public_values = vec_p3_to_lambda(public_values);
+
+        let mut constraints = Vec::with_capacity(symbolic_constraints.len());
+        let mut max_constraint_degree = 1;
+        for constraint in symbolic_constraints.iter() {
+            max_constraint_degree = max_constraint_degree.max(constraint.degree_multiple());
+            constraints.push(translate_expression(
+                constraint,
+                main_width,
+                &public_values,
+            )?);
+        }
+
+        Ok(Self {
+            plonky3_air,
+            public_values,
+            main_width,
+            preprocessed_columns,
+            constraints,
+            max_constraint_degree,
+        })
+    }
+
+    pub fn plonky3_air(&self) -> &A {
+        &self.plonky3_air
+    }
+}
+
+impl<A> AsBytes for Plonky3PublicInputs<A> {
+    fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = (self.main_width as u64).to_be_bytes().to_vec();
+        bytes.extend_from_slice(&(self.preprocessed_columns.len() as u64).to_be_bytes());
+        for column in self.preprocessed_columns.iter() {
+            write_elements(&mut bytes, column);
+        }
+        write_elements(&mut bytes, &self.public_values);
+        bytes.extend_from_slice(&(self.constraints.len() as u64).to_be_bytes());
+        for constraint in self.constraints.iter() {
+            write_expression(&mut bytes, constraint);
+        }
+        bytes
+    }
+}
+
+fn write_elements(bytes: &mut Vec<u8>, elements: &[FieldElement<Babybear31PrimeField>]) {
+    bytes.extend_from_slice(&(elements.len() as u64).to_be_bytes());
+    for element in elements.iter() {
+        bytes.extend_from_slice(&element.as_bytes());
+    }
+}
+
+fn write_cell(bytes: &mut Vec<u8>, cell: &TraceCell) {
+    for index in [cell.step, cell.row, cell.col] {
+        bytes.extend_from_slice(&(index as u64).to_be_bytes());
+    }
+}
+
+/// Writes `expression` in prefix order, each node starting with a tag telling its kind.
+fn write_expression(bytes: &mut Vec<u8>, expression: &Expr<Babybear31PrimeField>) {
+    match expression {
+        Expr::Main(cell) => {
+            bytes.push(0);
+            write_cell(bytes, cell);
+        }
+        Expr::Aux(cell) => {
+            bytes.push(1);
+            write_cell(bytes, cell);
+        }
+        Expr::Periodic(index) => {
+            bytes.push(2);
+            bytes.extend_from_slice(&(*index as u64).to_be_bytes());
+        }
+        Expr::Challenge(index) => {
+            bytes.push(3);
+            bytes.extend_from_slice(&(*index as u64).to_be_bytes());
+        }
+        Expr::Constant(value) => {
+            bytes.push(4);
+            bytes.extend_from_slice(&value.as_bytes());
+        }
+        Expr::Add(x, y) => {
+            bytes.push(5);
+            write_expression(bytes, x);
+            write_expression(bytes, y);
+        }
+        Expr::Sub(x, y) => {
+            bytes.push(6);
+            write_expression(bytes, x);
+            write_expression(bytes, y);
+        }
+        Expr::Mul(x, y) => {
+            bytes.push(7);
+            write_expression(bytes, x);
+            write_expression(bytes, y);
+        }
+        Expr::Neg(x) => {
+            bytes.push(8);
+            write_expression(bytes, x);
+        }
+        Expr::Pow(x, exponent) => {
+            bytes.push(9);
+            write_expression(bytes, x);
+            bytes.extend_from_slice(&exponent.to_be_bytes());
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use p3_air::{Air, AirBuilder, AirBuilderWithPublicValues, BaseAir};
+use p3_baby_bear::BabyBear;
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+
+/// The Fibonacci AIR of the Plonky3 examples. Each row holds two consecutive terms of the
+/// sequence, and the public values are the two first terms and the last one.
+#[derive(Clone)]
+pub struct FibonacciAir;
+
+impl<F> BaseAir<F> for FibonacciAir {
+    fn width(&self) -> usize {
+        2
+    }
+}
+
+impl<AB: AirBuilderWithPublicValues> Air<AB> for FibonacciAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let public_values = builder.public_values();
+        let (a, b, x) = (public_values[0], public_values[1], public_values[2]);
+
+        let local = main.row_slice(0);
+        let next = main.row_slice(1);
+
+        let mut when_first_row = builder.when_first_row();
+        when_first_row.assert_eq(local[0], a);
+        when_first_row.assert_eq(local[1], b);
+
+        let mut when_transition = builder.when_transition();
+        when_transition.assert_eq(local[1], next[0]);
+        when_transition.assert_eq(local[0] + local[1], next[1]);
+
+        builder.when_last_row().assert_eq(local[1], x);
+    }
+}
+
+/// Builds the trace of `FibonacciAir` with `num_rows` rows starting with the terms `a` and `b`.
+pub fn fibonacci_trace(a: u32, b: u32, num_rows: usize) -> RowMajorMatrix<BabyBear> {
+    let mut values = Vec::with_capacity(2 * num_rows);
+    let (mut left, mut right) = (BabyBear::new(a), BabyBear::new(b));
+    for _ in 0..num_rows {
+        values.push(left);
+        values.push(right);
+        (left, right) = (right, left + right);
+    }
+    RowMajorMatrix::new(values, 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_baby_bear::BabyBear;
+    use stark_platinum_prover::{
+        proof::options::ProofOptions,
+        prover::{IsStarkProver, Prover},
+        verifier::{IsStarkVerifier, Verifier},
+    };
+
+    use crate::{
+        adapter::{air::Plonky3AirAdapter, public_inputs::Plonky3PublicInputs, BabyBearTranscript},
+        examples::fibonacci::{fibonacci_trace, FibonacciAir},
+    };
+
+    #[test]
+    fn prove_and_verify_a_plonky3_fibonacci_air() {
+        let proof_options = ProofOptions::default_test_options();
+        let plonky3_trace = fibonacci_trace(0, 1, 16);
+        let mut trace =
+            Plonky3AirAdapter::<FibonacciAir>::convert_plonky3_trace_table(&plonky3_trace);
+        // The last row holds the 15th and 16th Fibonacci numbers.
+        let pub_inputs = Plonky3PublicInputs::new(
+            FibonacciAir,
+            &[BabyBear::new(0), BabyBear::new(1), BabyBear::new(987)],
+        )
+        .unwrap();
+
+        let proof = Prover::<Plonky3AirAdapter<FibonacciAir>>::prove(
+            &mut trace,
+            &pub_inputs,
+            &proof_options,
+            BabyBearTranscript::new(&[]),
+        )
+        .unwrap();
+
+        assert!(Verifier::<Plonky3AirAdapter<FibonacciAir>>::verify(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            BabyBearTranscript::new(&[]),
+        )
+        .is_ok());
+
+        let wrong_pub_inputs = Plonky3PublicInputs::new(
+            FibonacciAir,
+            &[BabyBear::new(0), BabyBear::new(1), BabyBear::new(988)],
+        )
+        .unwrap();
+        assert!(Verifier::<Plonky3AirAdapter<FibonacciAir>>::verify(
+            &proof,
+            &wrong_pub_inputs,
+            &proof_options,
+            BabyBearTranscript::new(&[]),
+        )
+        .is_err());
+    }
+}
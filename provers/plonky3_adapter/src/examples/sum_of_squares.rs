@@ -0,0 +1,183 @@
+use p3_air::{Air, AirBuilder, AirBuilderWithPublicValues, BaseAir, PairBuilder};
+use p3_baby_bear::BabyBear;
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+
+/// An AIR adding up the squares of `num_rows` consecutive integers starting at `first`.
+///
+/// The integers are a preprocessed column, fixed by the AIR, and the main column holds the
+/// partial sums. The public value is the whole sum, which is the last partial sum.
+#[derive(Clone)]
+pub struct SumOfSquaresAir {
+    pub first: u32,
+    pub num_rows: usize,
+}
+
+impl BaseAir<BabyBear> for SumOfSquaresAir {
+    fn width(&self) -> usize {
+        1
+    }
+
+    fn preprocessed_trace(&self) -> Option<RowMajorMatrix<BabyBear>> {
+        let integers = (0..self.num_rows as u32)
+            .map(|row| BabyBear::new(self.first + row))
+            .collect();
+        Some(RowMajorMatrix::new(integers, 1))
+    }
+}
+
+impl<AB> Air<AB> for SumOfSquaresAir
+where
+    AB: AirBuilderWithPublicValues<F = BabyBear> + PairBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let preprocessed = builder.preprocessed();
+        let sum = builder.public_values()[0];
+
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let (integer, next_integer) = (preprocessed.row_slice(0), preprocessed.row_slice(1));
+
+        builder
+            .when_first_row()
+            .assert_eq(local[0], integer[0] * integer[0]);
+        builder
+            .when_transition()
+            .assert_eq(next[0], local[0] + next_integer[0] * next_integer[0]);
+        builder.when_last_row().assert_eq(local[0], sum);
+    }
+}
+
+/// Builds the trace of `SumOfSquaresAir { first, num_rows }`.
+pub fn sum_of_squares_trace(first: u32, num_rows: usize) -> RowMajorMatrix<BabyBear> {
+    let mut values = Vec::with_capacity(num_rows);
+    let mut sum = BabyBear::new(0);
+    for row in 0..num_rows as u32 {
+        let integer = BabyBear::new(first + row);
+        sum += integer * integer;
+        values.push(sum);
+    }
+    RowMajorMatrix::new(values, 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::traits::AsBytes;
+    use p3_baby_bear::BabyBear;
+    use p3_matrix::dense::RowMajorMatrix;
+    use stark_platinum_prover::{
+        proof::{options::ProofOptions, stark::StarkProof},
+        prover::{IsStarkProver, Prover},
+        traits::AIR,
+        verifier::{IsStarkVerifier, Verifier},
+    };
+
+    use crate::{
+        adapter::{air::Plonky3AirAdapter, public_inputs::Plonky3PublicInputs, BabyBearTranscript},
+        examples::{
+            fibonacci::FibonacciAir,
+            sum_of_squares::{sum_of_squares_trace, SumOfSquaresAir},
+        },
+    };
+
+    type Adapter = Plonky3AirAdapter<SumOfSquaresAir>;
+    type Proof = StarkProof<<Adapter as AIR>::Field, <Adapter as AIR>::FieldExtension>;
+
+    const NUM_ROWS: usize = 16;
+    // 3^2 + 4^2 + ... + 18^2
+    const SUM: u32 = 2104;
+
+    fn public_inputs(first: u32, sum: u32) -> Plonky3PublicInputs<SumOfSquaresAir> {
+        Plonky3PublicInputs::new(
+            SumOfSquaresAir {
+                first,
+                num_rows: NUM_ROWS,
+            },
+            &[BabyBear::new(sum)],
+        )
+        .unwrap()
+    }
+
+    fn prove(trace: &RowMajorMatrix<BabyBear>, proof_options: &ProofOptions) -> Proof {
+        let mut trace = Adapter::convert_plonky3_trace_table(trace);
+        Prover::<Adapter>::prove(
+            &mut trace,
+            &public_inputs(3, SUM),
+            proof_options,
+            BabyBearTranscript::new(&[]),
+        )
+        .unwrap()
+    }
+
+    fn verifies(
+        proof: &Proof,
+        pub_inputs: &Plonky3PublicInputs<SumOfSquaresAir>,
+        proof_options: &ProofOptions,
+    ) -> bool {
+        Verifier::<Adapter>::verify(
+            proof,
+            pub_inputs,
+            proof_options,
+            BabyBearTranscript::new(&[]),
+        )
+        .is_ok()
+    }
+
+    #[test]
+    fn prove_and_verify_an_air_with_preprocessed_columns_and_row_selectors() {
+        let proof_options = ProofOptions::default_test_options();
+        let proof = prove(&sum_of_squares_trace(3, NUM_ROWS), &proof_options);
+
+        assert!(verifies(&proof, &public_inputs(3, SUM), &proof_options));
+        // The last row constraint checks the public value.
+        assert!(!verifies(
+            &proof,
+            &public_inputs(3, SUM + 1),
+            &proof_options
+        ));
+    }
+
+    #[test]
+    fn a_proof_is_rejected_with_other_preprocessed_columns() {
+        let proof_options = ProofOptions::default_test_options();
+        let proof = prove(&sum_of_squares_trace(3, NUM_ROWS), &proof_options);
+
+        let other_integers = public_inputs(4, SUM);
+        assert_ne!(other_integers.as_bytes(), public_inputs(3, SUM).as_bytes());
+        assert!(!verifies(&proof, &other_integers, &proof_options));
+    }
+
+    #[test]
+    fn a_wrong_trace_is_rejected() {
+        let proof_options = ProofOptions {
+            fri_number_of_queries: 30,
+            ..ProofOptions::default_test_options()
+        };
+
+        // The first partial sum is not the square of the first integer.
+        let mut wrong_first_row = sum_of_squares_trace(3, NUM_ROWS);
+        wrong_first_row.values[0] += BabyBear::new(1);
+        let proof = prove(&wrong_first_row, &proof_options);
+        assert!(!verifies(&proof, &public_inputs(3, SUM), &proof_options));
+
+        // A partial sum in the middle of the trace doesn't follow from the previous one.
+        let mut wrong_transition = sum_of_squares_trace(3, NUM_ROWS);
+        wrong_transition.values[7] += BabyBear::new(1);
+        let proof = prove(&wrong_transition, &proof_options);
+        assert!(!verifies(&proof, &public_inputs(3, SUM), &proof_options));
+    }
+
+    #[test]
+    fn wrapped_airs_are_bound_by_their_public_inputs() {
+        assert_eq!(
+            Plonky3AirAdapter::<SumOfSquaresAir>::AIR_ID,
+            Plonky3AirAdapter::<FibonacciAir>::AIR_ID
+        );
+
+        let fibonacci = Plonky3PublicInputs::new(
+            FibonacciAir,
+            &[BabyBear::new(0), BabyBear::new(1), BabyBear::new(SUM)],
+        )
+        .unwrap();
+        assert_ne!(fibonacci.as_bytes(), public_inputs(3, SUM).as_bytes());
+    }
+}
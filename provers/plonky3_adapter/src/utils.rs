@@ -0,0 +1,13 @@
+use lambdaworks_math::field::{
+    element::FieldElement, fields::fft_friendly::babybear::Babybear31PrimeField,
+};
+use p3_baby_bear::BabyBear;
+use p3_field::PrimeField32;
+
+pub fn p3_to_lambda(input: &BabyBear) -> FieldElement<Babybear31PrimeField> {
+    FieldElement::from(u64::from(input.as_canonical_u32()))
+}
+
+pub fn vec_p3_to_lambda(input: &[BabyBear]) -> Vec<FieldElement<Babybear31PrimeField>> {
+    input.iter().map(p3_to_lambda).collect()
+}
@@ -0,0 +1,1153 @@
+//! Batched STARK proofs of several traces of the same AIR.
+//!
+//! [`BatchProver`] proves N traces of one `AIR` type, of the same or different lengths, with a
+//! single proof checked by [`BatchVerifier`] against the N public inputs. Compared with N calls
+//! to `prove`, the FRI commit phase, the grinding and the queries are run once:
+//!
+//! - Every trace is evaluated over the LDE domain of the longest one, of size
+//!   `max_trace_length * blowup_factor`, which is a larger blowup for the shorter traces. The main
+//!   columns of all the traces are committed in a single Merkle tree, and so are the auxiliary
+//!   columns and the parts of the composition polynomials.
+//! - Each trace has its own composition polynomial, with its own constraint coefficients, and
+//!   the verifier checks its out-of-domain evaluation against the public inputs of the trace. All
+//!   of them are opened at the same out-of-domain point.
+//! - The DEEP composition polynomials `pᵢ` of the traces are combined into
+//!   `p = ∑ᵢ X^(N - nᵢ) pᵢ`, where `nᵢ` is the length of the i-th trace and `N` the length of the
+//!   longest one. Running FRI on `p` with degree bound `N` bounds the degree of every `pᵢ` by `nᵢ`.
+//!
+//! AIRs with preprocessed columns are not supported.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::marker::PhantomData;
+
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_crypto::merkle_tree::{merkle::MerkleTree, traits::IsMerkleTreeBackend};
+use lambdaworks_math::fft::cpu::bit_reversing::{in_place_bit_reverse_permute, reverse_index};
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsFFTField, IsField, IsSubFieldOf},
+};
+use lambdaworks_math::polynomial::Polynomial;
+use lambdaworks_math::traits::AsBytes;
+use log::info;
+
+use crate::config::{Commitment, IsCommitmentHash, Keccak256Commitments};
+use crate::constraints::evaluator::ConstraintEvaluator;
+#[cfg(debug_assertions)]
+use crate::debug::validate_trace;
+use crate::domain::Domain;
+use crate::fri;
+use crate::fri::fri_decommit::FriDecommitment;
+use crate::grinding;
+use crate::proof::options::ProofOptions;
+use crate::proof::stark::{DeepPolynomialOpening, DeepPolynomialOpenings, PolynomialOpenings};
+use crate::prover::{
+    evaluate_polynomial_on_lde_domain, merkle_proof, IsStarkProver, ProvingError, Round3,
+    StarkProver,
+};
+use crate::table::Table;
+use crate::trace::{columns2rows, get_trace_evaluations, LDETraceTable, TraceTable};
+use crate::traits::AIR;
+use crate::transcript::bind_statement;
+use crate::verifier::{Challenges, IsStarkVerifier, StarkVerifier, VerificationError};
+
+/// A STARK proof of several traces of the same AIR, see the [module documentation](self).
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct BatchStarkProof<F: IsSubFieldOf<E>, E: IsField> {
+    // Lengths of the execution traces
+    pub trace_lengths: Vec<usize>,
+    // Commitment of the main columns of all the traces
+    // [tⱼ]
+    pub lde_trace_main_merkle_root: Commitment,
+    // Commitment of the auxiliary columns of all the traces
+    // [tⱼ]
+    pub lde_trace_aux_merkle_root: Option<Commitment>,
+    // tⱼ(zgᵏ) of every trace
+    pub trace_ood_evaluations: Vec<Table<E>>,
+    // Commitment to the Hᵢ of all the traces
+    pub composition_poly_root: Commitment,
+    // Hᵢ(z^N) of every trace
+    pub composition_poly_parts_ood_evaluation: Vec<Vec<FieldElement<E>>>,
+    // [pₖ]
+    pub fri_layers_merkle_roots: Vec<Commitment>,
+    // pₙ
+    pub fri_last_value: FieldElement<E>,
+    // Open(pₖ(Dₖ), −𝜐ₛ^(2ᵏ))
+    pub query_list: Vec<FriDecommitment<E>>,
+    // Open(Hᵢ(D_LDE), 𝜐ᵢ), Open(tⱼ(D_LDE), 𝜐ᵢ) and their symmetric openings, with the values
+    // of all the traces concatenated in order.
+    pub deep_poly_openings: DeepPolynomialOpenings<F, E>,
+    // nonces obtained from grinding before each folding challenge
+    pub commit_phase_nonces: Vec<u64>,
+    // nonce obtained from grinding
+    pub nonce: Option<u64>,
+}
+
+/// The intermediate results of the prover for a single trace of the batch.
+struct Instance<A: AIR> {
+    air: A,
+    domain: Domain<A::Field>,
+    main_trace_polys: Vec<Polynomial<FieldElement<A::Field>>>,
+    aux_trace_polys: Vec<Polynomial<FieldElement<A::FieldExtension>>>,
+    lde_trace: LDETraceTable<A::Field, A::FieldExtension>,
+    rap_challenges: Vec<FieldElement<A::FieldExtension>>,
+    composition_poly_parts: Vec<Polynomial<FieldElement<A::FieldExtension>>>,
+}
+
+/// Returns the AIRs of the traces of lengths `trace_lengths` and their domains, which share
+/// the LDE domain of the longest trace.
+fn build_airs_and_domains<A: AIR>(
+    trace_lengths: &[usize],
+    pub_inputs: &[A::PublicInputs],
+    proof_options: &ProofOptions,
+) -> Vec<(A, Domain<A::Field>)> {
+    let max_trace_length = trace_lengths.iter().copied().max().unwrap_or_default();
+    let lde_size = max_trace_length * proof_options.blowup_factor as usize;
+    trace_lengths
+        .iter()
+        .zip(pub_inputs)
+        .map(|(trace_length, pub_inputs)| {
+            let air = A::new(*trace_length, pub_inputs, proof_options);
+            let domain = Domain::new_with_lde_size(&air, lde_size);
+            (air, domain)
+        })
+        .collect()
+}
+
+/// Returns the index of the longest trace, whose domain contains the trace domains of the others.
+fn longest(trace_lengths: &[usize]) -> usize {
+    trace_lengths
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, trace_length)| **trace_length)
+        .map(|(index, _)| index)
+        .unwrap_or_default()
+}
+
+/// Returns the coefficients `(transition_coefficients, boundary_coefficients)` of the
+/// composition polynomial of `air`, the successive powers of `beta`.
+#[allow(clippy::type_complexity)]
+fn composition_coefficients<A: AIR>(
+    air: &A,
+    rap_challenges: &[FieldElement<A::FieldExtension>],
+    beta: &FieldElement<A::FieldExtension>,
+) -> (
+    Vec<FieldElement<A::FieldExtension>>,
+    Vec<FieldElement<A::FieldExtension>>,
+) {
+    let num_boundary_constraints = air.boundary_constraints(rap_challenges).constraints.len();
+    let num_transition_constraints = air.context().num_transition_constraints;
+
+    let mut coefficients: Vec<_> =
+        core::iter::successors(Some(FieldElement::one()), |x| Some(x * beta))
+            .take(num_boundary_constraints + num_transition_constraints)
+            .collect();
+    let transition_coefficients = coefficients.drain(..num_transition_constraints).collect();
+    (transition_coefficients, coefficients)
+}
+
+/// Returns the coefficients `(trace_term_coeffs, gammas)` of the DEEP composition polynomial of
+/// every trace, taken in order from the successive powers of `gamma`.
+#[allow(clippy::type_complexity)]
+fn deep_composition_coefficients<A: AIR>(
+    airs: &[&A],
+    number_of_parts: &[usize],
+    gamma: &FieldElement<A::FieldExtension>,
+) -> Vec<(
+    Vec<Vec<FieldElement<A::FieldExtension>>>,
+    Vec<FieldElement<A::FieldExtension>>,
+)> {
+    let mut powers = core::iter::successors(Some(FieldElement::one()), |x| Some(x * gamma));
+    airs.iter()
+        .zip(number_of_parts)
+        .map(|(air, number_of_parts)| {
+            let frame_size = air.context().transition_offsets.len() * A::STEP_SIZE;
+            let num_terms_trace = frame_size * air.context().trace_columns;
+            let trace_term_coeffs: Vec<_> = powers.by_ref().take(num_terms_trace).collect();
+            let trace_term_coeffs = trace_term_coeffs
+                .chunks(frame_size)
+                .map(|chunk| chunk.to_vec())
+                .collect();
+            let gammas = powers.by_ref().take(*number_of_parts).collect();
+            (trace_term_coeffs, gammas)
+        })
+        .collect()
+}
+
+/// A prover of several traces of the same AIR in a single proof, see the
+/// [module documentation](self).
+pub struct BatchProver<A: AIR, H = Keccak256Commitments> {
+    phantom: PhantomData<(A, H)>,
+}
+
+impl<A, H> BatchProver<A, H>
+where
+    A: AIR + Send + Sync,
+    A::FieldExtension: IsFFTField,
+    H: IsCommitmentHash<A::Field, A::FieldExtension>,
+    FieldElement<A::Field>: AsBytes + Send + Sync,
+    FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+{
+    /// Returns the Merkle tree and the commitment to the evaluations `columns` over the LDE
+    /// domain, one leaf per row in bit-reversed order.
+    fn commit_columns<E, B>(
+        columns: &[&Vec<FieldElement<E>>],
+    ) -> Result<(MerkleTree<B>, Commitment), ProvingError>
+    where
+        E: IsField,
+        B: IsMerkleTreeBackend<Node = Commitment, Data = Vec<FieldElement<E>>>,
+    {
+        let mut permuted_columns: Vec<Vec<_>> =
+            columns.iter().map(|column| (*column).clone()).collect();
+        for column in permuted_columns.iter_mut() {
+            in_place_bit_reverse_permute(column);
+        }
+        let tree = MerkleTree::build(&columns2rows(permuted_columns))
+            .ok_or(ProvingError::EmptyCommitment)?;
+        let root = tree.root;
+        Ok((tree, root))
+    }
+
+    /// Returns the openings of the rows of the LDE at the index challenge `iota` and its
+    /// symmetric element, with the values of the rows `row` of all the traces concatenated.
+    fn open_rows<E, B>(
+        tree: &MerkleTree<B>,
+        lde_size: usize,
+        iota: usize,
+        row: impl Fn(usize) -> Vec<FieldElement<E>>,
+    ) -> Result<PolynomialOpenings<E>, ProvingError>
+    where
+        E: IsField,
+        B: IsMerkleTreeBackend<Node = Commitment>,
+    {
+        let (index, index_sym) = (iota * 2, iota * 2 + 1);
+        Ok(PolynomialOpenings {
+            proof: merkle_proof(tree, index)?,
+            proof_sym: merkle_proof(tree, index_sym)?,
+            evaluations: row(reverse_index(index, lde_size as u64)),
+            evaluations_sym: row(reverse_index(index_sym, lde_size as u64)),
+        })
+    }
+
+    /// Generates a single proof for the traces `traces` of `A` with public inputs `pub_inputs`.
+    /// The traces must have power of two lengths, not necessarily equal.
+    /// Warning: the transcript must be safely initializated before passing it to this method.
+    /// Use `prove_statement` to have it initialized from the statements being proven.
+    pub fn prove(
+        traces: &mut [TraceTable<A::Field, A::FieldExtension>],
+        pub_inputs: &[A::PublicInputs],
+        proof_options: &ProofOptions,
+        mut transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> Result<BatchStarkProof<A::Field, A::FieldExtension>, ProvingError> {
+        info!(
+            "Started batch proof generation of {} traces...",
+            traces.len()
+        );
+
+        if traces.is_empty() || traces.len() != pub_inputs.len() {
+            return Err(ProvingError::WrongParameter(format!(
+                "expected one public input per trace, got {} traces and {} public inputs",
+                traces.len(),
+                pub_inputs.len()
+            )));
+        }
+        let trace_lengths: Vec<_> = traces.iter().map(|trace| trace.num_rows()).collect();
+        for trace_length in trace_lengths.iter() {
+            StarkProver::<A, H>::validate_proof_options(*trace_length, proof_options)?;
+        }
+        let airs_and_domains =
+            build_airs_and_domains::<A>(&trace_lengths, pub_inputs, proof_options);
+        for ((air, _), trace) in airs_and_domains.iter().zip(traces.iter()) {
+            StarkProver::<A, H>::validate_trace_and_air(air, trace)?;
+            if air.num_preprocessed_columns() > 0 {
+                return Err(ProvingError::InvalidAirLayout(
+                    "batched proofs do not support preprocessed columns".to_string(),
+                ));
+            }
+        }
+        let longest = longest(&trace_lengths);
+        let lde_size = airs_and_domains[longest].1.lde_roots_of_unity_coset.len();
+
+        // ===================================
+        // ==========|   Round 1   |==========
+        // ===================================
+
+        let mut main_trace_polys = Vec::with_capacity(traces.len());
+        let mut main_evaluations = Vec::with_capacity(traces.len());
+        for ((_, domain), trace) in airs_and_domains.iter().zip(traces.iter()) {
+            let polys = trace.compute_trace_polys_main::<A::Field>()?;
+            main_evaluations.push(StarkProver::<A, H>::compute_lde_trace_evaluations::<
+                A::Field,
+            >(&polys, domain)?);
+            main_trace_polys.push(polys);
+        }
+        let (main_merkle_tree, main_merkle_root) = Self::commit_columns::<_, H::MainBackend>(
+            &main_evaluations.iter().flatten().collect::<Vec<_>>(),
+        )?;
+        // >>>> Send commitment: [tⱼ]
+        transcript.append_bytes(&main_merkle_root);
+
+        let rap_challenges: Vec<_> = airs_and_domains
+            .iter()
+            .map(|(air, _)| air.build_rap_challenges(&mut transcript))
+            .collect();
+
+        let mut aux_trace_polys = Vec::with_capacity(traces.len());
+        let mut aux_evaluations = Vec::with_capacity(traces.len());
+        for (((air, domain), trace), rap_challenges) in airs_and_domains
+            .iter()
+            .zip(traces.iter_mut())
+            .zip(&rap_challenges)
+        {
+            if !air.has_trace_interaction() {
+                aux_trace_polys.push(Vec::new());
+                aux_evaluations.push(Vec::new());
+                continue;
+            }
+            air.build_auxiliary_trace(trace, rap_challenges);
+            let (num_main_columns, num_aux_columns) = air.trace_layout();
+            if trace.num_aux_columns != num_aux_columns {
+                return Err(ProvingError::TraceColumnsMismatch {
+                    expected: (num_main_columns, num_aux_columns),
+                    actual: (trace.num_main_columns, trace.num_aux_columns),
+                });
+            }
+            let polys = trace.compute_trace_polys_aux::<A::Field>()?;
+            aux_evaluations.push(StarkProver::<A, H>::compute_lde_trace_evaluations(
+                &polys, domain,
+            )?);
+            aux_trace_polys.push(polys);
+        }
+        let aux_merkle_tree = if airs_and_domains
+            .iter()
+            .any(|(air, _)| air.has_trace_interaction())
+        {
+            let (tree, root) = Self::commit_columns::<_, H::ExtensionBackend>(
+                &aux_evaluations.iter().flatten().collect::<Vec<_>>(),
+            )?;
+            // >>>> Send commitment: [tⱼ]
+            transcript.append_bytes(&root);
+            Some(tree)
+        } else {
+            None
+        };
+
+        let mut instances: Vec<Instance<A>> = Vec::with_capacity(traces.len());
+        for (
+            ((((air, domain), main_trace_polys), main_evaluations), aux_trace_polys),
+            aux_evaluations,
+        ) in airs_and_domains
+            .into_iter()
+            .zip(main_trace_polys)
+            .zip(main_evaluations)
+            .zip(aux_trace_polys)
+            .zip(aux_evaluations)
+        {
+            let lde_trace = LDETraceTable::from_columns(
+                main_evaluations,
+                aux_evaluations,
+                A::STEP_SIZE,
+                domain.blowup_factor,
+            );
+            instances.push(Instance {
+                air,
+                domain,
+                main_trace_polys,
+                aux_trace_polys,
+                lde_trace,
+                rap_challenges: Vec::new(),
+                composition_poly_parts: Vec::new(),
+            });
+        }
+        for (instance, rap_challenges) in instances.iter_mut().zip(rap_challenges) {
+            instance.rap_challenges = rap_challenges;
+
+            #[cfg(debug_assertions)]
+            validate_trace(
+                &instance.air,
+                &instance.main_trace_polys,
+                &instance.aux_trace_polys,
+                &instance.domain,
+                &instance.rap_challenges,
+            );
+        }
+
+        // ===================================
+        // ==========|   Round 2   |==========
+        // ===================================
+
+        let mut lde_composition_poly_parts_evaluations = Vec::new();
+        for instance in instances.iter_mut() {
+            // <<<< Receive challenge: 𝛽
+            let beta = transcript.sample_field_element();
+            let (transition_coefficients, boundary_coefficients) =
+                composition_coefficients(&instance.air, &instance.rap_challenges, &beta);
+
+            let evaluator = ConstraintEvaluator::new(&instance.air, &instance.rap_challenges);
+            let constraint_evaluations = evaluator.evaluate(
+                &instance.air,
+                &instance.lde_trace,
+                &instance.domain,
+                &transition_coefficients,
+                &boundary_coefficients,
+                &instance.rap_challenges,
+            )?;
+            let composition_poly = Polynomial::interpolate_offset_fft(
+                &constraint_evaluations,
+                &instance.domain.coset_offset,
+            )?;
+
+            let number_of_parts =
+                instance.air.composition_poly_degree_bound() / instance.air.trace_length();
+            instance.composition_poly_parts = composition_poly.break_in_parts(number_of_parts);
+            for part in instance.composition_poly_parts.iter() {
+                lde_composition_poly_parts_evaluations.push(evaluate_polynomial_on_lde_domain(
+                    part,
+                    instance.domain.blowup_factor,
+                    instance.domain.interpolation_domain_size,
+                    &instance.domain.coset_offset,
+                )?);
+            }
+        }
+
+        let (composition_poly_merkle_tree, composition_poly_root) =
+            StarkProver::<A, H>::commit_composition_polynomial(
+                &lde_composition_poly_parts_evaluations,
+            )
+            .ok_or(ProvingError::EmptyCommitment)?;
+
+        // >>>> Send commitments: [H₁], [H₂]
+        transcript.append_bytes(&composition_poly_root);
+
+        // ===================================
+        // ==========|   Round 3   |==========
+        // ===================================
+
+        // <<<< Receive challenge: z
+        let longest_domain = &instances[longest].domain;
+        let z = transcript.sample_z_ood(
+            &longest_domain.lde_roots_of_unity_coset,
+            &longest_domain.trace_roots_of_unity,
+        );
+
+        let round_3_results: Vec<_> = instances
+            .iter()
+            .map(|instance| {
+                let z_power = z.pow(instance.composition_poly_parts.len());
+                Round3 {
+                    trace_ood_evaluations: get_trace_evaluations(
+                        &instance.main_trace_polys,
+                        &instance.aux_trace_polys,
+                        &z,
+                        &instance.air.context().transition_offsets,
+                        &instance.domain.trace_primitive_root,
+                        A::STEP_SIZE,
+                    ),
+                    composition_poly_parts_ood_evaluation: instance
+                        .composition_poly_parts
+                        .iter()
+                        .map(|part| part.evaluate(&z_power))
+                        .collect(),
+                }
+            })
+            .collect();
+
+        for round_3_result in round_3_results.iter() {
+            // >>>> Send values: tⱼ(zgᵏ)
+            for col in round_3_result.trace_ood_evaluations.columns().iter() {
+                for elem in col.iter() {
+                    transcript.append_field_element(elem);
+                }
+            }
+
+            // >>>> Send values: Hᵢ(z^N)
+            for element in round_3_result.composition_poly_parts_ood_evaluation.iter() {
+                transcript.append_field_element(element);
+            }
+        }
+
+        // ===================================
+        // ==========|   Round 4   |==========
+        // ===================================
+
+        // <<<< Receive challenges: 𝛾, 𝛾'
+        let gamma = transcript.sample_field_element();
+        let airs: Vec<_> = instances.iter().map(|instance| &instance.air).collect();
+        let number_of_parts: Vec<_> = instances
+            .iter()
+            .map(|instance| instance.composition_poly_parts.len())
+            .collect();
+        let coefficients = deep_composition_coefficients(&airs, &number_of_parts, &gamma);
+
+        // Compute p₀ = ∑ᵢ X^(N - nᵢ) pᵢ, where pᵢ is the DEEP composition polynomial of the i-th trace.
+        let max_trace_length = trace_lengths[longest];
+        let mut deep_composition_poly = Polynomial::zero();
+        for ((instance, round_3_result), (trace_term_coeffs, gammas)) in
+            instances.iter().zip(&round_3_results).zip(&coefficients)
+        {
+            let trace_polys: Vec<_> = instance
+                .main_trace_polys
+                .iter()
+                .map(|poly| poly.clone().to_extension())
+                .chain(instance.aux_trace_polys.iter().cloned())
+                .collect();
+            let instance_deep_composition_poly = StarkProver::<A, H>::compute_deep_composition_poly(
+                &trace_polys,
+                &instance.composition_poly_parts,
+                round_3_result,
+                &z,
+                &instance.domain.trace_primitive_root,
+                gammas,
+                trace_term_coeffs,
+            );
+
+            let degree_shift = max_trace_length - instance.air.trace_length();
+            let mut coefficients = vec![FieldElement::zero(); degree_shift];
+            coefficients.extend_from_slice(instance_deep_composition_poly.coefficients());
+            deep_composition_poly = deep_composition_poly + Polynomial::new(&coefficients);
+        }
+
+        // FRI commit and query phases
+        let coset_offset = FieldElement::<A::Field>::from(proof_options.coset_offset);
+        let (fri_last_value, fri_layers, commit_phase_nonces) =
+            fri::commit_phase::<A::Field, A::FieldExtension, H::ExtensionBackend, H::GrindingHash>(
+                longest_domain.root_order as usize,
+                deep_composition_poly,
+                &mut transcript,
+                &coset_offset,
+                lde_size,
+                proof_options.commit_phase_grinding_factor,
+            )?;
+
+        // grinding: generate nonce and append it to the transcript
+        let security_bits = proof_options.grinding_factor;
+        let mut nonce = None;
+        if security_bits > 0 {
+            let nonce_value =
+                grinding::generate_nonce::<H::GrindingHash>(&transcript.state(), security_bits)
+                    .ok_or(ProvingError::NonceNotFound(security_bits))?;
+            transcript.append_bytes(&nonce_value.to_be_bytes());
+            nonce = Some(nonce_value);
+        }
+
+        let iotas = StarkProver::<A, H>::sample_query_indexes(
+            proof_options.fri_number_of_queries,
+            longest_domain,
+            &mut transcript,
+        );
+        let query_list = fri::query_phase(&fri_layers, &iotas)?;
+        let fri_layers_merkle_roots: Vec<_> = fri_layers
+            .iter()
+            .map(|layer| layer.merkle_tree.root)
+            .collect();
+
+        let deep_poly_openings = iotas
+            .iter()
+            .map(|iota| {
+                Ok(DeepPolynomialOpening {
+                    composition_poly: StarkProver::<A, H>::open_composition_poly(
+                        &composition_poly_merkle_tree,
+                        &lde_composition_poly_parts_evaluations,
+                        *iota,
+                    )?,
+                    main_trace_polys: Self::open_rows(&main_merkle_tree, lde_size, *iota, |row| {
+                        instances
+                            .iter()
+                            .flat_map(|instance| instance.lde_trace.get_main_row(row).to_vec())
+                            .collect()
+                    })?,
+                    aux_trace_polys: aux_merkle_tree
+                        .as_ref()
+                        .map(|tree| {
+                            Self::open_rows(tree, lde_size, *iota, |row| {
+                                instances
+                                    .iter()
+                                    .flat_map(|instance| {
+                                        instance.lde_trace.get_aux_row(row).to_vec()
+                                    })
+                                    .collect()
+                            })
+                        })
+                        .transpose()?,
+                    preprocessed_trace_polys: None,
+                })
+            })
+            .collect::<Result<Vec<_>, ProvingError>>()?;
+
+        info!("End batch proof generation");
+
+        Ok(BatchStarkProof {
+            trace_lengths,
+            lde_trace_main_merkle_root: main_merkle_root,
+            lde_trace_aux_merkle_root: aux_merkle_tree.map(|tree| tree.root),
+            trace_ood_evaluations: round_3_results
+                .iter()
+                .map(|round_3_result| round_3_result.trace_ood_evaluations.clone())
+                .collect(),
+            composition_poly_root,
+            composition_poly_parts_ood_evaluation: round_3_results
+                .into_iter()
+                .map(|round_3_result| round_3_result.composition_poly_parts_ood_evaluation)
+                .collect(),
+            fri_layers_merkle_roots,
+            fri_last_value,
+            query_list,
+            deep_poly_openings,
+            commit_phase_nonces,
+            nonce,
+        })
+    }
+
+    /// Generates a single proof for the traces `traces` with public inputs `pub_inputs`, binding
+    /// the transcript to the statements being proven: the number of traces followed by the
+    /// statement of each trace, as absorbed by [`IsStarkProver::prove_statement`].
+    pub fn prove_statement(
+        traces: &mut [TraceTable<A::Field, A::FieldExtension>],
+        pub_inputs: &[A::PublicInputs],
+        proof_options: &ProofOptions,
+        mut transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> Result<BatchStarkProof<A::Field, A::FieldExtension>, ProvingError>
+    where
+        A::PublicInputs: AsBytes,
+    {
+        transcript.append_bytes(&(traces.len() as u64).to_be_bytes());
+        for (trace, pub_inputs) in traces.iter().zip(pub_inputs) {
+            bind_statement::<A>(&mut transcript, trace.num_rows(), pub_inputs, proof_options);
+        }
+        Self::prove(traces, pub_inputs, proof_options, transcript)
+    }
+}
+
+/// A verifier of the proofs generated by [`BatchProver`].
+pub struct BatchVerifier<A: AIR, H = Keccak256Commitments> {
+    phantom: PhantomData<(A, H)>,
+}
+
+impl<A, H> BatchVerifier<A, H>
+where
+    A: AIR,
+    H: IsCommitmentHash<A::Field, A::FieldExtension>,
+    FieldElement<A::Field>: AsBytes + Send + Sync,
+    FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+{
+    /// Checks that every part of the proof read by the verifier has the length and number of
+    /// columns expected by the AIRs of the traces.
+    fn validate_proof_shape(
+        airs_and_domains: &[(A, Domain<A::Field>)],
+        longest_domain: &Domain<A::Field>,
+        proof: &BatchStarkProof<A::Field, A::FieldExtension>,
+        proof_options: &ProofOptions,
+    ) -> Result<(), VerificationError> {
+        let malformed = |message: String| Err(VerificationError::MalformedProof(message));
+
+        if proof.trace_ood_evaluations.len() != airs_and_domains.len()
+            || proof.composition_poly_parts_ood_evaluation.len() != airs_and_domains.len()
+        {
+            return malformed(format!(
+                "expected out-of-domain evaluations of {} traces",
+                airs_and_domains.len()
+            ));
+        }
+
+        let (mut num_main_columns, mut num_aux_columns, mut num_parts) = (0, 0, 0);
+        for (i, (air, _)) in airs_and_domains.iter().enumerate() {
+            if air.num_preprocessed_columns() > 0 {
+                return malformed("batched proofs do not support preprocessed columns".to_string());
+            }
+
+            let ood_table = &proof.trace_ood_evaluations[i];
+            let expected_width = air.context().trace_columns;
+            let expected_height = air.context().transition_offsets.len() * A::STEP_SIZE;
+            if ood_table.width != expected_width
+                || ood_table.height != expected_height
+                || ood_table.data.len() != expected_width * expected_height
+            {
+                return malformed(format!(
+                    "expected {expected_height} x {expected_width} out-of-domain evaluations of trace {i}"
+                ));
+            }
+
+            let number_of_parts = air.composition_poly_degree_bound() / air.trace_length();
+            if proof.composition_poly_parts_ood_evaluation[i].len() != number_of_parts {
+                return malformed(format!(
+                    "expected {number_of_parts} composition polynomial parts of trace {i}"
+                ));
+            }
+
+            let num_aux = air.num_auxiliary_rap_columns();
+            num_main_columns += expected_width.checked_sub(num_aux).ok_or_else(|| {
+                VerificationError::MalformedProof(
+                    "the AIR declares fewer trace columns than its layout".to_string(),
+                )
+            })?;
+            num_aux_columns += num_aux;
+            num_parts += number_of_parts;
+        }
+
+        let number_of_queries = proof_options.fri_number_of_queries;
+        let number_of_fri_layers = (longest_domain.root_order as usize).saturating_sub(1);
+        if proof.fri_layers_merkle_roots.len() != number_of_fri_layers {
+            return malformed(format!(
+                "expected {number_of_fri_layers} FRI layers, got {}",
+                proof.fri_layers_merkle_roots.len()
+            ));
+        }
+        let number_of_commit_phase_nonces = if proof_options.commit_phase_grinding_factor > 0 {
+            number_of_fri_layers + 1
+        } else {
+            0
+        };
+        if proof.commit_phase_nonces.len() != number_of_commit_phase_nonces {
+            return malformed(format!(
+                "expected {number_of_commit_phase_nonces} commit phase grinding nonces, got {}",
+                proof.commit_phase_nonces.len()
+            ));
+        }
+        for (query, decommitment) in proof.query_list.iter().take(number_of_queries).enumerate() {
+            if decommitment.layers_auth_paths.len() != number_of_fri_layers
+                || decommitment.layers_evaluations_sym.len() != number_of_fri_layers
+            {
+                return malformed(format!(
+                    "FRI decommitment of query {query} does not have {number_of_fri_layers} layers"
+                ));
+            }
+        }
+
+        let has_trace_interaction = airs_and_domains
+            .iter()
+            .any(|(air, _)| air.has_trace_interaction());
+        if proof.lde_trace_aux_merkle_root.is_some() != has_trace_interaction {
+            return malformed("unexpected presence of the auxiliary trace commitment".to_string());
+        }
+
+        if proof.deep_poly_openings.len() < number_of_queries {
+            return malformed(format!(
+                "expected {number_of_queries} trace and composition polynomial openings, got {}",
+                proof.deep_poly_openings.len()
+            ));
+        }
+        fn has_expected_length<E: IsField>(
+            openings: &PolynomialOpenings<E>,
+            expected: usize,
+        ) -> bool {
+            openings.evaluations.len() == expected && openings.evaluations_sym.len() == expected
+        }
+        for (query, opening) in proof
+            .deep_poly_openings
+            .iter()
+            .take(number_of_queries)
+            .enumerate()
+        {
+            let aux_is_valid = match &opening.aux_trace_polys {
+                Some(aux) => has_trace_interaction && has_expected_length(aux, num_aux_columns),
+                None => !has_trace_interaction,
+            };
+            if !(has_expected_length(&opening.main_trace_polys, num_main_columns)
+                && aux_is_valid
+                && opening.preprocessed_trace_polys.is_none()
+                && has_expected_length(&opening.composition_poly, num_parts))
+            {
+                return malformed(format!(
+                    "openings of query {query} do not match the layout of the AIRs"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the evaluations of the trace polynomials of every trace, main followed by
+    /// auxiliary, from the openings `main` and `aux` of all the traces concatenated.
+    fn split_trace_evaluations(
+        airs_and_domains: &[(A, Domain<A::Field>)],
+        main: &[FieldElement<A::Field>],
+        aux: Option<&Vec<FieldElement<A::FieldExtension>>>,
+    ) -> Vec<Vec<FieldElement<A::FieldExtension>>> {
+        let (mut main_start, mut aux_start) = (0, 0);
+        airs_and_domains
+            .iter()
+            .map(|(air, _)| {
+                let num_aux_columns = air.num_auxiliary_rap_columns();
+                let num_main_columns = air.context().trace_columns - num_aux_columns;
+                let mut evaluations: Vec<_> = main[main_start..main_start + num_main_columns]
+                    .iter()
+                    .map(|x| x.clone().to_extension())
+                    .collect();
+                if let Some(aux) = aux {
+                    evaluations.extend_from_slice(&aux[aux_start..aux_start + num_aux_columns]);
+                }
+                main_start += num_main_columns;
+                aux_start += num_aux_columns;
+                evaluations
+            })
+            .collect()
+    }
+
+    /// Returns the evaluation of the combined DEEP composition polynomial `∑ᵢ X^(N - nᵢ) pᵢ` at
+    /// `evaluation_point`, from the openings of the trace and composition polynomials there.
+    #[allow(clippy::too_many_arguments)]
+    fn evaluate_deep_composition_poly(
+        airs_and_domains: &[(A, Domain<A::Field>)],
+        challenges: &[Challenges<A>],
+        proof: &BatchStarkProof<A::Field, A::FieldExtension>,
+        max_trace_length: usize,
+        evaluation_point: &FieldElement<A::Field>,
+        main: &[FieldElement<A::Field>],
+        aux: Option<&Vec<FieldElement<A::FieldExtension>>>,
+        composition_poly_parts: &[FieldElement<A::FieldExtension>],
+    ) -> FieldElement<A::FieldExtension> {
+        let trace_evaluations = Self::split_trace_evaluations(airs_and_domains, main, aux);
+        let mut parts_start = 0;
+        airs_and_domains
+            .iter()
+            .zip(challenges)
+            .zip(trace_evaluations)
+            .enumerate()
+            .fold(
+                FieldElement::zero(),
+                |acc, (i, (((air, domain), challenges), trace_evaluations))| {
+                    let parts_ood_evaluation = &proof.composition_poly_parts_ood_evaluation[i];
+                    let parts_end = parts_start + parts_ood_evaluation.len();
+                    let evaluation = StarkVerifier::<A, H>::evaluate_deep_composition_poly(
+                        &proof.trace_ood_evaluations[i],
+                        parts_ood_evaluation,
+                        evaluation_point,
+                        &domain.trace_primitive_root,
+                        challenges,
+                        &trace_evaluations,
+                        &composition_poly_parts[parts_start..parts_end],
+                    );
+                    parts_start = parts_end;
+                    acc + evaluation_point.pow(max_trace_length - air.trace_length()) * evaluation
+                },
+            )
+    }
+
+    /// Verifies a batched proof of several traces of `A` with public inputs `pub_inputs`, one
+    /// per trace and in the same order as given to the prover.
+    /// Warning: the transcript must be safely initializated before passing it to this method.
+    /// Use `verify_statement` to have it initialized from the statements being verified.
+    pub fn verify(
+        proof: &BatchStarkProof<A::Field, A::FieldExtension>,
+        pub_inputs: &[A::PublicInputs],
+        proof_options: &ProofOptions,
+        mut transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> Result<(), VerificationError> {
+        if proof.trace_lengths.is_empty() || proof.trace_lengths.len() != pub_inputs.len() {
+            return Err(VerificationError::MalformedProof(format!(
+                "expected one public input per trace, got {} traces and {} public inputs",
+                proof.trace_lengths.len(),
+                pub_inputs.len()
+            )));
+        }
+        if proof.query_list.len() < proof_options.fri_number_of_queries {
+            return Err(VerificationError::InsufficientQueries {
+                expected: proof_options.fri_number_of_queries,
+                actual: proof.query_list.len(),
+            });
+        }
+        for trace_length in proof.trace_lengths.iter() {
+            StarkVerifier::<A, H>::validate_trace_length(*trace_length, proof_options)?;
+        }
+
+        let airs_and_domains =
+            build_airs_and_domains::<A>(&proof.trace_lengths, pub_inputs, proof_options);
+        let longest = longest(&proof.trace_lengths);
+        let max_trace_length = proof.trace_lengths[longest];
+        let longest_domain = &airs_and_domains[longest].1;
+        Self::validate_proof_shape(&airs_and_domains, longest_domain, proof, proof_options)?;
+
+        // ===================================
+        // ==========|   Round 1   |==========
+        // ===================================
+
+        // <<<< Receive commitment: [tⱼ]
+        transcript.append_bytes(&proof.lde_trace_main_merkle_root);
+
+        let rap_challenges: Vec<_> = airs_and_domains
+            .iter()
+            .map(|(air, _)| air.build_rap_challenges(&mut transcript))
+            .collect();
+
+        // <<<< Receive commitment: [tⱼ]
+        if let Some(root) = proof.lde_trace_aux_merkle_root {
+            transcript.append_bytes(&root);
+        }
+
+        // ===================================
+        // ==========|   Round 2   |==========
+        // ===================================
+
+        let composition_coefficients: Vec<_> = airs_and_domains
+            .iter()
+            .zip(&rap_challenges)
+            .map(|((air, _), rap_challenges)| {
+                // <<<< Receive challenge: 𝛽
+                let beta = transcript.sample_field_element();
+                composition_coefficients(air, rap_challenges, &beta)
+            })
+            .collect();
+
+        // <<<< Receive commitments: [H₁], [H₂]
+        transcript.append_bytes(&proof.composition_poly_root);
+
+        // ===================================
+        // ==========|   Round 3   |==========
+        // ===================================
+
+        // >>>> Send challenge: z
+        let z = transcript.sample_z_ood(
+            &longest_domain.lde_roots_of_unity_coset,
+            &longest_domain.trace_roots_of_unity,
+        );
+
+        for (trace_ood_evaluations, composition_poly_parts_ood_evaluation) in proof
+            .trace_ood_evaluations
+            .iter()
+            .zip(&proof.composition_poly_parts_ood_evaluation)
+        {
+            // <<<< Receive values: tⱼ(zgᵏ)
+            for col in trace_ood_evaluations.columns().iter() {
+                for elem in col.iter() {
+                    transcript.append_field_element(elem);
+                }
+            }
+            // <<<< Receive value: Hᵢ(z^N)
+            for element in composition_poly_parts_ood_evaluation.iter() {
+                transcript.append_field_element(element);
+            }
+        }
+
+        // ===================================
+        // ==========|   Round 4   |==========
+        // ===================================
+
+        // <<<< Receive challenges: 𝛾, 𝛾'
+        let gamma = transcript.sample_field_element();
+        let airs: Vec<_> = airs_and_domains.iter().map(|(air, _)| air).collect();
+        let number_of_parts: Vec<_> = proof
+            .composition_poly_parts_ood_evaluation
+            .iter()
+            .map(|parts| parts.len())
+            .collect();
+        let deep_coefficients = deep_composition_coefficients(&airs, &number_of_parts, &gamma);
+
+        // FRI commit phase
+        let merkle_roots = &proof.fri_layers_merkle_roots;
+        let commit_phase_grinding_factor = proof_options.commit_phase_grinding_factor;
+        let mut zetas = Vec::with_capacity(merkle_roots.len() + 1);
+        for layer in 0..=merkle_roots.len() {
+            // verify commit phase grinding
+            if commit_phase_grinding_factor > 0 {
+                let grinding_seed = transcript.state();
+                let nonce_value = proof
+                    .commit_phase_nonces
+                    .get(layer)
+                    .filter(|nonce_value| {
+                        grinding::is_valid_nonce::<H::GrindingHash>(
+                            &grinding_seed,
+                            **nonce_value,
+                            commit_phase_grinding_factor,
+                        )
+                    })
+                    .ok_or(VerificationError::InvalidCommitPhaseGrindingNonce { layer })?;
+                transcript.append_bytes(&nonce_value.to_be_bytes());
+            }
+            // >>>> Send challenge 𝜁ₖ
+            zetas.push(transcript.sample_field_element());
+            // <<<< Receive commitment: [pₖ] (the first one is [p₀])
+            if let Some(root) = merkle_roots.get(layer) {
+                transcript.append_bytes(root);
+            }
+        }
+
+        // <<<< Receive value: pₙ
+        transcript.append_field_element(&proof.fri_last_value);
+
+        // verify grinding
+        let security_bits = proof_options.grinding_factor;
+        if security_bits > 0 {
+            let grinding_seed = transcript.state();
+            let nonce_is_valid = proof.nonce.map_or(false, |nonce_value| {
+                grinding::is_valid_nonce::<H::GrindingHash>(
+                    &grinding_seed,
+                    nonce_value,
+                    security_bits,
+                )
+            });
+            if !nonce_is_valid {
+                return Err(VerificationError::InvalidGrindingNonce);
+            }
+            if let Some(nonce_value) = proof.nonce {
+                transcript.append_bytes(&nonce_value.to_be_bytes());
+            }
+        }
+
+        // FRI query phase
+        // <<<< Send challenges 𝜄ₛ (iota_s)
+        let iotas = StarkVerifier::<A, H>::sample_query_indexes(
+            proof_options.fri_number_of_queries,
+            longest_domain,
+            &mut transcript,
+        );
+
+        // The challenges of each trace. The FRI challenges are shared by all of them.
+        let challenges: Vec<Challenges<A>> = rap_challenges
+            .into_iter()
+            .zip(composition_coefficients)
+            .zip(deep_coefficients)
+            .map(
+                |(
+                    (rap_challenges, (transition_coeffs, boundary_coeffs)),
+                    (trace_term_coeffs, gammas),
+                )| {
+                    Challenges {
+                        z: z.clone(),
+                        boundary_coeffs,
+                        transition_coeffs,
+                        trace_term_coeffs,
+                        gammas,
+                        zetas: Vec::new(),
+                        iotas: Vec::new(),
+                        rap_challenges,
+                        grinding_seed: [0u8; 32],
+                        commit_phase_grinding_seeds: Vec::new(),
+                    }
+                },
+            )
+            .collect();
+
+        // Check the composition polynomial of every trace against its public inputs.
+        for (i, ((air, domain), challenges)) in airs_and_domains.iter().zip(&challenges).enumerate()
+        {
+            StarkVerifier::<A, H>::verify_composition_poly_ood_evaluation(
+                air,
+                &proof.trace_ood_evaluations[i],
+                &proof.composition_poly_parts_ood_evaluation[i],
+                domain,
+                challenges,
+            )?;
+        }
+
+        // Verify FRI on the combined DEEP composition polynomial.
+        let mut evaluation_point_inverse = iotas
+            .iter()
+            .map(|iota| {
+                StarkVerifier::<A, H>::query_challenge_to_evaluation_point(*iota, longest_domain)
+            })
+            .collect::<Vec<FieldElement<A::Field>>>();
+        FieldElement::inplace_batch_inverse(&mut evaluation_point_inverse).unwrap();
+
+        for (query, ((iota, opening), evaluation_point_inv)) in iotas
+            .iter()
+            .zip(&proof.deep_poly_openings)
+            .zip(evaluation_point_inverse)
+            .enumerate()
+        {
+            let deep_composition_evaluation = Self::evaluate_deep_composition_poly(
+                &airs_and_domains,
+                &challenges,
+                proof,
+                max_trace_length,
+                &StarkVerifier::<A, H>::query_challenge_to_evaluation_point(*iota, longest_domain),
+                &opening.main_trace_polys.evaluations,
+                opening.aux_trace_polys.as_ref().map(|aux| &aux.evaluations),
+                &opening.composition_poly.evaluations,
+            );
+            let deep_composition_evaluation_sym = Self::evaluate_deep_composition_poly(
+                &airs_and_domains,
+                &challenges,
+                proof,
+                max_trace_length,
+                &StarkVerifier::<A, H>::query_challenge_to_evaluation_point_sym(
+                    *iota,
+                    longest_domain,
+                ),
+                &opening.main_trace_polys.evaluations_sym,
+                opening
+                    .aux_trace_polys
+                    .as_ref()
+                    .map(|aux| &aux.evaluations_sym),
+                &opening.composition_poly.evaluations_sym,
+            );
+
+            StarkVerifier::<A, H>::verify_fri_query(
+                &proof.fri_layers_merkle_roots,
+                &proof.fri_last_value,
+                &zetas,
+                query,
+                *iota,
+                &proof.query_list[query],
+                evaluation_point_inv,
+                &deep_composition_evaluation,
+                &deep_composition_evaluation_sym,
+            )?;
+        }
+
+        // Verify the openings of the trace and composition polynomials.
+        for (query, (iota, opening)) in iotas.iter().zip(&proof.deep_poly_openings).enumerate() {
+            if !StarkVerifier::<A, H>::verify_composition_poly_opening(
+                opening,
+                &proof.composition_poly_root,
+                iota,
+            ) {
+                return Err(VerificationError::CompositionPolynomialOpening { query });
+            }
+
+            let (index, index_sym) = (iota * 2, iota * 2 + 1);
+            let main = &opening.main_trace_polys;
+            let mut trace_is_valid =
+                StarkVerifier::<A, H>::verify_opening::<A::Field, H::MainBackend>(
+                    &main.proof,
+                    &proof.lde_trace_main_merkle_root,
+                    index,
+                    &main.evaluations,
+                ) && StarkVerifier::<A, H>::verify_opening::<A::Field, H::MainBackend>(
+                    &main.proof_sym,
+                    &proof.lde_trace_main_merkle_root,
+                    index_sym,
+                    &main.evaluations_sym,
+                );
+            if let (Some(aux_root), Some(aux)) =
+                (&proof.lde_trace_aux_merkle_root, &opening.aux_trace_polys)
+            {
+                trace_is_valid &= StarkVerifier::<A, H>::verify_opening::<
+                    A::FieldExtension,
+                    H::ExtensionBackend,
+                >(&aux.proof, aux_root, index, &aux.evaluations)
+                    && StarkVerifier::<A, H>::verify_opening::<
+                        A::FieldExtension,
+                        H::ExtensionBackend,
+                    >(
+                        &aux.proof_sym, aux_root, index_sym, &aux.evaluations_sym
+                    );
+            }
+            if !trace_is_valid {
+                return Err(VerificationError::TraceOpening { query });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verifies a batched proof generated with [`BatchProver::prove_statement`], binding the
+    /// transcript to the statements being verified before checking the proof.
+    pub fn verify_statement(
+        proof: &BatchStarkProof<A::Field, A::FieldExtension>,
+        pub_inputs: &[A::PublicInputs],
+        proof_options: &ProofOptions,
+        mut transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> Result<(), VerificationError>
+    where
+        A::PublicInputs: AsBytes,
+    {
+        transcript.append_bytes(&(proof.trace_lengths.len() as u64).to_be_bytes());
+        for (trace_length, pub_inputs) in proof.trace_lengths.iter().zip(pub_inputs) {
+            bind_statement::<A>(&mut transcript, *trace_length, pub_inputs, proof_options);
+        }
+        Self::verify(proof, pub_inputs, proof_options, transcript)
+    }
+}
@@ -0,0 +1,920 @@
+use std::{collections::BTreeMap, ops::Range};
+
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::traits::AsBytes;
+
+use crate::{
+    constraints::{
+        boundary::{BoundaryConstraint, BoundaryConstraints},
+        transition::TransitionConstraint,
+    },
+    context::AirContext,
+    proof::options::ProofOptions,
+    table::TableView,
+    trace::TraceTable,
+    traits::{TransitionEvaluationContext, AIR},
+    Felt252, PrimeField,
+};
+
+use super::{
+    builtins::{pedersen_points, pedersen_shift_point, Builtin},
+    decode::{self, CairoInstruction, NUM_FLAGS, OFFSET_BIAS},
+    layout::*,
+};
+
+/// The public inputs of a Cairo execution: the initial and final registers, the bounds of the
+/// range-checked values, the memory cells known to the verifier (the program and its output)
+/// and the segments of the range check, pedersen and bitwise builtins.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CairoPublicInputs {
+    pub pc_init: u64,
+    pub ap_init: u64,
+    pub fp_init: u64,
+    pub pc_final: u64,
+    pub ap_final: u64,
+    pub rc_min: u16,
+    pub rc_max: u16,
+    pub public_memory: BTreeMap<u64, Felt252>,
+    pub range_check_builtin: Option<Range<u64>>,
+    pub pedersen_builtin: Option<Range<u64>>,
+    pub bitwise_builtin: Option<Range<u64>>,
+}
+
+impl CairoPublicInputs {
+    /// Returns the first instruction of the program, together with the word encoding it.
+    pub fn initial_instruction(&self) -> Option<(Felt252, CairoInstruction)> {
+        let word = self.public_memory.get(&self.pc_init)?;
+        let instruction = CairoInstruction::decode(felt_to_u64(word)?)?;
+        Some((*word, instruction))
+    }
+
+    fn non_empty_range_check_builtin(&self) -> Option<&Range<u64>> {
+        self.range_check_builtin
+            .as_ref()
+            .filter(|segment| !segment.is_empty())
+    }
+
+    /// Returns the segment of a pedersen or bitwise builtin, if it holds any cell.
+    pub fn builtin_segment(&self, builtin: Builtin) -> Option<&Range<u64>> {
+        match builtin {
+            Builtin::Pedersen => self.pedersen_builtin.as_ref(),
+            Builtin::Bitwise => self.bitwise_builtin.as_ref(),
+        }
+        .filter(|segment| !segment.is_empty())
+    }
+
+    /// Returns the number of whole instances in the segment of a builtin.
+    pub fn builtin_instances(&self, builtin: Builtin) -> usize {
+        self.builtin_segment(builtin).map_or(0, |segment| {
+            ((segment.end - segment.start) / builtin.cells() as u64) as usize
+        })
+    }
+}
+
+impl AsBytes for CairoPublicInputs {
+    fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for register in [
+            self.pc_init,
+            self.ap_init,
+            self.fp_init,
+            self.pc_final,
+            self.ap_final,
+        ] {
+            bytes.extend_from_slice(&register.to_be_bytes());
+        }
+        bytes.extend_from_slice(&self.rc_min.to_be_bytes());
+        bytes.extend_from_slice(&self.rc_max.to_be_bytes());
+
+        bytes.extend_from_slice(&(self.public_memory.len() as u64).to_be_bytes());
+        for (address, value) in self.public_memory.iter() {
+            bytes.extend_from_slice(&address.to_be_bytes());
+            bytes.extend_from_slice(&value.as_bytes());
+        }
+
+        for segment in [
+            &self.range_check_builtin,
+            &self.pedersen_builtin,
+            &self.bitwise_builtin,
+        ] {
+            match segment {
+                Some(segment) => {
+                    bytes.push(1);
+                    bytes.extend_from_slice(&segment.start.to_be_bytes());
+                    bytes.extend_from_slice(&segment.end.to_be_bytes());
+                }
+                None => bytes.push(0),
+            }
+        }
+        bytes
+    }
+}
+
+/// Returns the value of a field element if it fits in a `u64`.
+pub(crate) fn felt_to_u64(value: &Felt252) -> Option<u64> {
+    let limbs = value.representative().limbs;
+    (limbs[..3] == [0, 0, 0]).then_some(limbs[3])
+}
+
+#[derive(Clone, Copy, Debug)]
+enum ConstraintKind {
+    FlagBit(usize),
+    LastFlagZero,
+    Instruction,
+    DstAddress,
+    Op0Address,
+    Op1Address,
+    Mul,
+    Res,
+    T0,
+    T1,
+    NextPcRegular,
+    NextPcJump,
+    NextAp,
+    NextFp,
+    CallSavesFp,
+    CallSavesReturnPc,
+    AssertEq,
+    /// Sorted memory addresses increase by at most one, between the slot `k - 1` and `k` of a
+    /// row, or between the last slot of a row and the first one of the next row for `k = 0`.
+    MemoryContinuity(usize),
+    /// Sorted memory cells with the same address have the same value.
+    MemorySingleValue(usize),
+    MemoryPermutation(usize),
+    /// Sorted range-checked values increase by at most one.
+    RangeCheckContinuity(usize),
+    RangeCheckPermutation(usize),
+    RangeCheckBuiltinValue,
+    /// The cells of the range check builtin are consecutive.
+    RangeCheckBuiltinAddress,
+    /// The used flag of a builtin is a bit.
+    BuiltinUsedBit(Builtin),
+    /// The used instances of a builtin come before the unused ones.
+    BuiltinUsedDecreasing(Builtin),
+    /// In the used instances of a builtin, the address of its memory access moves to the next
+    /// cell after the rows where the periodic column of the given index is 1.
+    BuiltinAddress(Builtin, usize),
+    /// In the used instances of a builtin, its memory access at the given row of an instance
+    /// holds the corresponding cell.
+    BuiltinCell(Builtin, usize),
+    /// A column is the same in the rows of each block of the given number of rows.
+    ColumnKeep {
+        col: usize,
+        rows: usize,
+    },
+    /// The difference between a suffix of the bits of a builtin input and twice the next one is
+    /// a bit.
+    SuffixBit(usize),
+    /// A suffix of the bits of a builtin input is zero at the given row of its 256 rows.
+    SuffixEnd {
+        col: usize,
+        row: usize,
+    },
+    /// The high bit column holds the bit 251 of the pedersen input.
+    PedersenHighBit,
+    /// If the bit 251 of a pedersen input is set, its bits 196 to 250 are zero, so that the bits
+    /// of the input encode an integer below the modulus.
+    PedersenCanonical,
+    /// When the bit of the row is set, the pedersen accumulator adds the constant point held
+    /// by the periodic columns of the given index and the next one, with the slope of the line
+    /// through both points, ...
+    PedersenSlope(usize),
+    /// ... the `x` coordinate of the sum, ...
+    PedersenAddX(usize),
+    /// ... and its `y` coordinate.
+    PedersenAddY,
+    /// When the bit of the row is not set, the given coordinate of the accumulator is kept.
+    PedersenKeep(usize),
+    /// The accumulator of a pedersen instance starts at the given coordinate of the shift point.
+    PedersenShiftPoint(usize, Felt252),
+    /// The accumulator is carried over from the first input to the second one.
+    PedersenNextInput(usize),
+    /// The suffixes of the `and` of the bitwise inputs follow from the bits of the inputs.
+    BitwiseAnd,
+    /// The bitwise columns holding the inputs and their `and` are the value of their suffixes.
+    BitwiseValue {
+        value: usize,
+        suffix: usize,
+    },
+}
+
+impl ConstraintKind {
+    /// Returns whether the constraint applies to every row and reads the next one, so that it
+    /// doesn't apply to the last row.
+    fn reads_next_row(&self) -> bool {
+        matches!(
+            self,
+            Self::NextPcRegular
+                | Self::NextPcJump
+                | Self::NextAp
+                | Self::NextFp
+                | Self::MemoryContinuity(0)
+                | Self::MemorySingleValue(0)
+                | Self::MemoryPermutation(0)
+                | Self::RangeCheckContinuity(0)
+                | Self::RangeCheckPermutation(0)
+                | Self::RangeCheckBuiltinAddress
+                | Self::BuiltinUsedDecreasing(_)
+                | Self::BuiltinAddress(..)
+        )
+    }
+
+    /// Returns the period and offset of the rows the constraint applies to.
+    fn rows(&self) -> (usize, usize) {
+        match self {
+            Self::BuiltinCell(builtin, row) => (builtin.instance_rows(), *row),
+            Self::SuffixEnd { row, .. } => (BUILTIN_INPUT_ROWS, *row),
+            Self::PedersenHighBit => (BUILTIN_INPUT_ROWS, PEDERSEN_INPUT_BITS - 1),
+            Self::PedersenCanonical => (BUILTIN_INPUT_ROWS, 196),
+            Self::PedersenShiftPoint(..) => (PEDERSEN_INSTANCE_ROWS, 0),
+            Self::PedersenNextInput(_) => (PEDERSEN_INSTANCE_ROWS, BUILTIN_INPUT_ROWS - 1),
+            Self::BitwiseValue { .. } => (BITWISE_INSTANCE_ROWS, 0),
+            _ => (1, 0),
+        }
+    }
+
+    /// Returns the number of rows of the blocks whose last row the constraint doesn't apply to,
+    /// for constraints that read the next row of the same block.
+    fn exempted_blocks(&self) -> Option<usize> {
+        match self {
+            Self::ColumnKeep { rows, .. } => Some(*rows),
+            Self::SuffixBit(_)
+            | Self::PedersenSlope(_)
+            | Self::PedersenAddX(_)
+            | Self::PedersenAddY
+            | Self::PedersenKeep(_)
+            | Self::BitwiseAnd => Some(BUILTIN_INPUT_ROWS),
+            _ => None,
+        }
+    }
+}
+
+struct CairoConstraint {
+    kind: ConstraintKind,
+    idx: usize,
+}
+
+impl CairoConstraint {
+    fn evaluate_kind(
+        &self,
+        current: &TableView<PrimeField, PrimeField>,
+        next: &TableView<PrimeField, PrimeField>,
+        periodic_values: &[Felt252],
+        rap_challenges: &[Felt252],
+    ) -> Felt252 {
+        let c = |col: usize| *current.get_main_evaluation_element(0, col);
+        let n = |col: usize| *next.get_main_evaluation_element(0, col);
+        let f = |flag: usize| c(FLAGS + flag);
+        let one = Felt252::one();
+        let two = Felt252::from(2);
+        let bias = Felt252::from(OFFSET_BIAS);
+        let size = f(decode::OP1_IMM) + one;
+
+        match self.kind {
+            ConstraintKind::FlagBit(flag) => f(flag) * (f(flag) - one),
+            ConstraintKind::LastFlagZero => f(NUM_FLAGS - 1),
+            ConstraintKind::Instruction => {
+                let flags = (0..NUM_FLAGS - 1)
+                    .rev()
+                    .fold(Felt252::zero(), |acc, flag| acc * two + f(flag));
+                c(OFF_DST)
+                    + c(OFF_OP0) * Felt252::from(1 << 16)
+                    + c(OFF_OP1) * Felt252::from(1 << 32)
+                    + flags * Felt252::from(1 << 48)
+                    - c(INST)
+            }
+            ConstraintKind::DstAddress => {
+                let dst_reg = f(decode::DST_REG);
+                dst_reg * c(FP) + (one - dst_reg) * c(AP) + c(OFF_DST) - bias - c(DST_ADDR)
+            }
+            ConstraintKind::Op0Address => {
+                let op0_reg = f(decode::OP0_REG);
+                op0_reg * c(FP) + (one - op0_reg) * c(AP) + c(OFF_OP0) - bias - c(OP0_ADDR)
+            }
+            ConstraintKind::Op1Address => {
+                let (imm, fp, ap) = (f(decode::OP1_IMM), f(decode::OP1_FP), f(decode::OP1_AP));
+                let op0_base = one - imm - fp - ap;
+                imm * c(PC) + ap * c(AP) + fp * c(FP) + op0_base * c(OP0) + c(OFF_OP1)
+                    - bias
+                    - c(OP1_ADDR)
+            }
+            ConstraintKind::Mul => c(OP0) * c(OP1) - c(MUL),
+            ConstraintKind::Res => {
+                let (add, mul, jnz) = (f(decode::RES_ADD), f(decode::RES_MUL), f(decode::PC_JNZ));
+                let op1_res = one - add - mul - jnz;
+                (one - jnz) * c(RES) - (add * (c(OP0) + c(OP1)) + mul * c(MUL) + op1_res * c(OP1))
+            }
+            ConstraintKind::T0 => f(decode::PC_JNZ) * c(DST) - c(T0),
+            ConstraintKind::T1 => c(T0) * c(RES) - c(T1),
+            ConstraintKind::NextPcRegular => (c(T1) - f(decode::PC_JNZ)) * (n(PC) - (c(PC) + size)),
+            ConstraintKind::NextPcJump => {
+                let (abs, rel, jnz) = (
+                    f(decode::PC_JUMP_ABS),
+                    f(decode::PC_JUMP_REL),
+                    f(decode::PC_JNZ),
+                );
+                let regular = one - abs - rel - jnz;
+                c(T0) * (n(PC) - (c(PC) + c(OP1))) + (one - jnz) * n(PC)
+                    - (regular * (c(PC) + size) + abs * c(RES) + rel * (c(PC) + c(RES)))
+            }
+            ConstraintKind::NextAp => {
+                n(AP)
+                    - (c(AP)
+                        + f(decode::AP_ADD) * c(RES)
+                        + f(decode::AP_ADD1)
+                        + f(decode::OPCODE_CALL) * two)
+            }
+            ConstraintKind::NextFp => {
+                let (call, ret) = (f(decode::OPCODE_CALL), f(decode::OPCODE_RET));
+                let regular = one - call - ret;
+                n(FP) - (ret * c(DST) + call * (c(AP) + two) + regular * c(FP))
+            }
+            ConstraintKind::CallSavesFp => f(decode::OPCODE_CALL) * (c(DST) - c(FP)),
+            ConstraintKind::CallSavesReturnPc => f(decode::OPCODE_CALL) * (c(OP0) - (c(PC) + size)),
+            ConstraintKind::AssertEq => f(decode::OPCODE_ASSERT_EQ) * (c(DST) - c(RES)),
+            ConstraintKind::MemoryContinuity(k) => {
+                let (previous, current) = sorted_pair(&c, &n, SORTED_ADDRS, MEMORY_SLOTS.len(), k);
+                let delta = current - previous;
+                delta * (delta - one)
+            }
+            ConstraintKind::MemorySingleValue(k) => {
+                let (previous_address, address) =
+                    sorted_pair(&c, &n, SORTED_ADDRS, MEMORY_SLOTS.len(), k);
+                let (previous_value, value) =
+                    sorted_pair(&c, &n, SORTED_VALUES, MEMORY_SLOTS.len(), k);
+                (value - previous_value) * (address - previous_address - one)
+            }
+            ConstraintKind::MemoryPermutation(k) => {
+                let (z, alpha) = (&rap_challenges[0], &rap_challenges[1]);
+                let (row, previous_product) = if k == 0 {
+                    let last = MEMORY_SLOTS.len() - 1;
+                    (next, aux(current, MEMORY_PERMUTATION + last))
+                } else {
+                    (current, aux(current, MEMORY_PERMUTATION + k - 1))
+                };
+                let main = |col: usize| *row.get_main_evaluation_element(0, col);
+                let (address, value) = MEMORY_SLOTS[k];
+                let unsorted = z - (main(address) + alpha * main(value));
+                let sorted = z - (main(SORTED_ADDRS + k) + alpha * main(SORTED_VALUES + k));
+                aux(row, MEMORY_PERMUTATION + k) * sorted - previous_product * unsorted
+            }
+            ConstraintKind::RangeCheckContinuity(k) => {
+                let (previous, current) = sorted_pair(&c, &n, SORTED_RC, RC_SLOTS.len(), k);
+                let delta = current - previous;
+                delta * (delta - one)
+            }
+            ConstraintKind::RangeCheckPermutation(k) => {
+                let z = &rap_challenges[2];
+                let (row, previous_product) = if k == 0 {
+                    let last = RC_SLOTS.len() - 1;
+                    (next, aux(current, RC_PERMUTATION + last))
+                } else {
+                    (current, aux(current, RC_PERMUTATION + k - 1))
+                };
+                let main = |col: usize| *row.get_main_evaluation_element(0, col);
+                let unsorted = z - main(RC_SLOTS[k]);
+                let sorted = z - main(SORTED_RC + k);
+                aux(row, RC_PERMUTATION + k) * sorted - previous_product * unsorted
+            }
+            ConstraintKind::RangeCheckBuiltinValue => {
+                let limbs = (0..RC_BUILTIN_NUM_LIMBS)
+                    .rev()
+                    .fold(Felt252::zero(), |acc, limb| {
+                        acc * Felt252::from(1 << 16) + c(RC_BUILTIN_LIMBS + limb)
+                    });
+                limbs - c(RC_BUILTIN_VALUE)
+            }
+            ConstraintKind::RangeCheckBuiltinAddress => {
+                let delta = n(RC_BUILTIN_ADDR) - c(RC_BUILTIN_ADDR);
+                delta * (delta - one)
+            }
+            ConstraintKind::BuiltinUsedBit(builtin) => {
+                let used = c(builtin.used_column());
+                used * (used - one)
+            }
+            ConstraintKind::BuiltinUsedDecreasing(builtin) => {
+                let used = builtin.used_column();
+                n(used) * (one - c(used))
+            }
+            ConstraintKind::BuiltinAddress(builtin, increments) => {
+                let (address, _) = builtin.memory_slot();
+                n(builtin.used_column()) * (n(address) - c(address) - periodic_values[increments])
+            }
+            ConstraintKind::BuiltinCell(builtin, row) => {
+                let (_, value) = builtin.memory_slot();
+                let cell = match builtin {
+                    Builtin::Pedersen if row == PEDERSEN_INSTANCE_ROWS - 1 => c(PEDERSEN_ACC_X),
+                    Builtin::Pedersen => c(PEDERSEN_SUFFIX),
+                    Builtin::Bitwise => {
+                        let (x, y, and) = (c(BITWISE_X), c(BITWISE_Y), c(BITWISE_AND));
+                        [x, y, and, x + y - two * and, x + y - and][row]
+                    }
+                };
+                c(builtin.used_column()) * (c(value) - cell)
+            }
+            ConstraintKind::ColumnKeep { col, .. } => n(col) - c(col),
+            ConstraintKind::SuffixBit(col) => {
+                let bit = c(col) - two * n(col);
+                bit * (bit - one)
+            }
+            ConstraintKind::SuffixEnd { col, .. } => c(col),
+            ConstraintKind::PedersenHighBit => c(PEDERSEN_HIGH_BIT) - c(PEDERSEN_SUFFIX),
+            ConstraintKind::PedersenCanonical => {
+                let top_bits = Felt252::from(1u64 << (PEDERSEN_INPUT_BITS - 1 - 196));
+                c(PEDERSEN_HIGH_BIT) * (c(PEDERSEN_SUFFIX) - top_bits)
+            }
+            ConstraintKind::PedersenSlope(point) => {
+                let (q_x, q_y) = (periodic_values[point], periodic_values[point + 1]);
+                let bit = c(PEDERSEN_SUFFIX) - two * n(PEDERSEN_SUFFIX);
+                bit * (c(PEDERSEN_ACC_Y) - q_y) - c(PEDERSEN_SLOPE) * (c(PEDERSEN_ACC_X) - q_x)
+            }
+            ConstraintKind::PedersenAddX(point) => {
+                let q_x = periodic_values[point];
+                let bit = c(PEDERSEN_SUFFIX) - two * n(PEDERSEN_SUFFIX);
+                c(PEDERSEN_SLOPE) * c(PEDERSEN_SLOPE)
+                    - bit * (c(PEDERSEN_ACC_X) + q_x + n(PEDERSEN_ACC_X))
+            }
+            ConstraintKind::PedersenAddY => {
+                let bit = c(PEDERSEN_SUFFIX) - two * n(PEDERSEN_SUFFIX);
+                bit * (c(PEDERSEN_ACC_Y) + n(PEDERSEN_ACC_Y))
+                    - c(PEDERSEN_SLOPE) * (c(PEDERSEN_ACC_X) - n(PEDERSEN_ACC_X))
+            }
+            ConstraintKind::PedersenKeep(col) => {
+                let bit = c(PEDERSEN_SUFFIX) - two * n(PEDERSEN_SUFFIX);
+                (one - bit) * (n(col) - c(col))
+            }
+            ConstraintKind::PedersenShiftPoint(col, coordinate) => c(col) - coordinate,
+            ConstraintKind::PedersenNextInput(col) => n(col) - c(col),
+            ConstraintKind::BitwiseAnd => {
+                let x_bit = c(BITWISE_X_SUFFIX) - two * n(BITWISE_X_SUFFIX);
+                let y_bit = c(BITWISE_Y_SUFFIX) - two * n(BITWISE_Y_SUFFIX);
+                c(BITWISE_AND_SUFFIX) - two * n(BITWISE_AND_SUFFIX) - x_bit * y_bit
+            }
+            ConstraintKind::BitwiseValue { value, suffix } => c(value) - c(suffix),
+        }
+    }
+}
+
+fn aux(row: &TableView<PrimeField, PrimeField>, col: usize) -> Felt252 {
+    *row.get_aux_evaluation_element(0, col)
+}
+
+/// Returns the values of the sorted slots `k - 1` and `k` of a group of `num_slots` columns
+/// starting at `first_col`, where the slot before the first one is the last slot of the current
+/// row and the first one is taken from the next row.
+fn sorted_pair(
+    current: &impl Fn(usize) -> Felt252,
+    next: &impl Fn(usize) -> Felt252,
+    first_col: usize,
+    num_slots: usize,
+    k: usize,
+) -> (Felt252, Felt252) {
+    if k == 0 {
+        (current(first_col + num_slots - 1), next(first_col))
+    } else {
+        (current(first_col + k - 1), current(first_col + k))
+    }
+}
+
+impl TransitionConstraint<PrimeField, PrimeField> for CairoConstraint {
+    fn degree(&self) -> usize {
+        match self.kind {
+            ConstraintKind::LastFlagZero
+            | ConstraintKind::Instruction
+            | ConstraintKind::RangeCheckBuiltinValue
+            | ConstraintKind::ColumnKeep { .. }
+            | ConstraintKind::SuffixEnd { .. }
+            | ConstraintKind::PedersenHighBit
+            | ConstraintKind::PedersenShiftPoint(..)
+            | ConstraintKind::PedersenNextInput(_)
+            | ConstraintKind::BitwiseValue { .. } => 1,
+            _ => 2,
+        }
+    }
+
+    fn constraint_idx(&self) -> usize {
+        self.idx
+    }
+
+    fn period(&self) -> usize {
+        self.kind.rows().0
+    }
+
+    fn offset(&self) -> usize {
+        self.kind.rows().1
+    }
+
+    fn exemptions_period(&self) -> Option<usize> {
+        self.kind.exempted_blocks()
+    }
+
+    fn periodic_exemptions_offset(&self) -> Option<usize> {
+        self.kind.exempted_blocks().map(|rows| rows - 1)
+    }
+
+    fn end_exemptions(&self) -> usize {
+        self.kind.reads_next_row() as usize
+    }
+
+    fn evaluate(
+        &self,
+        evaluation_context: &TransitionEvaluationContext<PrimeField, PrimeField>,
+        transition_evaluations: &mut [Felt252],
+    ) {
+        let (frame, periodic_values, rap_challenges) = match evaluation_context {
+            TransitionEvaluationContext::Prover {
+                frame,
+                periodic_values,
+                rap_challenges,
+            }
+            | TransitionEvaluationContext::Verifier {
+                frame,
+                periodic_values,
+                rap_challenges,
+            } => (frame, periodic_values, rap_challenges),
+        };
+
+        let current = frame.get_evaluation_step(0);
+        let next = frame.get_evaluation_step(1);
+
+        transition_evaluations[self.constraint_idx()] =
+            self.evaluate_kind(current, next, periodic_values, rap_challenges);
+    }
+}
+
+/// AIR of the Cairo CPU, with the memory and range check permutation arguments, and the output,
+/// range check, pedersen and bitwise builtins. The output builtin needs no constraints of its
+/// own, since its cells are part of the public memory. The constraints and periodic columns of
+/// the pedersen and bitwise builtins are only part of the AIR when their segments hold cells.
+pub struct CairoAIR {
+    context: AirContext,
+    trace_length: usize,
+    pub_inputs: CairoPublicInputs,
+    transition_constraints: Vec<Box<dyn TransitionConstraint<PrimeField, PrimeField>>>,
+    periodic_columns: Vec<Vec<Felt252>>,
+}
+
+/// Returns the constraints of the pedersen builtin, with its periodic columns starting at the
+/// index `first_periodic_column`: the coordinates of the constant points and the increments of
+/// the address.
+fn pedersen_constraints(first_periodic_column: usize) -> Vec<ConstraintKind> {
+    let (shift_x, shift_y) = pedersen_shift_point();
+    let builtin = Builtin::Pedersen;
+    let mut kinds = builtin_constraints(builtin, first_periodic_column + 2);
+    kinds.extend([
+        ConstraintKind::SuffixBit(PEDERSEN_SUFFIX),
+        ConstraintKind::ColumnKeep {
+            col: PEDERSEN_HIGH_BIT,
+            rows: BUILTIN_INPUT_ROWS,
+        },
+        ConstraintKind::PedersenHighBit,
+        ConstraintKind::PedersenCanonical,
+        ConstraintKind::PedersenSlope(first_periodic_column),
+        ConstraintKind::PedersenAddX(first_periodic_column),
+        ConstraintKind::PedersenAddY,
+        ConstraintKind::PedersenKeep(PEDERSEN_ACC_X),
+        ConstraintKind::PedersenKeep(PEDERSEN_ACC_Y),
+        ConstraintKind::PedersenShiftPoint(PEDERSEN_ACC_X, shift_x),
+        ConstraintKind::PedersenShiftPoint(PEDERSEN_ACC_Y, shift_y),
+        ConstraintKind::PedersenNextInput(PEDERSEN_ACC_X),
+        ConstraintKind::PedersenNextInput(PEDERSEN_ACC_Y),
+    ]);
+    // The suffix is zero past the 252 bits of the input, so that no constant point is added
+    // there.
+    kinds.extend(
+        (PEDERSEN_INPUT_BITS..BUILTIN_INPUT_ROWS).map(|row| ConstraintKind::SuffixEnd {
+            col: PEDERSEN_SUFFIX,
+            row,
+        }),
+    );
+    kinds
+}
+
+/// Returns the constraints of the bitwise builtin, with the increments of the address in the
+/// periodic column `increments`.
+fn bitwise_constraints(increments: usize) -> Vec<ConstraintKind> {
+    let builtin = Builtin::Bitwise;
+    let mut kinds = builtin_constraints(builtin, increments);
+    kinds.extend([
+        ConstraintKind::SuffixBit(BITWISE_X_SUFFIX),
+        ConstraintKind::SuffixBit(BITWISE_Y_SUFFIX),
+        ConstraintKind::BitwiseAnd,
+    ]);
+    for (value, suffix) in [
+        (BITWISE_X, BITWISE_X_SUFFIX),
+        (BITWISE_Y, BITWISE_Y_SUFFIX),
+        (BITWISE_AND, BITWISE_AND_SUFFIX),
+    ] {
+        kinds.extend([
+            ConstraintKind::SuffixEnd {
+                col: suffix,
+                row: BITWISE_INPUT_BITS,
+            },
+            ConstraintKind::ColumnKeep {
+                col: value,
+                rows: BITWISE_INSTANCE_ROWS,
+            },
+            ConstraintKind::BitwiseValue { value, suffix },
+        ]);
+    }
+    kinds
+}
+
+/// Returns the constraints shared by the pedersen and bitwise builtins, on their used flag and
+/// their memory accesses.
+fn builtin_constraints(builtin: Builtin, increments: usize) -> Vec<ConstraintKind> {
+    let mut kinds = vec![
+        ConstraintKind::BuiltinUsedBit(builtin),
+        ConstraintKind::ColumnKeep {
+            col: builtin.used_column(),
+            rows: builtin.instance_rows(),
+        },
+        ConstraintKind::BuiltinUsedDecreasing(builtin),
+        ConstraintKind::BuiltinAddress(builtin, increments),
+    ];
+    kinds.extend(
+        builtin
+            .cell_rows()
+            .iter()
+            .map(|row| ConstraintKind::BuiltinCell(builtin, *row)),
+    );
+    kinds
+}
+
+impl AIR for CairoAIR {
+    type Field = PrimeField;
+    type FieldExtension = PrimeField;
+    type PublicInputs = CairoPublicInputs;
+
+    const STEP_SIZE: usize = 1;
+    const AIR_ID: &'static str = "cairo";
+
+    fn new(
+        trace_length: usize,
+        pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Self {
+        let kinds = (0..NUM_FLAGS - 1)
+            .map(ConstraintKind::FlagBit)
+            .chain([
+                ConstraintKind::LastFlagZero,
+                ConstraintKind::Instruction,
+                ConstraintKind::DstAddress,
+                ConstraintKind::Op0Address,
+                ConstraintKind::Op1Address,
+                ConstraintKind::Mul,
+                ConstraintKind::Res,
+                ConstraintKind::T0,
+                ConstraintKind::T1,
+                ConstraintKind::NextPcRegular,
+                ConstraintKind::NextPcJump,
+                ConstraintKind::NextAp,
+                ConstraintKind::NextFp,
+                ConstraintKind::CallSavesFp,
+                ConstraintKind::CallSavesReturnPc,
+                ConstraintKind::AssertEq,
+            ])
+            .chain((0..MEMORY_SLOTS.len()).flat_map(|k| {
+                [
+                    ConstraintKind::MemoryContinuity(k),
+                    ConstraintKind::MemorySingleValue(k),
+                    ConstraintKind::MemoryPermutation(k),
+                ]
+            }))
+            .chain((0..RC_SLOTS.len()).flat_map(|k| {
+                [
+                    ConstraintKind::RangeCheckContinuity(k),
+                    ConstraintKind::RangeCheckPermutation(k),
+                ]
+            }))
+            .chain([
+                ConstraintKind::RangeCheckBuiltinValue,
+                ConstraintKind::RangeCheckBuiltinAddress,
+            ]);
+
+        let mut periodic_columns = Vec::new();
+        let mut builtin_kinds = Vec::new();
+        if pub_inputs.builtin_segment(Builtin::Pedersen).is_some() {
+            builtin_kinds.extend(pedersen_constraints(periodic_columns.len()));
+            let (points_x, points_y) = pedersen_points().into_iter().unzip();
+            periodic_columns.extend([points_x, points_y, Builtin::Pedersen.address_increments()]);
+        }
+        if pub_inputs.builtin_segment(Builtin::Bitwise).is_some() {
+            builtin_kinds.extend(bitwise_constraints(periodic_columns.len()));
+            periodic_columns.push(Builtin::Bitwise.address_increments());
+        }
+        let kinds = kinds.chain(builtin_kinds);
+
+        let transition_constraints: Vec<
+            Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>,
+        > = kinds
+            .enumerate()
+            .map(|(idx, kind)| {
+                Box::new(CairoConstraint { kind, idx })
+                    as Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>
+            })
+            .collect();
+
+        let context = AirContext {
+            proof_options: proof_options.clone(),
+            trace_columns: NUM_MAIN_COLUMNS + NUM_AUX_COLUMNS,
+            transition_offsets: vec![0, 1],
+            num_transition_constraints: transition_constraints.len(),
+        };
+
+        Self {
+            context,
+            trace_length,
+            pub_inputs: pub_inputs.clone(),
+            transition_constraints,
+            periodic_columns,
+        }
+    }
+
+    fn build_auxiliary_trace(
+        &self,
+        trace: &mut TraceTable<Self::Field, Self::FieldExtension>,
+        challenges: &[Felt252],
+    ) {
+        let (z, alpha, z_rc) = (&challenges[0], &challenges[1], &challenges[2]);
+
+        let mut memory_product = Felt252::one();
+        let mut rc_product = Felt252::one();
+        for row in 0..trace.num_rows() {
+            for (k, (address, value)) in MEMORY_SLOTS.iter().enumerate() {
+                let main = |col: usize| *trace.get_main(row, col);
+                let unsorted = z - (main(*address) + alpha * main(*value));
+                let sorted = z - (main(SORTED_ADDRS + k) + alpha * main(SORTED_VALUES + k));
+                memory_product = memory_product * unsorted * sorted.inv().unwrap();
+                trace.set_aux(row, MEMORY_PERMUTATION + k, memory_product);
+            }
+            for (k, col) in RC_SLOTS.iter().enumerate() {
+                let unsorted = z_rc - trace.get_main(row, *col);
+                let sorted = z_rc - trace.get_main(row, SORTED_RC + k);
+                rc_product = rc_product * unsorted * sorted.inv().unwrap();
+                trace.set_aux(row, RC_PERMUTATION + k, rc_product);
+            }
+        }
+    }
+
+    fn build_rap_challenges(
+        &self,
+        transcript: &mut impl IsTranscript<Self::Field>,
+    ) -> Vec<Felt252> {
+        vec![
+            transcript.sample_field_element(),
+            transcript.sample_field_element(),
+            transcript.sample_field_element(),
+        ]
+    }
+
+    fn trace_layout(&self) -> (usize, usize) {
+        (NUM_MAIN_COLUMNS, NUM_AUX_COLUMNS)
+    }
+
+    fn boundary_constraints(&self, rap_challenges: &[Felt252]) -> BoundaryConstraints<PrimeField> {
+        let (z, alpha, z_rc) = (&rap_challenges[0], &rap_challenges[1], &rap_challenges[2]);
+        let pub_inputs = &self.pub_inputs;
+        let last = self.trace_length - 1;
+
+        // The verifier rejects public inputs without a valid first instruction or public memory
+        // before building the AIR, so the defaults are never used to verify a proof.
+        let (initial_word, initial_instruction) = pub_inputs.initial_instruction().unwrap_or((
+            Felt252::zero(),
+            CairoInstruction {
+                off_dst: 0,
+                off_op0: 0,
+                off_op1: 0,
+                flags: 0,
+            },
+        ));
+        let (first_address, first_value) = pub_inputs
+            .public_memory
+            .iter()
+            .next()
+            .map(|(address, value)| (Felt252::from(*address), *value))
+            .unwrap_or((Felt252::zero(), Felt252::zero()));
+        let pc_init = Felt252::from(pub_inputs.pc_init);
+        let off_dst_init = Felt252::from(initial_instruction.off_dst as u64);
+        let rc_min = Felt252::from(pub_inputs.rc_min as u64);
+
+        let mut constraints = vec![
+            BoundaryConstraint::new_main(PC, 0, pc_init),
+            BoundaryConstraint::new_main(AP, 0, Felt252::from(pub_inputs.ap_init)),
+            BoundaryConstraint::new_main(FP, 0, Felt252::from(pub_inputs.fp_init)),
+            BoundaryConstraint::new_main(INST, 0, initial_word),
+            BoundaryConstraint::new_main(OFF_DST, 0, off_dst_init),
+            BoundaryConstraint::new_main(PC, last, Felt252::from(pub_inputs.pc_final)),
+            BoundaryConstraint::new_main(AP, last, Felt252::from(pub_inputs.ap_final)),
+            BoundaryConstraint::new_main(SORTED_ADDRS, 0, first_address),
+            BoundaryConstraint::new_main(SORTED_VALUES, 0, first_value),
+            BoundaryConstraint::new_main(SORTED_RC, 0, rc_min),
+            BoundaryConstraint::new_main(
+                SORTED_RC + RC_SLOTS.len() - 1,
+                last,
+                Felt252::from(pub_inputs.rc_max as u64),
+            ),
+        ];
+
+        if let Some(segment) = pub_inputs.non_empty_range_check_builtin() {
+            constraints.push(BoundaryConstraint::new_main(
+                RC_BUILTIN_ADDR,
+                0,
+                Felt252::from(segment.start),
+            ));
+            constraints.push(BoundaryConstraint::new_main(
+                RC_BUILTIN_ADDR,
+                last,
+                Felt252::from(segment.end - 1),
+            ));
+        }
+
+        // The used flag is 1 up to the last instance of the segment and 0 after it, if the
+        // trace has more instances. The verifier rejects segments with more instances than fit
+        // in the trace before building the AIR.
+        for builtin in [Builtin::Pedersen, Builtin::Bitwise] {
+            if let Some(segment) = pub_inputs.builtin_segment(builtin) {
+                let (address, _) = builtin.memory_slot();
+                let used_rows = pub_inputs.builtin_instances(builtin) * builtin.instance_rows();
+                constraints.push(BoundaryConstraint::new_main(
+                    address,
+                    0,
+                    Felt252::from(segment.start),
+                ));
+                constraints.push(BoundaryConstraint::new_main(
+                    builtin.used_column(),
+                    used_rows.saturating_sub(builtin.instance_rows()),
+                    Felt252::one(),
+                ));
+                if used_rows < self.trace_length {
+                    constraints.push(BoundaryConstraint::new_main(
+                        builtin.used_column(),
+                        used_rows,
+                        Felt252::zero(),
+                    ));
+                }
+            }
+        }
+
+        // The first cumulative products only hold the factors of the first slot, and the last
+        // ones account for the public memory, which is in the sorted columns but not in the
+        // unsorted ones, where it's replaced by dummy accesses `(0, 0)`.
+        let first_memory_product = (z - (pc_init + alpha * initial_word))
+            * (z - (first_address + alpha * first_value)).inv().unwrap();
+        let public_memory_product = pub_inputs
+            .public_memory
+            .iter()
+            .fold(Felt252::one(), |acc, (address, value)| {
+                acc * (z - (Felt252::from(*address) + alpha * value))
+            });
+        let last_memory_product =
+            z.pow(pub_inputs.public_memory.len()) * public_memory_product.inv().unwrap();
+        let first_rc_product = (z_rc - off_dst_init) * (z_rc - rc_min).inv().unwrap();
+
+        constraints.extend([
+            BoundaryConstraint::new_aux(MEMORY_PERMUTATION, 0, first_memory_product),
+            BoundaryConstraint::new_aux(
+                MEMORY_PERMUTATION + MEMORY_SLOTS.len() - 1,
+                last,
+                last_memory_product,
+            ),
+            BoundaryConstraint::new_aux(RC_PERMUTATION, 0, first_rc_product),
+            BoundaryConstraint::new_aux(RC_PERMUTATION + RC_SLOTS.len() - 1, last, Felt252::one()),
+        ]);
+
+        BoundaryConstraints::from_constraints(constraints)
+    }
+
+    fn transition_constraints(
+        &self,
+    ) -> &Vec<Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>> {
+        &self.transition_constraints
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Felt252>> {
+        self.periodic_columns.clone()
+    }
+
+    fn context(&self) -> &AirContext {
+        &self.context
+    }
+
+    fn composition_poly_degree_bound(&self) -> usize {
+        // The constraints of the CPU apply to every row but the last one at most, so dividing
+        // them by their zerofiers leaves polynomials of degree below the trace length. Those of
+        // the builtins skip rows in each instance, or apply once per instance.
+        if self.periodic_columns.is_empty() {
+            self.trace_length()
+        } else {
+            2 * self.trace_length()
+        }
+    }
+
+    fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    fn pub_inputs(&self) -> &Self::PublicInputs {
+        &self.pub_inputs
+    }
+}
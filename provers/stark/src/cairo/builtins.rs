@@ -0,0 +1,372 @@
+//! The instances of the pedersen and bitwise builtins: the validation of their memory cells and
+//! the values of their columns, as described in the `layout` module.
+
+use lambdaworks_crypto::hash::pedersen::{PedersenParameters, PedersenStarkCurve};
+
+use crate::Felt252;
+
+use super::{errors::CairoError, layout::*, vm_files::CairoMemory};
+
+/// The builtins whose instances span several rows of the trace.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Builtin {
+    Pedersen,
+    Bitwise,
+}
+
+impl Builtin {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Pedersen => "pedersen",
+            Self::Bitwise => "bitwise",
+        }
+    }
+
+    pub fn instance_rows(&self) -> usize {
+        match self {
+            Self::Pedersen => PEDERSEN_INSTANCE_ROWS,
+            Self::Bitwise => BITWISE_INSTANCE_ROWS,
+        }
+    }
+
+    pub fn cells(&self) -> usize {
+        match self {
+            Self::Pedersen => PEDERSEN_CELLS,
+            Self::Bitwise => BITWISE_CELLS,
+        }
+    }
+
+    pub fn used_column(&self) -> usize {
+        match self {
+            Self::Pedersen => PEDERSEN_USED,
+            Self::Bitwise => BITWISE_USED,
+        }
+    }
+
+    /// The (address, value) columns of the memory access of the builtin.
+    pub fn memory_slot(&self) -> (usize, usize) {
+        match self {
+            Self::Pedersen => (PEDERSEN_ADDR, PEDERSEN_VALUE),
+            Self::Bitwise => (BITWISE_ADDR, BITWISE_VALUE),
+        }
+    }
+
+    /// The rows of an instance where its cells are accessed, in the order of their addresses.
+    pub fn cell_rows(&self) -> &'static [usize] {
+        match self {
+            Self::Pedersen => &[0, BUILTIN_INPUT_ROWS, PEDERSEN_INSTANCE_ROWS - 1],
+            Self::Bitwise => &[0, 1, 2, 3, 4],
+        }
+    }
+
+    /// The periodic column that is 1 at the rows of an instance after which the address of the
+    /// memory access moves to the next cell, and 0 elsewhere.
+    pub fn address_increments(&self) -> Vec<Felt252> {
+        let rows = self.instance_rows();
+        let mut increments = vec![Felt252::zero(); rows];
+        for row in self.cell_rows().windows(2).map(|pair| pair[1] - 1) {
+            increments[row] = Felt252::one();
+        }
+        increments[rows - 1] = Felt252::one();
+        increments
+    }
+}
+
+/// An affine point of the Stark curve.
+pub type AffinePoint = (Felt252, Felt252);
+
+/// Returns the shift point the accumulator of each pedersen instance starts from.
+pub fn pedersen_shift_point() -> AffinePoint {
+    let point = PedersenStarkCurve::SHIFT_POINT.to_affine();
+    (*point.x(), *point.y())
+}
+
+/// Returns the point added to the accumulator of a pedersen instance at each of its rows when
+/// the bit of the row is set: `2^j P1` and `2^(j - 248) P2` for the bit `j` of `x`, and `2^j P3`
+/// and `2^(j - 248) P4` for the bit `j` of `y`. The rows past the 252 bits of an input hold
+/// zeros, since no bit is set there.
+pub fn pedersen_points() -> Vec<AffinePoint> {
+    // The lookup tables of the hash hold the multiples 1 to 15 of each constant point times
+    // `16^i`, for each 4-bit chunk `i` of the input.
+    let table_size = PedersenStarkCurve::TABLE_SIZE;
+    let multiple = |chunk: usize, bit: usize| chunk * table_size + (1 << bit) - 1;
+    let low_bits = PEDERSEN_INPUT_BITS - 4;
+
+    let mut points = Vec::with_capacity(PEDERSEN_INSTANCE_ROWS);
+    for (low, high) in [
+        (
+            &PedersenStarkCurve::POINTS_P1[..],
+            &PedersenStarkCurve::POINTS_P2[..],
+        ),
+        (
+            &PedersenStarkCurve::POINTS_P3[..],
+            &PedersenStarkCurve::POINTS_P4[..],
+        ),
+    ] {
+        for j in 0..BUILTIN_INPUT_ROWS {
+            let point = if j < low_bits {
+                low[multiple(j / 4, j % 4)].to_affine()
+            } else if j < PEDERSEN_INPUT_BITS {
+                high[multiple(0, j - low_bits)].to_affine()
+            } else {
+                points.push((Felt252::zero(), Felt252::zero()));
+                continue;
+            };
+            points.push((*point.x(), *point.y()));
+        }
+    }
+    points
+}
+
+/// The values of the pedersen columns in a row of an instance.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PedersenRow {
+    pub suffix: Felt252,
+    pub high_bit: Felt252,
+    pub acc_x: Felt252,
+    pub acc_y: Felt252,
+    pub slope: Felt252,
+}
+
+/// An instance of the pedersen builtin, hashing the cells at `address` and `address + 1` into
+/// the one at `address + 2`.
+#[derive(Clone, Debug)]
+pub struct PedersenInstance {
+    pub address: u64,
+    pub cells: [Felt252; PEDERSEN_CELLS],
+    pub rows: Vec<PedersenRow>,
+}
+
+impl PedersenInstance {
+    /// Builds the rows of the instance at `address`, checking that the hash cell holds the hash
+    /// of the inputs. `points` are the ones returned by `pedersen_points`.
+    ///
+    /// An input with the bit 251 set needs its bits 196 to 250 to be zero, so that the bits
+    /// can't encode an integer above the modulus. This leaves out the inputs in
+    /// `[2^251 + 2^196, p)`, which the AIR can't hash.
+    pub fn new(
+        address: u64,
+        memory: &CairoMemory,
+        points: &[AffinePoint],
+    ) -> Result<Self, CairoError> {
+        let cells = [
+            *memory.get(address)?,
+            *memory.get(address + 1)?,
+            *memory.get(address + 2)?,
+        ];
+        let top_bits = Felt252::from(2).pow(PEDERSEN_INPUT_BITS - 1 - 196);
+
+        let mut rows = Vec::with_capacity(PEDERSEN_INSTANCE_ROWS);
+        let (mut acc_x, mut acc_y) = pedersen_shift_point();
+        for (k, input) in cells[..2].iter().enumerate() {
+            let bits = &input.to_bits_le()[..PEDERSEN_INPUT_BITS];
+            let suffixes = suffixes(bits, BUILTIN_INPUT_ROWS);
+            let high_bit = suffixes[PEDERSEN_INPUT_BITS - 1];
+            if high_bit == Felt252::one() && suffixes[196] != top_bits {
+                return Err(CairoError::UnprovableBuiltinInput(address + k as u64));
+            }
+
+            for (j, (q_x, q_y)) in points[k * BUILTIN_INPUT_ROWS..(k + 1) * BUILTIN_INPUT_ROWS]
+                .iter()
+                .enumerate()
+            {
+                let mut row = PedersenRow {
+                    suffix: suffixes[j],
+                    high_bit,
+                    acc_x,
+                    acc_y,
+                    slope: Felt252::zero(),
+                };
+                if bits.get(j) == Some(&true) {
+                    // The accumulator only meets the point added to it if a relation between the
+                    // constant points is known.
+                    let run = (acc_x - q_x)
+                        .inv()
+                        .map_err(|_| CairoError::UnprovableBuiltinInput(address + k as u64))?;
+                    row.slope = (acc_y - q_y) * run;
+                    let x = row.slope.square() - acc_x - q_x;
+                    acc_y = row.slope * (acc_x - x) - acc_y;
+                    acc_x = x;
+                }
+                rows.push(row);
+            }
+        }
+
+        if cells[2] != acc_x {
+            return Err(CairoError::WrongBuiltinOutput(address + 2));
+        }
+        Ok(Self {
+            address,
+            cells,
+            rows,
+        })
+    }
+}
+
+/// The values of the suffix columns of the bitwise builtin in a row of an instance.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BitwiseRow {
+    pub x_suffix: Felt252,
+    pub y_suffix: Felt252,
+    pub and_suffix: Felt252,
+}
+
+/// An instance of the bitwise builtin, with inputs at `address` and `address + 1`, and their
+/// `and`, `xor` and `or` in the next three cells.
+#[derive(Clone, Debug)]
+pub struct BitwiseInstance {
+    pub address: u64,
+    pub cells: [Felt252; BITWISE_CELLS],
+    pub rows: Vec<BitwiseRow>,
+}
+
+impl BitwiseInstance {
+    /// Builds the rows of the instance at `address`, checking that the inputs fit in 251 bits
+    /// and that the outputs are the `and`, `xor` and `or` of the inputs.
+    pub fn new(address: u64, memory: &CairoMemory) -> Result<Self, CairoError> {
+        let cells: [Felt252; BITWISE_CELLS] = (0..BITWISE_CELLS as u64)
+            .map(|k| memory.get(address + k).copied())
+            .collect::<Result<Vec<_>, _>>()?
+            .try_into()
+            .unwrap();
+
+        let mut bits = [[false; BITWISE_INPUT_BITS]; 2];
+        for (k, input) in cells[..2].iter().enumerate() {
+            let input_bits = input.to_bits_le();
+            if input_bits[BITWISE_INPUT_BITS..].contains(&true) {
+                return Err(CairoError::UnprovableBuiltinInput(address + k as u64));
+            }
+            bits[k].copy_from_slice(&input_bits[..BITWISE_INPUT_BITS]);
+        }
+        let and_bits: Vec<bool> = bits[0].iter().zip(bits[1]).map(|(x, y)| *x && y).collect();
+
+        let x_suffixes = suffixes(&bits[0], BITWISE_INSTANCE_ROWS);
+        let y_suffixes = suffixes(&bits[1], BITWISE_INSTANCE_ROWS);
+        let and_suffixes = suffixes(&and_bits, BITWISE_INSTANCE_ROWS);
+
+        let (x, y, and) = (cells[0], cells[1], and_suffixes[0]);
+        let outputs = [and, x + y - Felt252::from(2) * and, x + y - and];
+        if let Some(k) = (0..outputs.len()).find(|k| cells[2 + k] != outputs[*k]) {
+            return Err(CairoError::WrongBuiltinOutput(address + 2 + k as u64));
+        }
+
+        let rows = (0..BITWISE_INSTANCE_ROWS)
+            .map(|j| BitwiseRow {
+                x_suffix: x_suffixes[j],
+                y_suffix: y_suffixes[j],
+                and_suffix: and_suffixes[j],
+            })
+            .collect();
+        Ok(Self {
+            address,
+            cells,
+            rows,
+        })
+    }
+}
+
+/// Returns the `rows` suffixes of the little-endian `bits`, where the suffix `j` is the integer
+/// they encode shifted right by `j`, and is zero past the last bit.
+fn suffixes(bits: &[bool], rows: usize) -> Vec<Felt252> {
+    let mut suffixes = vec![Felt252::zero(); rows];
+    for j in (0..bits.len()).rev() {
+        suffixes[j] = Felt252::from(2) * suffixes.get(j + 1).unwrap_or(&Felt252::zero())
+            + Felt252::from(bits[j] as u64);
+    }
+    suffixes
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use lambdaworks_crypto::hash::pedersen::Pedersen;
+
+    use super::*;
+
+    fn memory(address: u64, cells: &[Felt252]) -> CairoMemory {
+        CairoMemory::new(HashMap::from_iter((address..).zip(cells.iter().copied())))
+    }
+
+    #[test]
+    fn pedersen_rows_accumulate_the_hash_of_the_inputs() {
+        let x = Felt252::from_hex_unchecked(
+            "03d937c035c878245caf64531a5756109c53068da139362728feb561405371cb",
+        );
+        let y = Felt252::from_hex_unchecked(
+            "0208a0a10250e382e1e4bbe2880906c2791bf6275695e02fbbc6aeff9cd8b31a",
+        );
+        let hash = PedersenStarkCurve::hash(&x, &y);
+        let points = pedersen_points();
+
+        let instance = PedersenInstance::new(7, &memory(7, &[x, y, hash]), &points).unwrap();
+
+        assert_eq!(instance.rows.len(), PEDERSEN_INSTANCE_ROWS);
+        assert_eq!(instance.rows[0].suffix, x);
+        assert_eq!(instance.rows[BUILTIN_INPUT_ROWS].suffix, y);
+        assert_eq!(instance.rows[PEDERSEN_INSTANCE_ROWS - 1].acc_x, hash);
+        assert_eq!(
+            (instance.rows[0].acc_x, instance.rows[0].acc_y),
+            pedersen_shift_point()
+        );
+
+        assert_eq!(
+            PedersenInstance::new(7, &memory(7, &[x, y, hash + Felt252::one()]), &points)
+                .unwrap_err(),
+            CairoError::WrongBuiltinOutput(9)
+        );
+    }
+
+    #[test]
+    fn pedersen_inputs_with_the_bits_196_and_251_set_are_rejected() {
+        let input = Felt252::from(2).pow(251u64) + Felt252::from(2).pow(196u64);
+        let hash = PedersenStarkCurve::hash(&Felt252::one(), &input);
+
+        assert_eq!(
+            PedersenInstance::new(
+                7,
+                &memory(7, &[Felt252::one(), input, hash]),
+                &pedersen_points()
+            )
+            .unwrap_err(),
+            CairoError::UnprovableBuiltinInput(8)
+        );
+    }
+
+    #[test]
+    fn bitwise_rows_decompose_the_inputs() {
+        let (x, y) = (0b1100u64, 0b1010u64);
+        let cells = [x, y, x & y, x ^ y, x | y].map(Felt252::from);
+
+        let instance = BitwiseInstance::new(3, &memory(3, &cells)).unwrap();
+
+        assert_eq!(instance.rows[0].x_suffix, Felt252::from(x));
+        assert_eq!(instance.rows[2].x_suffix, Felt252::from(x >> 2));
+        assert_eq!(instance.rows[3].and_suffix, Felt252::from(1));
+        assert_eq!(instance.rows[4].y_suffix, Felt252::zero());
+
+        let mut wrong_xor = cells;
+        wrong_xor[3] = Felt252::from(x | y);
+        assert_eq!(
+            BitwiseInstance::new(3, &memory(3, &wrong_xor)).unwrap_err(),
+            CairoError::WrongBuiltinOutput(6)
+        );
+
+        let mut too_large = cells;
+        too_large[1] = Felt252::from(2).pow(251u64);
+        assert_eq!(
+            BitwiseInstance::new(3, &memory(3, &too_large)).unwrap_err(),
+            CairoError::UnprovableBuiltinInput(4)
+        );
+    }
+
+    #[test]
+    fn address_increments_move_to_the_next_cell_after_each_access() {
+        let increments = Builtin::Pedersen.address_increments();
+        let rows: Vec<usize> = (0..PEDERSEN_INSTANCE_ROWS)
+            .filter(|row| increments[*row] == Felt252::one())
+            .collect();
+
+        assert_eq!(rows, vec![255, 510, 511]);
+    }
+}
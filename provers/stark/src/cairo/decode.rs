@@ -0,0 +1,137 @@
+//! Decoding of Cairo instructions, as described in section 4.5 of the Cairo whitepaper.
+//!
+//! An instruction is a 63-bit word `off_dst + 2^16 * off_op0 + 2^32 * off_op1 + 2^48 * flags`,
+//! where the offsets are stored with a bias of `2^15` so they fit in 16 unsigned bits.
+
+/// Bias added to the offsets of an instruction.
+pub const OFFSET_BIAS: u64 = 1 << 15;
+
+pub const DST_REG: usize = 0;
+pub const OP0_REG: usize = 1;
+pub const OP1_IMM: usize = 2;
+pub const OP1_FP: usize = 3;
+pub const OP1_AP: usize = 4;
+pub const RES_ADD: usize = 5;
+pub const RES_MUL: usize = 6;
+pub const PC_JUMP_ABS: usize = 7;
+pub const PC_JUMP_REL: usize = 8;
+pub const PC_JNZ: usize = 9;
+pub const AP_ADD: usize = 10;
+pub const AP_ADD1: usize = 11;
+pub const OPCODE_CALL: usize = 12;
+pub const OPCODE_RET: usize = 13;
+pub const OPCODE_ASSERT_EQ: usize = 14;
+pub const NUM_FLAGS: usize = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CairoInstruction {
+    /// Biased offset of `dst`.
+    pub off_dst: u16,
+    /// Biased offset of `op0`.
+    pub off_op0: u16,
+    /// Biased offset of `op1`.
+    pub off_op1: u16,
+    /// The flags of the instruction, with the flag `i` in the bit `i`.
+    pub flags: u16,
+}
+
+impl CairoInstruction {
+    /// Decodes an instruction word, checking that it is smaller than `2^63` and that each group of
+    /// flags (`op1_src`, `res_logic`, `pc_update`, `ap_update` and `opcode`) sets at most one bit.
+    pub fn decode(word: u64) -> Option<Self> {
+        let instruction = Self {
+            off_dst: word as u16,
+            off_op0: (word >> 16) as u16,
+            off_op1: (word >> 32) as u16,
+            flags: (word >> 48) as u16,
+        };
+
+        let groups = [
+            OP1_IMM..OP1_AP + 1,
+            RES_ADD..RES_MUL + 1,
+            PC_JUMP_ABS..PC_JNZ + 1,
+            AP_ADD..AP_ADD1 + 1,
+            OPCODE_CALL..OPCODE_ASSERT_EQ + 1,
+        ];
+        let valid_groups = groups
+            .into_iter()
+            .all(|group| group.filter(|flag| instruction.flag(*flag)).count() <= 1);
+
+        if instruction.flag(NUM_FLAGS - 1) || !valid_groups {
+            return None;
+        }
+        Some(instruction)
+    }
+
+    pub fn encode(&self) -> u64 {
+        self.off_dst as u64
+            | (self.off_op0 as u64) << 16
+            | (self.off_op1 as u64) << 32
+            | (self.flags as u64) << 48
+    }
+
+    pub fn flag(&self, flag: usize) -> bool {
+        (self.flags >> flag) & 1 == 1
+    }
+
+    /// Number of memory cells taken by the instruction, which is 2 when `op1` is an immediate.
+    pub fn size(&self) -> u64 {
+        1 + self.flag(OP1_IMM) as u64
+    }
+
+    pub fn dst_offset(&self) -> i64 {
+        self.off_dst as i64 - OFFSET_BIAS as i64
+    }
+
+    pub fn op0_offset(&self) -> i64 {
+        self.off_op0 as i64 - OFFSET_BIAS as i64
+    }
+
+    pub fn op1_offset(&self) -> i64 {
+        self.off_op1 as i64 - OFFSET_BIAS as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_assert_eq_with_immediate() {
+        // [ap] = 5, ap++
+        let instruction = CairoInstruction::decode(0x480680017fff8000).unwrap();
+
+        assert_eq!(instruction.dst_offset(), 0);
+        assert_eq!(instruction.op0_offset(), -1);
+        assert_eq!(instruction.op1_offset(), 1);
+        assert!(instruction.flag(OP1_IMM));
+        assert!(instruction.flag(AP_ADD1));
+        assert!(instruction.flag(OPCODE_ASSERT_EQ));
+        assert!(!instruction.flag(DST_REG));
+        assert_eq!(instruction.size(), 2);
+        assert_eq!(instruction.encode(), 0x480680017fff8000);
+    }
+
+    #[test]
+    fn decode_ret() {
+        let instruction = CairoInstruction::decode(0x208b7fff7fff7ffe).unwrap();
+
+        assert_eq!(instruction.dst_offset(), -2);
+        assert!(instruction.flag(DST_REG));
+        assert!(instruction.flag(OP1_FP));
+        assert!(instruction.flag(PC_JUMP_ABS));
+        assert!(instruction.flag(OPCODE_RET));
+        assert_eq!(instruction.size(), 1);
+    }
+
+    #[test]
+    fn decode_rejects_words_with_the_last_flag_set() {
+        assert_eq!(CairoInstruction::decode(0x8000800080008000), None);
+    }
+
+    #[test]
+    fn decode_rejects_two_opcodes() {
+        let word = (1 << (48 + OPCODE_CALL)) | (1 << (48 + OPCODE_RET));
+        assert_eq!(CairoInstruction::decode(word), None);
+    }
+}
@@ -0,0 +1,41 @@
+use crate::verifier::VerificationError;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CairoError {
+    /// A file written by the Cairo VM couldn't be read.
+    Io(String),
+    /// The content of a trace or memory file is not a whole number of entries.
+    MalformedFile(String),
+    /// A memory cell holds a value that is not smaller than the modulus of the field.
+    NonCanonicalValue(u64),
+    /// The AIR public input JSON written by the Cairo VM couldn't be parsed.
+    InvalidAirPublicInput(String),
+    /// The execution uses a builtin the AIR doesn't support.
+    UnsupportedBuiltin(String),
+    /// The word at the given address is not a valid instruction.
+    InvalidInstruction(u64),
+    /// The execution accesses a memory cell that is not in the memory file.
+    MissingMemoryCell(u64),
+    /// An operand of the instruction executed at the given step has an invalid address.
+    InvalidAddress(usize),
+    /// The registers at the given step don't follow from the instruction executed at that step.
+    InvalidTransition(usize),
+    /// The execution doesn't end in an infinite loop (`jmp rel 0`), as it does when the program
+    /// is run in proof mode, so the trace can't be padded.
+    NotProofMode,
+    /// The execution has no steps.
+    EmptyExecution,
+    /// The cell of the range check builtin at the given address holds a value of more than 128 bits.
+    RangeCheckOutOfBounds(u64),
+    /// An input of the pedersen or bitwise builtin at the given address is outside of the range
+    /// the AIR can prove.
+    UnprovableBuiltinInput(u64),
+    /// An output of the pedersen or bitwise builtin at the given address doesn't match its inputs.
+    WrongBuiltinOutput(u64),
+    /// The execution accesses memory below the first cell of the public memory.
+    AddressBelowPublicMemory(u64),
+    /// The public inputs have no valid instruction at `pc_init`.
+    InvalidPublicInputs(String),
+    /// The proof was rejected by the STARK verifier.
+    Verification(VerificationError),
+}
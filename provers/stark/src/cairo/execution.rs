@@ -0,0 +1,596 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+use crate::{trace::TraceTable, Felt252, PrimeField};
+
+use super::{
+    air::{felt_to_u64, CairoPublicInputs},
+    builtins::{
+        pedersen_points, pedersen_shift_point, BitwiseInstance, BitwiseRow, Builtin,
+        PedersenInstance, PedersenRow,
+    },
+    decode::{self, CairoInstruction, NUM_FLAGS},
+    errors::CairoError,
+    layout::*,
+    vm_files::{CairoMemory, MemorySegments, RegisterState, RegisterStates},
+};
+
+/// A step of the execution, with the instruction and the memory cells it accesses.
+#[derive(Clone, Debug)]
+struct ExecutionStep {
+    state: RegisterState,
+    instruction: CairoInstruction,
+    word: Felt252,
+    dst_addr: u64,
+    op0_addr: u64,
+    op1_addr: u64,
+    dst: Felt252,
+    op0: Felt252,
+    op1: Felt252,
+    res: Felt252,
+}
+
+impl ExecutionStep {
+    fn new(step: usize, state: RegisterState, memory: &CairoMemory) -> Result<Self, CairoError> {
+        let word = *memory.get(state.pc)?;
+        let instruction = felt_to_u64(&word)
+            .and_then(CairoInstruction::decode)
+            .ok_or(CairoError::InvalidInstruction(state.pc))?;
+
+        let dst_base = if instruction.flag(decode::DST_REG) {
+            state.fp
+        } else {
+            state.ap
+        };
+        let dst_addr = offset_address(step, dst_base, instruction.dst_offset())?;
+
+        let op0_base = if instruction.flag(decode::OP0_REG) {
+            state.fp
+        } else {
+            state.ap
+        };
+        let op0_addr = offset_address(step, op0_base, instruction.op0_offset())?;
+        let op0 = *memory.get(op0_addr)?;
+
+        let op1_base = if instruction.flag(decode::OP1_IMM) {
+            state.pc
+        } else if instruction.flag(decode::OP1_FP) {
+            state.fp
+        } else if instruction.flag(decode::OP1_AP) {
+            state.ap
+        } else {
+            felt_to_u64(&op0).ok_or(CairoError::InvalidAddress(step))?
+        };
+        let op1_addr = offset_address(step, op1_base, instruction.op1_offset())?;
+
+        let dst = *memory.get(dst_addr)?;
+        let op1 = *memory.get(op1_addr)?;
+
+        // For conditional jumps the `res` column holds the inverse of `dst`, so that
+        // `t1 = dst * res` is 1 exactly when the jump is taken.
+        let res = if instruction.flag(decode::PC_JNZ) {
+            dst.inv().unwrap_or_else(|_| Felt252::zero())
+        } else if instruction.flag(decode::RES_ADD) {
+            op0 + op1
+        } else if instruction.flag(decode::RES_MUL) {
+            op0 * op1
+        } else {
+            op1
+        };
+
+        Ok(Self {
+            state,
+            instruction,
+            word,
+            dst_addr,
+            op0_addr,
+            op1_addr,
+            dst,
+            op0,
+            op1,
+            res,
+        })
+    }
+
+    /// Returns the registers after executing the step, if they are valid addresses.
+    fn next_state(&self) -> Option<RegisterState> {
+        let flag = |flag: usize| self.instruction.flag(flag);
+        let pc = Felt252::from(self.state.pc);
+        let ap = Felt252::from(self.state.ap);
+        let size = Felt252::from(self.instruction.size());
+
+        let next_pc = if flag(decode::PC_JNZ) {
+            if self.dst == Felt252::zero() {
+                pc + size
+            } else {
+                pc + self.op1
+            }
+        } else if flag(decode::PC_JUMP_ABS) {
+            self.res
+        } else if flag(decode::PC_JUMP_REL) {
+            pc + self.res
+        } else {
+            pc + size
+        };
+
+        let mut next_ap = ap;
+        if flag(decode::AP_ADD) {
+            next_ap += self.res;
+        }
+        if flag(decode::AP_ADD1) {
+            next_ap += Felt252::one();
+        }
+        if flag(decode::OPCODE_CALL) {
+            next_ap += Felt252::from(2);
+        }
+
+        let next_fp = if flag(decode::OPCODE_RET) {
+            self.dst
+        } else if flag(decode::OPCODE_CALL) {
+            ap + Felt252::from(2)
+        } else {
+            Felt252::from(self.state.fp)
+        };
+
+        Some(RegisterState {
+            pc: felt_to_u64(&next_pc)?,
+            ap: felt_to_u64(&next_ap)?,
+            fp: felt_to_u64(&next_fp)?,
+        })
+    }
+
+    fn memory_accesses(&self) -> [(u64, Felt252); 4] {
+        [
+            (self.state.pc, self.word),
+            (self.dst_addr, self.dst),
+            (self.op0_addr, self.op0),
+            (self.op1_addr, self.op1),
+        ]
+    }
+
+    fn offsets(&self) -> [u16; 3] {
+        [
+            self.instruction.off_dst,
+            self.instruction.off_op0,
+            self.instruction.off_op1,
+        ]
+    }
+}
+
+/// A cell of the range check builtin, with its value split in 16-bit limbs, least significant
+/// first.
+#[derive(Clone, Debug)]
+struct RangeCheckCell {
+    address: u64,
+    value: Felt252,
+    limbs: [u16; RC_BUILTIN_NUM_LIMBS],
+}
+
+impl RangeCheckCell {
+    fn new(address: u64, value: Felt252) -> Option<Self> {
+        let words = value.representative().limbs;
+        if words[0] != 0 || words[1] != 0 {
+            return None;
+        }
+        let limbs = std::array::from_fn(|k| (words[3 - k / 4] >> (16 * (k % 4))) as u16);
+        Some(Self {
+            address,
+            value,
+            limbs,
+        })
+    }
+}
+
+/// Returns the instance of a pedersen or bitwise builtin holding a row of the trace, together
+/// with the row within the instance and the cell of the instance accessed at that row.
+fn builtin_row(builtin: Builtin, row: usize) -> (usize, usize, usize) {
+    let (instance, row) = (row / builtin.instance_rows(), row % builtin.instance_rows());
+    let cell = builtin
+        .cell_rows()
+        .iter()
+        .filter(|cell_row| **cell_row <= row)
+        .count()
+        - 1;
+    (instance, row, cell)
+}
+
+fn offset_address(step: usize, base: u64, offset: i64) -> Result<u64, CairoError> {
+    base.checked_add_signed(offset)
+        .filter(|address| *address != 0)
+        .ok_or(CairoError::InvalidAddress(step))
+}
+
+/// An execution of a Cairo program in proof mode, checked against the rules of the Cairo VM,
+/// from which the public inputs and the trace of the Cairo AIR are built.
+#[derive(Clone, Debug)]
+pub struct CairoExecution {
+    steps: Vec<ExecutionStep>,
+    /// The cells of the range check builtin, or the first instruction of the program when the
+    /// builtin is unused, since its columns still need to hold a valid memory access.
+    range_check_cells: Vec<RangeCheckCell>,
+    pedersen_instances: Vec<PedersenInstance>,
+    bitwise_instances: Vec<BitwiseInstance>,
+    memory: CairoMemory,
+    public_inputs: CairoPublicInputs,
+}
+
+impl CairoExecution {
+    pub fn new(
+        registers: &RegisterStates,
+        memory: CairoMemory,
+        segments: &MemorySegments,
+    ) -> Result<Self, CairoError> {
+        let steps = registers
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(step, state)| ExecutionStep::new(step, *state, &memory))
+            .collect::<Result<Vec<_>, _>>()?;
+        let (first_step, last_step) = match (steps.first(), steps.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Err(CairoError::EmptyExecution),
+        };
+
+        for (step, window) in steps.windows(2).enumerate() {
+            if window[0].next_state() != Some(window[1].state) {
+                return Err(CairoError::InvalidTransition(step));
+            }
+        }
+        // The trace is padded by repeating the last step, which needs to be a fixed point.
+        if last_step.next_state() != Some(last_step.state) {
+            return Err(CairoError::NotProofMode);
+        }
+
+        let range_check_segment = segments.range_check.clone().unwrap_or(0..0);
+        let mut range_check_cells = range_check_segment
+            .clone()
+            .map(|address| {
+                let value = *memory.get(address)?;
+                RangeCheckCell::new(address, value)
+                    .ok_or(CairoError::RangeCheckOutOfBounds(address))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if range_check_cells.is_empty() {
+            // An instruction fits in 63 bits, so it always passes the range check.
+            range_check_cells
+                .push(RangeCheckCell::new(first_step.state.pc, first_step.word).unwrap());
+        }
+
+        let instance_addresses = |segment: &Option<std::ops::Range<u64>>, builtin: Builtin| {
+            segment
+                .clone()
+                .unwrap_or(0..0)
+                .step_by(builtin.cells())
+                .collect::<Vec<_>>()
+        };
+        let points = pedersen_points();
+        let pedersen_instances = instance_addresses(&segments.pedersen, Builtin::Pedersen)
+            .into_iter()
+            .map(|address| PedersenInstance::new(address, &memory, &points))
+            .collect::<Result<Vec<_>, _>>()?;
+        let bitwise_instances = instance_addresses(&segments.bitwise, Builtin::Bitwise)
+            .into_iter()
+            .map(|address| BitwiseInstance::new(address, &memory))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut public_memory = BTreeMap::new();
+        let output = segments.output.clone().unwrap_or(0..0);
+        for address in segments.program.clone().chain(output) {
+            public_memory.insert(address, *memory.get(address)?);
+        }
+
+        let first_public_address = *public_memory
+            .keys()
+            .next()
+            .ok_or(CairoError::MissingMemoryCell(segments.program.start))?;
+        let lowest_access = steps
+            .iter()
+            .flat_map(|step| step.memory_accesses().map(|(address, _)| address))
+            .chain(range_check_cells.iter().map(|cell| cell.address))
+            .chain(pedersen_instances.iter().map(|instance| instance.address))
+            .chain(bitwise_instances.iter().map(|instance| instance.address))
+            .min()
+            .unwrap();
+        if lowest_access < first_public_address {
+            return Err(CairoError::AddressBelowPublicMemory(lowest_access));
+        }
+
+        let range_checked = steps
+            .iter()
+            .flat_map(|step| step.offsets())
+            .chain(range_check_cells.iter().flat_map(|cell| cell.limbs));
+        let (rc_min, rc_max) = range_checked.fold((u16::MAX, u16::MIN), |(min, max), value| {
+            (min.min(value), max.max(value))
+        });
+
+        let public_inputs = CairoPublicInputs {
+            pc_init: first_step.state.pc,
+            ap_init: first_step.state.ap,
+            fp_init: first_step.state.fp,
+            pc_final: last_step.state.pc,
+            ap_final: last_step.state.ap,
+            rc_min,
+            rc_max,
+            public_memory,
+            range_check_builtin: segments.range_check.clone(),
+            pedersen_builtin: segments.pedersen.clone(),
+            bitwise_builtin: segments.bitwise.clone(),
+        };
+
+        Ok(Self {
+            steps,
+            range_check_cells,
+            pedersen_instances,
+            bitwise_instances,
+            memory,
+            public_inputs,
+        })
+    }
+
+    /// Loads an execution from the trace, memory and AIR public input files written by the
+    /// Cairo VM.
+    pub fn from_files(
+        trace_path: impl AsRef<Path>,
+        memory_path: impl AsRef<Path>,
+        air_public_input_path: impl AsRef<Path>,
+    ) -> Result<Self, CairoError> {
+        let registers = RegisterStates::from_file(trace_path)?;
+        let memory = CairoMemory::from_file(memory_path)?;
+        let segments = MemorySegments::from_air_public_input_file(air_public_input_path)?;
+        Self::new(&registers, memory, &segments)
+    }
+
+    pub fn public_inputs(&self) -> &CairoPublicInputs {
+        &self.public_inputs
+    }
+
+    pub fn num_steps(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Builds the main trace of the Cairo AIR, with a number of rows that is the smallest power
+    /// of two fitting the steps, the memory holes, the public memory, the holes between the
+    /// range-checked values, the cells of the range check builtin and the instances of the
+    /// pedersen and bitwise builtins.
+    pub fn build_trace(&self) -> TraceTable<PrimeField, PrimeField> {
+        let public_memory = &self.public_inputs.public_memory;
+
+        let accessed: BTreeSet<u64> =
+            self.steps
+                .iter()
+                .flat_map(|step| step.memory_accesses().map(|(address, _)| address))
+                .chain(self.range_check_cells.iter().map(|cell| cell.address))
+                .chain(self.pedersen_instances.iter().flat_map(|instance| {
+                    (0..PEDERSEN_CELLS as u64).map(|cell| instance.address + cell)
+                }))
+                .chain(self.bitwise_instances.iter().flat_map(|instance| {
+                    (0..BITWISE_CELLS as u64).map(|cell| instance.address + cell)
+                }))
+                .chain(public_memory.keys().copied())
+                .collect();
+        let first_address = *accessed.first().unwrap();
+        let last_address = *accessed.last().unwrap();
+        let memory_holes: Vec<(u64, Felt252)> = (first_address..last_address)
+            .filter(|address| !accessed.contains(address))
+            .map(|address| {
+                let value = self
+                    .memory
+                    .get(address)
+                    .cloned()
+                    .unwrap_or_else(|_| Felt252::zero());
+                (address, value)
+            })
+            .collect();
+
+        let range_checked: BTreeSet<u16> = self
+            .steps
+            .iter()
+            .flat_map(|step| step.offsets())
+            .chain(self.range_check_cells.iter().flat_map(|cell| cell.limbs))
+            .collect();
+        let (rc_min, rc_max) = (self.public_inputs.rc_min, self.public_inputs.rc_max);
+        let rc_holes: Vec<u16> = (rc_min..rc_max)
+            .filter(|value| !range_checked.contains(value))
+            .collect();
+
+        let num_rows = [
+            self.steps.len(),
+            memory_holes.len() + public_memory.len(),
+            rc_holes.len(),
+            self.range_check_cells.len(),
+            self.pedersen_instances.len() * PEDERSEN_INSTANCE_ROWS,
+            self.bitwise_instances.len() * BITWISE_INSTANCE_ROWS,
+            2,
+        ]
+        .into_iter()
+        .max()
+        .unwrap()
+        .next_power_of_two();
+
+        // The extra memory accesses fill the holes, then leave room for the public memory with
+        // dummy accesses `(0, 0)`, and repeat the first instruction fetch in the remaining rows.
+        let first_step = &self.steps[0];
+        let extra_memory: Vec<(u64, Felt252)> = memory_holes
+            .into_iter()
+            .chain(std::iter::repeat((0, Felt252::zero())).take(public_memory.len()))
+            .chain(std::iter::repeat((first_step.state.pc, first_step.word)))
+            .take(num_rows)
+            .collect();
+        let extra_rc: Vec<u16> = rc_holes
+            .into_iter()
+            .chain(std::iter::repeat(rc_min))
+            .take(num_rows)
+            .collect();
+
+        // The instances of the pedersen and bitwise builtins past their segments hold the inputs
+        // zero, without accessing their cells.
+        let (shift_x, shift_y) = pedersen_shift_point();
+        let unused_pedersen_row = PedersenRow {
+            suffix: Felt252::zero(),
+            high_bit: Felt252::zero(),
+            acc_x: shift_x,
+            acc_y: shift_y,
+            slope: Felt252::zero(),
+        };
+        let unused_bitwise_row = BitwiseRow {
+            x_suffix: Felt252::zero(),
+            y_suffix: Felt252::zero(),
+            and_suffix: Felt252::zero(),
+        };
+        let unused_access = (first_step.state.pc, first_step.word);
+
+        let mut columns: Vec<Vec<Felt252>> = (0..NUM_MAIN_COLUMNS)
+            .map(|_| Vec::with_capacity(num_rows))
+            .collect();
+        for row in 0..num_rows {
+            let step = &self.steps[row.min(self.steps.len() - 1)];
+            let cell = &self.range_check_cells[row.min(self.range_check_cells.len() - 1)];
+            let instruction = &step.instruction;
+
+            for flag in 0..NUM_FLAGS {
+                columns[FLAGS + flag].push(Felt252::from(instruction.flag(flag) as u64));
+            }
+            let t0 = if instruction.flag(decode::PC_JNZ) {
+                step.dst
+            } else {
+                Felt252::zero()
+            };
+            let t1 = t0 * step.res;
+            let values = [
+                (RES, step.res),
+                (AP, Felt252::from(step.state.ap)),
+                (FP, Felt252::from(step.state.fp)),
+                (PC, Felt252::from(step.state.pc)),
+                (DST_ADDR, Felt252::from(step.dst_addr)),
+                (OP0_ADDR, Felt252::from(step.op0_addr)),
+                (OP1_ADDR, Felt252::from(step.op1_addr)),
+                (INST, step.word),
+                (DST, step.dst),
+                (OP0, step.op0),
+                (OP1, step.op1),
+                (OFF_DST, Felt252::from(instruction.off_dst as u64)),
+                (OFF_OP0, Felt252::from(instruction.off_op0 as u64)),
+                (OFF_OP1, Felt252::from(instruction.off_op1 as u64)),
+                (T0, t0),
+                (T1, t1),
+                (MUL, step.op0 * step.op1),
+                (EXTRA_ADDR, Felt252::from(extra_memory[row].0)),
+                (EXTRA_VALUE, extra_memory[row].1),
+                (EXTRA_RC, Felt252::from(extra_rc[row] as u64)),
+                (RC_BUILTIN_ADDR, Felt252::from(cell.address)),
+                (RC_BUILTIN_VALUE, cell.value),
+            ];
+            for (col, value) in values {
+                columns[col].push(value);
+            }
+            for (k, limb) in cell.limbs.iter().enumerate() {
+                columns[RC_BUILTIN_LIMBS + k].push(Felt252::from(*limb as u64));
+            }
+
+            let (instance, instance_row, cell) = builtin_row(Builtin::Pedersen, row);
+            let (pedersen, used, (address, value)) = match self.pedersen_instances.get(instance) {
+                Some(instance) => (
+                    &instance.rows[instance_row],
+                    Felt252::one(),
+                    (instance.address + cell as u64, instance.cells[cell]),
+                ),
+                None => (&unused_pedersen_row, Felt252::zero(), unused_access),
+            };
+            let values = [
+                (PEDERSEN_SUFFIX, pedersen.suffix),
+                (PEDERSEN_HIGH_BIT, pedersen.high_bit),
+                (PEDERSEN_ACC_X, pedersen.acc_x),
+                (PEDERSEN_ACC_Y, pedersen.acc_y),
+                (PEDERSEN_SLOPE, pedersen.slope),
+                (PEDERSEN_USED, used),
+                (PEDERSEN_ADDR, Felt252::from(address)),
+                (PEDERSEN_VALUE, value),
+            ];
+            for (col, value) in values {
+                columns[col].push(value);
+            }
+
+            let (instance, instance_row, cell) = builtin_row(Builtin::Bitwise, row);
+            let (bitwise, inputs, used, (address, value)) =
+                match self.bitwise_instances.get(instance) {
+                    Some(instance) => (
+                        &instance.rows[instance_row],
+                        &instance.rows[0],
+                        Felt252::one(),
+                        (instance.address + cell as u64, instance.cells[cell]),
+                    ),
+                    None => (
+                        &unused_bitwise_row,
+                        &unused_bitwise_row,
+                        Felt252::zero(),
+                        unused_access,
+                    ),
+                };
+            let values = [
+                (BITWISE_X_SUFFIX, bitwise.x_suffix),
+                (BITWISE_Y_SUFFIX, bitwise.y_suffix),
+                (BITWISE_AND_SUFFIX, bitwise.and_suffix),
+                (BITWISE_X, inputs.x_suffix),
+                (BITWISE_Y, inputs.y_suffix),
+                (BITWISE_AND, inputs.and_suffix),
+                (BITWISE_USED, used),
+                (BITWISE_ADDR, Felt252::from(address)),
+                (BITWISE_VALUE, value),
+            ];
+            for (col, value) in values {
+                columns[col].push(value);
+            }
+        }
+
+        // The sorted memory holds every access except the dummy ones, plus the public memory.
+        let mut sorted_memory: Vec<(u64, Felt252)> = (0..num_rows)
+            .flat_map(|row| {
+                MEMORY_SLOTS.map(|(address, value)| {
+                    let address = felt_to_u64(&columns[address][row]).unwrap();
+                    (address, columns[value][row])
+                })
+            })
+            .filter(|(address, _)| *address != 0)
+            .chain(public_memory.clone())
+            .collect();
+        sorted_memory.sort_by_key(|(address, _)| *address);
+
+        let mut sorted_rc: Vec<u64> = (0..num_rows)
+            .flat_map(|row| RC_SLOTS.map(|col| felt_to_u64(&columns[col][row]).unwrap()))
+            .collect();
+        sorted_rc.sort_unstable();
+
+        for (k, (address, value)) in sorted_memory.into_iter().enumerate() {
+            let slot = k % MEMORY_SLOTS.len();
+            columns[SORTED_ADDRS + slot].push(Felt252::from(address));
+            columns[SORTED_VALUES + slot].push(value);
+        }
+        for (k, value) in sorted_rc.into_iter().enumerate() {
+            columns[SORTED_RC + k % RC_SLOTS.len()].push(Felt252::from(value));
+        }
+
+        let aux_columns = vec![vec![Felt252::zero(); num_rows]; NUM_AUX_COLUMNS];
+        TraceTable::from_columns(columns, aux_columns, 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_check_cells_are_split_in_little_endian_limbs() {
+        let value = Felt252::from_hex_unchecked("0x00070006000500040003000200010000");
+        let cell = RangeCheckCell::new(5, value).unwrap();
+
+        assert_eq!(cell.limbs, [0, 1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn range_check_cells_reject_values_of_more_than_128_bits() {
+        let value = Felt252::from_hex_unchecked("0x100000000000000000000000000000000");
+        assert!(RangeCheckCell::new(5, value).is_none());
+    }
+}
@@ -0,0 +1,143 @@
+//! Columns of the execution trace of the Cairo AIR.
+//!
+//! The main trace has one row per step of the execution, holding the decoded instruction, the
+//! registers and the memory cells it accesses, as described in the Cairo whitepaper, followed by
+//! the memory cells and range-checked values used to fill holes, the cells of the range check
+//! builtin, the components of the pedersen and bitwise builtins, and the sorted copies of the
+//! memory cells and range-checked values:
+//!
+//! ```text
+//!  flags      (16) : Decoded instruction flags
+//!  res        (1)  : Res value
+//!  pointers   (2)  : ap and fp
+//!  mem_a      (4)  : Memory addresses (pc, dst_addr, op0_addr, op1_addr)
+//!  mem_v      (4)  : Memory values (inst, dst, op0, op1)
+//!  offsets    (3)  : Biased offsets (off_dst, off_op0, off_op1)
+//!  derived    (3)  : (t0, t1, mul)
+//!  extra_mem  (2)  : Address and value of an extra memory access
+//!  extra_rc   (1)  : Extra range-checked value
+//!  rc_builtin (10) : Address, value and 16-bit limbs of a cell of the range check builtin
+//!  pedersen   (8)  : (suffix, high_bit, acc_x, acc_y, slope, used, address, value)
+//!  bitwise    (9)  : (x_suffix, y_suffix, and_suffix, x, y, and, used, address, value)
+//!  mem_a'     (8)  : Sorted memory addresses
+//!  mem_v'     (8)  : Sorted memory values
+//!  rc'        (12) : Sorted range-checked values
+//! ```
+//!
+//! Unlike the CPU, which uses one row per step, the pedersen and bitwise builtins lay out each
+//! instance over a fixed number of rows:
+//!
+//! - A pedersen instance takes 512 rows, hashing `x` in the first 256 and `y` in the last 256.
+//!   The suffix column holds the input shifted right by the row index within the half, so that
+//!   its difference with twice the next one is the bit of the row, and the accumulator adds the
+//!   constant point of each set bit to the shift point, as in `PedersenStarkCurve::hash`. Its
+//!   memory cells `x`, `y` and `hash` are accessed at the rows 0, 256 and 511.
+//! - A bitwise instance takes 256 rows, with the suffixes of `x`, `y` and `x & y` over the 251
+//!   bits of the inputs, and their values kept in the `x`, `y` and `and` columns. Its memory
+//!   cells `x`, `y`, `x & y`, `x ^ y` and `x | y` are accessed at the rows 0 to 4.
+//!
+//! The used column of a builtin is 1 in the instances holding a cell of its segment, and 0 in the
+//! remaining ones, whose memory accesses are not constrained. Outside of the rows where a cell is
+//! accessed, the memory access of a builtin repeats the last one.
+//!
+//! The auxiliary trace holds the cumulative products of the permutation arguments of the memory
+//! and of the range-checked values.
+
+pub const FLAGS: usize = 0;
+pub const RES: usize = 16;
+pub const AP: usize = 17;
+pub const FP: usize = 18;
+pub const PC: usize = 19;
+pub const DST_ADDR: usize = 20;
+pub const OP0_ADDR: usize = 21;
+pub const OP1_ADDR: usize = 22;
+pub const INST: usize = 23;
+pub const DST: usize = 24;
+pub const OP0: usize = 25;
+pub const OP1: usize = 26;
+pub const OFF_DST: usize = 27;
+pub const OFF_OP0: usize = 28;
+pub const OFF_OP1: usize = 29;
+pub const T0: usize = 30;
+pub const T1: usize = 31;
+pub const MUL: usize = 32;
+pub const EXTRA_ADDR: usize = 33;
+pub const EXTRA_VALUE: usize = 34;
+pub const EXTRA_RC: usize = 35;
+pub const RC_BUILTIN_ADDR: usize = 36;
+pub const RC_BUILTIN_VALUE: usize = 37;
+pub const RC_BUILTIN_LIMBS: usize = 38;
+pub const PEDERSEN_SUFFIX: usize = 46;
+pub const PEDERSEN_HIGH_BIT: usize = 47;
+pub const PEDERSEN_ACC_X: usize = 48;
+pub const PEDERSEN_ACC_Y: usize = 49;
+pub const PEDERSEN_SLOPE: usize = 50;
+pub const PEDERSEN_USED: usize = 51;
+pub const PEDERSEN_ADDR: usize = 52;
+pub const PEDERSEN_VALUE: usize = 53;
+pub const BITWISE_X_SUFFIX: usize = 54;
+pub const BITWISE_Y_SUFFIX: usize = 55;
+pub const BITWISE_AND_SUFFIX: usize = 56;
+pub const BITWISE_X: usize = 57;
+pub const BITWISE_Y: usize = 58;
+pub const BITWISE_AND: usize = 59;
+pub const BITWISE_USED: usize = 60;
+pub const BITWISE_ADDR: usize = 61;
+pub const BITWISE_VALUE: usize = 62;
+pub const SORTED_ADDRS: usize = 63;
+pub const SORTED_VALUES: usize = 71;
+pub const SORTED_RC: usize = 79;
+pub const NUM_MAIN_COLUMNS: usize = 91;
+
+pub const MEMORY_PERMUTATION: usize = 0;
+pub const RC_PERMUTATION: usize = 8;
+pub const NUM_AUX_COLUMNS: usize = 20;
+
+/// Number of 16-bit limbs of a value checked by the range check builtin.
+pub const RC_BUILTIN_NUM_LIMBS: usize = 8;
+
+/// Rows over which the bits of an input of the pedersen and bitwise builtins are decomposed.
+pub const BUILTIN_INPUT_ROWS: usize = 256;
+
+/// Rows and memory cells of an instance of the pedersen builtin.
+pub const PEDERSEN_INSTANCE_ROWS: usize = 2 * BUILTIN_INPUT_ROWS;
+pub const PEDERSEN_CELLS: usize = 3;
+/// Number of bits of the inputs of the pedersen builtin, which is the number of bits of the
+/// modulus of the field.
+pub const PEDERSEN_INPUT_BITS: usize = 252;
+
+/// Rows and memory cells of an instance of the bitwise builtin.
+pub const BITWISE_INSTANCE_ROWS: usize = BUILTIN_INPUT_ROWS;
+pub const BITWISE_CELLS: usize = 5;
+/// Number of bits of the inputs of the bitwise builtin.
+pub const BITWISE_INPUT_BITS: usize = 251;
+
+/// The (address, value) columns of the memory accesses of a row, in the order they enter the
+/// memory permutation argument.
+pub const MEMORY_SLOTS: [(usize, usize); 8] = [
+    (PC, INST),
+    (DST_ADDR, DST),
+    (OP0_ADDR, OP0),
+    (OP1_ADDR, OP1),
+    (EXTRA_ADDR, EXTRA_VALUE),
+    (RC_BUILTIN_ADDR, RC_BUILTIN_VALUE),
+    (PEDERSEN_ADDR, PEDERSEN_VALUE),
+    (BITWISE_ADDR, BITWISE_VALUE),
+];
+
+/// The columns of the range-checked values of a row, in the order they enter the range check
+/// permutation argument.
+pub const RC_SLOTS: [usize; 12] = [
+    OFF_DST,
+    OFF_OP0,
+    OFF_OP1,
+    EXTRA_RC,
+    RC_BUILTIN_LIMBS,
+    RC_BUILTIN_LIMBS + 1,
+    RC_BUILTIN_LIMBS + 2,
+    RC_BUILTIN_LIMBS + 3,
+    RC_BUILTIN_LIMBS + 4,
+    RC_BUILTIN_LIMBS + 5,
+    RC_BUILTIN_LIMBS + 6,
+    RC_BUILTIN_LIMBS + 7,
+];
@@ -0,0 +1,92 @@
+//! A prover and verifier for executions of Cairo programs over `Stark252PrimeField`.
+//!
+//! The execution is loaded from the trace, memory and AIR public input files written by the
+//! Cairo VM when running a program in proof mode (`--proof_mode --trace_file --memory_file
+//! --air_public_input`), and proven with the `CairoAIR`, which checks the decoding of the
+//! instructions, the transitions of the registers, the consistency of the memory and the
+//! range-checked offsets with permutation arguments, and the output, range check, pedersen and
+//! bitwise builtins. These are the only supported builtins: executions using any other one, such
+//! as ecdsa, ec_op, keccak or poseidon, are rejected when loading the AIR public input.
+//!
+//! See https://eprint.iacr.org/2021/1063.pdf for the description of the Cairo CPU and its AIR.
+
+pub mod air;
+pub mod builtins;
+pub mod decode;
+pub mod errors;
+pub mod execution;
+pub mod layout;
+pub mod vm_files;
+
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+
+use crate::{
+    proof::{options::ProofOptions, stark::StarkProof},
+    prover::{IsStarkProver, Prover, ProvingError},
+    verifier::{IsStarkVerifier, Verifier},
+    PrimeField,
+};
+
+use self::{
+    air::{CairoAIR, CairoPublicInputs},
+    builtins::Builtin,
+    errors::CairoError,
+    execution::CairoExecution,
+};
+
+/// Proves a Cairo execution, returning the proof together with the public inputs it is
+/// verified against.
+pub fn prove_cairo_execution(
+    execution: &CairoExecution,
+    proof_options: &ProofOptions,
+    transcript: impl IsTranscript<PrimeField>,
+) -> Result<(StarkProof<PrimeField, PrimeField>, CairoPublicInputs), ProvingError> {
+    let mut trace = execution.build_trace();
+    let public_inputs = execution.public_inputs().clone();
+    let proof =
+        Prover::<CairoAIR>::prove_statement(&mut trace, &public_inputs, proof_options, transcript)?;
+    Ok((proof, public_inputs))
+}
+
+/// Verifies the proof of a Cairo execution. The public memory needs to contain a valid
+/// instruction at `pc_init`, which the AIR uses to start the memory argument, and the trace
+/// needs room for every instance of the pedersen and bitwise builtins.
+pub fn verify_cairo_execution(
+    proof: &StarkProof<PrimeField, PrimeField>,
+    public_inputs: &CairoPublicInputs,
+    proof_options: &ProofOptions,
+    transcript: impl IsTranscript<PrimeField>,
+) -> Result<(), CairoError> {
+    if public_inputs.initial_instruction().is_none() {
+        return Err(CairoError::InvalidPublicInputs(format!(
+            "no valid instruction at pc_init = {}",
+            public_inputs.pc_init
+        )));
+    }
+    if public_inputs.rc_min > public_inputs.rc_max {
+        return Err(CairoError::InvalidPublicInputs(
+            "rc_min is greater than rc_max".to_string(),
+        ));
+    }
+    let builtin_segments = [Builtin::Pedersen, Builtin::Bitwise]
+        .into_iter()
+        .filter_map(|builtin| Some((builtin, public_inputs.builtin_segment(builtin)?)));
+    for (builtin, segment) in builtin_segments {
+        if (segment.end - segment.start) % builtin.cells() as u64 != 0 {
+            return Err(CairoError::InvalidPublicInputs(format!(
+                "the {} segment doesn't hold whole instances",
+                builtin.name()
+            )));
+        }
+        let rows = public_inputs.builtin_instances(builtin) * builtin.instance_rows();
+        if rows > proof.trace_length {
+            return Err(CairoError::InvalidPublicInputs(format!(
+                "the {} segment needs {rows} rows, but the trace has {}",
+                builtin.name(),
+                proof.trace_length
+            )));
+        }
+    }
+    Verifier::<CairoAIR>::verify_statement(proof, public_inputs, proof_options, transcript)
+        .map_err(CairoError::Verification)
+}
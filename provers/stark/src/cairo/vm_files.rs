@@ -0,0 +1,369 @@
+//! Loading of the files written by the Cairo VM when it runs a program with `--trace_file`,
+//! `--memory_file` and `--air_public_input`.
+//!
+//! Only the output, range check, pedersen and bitwise builtins are supported: the AIR public
+//! input is rejected with [`CairoError::UnsupportedBuiltin`] if the segment of any other
+//! builtin, such as ecdsa, ec_op, keccak or poseidon, is not empty.
+
+use std::{collections::HashMap, fs, ops::Range, path::Path};
+
+use lambdaworks_math::{
+    field::fields::{
+        fft_friendly::stark_252_prime_field::MontgomeryConfigStark252PrimeField,
+        montgomery_backed_prime_fields::IsModulus,
+    },
+    traits::ByteConversion,
+    unsigned_integer::element::U256,
+};
+use serde::Deserialize;
+
+use crate::Felt252;
+
+use super::{builtins::Builtin, errors::CairoError};
+
+const TRACE_ENTRY_SIZE: usize = 24;
+const MEMORY_ENTRY_SIZE: usize = 40;
+
+/// Segments of the AIR public input that an execution may use. The segments of any other
+/// builtin, such as ecdsa, ec_op, keccak or poseidon, may be present as long as they are empty.
+const SUPPORTED_SEGMENTS: [&str; 6] = [
+    "program",
+    "execution",
+    "output",
+    "range_check",
+    "pedersen",
+    "bitwise",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegisterState {
+    pub pc: u64,
+    pub ap: u64,
+    pub fp: u64,
+}
+
+/// The registers at each step of an execution. The trace file holds one entry of 24 bytes per
+/// step, with `ap`, `fp` and `pc` as little-endian `u64`s.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RegisterStates {
+    pub rows: Vec<RegisterState>,
+}
+
+impl RegisterStates {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CairoError> {
+        if bytes.len() % TRACE_ENTRY_SIZE != 0 {
+            return Err(CairoError::MalformedFile(format!(
+                "the trace file has {} bytes, which is not a multiple of {TRACE_ENTRY_SIZE}",
+                bytes.len()
+            )));
+        }
+
+        let rows = bytes
+            .chunks_exact(TRACE_ENTRY_SIZE)
+            .map(|entry| RegisterState {
+                ap: read_u64_le(&entry[0..8]),
+                fp: read_u64_le(&entry[8..16]),
+                pc: read_u64_le(&entry[16..24]),
+            })
+            .collect();
+        Ok(Self { rows })
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, CairoError> {
+        Self::from_bytes(&read_file(path)?)
+    }
+
+    pub fn steps(&self) -> usize {
+        self.rows.len()
+    }
+}
+
+/// The relocated memory at the end of an execution. The memory file holds one entry of 40 bytes
+/// per cell, with its address as a little-endian `u64` followed by its value as a little-endian
+/// 32-byte integer.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CairoMemory {
+    pub data: HashMap<u64, Felt252>,
+}
+
+impl CairoMemory {
+    pub fn new(data: HashMap<u64, Felt252>) -> Self {
+        Self { data }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CairoError> {
+        if bytes.len() % MEMORY_ENTRY_SIZE != 0 {
+            return Err(CairoError::MalformedFile(format!(
+                "the memory file has {} bytes, which is not a multiple of {MEMORY_ENTRY_SIZE}",
+                bytes.len()
+            )));
+        }
+
+        let mut data = HashMap::with_capacity(bytes.len() / MEMORY_ENTRY_SIZE);
+        for entry in bytes.chunks_exact(MEMORY_ENTRY_SIZE) {
+            let address = read_u64_le(&entry[0..8]);
+            // 32 bytes are always a valid `U256`.
+            let value = U256::from_bytes_le(&entry[8..40]).unwrap();
+            if value >= MontgomeryConfigStark252PrimeField::MODULUS {
+                return Err(CairoError::NonCanonicalValue(address));
+            }
+            data.insert(address, Felt252::new(value));
+        }
+        Ok(Self { data })
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, CairoError> {
+        Self::from_bytes(&read_file(path)?)
+    }
+
+    pub fn get(&self, address: u64) -> Result<&Felt252, CairoError> {
+        self.data
+            .get(&address)
+            .ok_or(CairoError::MissingMemoryCell(address))
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+/// The segments of the memory an execution uses, as `begin..stop` address ranges.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemorySegments {
+    pub program: Range<u64>,
+    pub execution: Range<u64>,
+    pub output: Option<Range<u64>>,
+    pub range_check: Option<Range<u64>>,
+    pub pedersen: Option<Range<u64>>,
+    pub bitwise: Option<Range<u64>>,
+}
+
+#[derive(Deserialize)]
+struct AirPublicInputJson {
+    memory_segments: HashMap<String, SegmentJson>,
+}
+
+#[derive(Deserialize)]
+struct SegmentJson {
+    begin_addr: u64,
+    stop_ptr: u64,
+}
+
+impl MemorySegments {
+    /// Reads the memory segments from the AIR public input written by the Cairo VM. Segments of
+    /// builtins other than output, range check, pedersen and bitwise, including the ones this
+    /// crate doesn't know of, must be empty, and the
+    /// pedersen and bitwise segments must hold whole instances.
+    pub fn from_air_public_input(json: &str) -> Result<Self, CairoError> {
+        let public_input: AirPublicInputJson = serde_json::from_str(json)
+            .map_err(|error| CairoError::InvalidAirPublicInput(error.to_string()))?;
+        let segments = public_input.memory_segments;
+
+        let range = |name: &str| {
+            segments
+                .get(name)
+                .map(|segment| segment.begin_addr..segment.stop_ptr)
+        };
+        let required = |name: &str| {
+            range(name).ok_or_else(|| {
+                CairoError::InvalidAirPublicInput(format!("missing the {name} segment"))
+            })
+        };
+
+        if let Some(builtin) = segments
+            .iter()
+            .filter(|(name, segment)| {
+                !SUPPORTED_SEGMENTS.contains(&name.as_str())
+                    && segment.begin_addr != segment.stop_ptr
+            })
+            .map(|(name, _)| name)
+            .min()
+        {
+            return Err(CairoError::UnsupportedBuiltin(builtin.to_string()));
+        }
+
+        for builtin in [Builtin::Pedersen, Builtin::Bitwise] {
+            if let Some(segment) = range(builtin.name()) {
+                let cells = segment.end.saturating_sub(segment.start);
+                if cells % builtin.cells() as u64 != 0 {
+                    return Err(CairoError::InvalidAirPublicInput(format!(
+                        "the {} segment has {cells} cells, which is not a multiple of {}",
+                        builtin.name(),
+                        builtin.cells()
+                    )));
+                }
+            }
+        }
+
+        Ok(Self {
+            program: required("program")?,
+            execution: required("execution")?,
+            output: range("output"),
+            range_check: range("range_check"),
+            pedersen: range(Builtin::Pedersen.name()),
+            bitwise: range(Builtin::Bitwise.name()),
+        })
+    }
+
+    pub fn from_air_public_input_file(path: impl AsRef<Path>) -> Result<Self, CairoError> {
+        let bytes = read_file(path)?;
+        let json = String::from_utf8(bytes)
+            .map_err(|error| CairoError::InvalidAirPublicInput(error.to_string()))?;
+        Self::from_air_public_input(&json)
+    }
+}
+
+fn read_file(path: impl AsRef<Path>) -> Result<Vec<u8>, CairoError> {
+    fs::read(path).map_err(|error| CairoError::Io(error.to_string()))
+}
+
+fn read_u64_le(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes.try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_are_read_in_ap_fp_pc_order() {
+        let bytes: Vec<u8> = [2u64, 3, 1, 5, 3, 7]
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect();
+
+        let registers = RegisterStates::from_bytes(&bytes).unwrap();
+
+        assert_eq!(
+            registers.rows,
+            vec![
+                RegisterState {
+                    pc: 1,
+                    ap: 2,
+                    fp: 3
+                },
+                RegisterState {
+                    pc: 7,
+                    ap: 5,
+                    fp: 3
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn memory_values_are_little_endian() {
+        let mut bytes = 4u64.to_le_bytes().to_vec();
+        let mut value = [0u8; 32];
+        value[0] = 1;
+        value[1] = 2;
+        bytes.extend_from_slice(&value);
+
+        let memory = CairoMemory::from_bytes(&bytes).unwrap();
+
+        assert_eq!(memory.get(4), Ok(&Felt252::from(0x0201)));
+        assert_eq!(memory.get(5), Err(CairoError::MissingMemoryCell(5)));
+    }
+
+    #[test]
+    fn memory_rejects_non_canonical_values() {
+        let mut bytes = 4u64.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&[0xff; 32]);
+
+        assert_eq!(
+            CairoMemory::from_bytes(&bytes),
+            Err(CairoError::NonCanonicalValue(4))
+        );
+    }
+
+    #[test]
+    fn truncated_files_are_rejected() {
+        assert!(matches!(
+            RegisterStates::from_bytes(&[0; 25]),
+            Err(CairoError::MalformedFile(_))
+        ));
+        assert!(matches!(
+            CairoMemory::from_bytes(&[0; 39]),
+            Err(CairoError::MalformedFile(_))
+        ));
+    }
+
+    #[test]
+    fn memory_segments_are_read_from_the_air_public_input() {
+        let json = r#"{
+            "layout": "small",
+            "rc_min": 32762,
+            "rc_max": 32769,
+            "n_steps": 8,
+            "memory_segments": {
+                "program": {"begin_addr": 1, "stop_ptr": 10},
+                "execution": {"begin_addr": 10, "stop_ptr": 15},
+                "output": {"begin_addr": 15, "stop_ptr": 15},
+                "pedersen": {"begin_addr": 15, "stop_ptr": 15},
+                "range_check": {"begin_addr": 15, "stop_ptr": 16}
+            },
+            "public_memory": []
+        }"#;
+
+        let segments = MemorySegments::from_air_public_input(json).unwrap();
+
+        assert_eq!(segments.program, 1..10);
+        assert_eq!(segments.execution, 10..15);
+        assert_eq!(segments.output, Some(15..15));
+        assert_eq!(segments.range_check, Some(15..16));
+        assert_eq!(segments.pedersen, Some(15..15));
+        assert_eq!(segments.bitwise, None);
+    }
+
+    #[test]
+    fn non_empty_segments_of_unsupported_builtins_are_rejected() {
+        let json = r#"{
+            "memory_segments": {
+                "program": {"begin_addr": 1, "stop_ptr": 10},
+                "execution": {"begin_addr": 10, "stop_ptr": 15},
+                "ecdsa": {"begin_addr": 15, "stop_ptr": 17}
+            }
+        }"#;
+
+        assert_eq!(
+            MemorySegments::from_air_public_input(json),
+            Err(CairoError::UnsupportedBuiltin("ecdsa".to_string()))
+        );
+    }
+
+    #[test]
+    fn non_empty_segments_of_unknown_builtins_are_rejected() {
+        let json = r#"{
+            "memory_segments": {
+                "program": {"begin_addr": 1, "stop_ptr": 10},
+                "execution": {"begin_addr": 10, "stop_ptr": 15},
+                "range_check96": {"begin_addr": 15, "stop_ptr": 17}
+            }
+        }"#;
+
+        assert_eq!(
+            MemorySegments::from_air_public_input(json),
+            Err(CairoError::UnsupportedBuiltin("range_check96".to_string()))
+        );
+    }
+
+    #[test]
+    fn segments_with_partial_builtin_instances_are_rejected() {
+        let json = r#"{
+            "memory_segments": {
+                "program": {"begin_addr": 1, "stop_ptr": 10},
+                "execution": {"begin_addr": 10, "stop_ptr": 15},
+                "bitwise": {"begin_addr": 15, "stop_ptr": 22}
+            }
+        }"#;
+
+        assert!(matches!(
+            MemorySegments::from_air_public_input(json),
+            Err(CairoError::InvalidAirPublicInput(_))
+        ));
+    }
+}
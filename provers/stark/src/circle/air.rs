@@ -0,0 +1,62 @@
+use lambdaworks_math::field::{
+    element::FieldElement,
+    fields::mersenne31::field::Mersenne31Field,
+    traits::{IsField, IsSubFieldOf},
+};
+
+use crate::{
+    constraints::boundary::BoundaryConstraints, context::AirContext, frame::Frame,
+    proof::options::ProofOptions,
+};
+
+/// The constraints of a computation proven with the Circle STARK prover. It follows the shape of
+/// [`AIR`](crate::traits::AIR), with the trace over `Mersenne31Field` and challenges sampled from
+/// its degree 4 extension.
+///
+/// The trace domain is the standard circle coset of size `trace_length`, whose `i`-th point
+/// holds the `i`-th row of the trace.
+pub trait CircleAIR {
+    type PublicInputs;
+
+    fn new(
+        trace_length: usize,
+        pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Self;
+
+    fn context(&self) -> &AirContext;
+
+    fn trace_length(&self) -> usize;
+
+    fn pub_inputs(&self) -> &Self::PublicInputs;
+
+    fn boundary_constraints(&self) -> BoundaryConstraints<Mersenne31Field>;
+
+    /// Evaluates the transition constraints on `frame`. It is called with the trace values over
+    /// `Mersenne31Field` by the prover and with the out-of-domain evaluations over the extension
+    /// by the verifier.
+    fn compute_transition<K>(&self, frame: &Frame<K, K>) -> Vec<FieldElement<K>>
+    where
+        K: IsField,
+        Mersenne31Field: IsSubFieldOf<K>;
+
+    /// The number of rows at the end of the trace where each transition constraint doesn't
+    /// apply, indexed by constraint.
+    fn transition_end_exemptions(&self) -> Vec<usize>;
+
+    /// The size of the circle FFT space containing the composition polynomial. It is a power of
+    /// two and at least twice the trace length.
+    fn composition_poly_degree_bound(&self) -> usize;
+
+    fn options(&self) -> &ProofOptions {
+        &self.context().proof_options
+    }
+
+    fn blowup_factor(&self) -> u8 {
+        self.options().blowup_factor
+    }
+
+    fn num_transition_constraints(&self) -> usize {
+        self.context().num_transition_constraints
+    }
+}
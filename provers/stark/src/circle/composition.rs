@@ -0,0 +1,194 @@
+use lambdaworks_math::{
+    circle::point::{CirclePoint, HasCircleParams},
+    field::{
+        element::FieldElement,
+        fields::mersenne31::{extensions::Degree4ExtensionField, field::Mersenne31Field},
+        traits::{IsField, IsSubFieldOf},
+    },
+};
+
+use super::{
+    air::CircleAIR,
+    domain::{coset_point, coset_vanishing, embed_point, point_excluder, point_vanishing_inverse},
+};
+use crate::{constraints::boundary::BoundaryConstraints, frame::Frame, table::TableView};
+
+type FpE = FieldElement<Mersenne31Field>;
+type Fp4E = FieldElement<Degree4ExtensionField>;
+
+/// The largest LDE domain supported, so that every domain of the FRI layers lies in the circle
+/// group of order `2^31`.
+const MAX_LDE_LOG_SIZE: u32 = 30;
+
+/// Checks that the trace length, the blowup factor and the shape of the AIR can be proven with
+/// the Circle STARK protocol, returning the reason why they can't otherwise.
+pub(crate) fn check_parameters<A: CircleAIR>(air: &A) -> Result<(), String> {
+    let trace_length = air.trace_length();
+    if !trace_length.is_power_of_two() || trace_length < 4 {
+        return Err(format!(
+            "trace length {trace_length} is not a power of two greater than or equal to 4"
+        ));
+    }
+    let blowup_factor = air.blowup_factor() as usize;
+    if !blowup_factor.is_power_of_two() {
+        return Err(format!(
+            "blowup factor {blowup_factor} is not a power of two"
+        ));
+    }
+    let lde_size = trace_length * blowup_factor;
+    if lde_size.trailing_zeros() > MAX_LDE_LOG_SIZE {
+        return Err(format!(
+            "an LDE domain of size {trace_length} * {blowup_factor} is larger than 2^{MAX_LDE_LOG_SIZE}"
+        ));
+    }
+    let degree_bound = air.composition_poly_degree_bound();
+    if !degree_bound.is_power_of_two() || degree_bound < 2 * trace_length {
+        return Err(format!(
+            "composition polynomial degree bound {degree_bound} is not a power of two greater than or equal to twice the trace length"
+        ));
+    }
+    if 2 * degree_bound > lde_size {
+        return Err(format!(
+            "composition polynomial degree bound {degree_bound} is larger than half the LDE domain size {lde_size}"
+        ));
+    }
+    let offsets = &air.context().transition_offsets;
+    if !offsets.contains(&0) || offsets.iter().any(|offset| *offset >= trace_length) {
+        return Err(format!(
+            "transition offsets {offsets:?} must include 0 and be smaller than the trace length"
+        ));
+    }
+    if air.transition_end_exemptions().len() != air.num_transition_constraints() {
+        return Err(format!(
+            "{} end exemptions given for {} transition constraints",
+            air.transition_end_exemptions().len(),
+            air.num_transition_constraints()
+        ));
+    }
+    if air.options().commit_phase_grinding_factor > 0 {
+        return Err("commit phase grinding is not supported by the Circle STARK".to_string());
+    }
+    Ok(())
+}
+
+/// Returns the points `ζ + k gₙ` where the trace is evaluated out of the domain, one per
+/// transition offset `k`.
+pub(crate) fn out_of_domain_points(
+    trace_length: usize,
+    z: &CirclePoint<Degree4ExtensionField>,
+    offsets: &[usize],
+) -> Vec<CirclePoint<Degree4ExtensionField>> {
+    let trace_generator =
+        CirclePoint::<Mersenne31Field>::get_generator_of_subgroup(trace_length.trailing_zeros());
+    offsets
+        .iter()
+        .map(|offset| z + embed_point(&(&trace_generator * *offset as u128)))
+        .collect()
+}
+
+/// Builds the frame of the out-of-domain evaluations of the trace, given per offset and column.
+pub(crate) fn out_of_domain_frame(
+    evaluations: &[Vec<Fp4E>],
+) -> Frame<'_, Degree4ExtensionField, Degree4ExtensionField> {
+    Frame::new(
+        evaluations
+            .iter()
+            .map(|row| TableView::new(vec![row.as_slice()], vec![]))
+            .collect(),
+    )
+}
+
+/// Evaluates the composition polynomial at `point`, given the frame of the trace at that point.
+/// Each transition constraint is divided by the polynomial vanishing on the trace domain and
+/// multiplied by functions excluding the rows where it doesn't apply, while each boundary
+/// constraint `t(x, y) - v` is divided by a function vanishing at the point of its row.
+pub(crate) fn evaluate_composition<A, K>(
+    air: &A,
+    frame: &Frame<K, K>,
+    point: &CirclePoint<K>,
+    boundary_constraints: &BoundaryConstraints<Mersenne31Field>,
+    transition_coeffs: &[Fp4E],
+    boundary_coeffs: &[Fp4E],
+) -> Fp4E
+where
+    A: CircleAIR,
+    K: IsField + HasCircleParams<K> + IsSubFieldOf<Degree4ExtensionField>,
+    Mersenne31Field: IsSubFieldOf<K>,
+{
+    let trace_length = air.trace_length();
+    let trace_log_size = trace_length.trailing_zeros();
+    let vanishing_inverse = coset_vanishing(trace_log_size, point).inv().unwrap();
+
+    let transition_term = air
+        .compute_transition(frame)
+        .iter()
+        .zip(air.transition_end_exemptions())
+        .zip(transition_coeffs)
+        .fold(Fp4E::zero(), |acc, ((evaluation, exemptions), coeff)| {
+            let excluder = (trace_length - exemptions..trace_length)
+                .map(|row| point_excluder(&embed_point(&coset_point(trace_log_size, row)), point))
+                .fold(FieldElement::<K>::one(), |acc, excluder| acc * excluder);
+            acc + (evaluation * excluder * &vanishing_inverse) * coeff
+        });
+
+    let current_step = frame.get_evaluation_step(0);
+    let boundary_term = boundary_constraints
+        .constraints
+        .iter()
+        .zip(boundary_coeffs)
+        .fold(Fp4E::zero(), |acc, (constraint, coeff)| {
+            let constraint_point = embed_point(&coset_point(trace_log_size, constraint.step));
+            let numerator = current_step.get_main_evaluation_element(0, constraint.col)
+                - constraint.value.to_extension::<K>();
+            acc + (numerator * point_vanishing_inverse(&constraint_point, point)) * coeff
+        });
+
+    transition_term + boundary_term
+}
+
+/// The out-of-domain evaluations and the challenges of the DEEP composition polynomial.
+pub(crate) struct DeepCompositionParameters<'a> {
+    pub(crate) z: &'a CirclePoint<Degree4ExtensionField>,
+    pub(crate) ood_points: &'a [CirclePoint<Degree4ExtensionField>],
+    pub(crate) trace_ood_evaluations: &'a [Vec<Fp4E>],
+    pub(crate) composition_poly_ood_evaluation: &'a Fp4E,
+    /// One coefficient per offset and column, in the order of `trace_ood_evaluations`.
+    pub(crate) trace_terms_gammas: &'a [Fp4E],
+    pub(crate) composition_poly_gamma: &'a Fp4E,
+}
+
+/// Evaluates the DEEP composition polynomial at a point of the LDE domain, given the row of the
+/// trace and the value of the composition polynomial at that point:
+/// `Σ γ (t(P) - t(ζ + k gₙ)) / v(P) + γ_H (H(P) - H(ζ)) / v(P)`, where each `v` vanishes at the
+/// corresponding out-of-domain point.
+pub(crate) fn evaluate_deep_composition(
+    point: &CirclePoint<Mersenne31Field>,
+    trace_row: &[FpE],
+    composition_poly_evaluation: &Fp4E,
+    parameters: &DeepCompositionParameters,
+) -> Fp4E {
+    let point = embed_point(point);
+
+    let trace_term = parameters
+        .ood_points
+        .iter()
+        .zip(parameters.trace_ood_evaluations)
+        .zip(parameters.trace_terms_gammas.chunks(trace_row.len().max(1)))
+        .fold(Fp4E::zero(), |acc, ((ood_point, ood_row), gammas)| {
+            let numerator = trace_row
+                .iter()
+                .zip(ood_row)
+                .zip(gammas)
+                .fold(Fp4E::zero(), |acc, ((value, ood_value), gamma)| {
+                    acc + (value - ood_value) * gamma
+                });
+            acc + numerator * point_vanishing_inverse(ood_point, &point)
+        });
+
+    let composition_term = (composition_poly_evaluation
+        - parameters.composition_poly_ood_evaluation)
+        * parameters.composition_poly_gamma
+        * point_vanishing_inverse(parameters.z, &point);
+
+    trace_term + composition_term
+}
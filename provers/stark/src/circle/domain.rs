@@ -0,0 +1,198 @@
+use lambdaworks_math::{
+    circle::{
+        cosets::Coset,
+        point::{CirclePoint, HasCircleParams},
+    },
+    field::{
+        element::FieldElement,
+        fields::mersenne31::field::Mersenne31Field,
+        traits::{IsField, IsSubFieldOf},
+    },
+};
+
+/// The trace domain and the LDE domain of a Circle STARK. Both are standard circle cosets, the
+/// LDE domain being `blowup_factor` times larger than the trace domain.
+pub struct CircleDomain {
+    pub(crate) trace_log_size: u32,
+    pub(crate) lde_log_size: u32,
+    /// The points of the LDE domain, in natural order.
+    pub(crate) lde_points: Vec<CirclePoint<Mersenne31Field>>,
+}
+
+impl CircleDomain {
+    pub fn new(trace_length: usize, blowup_factor: usize) -> Self {
+        let trace_log_size = trace_length.trailing_zeros();
+        let lde_log_size = (trace_length * blowup_factor).trailing_zeros();
+        Self {
+            trace_log_size,
+            lde_log_size,
+            lde_points: Coset::get_coset_points(&Coset::new_standard(lde_log_size)),
+        }
+    }
+
+    pub fn trace_length(&self) -> usize {
+        1 << self.trace_log_size
+    }
+
+    pub fn lde_size(&self) -> usize {
+        1 << self.lde_log_size
+    }
+
+    pub fn blowup_factor(&self) -> usize {
+        self.lde_size() / self.trace_length()
+    }
+
+    /// Returns the point of the trace domain holding the row `row`.
+    pub fn trace_point(&self, row: usize) -> CirclePoint<Mersenne31Field> {
+        coset_point(self.trace_log_size, row)
+    }
+}
+
+/// Returns the `index`-th point of the standard coset of size `2^log_size`.
+pub(crate) fn coset_point(log_size: u32, index: usize) -> CirclePoint<Mersenne31Field> {
+    CirclePoint::get_generator_of_subgroup(log_size + 1) * (2 * index as u128 + 1)
+}
+
+/// Embeds a point of the circle over `Mersenne31Field` into the circle over its extension `K`.
+pub(crate) fn embed_point<K>(point: &CirclePoint<Mersenne31Field>) -> CirclePoint<K>
+where
+    K: IsField,
+    Mersenne31Field: IsSubFieldOf<K>,
+{
+    CirclePoint {
+        x: point.x.to_extension(),
+        y: point.y.to_extension(),
+    }
+}
+
+/// Returns `p - q`.
+pub(crate) fn sub_points<K>(p: &CirclePoint<K>, q: &CirclePoint<K>) -> CirclePoint<K>
+where
+    K: IsField + HasCircleParams<K>,
+{
+    p + &q.clone().conjugate()
+}
+
+/// Evaluates at `point` the polynomial vanishing on the standard coset of size `2^log_size`,
+/// that is, `v_n(x)` with `v_1(x) = x` and `v_{k+1}(x) = 2 v_k(x)^2 - 1`.
+pub(crate) fn coset_vanishing<K: IsField>(
+    log_size: u32,
+    point: &CirclePoint<K>,
+) -> FieldElement<K> {
+    let mut x = point.x.clone();
+    for _ in 1..log_size {
+        x = x.square().double() - FieldElement::one();
+    }
+    x
+}
+
+/// Evaluates at `point` a function vanishing at `excluded` with multiplicity two, and nowhere
+/// else. Multiplying a constraint by it removes `excluded` from the points where the constraint
+/// must hold.
+pub(crate) fn point_excluder<K>(
+    excluded: &CirclePoint<K>,
+    point: &CirclePoint<K>,
+) -> FieldElement<K>
+where
+    K: IsField + HasCircleParams<K>,
+{
+    sub_points(point, excluded).x - FieldElement::one()
+}
+
+/// Evaluates at `point` the inverse of a function vanishing exactly at `vanish_point`, with a
+/// double pole at its antipode. Multiplying by it a polynomial vanishing at `vanish_point` gives
+/// a polynomial of the same degree. `point` must be neither `vanish_point` nor its antipode.
+pub(crate) fn point_vanishing_inverse<K>(
+    vanish_point: &CirclePoint<K>,
+    point: &CirclePoint<K>,
+) -> FieldElement<K>
+where
+    K: IsField + HasCircleParams<K>,
+{
+    let h = sub_points(point, vanish_point);
+    (FieldElement::<K>::one() + h.x) / h.y
+}
+
+/// Evaluates at `point` the circle polynomial with `coefficients` in the basis used by the
+/// circle FFT: `{1, y, x, xy, v_2(x), v_2(x)y, x v_2(x), ...}`.
+pub fn evaluate_at_point<K>(
+    coefficients: &[FieldElement<Mersenne31Field>],
+    point: &CirclePoint<K>,
+) -> FieldElement<K>
+where
+    K: IsField,
+    Mersenne31Field: IsSubFieldOf<K>,
+{
+    let mut values: Vec<FieldElement<K>> = coefficients.iter().map(|c| c.to_extension()).collect();
+    // The lowest bit of the index of a coefficient selects `y`, and the following ones
+    // select `x`, `v_2(x)`, `v_3(x)`, etc.
+    let mut factor = point.y.clone();
+    let mut x = point.x.clone();
+    while values.len() > 1 {
+        values = values
+            .chunks(2)
+            .map(|pair| &pair[0] + &factor * &pair[1])
+            .collect();
+        factor = x.clone();
+        x = x.square().double() - FieldElement::one();
+    }
+    values.pop().unwrap_or_else(FieldElement::zero)
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::{
+        circle::polynomial::evaluate_cfft,
+        field::fields::mersenne31::extensions::Degree4ExtensionField,
+    };
+
+    use super::*;
+
+    type FpE = FieldElement<Mersenne31Field>;
+
+    #[test]
+    fn evaluation_at_a_point_matches_the_circle_fft() {
+        let coefficients: Vec<FpE> = (1..=16).map(FpE::from).collect();
+        let evaluations = evaluate_cfft(coefficients.clone());
+        let domain = CircleDomain::new(16, 1);
+
+        for (row, evaluation) in evaluations.iter().enumerate() {
+            let point = domain.trace_point(row);
+            assert_eq!(&evaluate_at_point(&coefficients, &point), evaluation);
+            assert_eq!(
+                evaluate_at_point::<Degree4ExtensionField>(&coefficients, &embed_point(&point)),
+                evaluation.to_extension()
+            );
+        }
+    }
+
+    #[test]
+    fn coset_vanishing_vanishes_exactly_on_the_trace_domain() {
+        let domain = CircleDomain::new(8, 4);
+
+        for row in 0..8 {
+            assert_eq!(
+                coset_vanishing(domain.trace_log_size, &domain.trace_point(row)),
+                FpE::zero()
+            );
+        }
+        for point in domain.lde_points.iter() {
+            assert_ne!(coset_vanishing(domain.trace_log_size, point), FpE::zero());
+        }
+    }
+
+    #[test]
+    fn next_row_is_reached_adding_the_trace_generator() {
+        let domain = CircleDomain::new(8, 4);
+        let trace_generator = CirclePoint::get_generator_of_subgroup(domain.trace_log_size);
+
+        assert_eq!(
+            &domain.trace_point(3) + &trace_generator,
+            domain.trace_point(4)
+        );
+        assert_eq!(
+            &domain.lde_points[5] + &trace_generator,
+            domain.lde_points[5 + domain.blowup_factor()]
+        );
+    }
+}
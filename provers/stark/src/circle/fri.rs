@@ -0,0 +1,112 @@
+use lambdaworks_math::{
+    circle::point::CirclePoint,
+    field::{
+        element::FieldElement,
+        fields::mersenne31::{extensions::Degree4ExtensionField, field::Mersenne31Field},
+        traits::IsField,
+    },
+};
+
+use crate::config::{BatchedMerkleTree, Commitment};
+
+type FpE = FieldElement<Mersenne31Field>;
+type Fp4E = FieldElement<Degree4ExtensionField>;
+
+/// A committed layer of the circle FRI protocol. The leaf `i` of its Merkle tree holds the
+/// evaluations at the indexes `i` and `len - 1 - i`, which are the two evaluations folded
+/// together into the index `i` of the next layer.
+pub(crate) struct CircleFriLayer {
+    pub(crate) evaluations: Vec<Fp4E>,
+    pub(crate) merkle_tree: BatchedMerkleTree<Degree4ExtensionField>,
+    pub(crate) root: Commitment,
+}
+
+impl CircleFriLayer {
+    pub(crate) fn new(evaluations: Vec<Fp4E>) -> Option<Self> {
+        let leaves = pair_leaves(
+            &evaluations
+                .iter()
+                .map(|evaluation| vec![evaluation.clone()])
+                .collect::<Vec<_>>(),
+        );
+        let merkle_tree = BatchedMerkleTree::build(&leaves)?;
+        let root = merkle_tree.root;
+        Some(Self {
+            evaluations,
+            merkle_tree,
+            root,
+        })
+    }
+}
+
+/// Groups the rows `i` and `len - 1 - i` in a single leaf, for `i` in the first half of `rows`.
+pub(crate) fn pair_leaves<F: IsField>(rows: &[Vec<FieldElement<F>>]) -> Vec<Vec<FieldElement<F>>> {
+    let len = rows.len();
+    (0..len / 2)
+        .map(|i| [rows[i].clone(), rows[len - 1 - i].clone()].concat())
+        .collect()
+}
+
+/// Returns the twiddle `1 / 2y` of the first fold, which splits a function `f` on the LDE domain
+/// as `f(x, y) = f_0(x) + y f_1(x)`.
+pub(crate) fn y_fold_twiddle(point: &CirclePoint<Mersenne31Field>) -> FpE {
+    // The LDE domain is a standard coset, so no point has `y = 0`.
+    point.y.double().inv().unwrap()
+}
+
+/// Returns the twiddle `1 / 2x` folding the index `index` of a layer of length `layer_len`
+/// after the first fold, which splits a function `f` as `f(x) = f_0(2x^2 - 1) + x f_1(2x^2 - 1)`.
+/// The `index`-th value of such a layer is the one at the `x` coordinate of
+/// `(2 index + 1) g`, with `g` the generator of the subgroup of order `4 layer_len`.
+pub(crate) fn x_fold_twiddle(layer_len: usize, index: usize) -> FpE {
+    let log_size = (4 * layer_len).trailing_zeros();
+    let point = CirclePoint::<Mersenne31Field>::get_generator_of_subgroup(log_size)
+        * (2 * index as u128 + 1);
+    // The points of the layer lie on a standard coset, so no point has `x = 0`.
+    point.x.double().inv().unwrap()
+}
+
+/// Folds the values `a` at a point and `b` at its pair with the challenge `beta`:
+/// `(a + b) / 2 + beta (a - b) twiddle`.
+pub(crate) fn fold_pair(a: &Fp4E, b: &Fp4E, beta: &Fp4E, twiddle: &FpE) -> Fp4E {
+    let half = FpE::from(2).inv().unwrap();
+    half * (a + b) + twiddle * ((a - b) * beta)
+}
+
+/// Folds a whole layer into the next one, half its length.
+pub(crate) fn fold_layer(layer: &[Fp4E], beta: &Fp4E, twiddle: impl Fn(usize) -> FpE) -> Vec<Fp4E> {
+    let len = layer.len();
+    (0..len / 2)
+        .map(|i| fold_pair(&layer[i], &layer[len - 1 - i], beta, &twiddle(i)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::circle::polynomial::evaluate_cfft;
+
+    use super::*;
+    use crate::circle::domain::CircleDomain;
+
+    #[test]
+    fn folding_a_low_degree_function_ends_in_a_constant() {
+        // A polynomial in the circle FFT space of size 8, evaluated over a domain of size 32.
+        let mut coefficients: Vec<FpE> = (1..=8).map(FpE::from).collect();
+        coefficients.resize(32, FpE::zero());
+        let domain = CircleDomain::new(8, 4);
+        let mut layer: Vec<Fp4E> = evaluate_cfft(coefficients)
+            .iter()
+            .map(|evaluation| evaluation.to_extension())
+            .collect();
+        let beta = Fp4E::from(7);
+
+        layer = fold_layer(&layer, &beta, |i| y_fold_twiddle(&domain.lde_points[i]));
+        for _ in 0..2 {
+            let len = layer.len();
+            layer = fold_layer(&layer, &beta, |i| x_fold_twiddle(len, i));
+        }
+
+        assert_eq!(layer.len(), 4);
+        assert!(layer.iter().all(|value| value == &layer[0]));
+    }
+}
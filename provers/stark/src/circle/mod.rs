@@ -0,0 +1,18 @@
+//! A Circle STARK prover and verifier for computations over the Mersenne prime `2^31 - 1`.
+//!
+//! `Mersenne31Field` has no large multiplicative subgroups of order a power of two, so the
+//! trace is instead interpolated over standard cosets of the circle group `x^2 + y^2 = 1`,
+//! which has order `2^31`, with the circle FFT. Challenges are sampled from the degree 4
+//! extension of the field, and low degree is tested with the circle FRI, whose first fold
+//! splits on `y` and the following ones on `x`.
+//!
+//! See https://eprint.iacr.org/2024/278 for the description of the protocol.
+
+pub mod air;
+pub mod composition;
+pub mod domain;
+pub mod fri;
+pub mod proof;
+pub mod prover;
+pub mod transcript;
+pub mod verifier;
@@ -0,0 +1,47 @@
+use lambdaworks_crypto::merkle_tree::proof::Proof;
+use lambdaworks_math::field::{
+    element::FieldElement,
+    fields::mersenne31::{extensions::Degree4ExtensionField, field::Mersenne31Field},
+    traits::IsField,
+};
+
+use crate::config::Commitment;
+
+/// The opening of a leaf of a Merkle tree whose leaf `i` holds the values at the indexes `i`
+/// and `len - 1 - i` of the committed vector, that is, at a point of the domain and at its pair.
+#[derive(Debug, Clone)]
+pub struct CirclePairOpening<F: IsField> {
+    pub values: Vec<FieldElement<F>>,
+    pub proof: Proof<Commitment>,
+}
+
+/// The openings sent for a single FRI query.
+#[derive(Debug, Clone)]
+pub struct CircleQueryOpening {
+    /// The rows of the LDE of the trace at the query and at its pair.
+    pub trace: CirclePairOpening<Mersenne31Field>,
+    /// The evaluations of the composition polynomial at the query and at its pair.
+    pub composition_poly: CirclePairOpening<Degree4ExtensionField>,
+    /// The evaluations of each committed FRI layer needed to fold the query.
+    pub fri_layers: Vec<CirclePairOpening<Degree4ExtensionField>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CircleStarkProof {
+    // Length of the execution trace
+    pub trace_length: usize,
+    // Commitment of the LDE of the trace
+    pub lde_trace_merkle_root: Commitment,
+    // Commitment of the LDE of the composition polynomial
+    pub composition_poly_root: Commitment,
+    // tⱼ(ζ + k gₙ), indexed by transition offset and then by column
+    pub trace_ood_evaluations: Vec<Vec<FieldElement<Degree4ExtensionField>>>,
+    // Commitments of the FRI layers, excluding the first one
+    pub fri_layers_merkle_roots: Vec<Commitment>,
+    // The constant the last FRI layer folds into
+    pub fri_last_value: FieldElement<Degree4ExtensionField>,
+    // Proof of work nonce
+    pub nonce: Option<u64>,
+    // Openings for each FRI query
+    pub query_openings: Vec<CircleQueryOpening>,
+}
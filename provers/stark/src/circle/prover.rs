@@ -0,0 +1,267 @@
+use std::marker::PhantomData;
+
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::{
+    circle::polynomial::{evaluate_cfft, interpolate_cfft},
+    field::{
+        element::FieldElement,
+        fields::mersenne31::{extensions::Degree4ExtensionField, field::Mersenne31Field},
+    },
+};
+use log::info;
+use sha3::Keccak256;
+
+use super::{
+    air::CircleAIR,
+    composition::{
+        check_parameters, evaluate_composition, evaluate_deep_composition, out_of_domain_frame,
+        out_of_domain_points, DeepCompositionParameters,
+    },
+    domain::{evaluate_at_point, CircleDomain},
+    fri::{fold_layer, pair_leaves, x_fold_twiddle, y_fold_twiddle, CircleFriLayer},
+    proof::{CirclePairOpening, CircleQueryOpening, CircleStarkProof},
+    transcript::sample_circle_point,
+};
+use crate::{
+    config::BatchedMerkleTree,
+    frame::Frame,
+    grinding,
+    proof::options::ProofOptions,
+    prover::{merkle_proof, ProvingError},
+    table::Table,
+    trace::LDETraceTable,
+};
+
+type FpE = FieldElement<Mersenne31Field>;
+type Fp4E = FieldElement<Degree4ExtensionField>;
+
+/// A Circle STARK prover for AIRs over `Mersenne31Field`.
+pub struct CircleProver<A: CircleAIR> {
+    phantom: PhantomData<A>,
+}
+
+impl<A: CircleAIR> CircleProver<A> {
+    /// Generates a Circle STARK proof that `trace` satisfies the constraints of the AIR with
+    /// public inputs `pub_inputs`. The trace is given as a table with one column per trace column.
+    ///
+    /// The `coset_offset` of `proof_options` is not used: the LDE domain is always the standard
+    /// circle coset of size `trace_length * blowup_factor`, disjoint from the trace domain.
+    pub fn prove(
+        trace: &Table<Mersenne31Field>,
+        pub_inputs: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        mut transcript: impl IsTranscript<Degree4ExtensionField>,
+    ) -> Result<CircleStarkProof, ProvingError> {
+        info!("Started Circle STARK proof generation...");
+        let trace_length = trace.height;
+        if !trace_length.is_power_of_two() {
+            return Err(ProvingError::TraceLengthNotPowerOfTwo(trace_length));
+        }
+        if proof_options.grinding_factor >= 64 {
+            return Err(ProvingError::InvalidProofOptions(format!(
+                "grinding factor {} must be smaller than 64",
+                proof_options.grinding_factor
+            )));
+        }
+        let air = A::new(trace_length, pub_inputs, proof_options);
+        check_parameters(&air).map_err(ProvingError::InvalidProofOptions)?;
+        if trace.width != air.context().trace_columns {
+            return Err(ProvingError::TraceColumnsMismatch {
+                expected: (air.context().trace_columns, 0),
+                actual: (trace.width, 0),
+            });
+        }
+
+        let domain = CircleDomain::new(trace_length, air.blowup_factor() as usize);
+        let lde_size = domain.lde_size();
+        let offsets = &air.context().transition_offsets;
+
+        // ===================================
+        // ==========|   Round 1   |==========
+        // ===================================
+
+        let trace_coefficients: Vec<Vec<FpE>> =
+            trace.columns().into_iter().map(interpolate_cfft).collect();
+        let lde_columns = trace_coefficients
+            .iter()
+            .map(|coefficients| {
+                let mut coefficients = coefficients.clone();
+                coefficients.resize(lde_size, FpE::zero());
+                evaluate_cfft(coefficients)
+            })
+            .collect();
+        let lde_trace = LDETraceTable::<Mersenne31Field, Mersenne31Field>::from_columns(
+            lde_columns,
+            Vec::new(),
+            1,
+            domain.blowup_factor(),
+        );
+        let lde_trace_merkle_tree =
+            BatchedMerkleTree::<Mersenne31Field>::build(&pair_leaves(&lde_trace.main_table.rows()))
+                .ok_or(ProvingError::EmptyCommitment)?;
+        transcript.append_bytes(&lde_trace_merkle_tree.root);
+
+        // ===================================
+        // ==========|   Round 2   |==========
+        // ===================================
+
+        let boundary_constraints = air.boundary_constraints();
+        let transition_coeffs: Vec<Fp4E> = (0..air.num_transition_constraints())
+            .map(|_| transcript.sample_field_element())
+            .collect();
+        let boundary_coeffs: Vec<Fp4E> = (0..boundary_constraints.constraints.len())
+            .map(|_| transcript.sample_field_element())
+            .collect();
+
+        let composition_poly_evaluations = (0..lde_size)
+            .map(|row| {
+                let frame = Frame::read_from_lde(&lde_trace, row, offsets);
+                evaluate_composition(
+                    &air,
+                    &frame,
+                    &domain.lde_points[row],
+                    &boundary_constraints,
+                    &transition_coeffs,
+                    &boundary_coeffs,
+                )
+            })
+            .collect();
+        let composition_poly = CircleFriLayer::new(composition_poly_evaluations)
+            .ok_or(ProvingError::EmptyCommitment)?;
+        transcript.append_bytes(&composition_poly.root);
+
+        // ===================================
+        // ==========|   Round 3   |==========
+        // ===================================
+
+        let z = sample_circle_point(&mut transcript);
+        let ood_points = out_of_domain_points(trace_length, &z, offsets);
+        let trace_ood_evaluations: Vec<Vec<Fp4E>> = ood_points
+            .iter()
+            .map(|point| {
+                trace_coefficients
+                    .iter()
+                    .map(|coefficients| evaluate_at_point(coefficients, point))
+                    .collect()
+            })
+            .collect();
+        for evaluation in trace_ood_evaluations.iter().flatten() {
+            transcript.append_field_element(evaluation);
+        }
+        let composition_poly_ood_evaluation = evaluate_composition(
+            &air,
+            &out_of_domain_frame(&trace_ood_evaluations),
+            &z,
+            &boundary_constraints,
+            &transition_coeffs,
+            &boundary_coeffs,
+        );
+
+        // ===================================
+        // ==========|   Round 4   |==========
+        // ===================================
+
+        let trace_terms_gammas: Vec<Fp4E> = (0..offsets.len() * trace.width)
+            .map(|_| transcript.sample_field_element())
+            .collect();
+        let composition_poly_gamma = transcript.sample_field_element();
+        let deep_parameters = DeepCompositionParameters {
+            z: &z,
+            ood_points: &ood_points,
+            trace_ood_evaluations: &trace_ood_evaluations,
+            composition_poly_ood_evaluation: &composition_poly_ood_evaluation,
+            trace_terms_gammas: &trace_terms_gammas,
+            composition_poly_gamma: &composition_poly_gamma,
+        };
+        let deep_composition_poly_evaluations: Vec<Fp4E> = (0..lde_size)
+            .map(|row| {
+                evaluate_deep_composition(
+                    &domain.lde_points[row],
+                    lde_trace.get_main_row(row),
+                    &composition_poly.evaluations[row],
+                    &deep_parameters,
+                )
+            })
+            .collect();
+
+        // FRI commit phase: the first fold splits on `y`, the following ones on `x`, until the
+        // polynomial of the circle FFT space of size `composition_poly_degree_bound` is constant.
+        let number_of_folds = air.composition_poly_degree_bound().trailing_zeros();
+        let beta = transcript.sample_field_element();
+        let mut current_layer = fold_layer(&deep_composition_poly_evaluations, &beta, |i| {
+            y_fold_twiddle(&domain.lde_points[i])
+        });
+        let mut fri_layers = Vec::new();
+        for _ in 1..number_of_folds {
+            let layer = CircleFriLayer::new(current_layer).ok_or(ProvingError::EmptyCommitment)?;
+            transcript.append_bytes(&layer.root);
+            let beta = transcript.sample_field_element();
+            let len = layer.evaluations.len();
+            current_layer = fold_layer(&layer.evaluations, &beta, |i| x_fold_twiddle(len, i));
+            fri_layers.push(layer);
+        }
+        let fri_last_value = current_layer[0].clone();
+        transcript.append_field_element(&fri_last_value);
+
+        let grinding_factor = proof_options.grinding_factor;
+        let mut nonce = None;
+        if grinding_factor > 0 {
+            let nonce_value =
+                grinding::generate_nonce::<Keccak256>(&transcript.state(), grinding_factor)
+                    .ok_or(ProvingError::NonceNotFound(grinding_factor))?;
+            transcript.append_bytes(&nonce_value.to_be_bytes());
+            nonce = Some(nonce_value);
+        }
+
+        // FRI query phase
+        let query_openings = (0..proof_options.fri_number_of_queries)
+            .map(|_| {
+                let index = transcript.sample_u64((lde_size / 2) as u64) as usize;
+                let trace_row = lde_trace.get_main_row(index);
+                let trace_row_sym = lde_trace.get_main_row(lde_size - 1 - index);
+                Ok(CircleQueryOpening {
+                    trace: CirclePairOpening {
+                        values: [trace_row, trace_row_sym].concat(),
+                        proof: merkle_proof(&lde_trace_merkle_tree, index)?,
+                    },
+                    composition_poly: open_layer(&composition_poly, index)?,
+                    fri_layers: fri_layers
+                        .iter()
+                        .scan(index, |index, layer| {
+                            let len = layer.evaluations.len();
+                            *index = (*index).min(len - 1 - *index);
+                            Some(open_layer(layer, *index))
+                        })
+                        .collect::<Result<_, _>>()?,
+                })
+            })
+            .collect::<Result<_, ProvingError>>()?;
+
+        info!("Circle STARK proof generated");
+        Ok(CircleStarkProof {
+            trace_length,
+            lde_trace_merkle_root: lde_trace_merkle_tree.root,
+            composition_poly_root: composition_poly.root,
+            trace_ood_evaluations,
+            fri_layers_merkle_roots: fri_layers.iter().map(|layer| layer.root).collect(),
+            fri_last_value,
+            nonce,
+            query_openings,
+        })
+    }
+}
+
+/// Opens the leaf `index` of a layer, holding the evaluations at `index` and at its pair.
+fn open_layer(
+    layer: &CircleFriLayer,
+    index: usize,
+) -> Result<CirclePairOpening<Degree4ExtensionField>, ProvingError> {
+    let len = layer.evaluations.len();
+    Ok(CirclePairOpening {
+        values: vec![
+            layer.evaluations[index].clone(),
+            layer.evaluations[len - 1 - index].clone(),
+        ],
+        proof: merkle_proof(&layer.merkle_tree, index)?,
+    })
+}
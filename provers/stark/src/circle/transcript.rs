@@ -0,0 +1,104 @@
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::{
+    circle::point::CirclePoint,
+    field::{
+        element::FieldElement,
+        fields::mersenne31::{
+            extensions::{Degree2ExtensionField, Degree4ExtensionField},
+            field::Mersenne31Field,
+        },
+    },
+    traits::AsBytes,
+};
+use sha3::{Digest, Keccak256};
+
+type FpE = FieldElement<Mersenne31Field>;
+type Fp2E = FieldElement<Degree2ExtensionField>;
+type Fp4E = FieldElement<Degree4ExtensionField>;
+
+/// A Keccak256 transcript sampling challenges from the degree 4 extension of `Mersenne31Field`.
+#[derive(Clone)]
+pub struct CircleTranscript {
+    hasher: Keccak256,
+}
+
+impl CircleTranscript {
+    pub fn new(data: &[u8]) -> Self {
+        let mut transcript = Self {
+            hasher: Keccak256::new(),
+        };
+        transcript.append_bytes(data);
+        transcript
+    }
+
+    fn sample(&mut self) -> [u8; 32] {
+        let mut result_hash = [0_u8; 32];
+        result_hash.copy_from_slice(&self.hasher.finalize_reset());
+        self.hasher.update(result_hash);
+        result_hash
+    }
+
+    /// Samples an element of `Mersenne31Field` by rejection sampling over 31-bit integers.
+    fn sample_base_field_element(&mut self) -> FpE {
+        loop {
+            let bytes = self.sample();
+            let value = u32::from_be_bytes(bytes[..4].try_into().unwrap()) & 0x7fff_ffff;
+            if value != 0x7fff_ffff {
+                return FpE::from(value as u64);
+            }
+        }
+    }
+}
+
+impl Default for CircleTranscript {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
+impl IsTranscript<Degree4ExtensionField> for CircleTranscript {
+    fn append_field_element(&mut self, element: &Fp4E) {
+        self.append_bytes(&element.as_bytes());
+    }
+
+    fn append_bytes(&mut self, new_bytes: &[u8]) {
+        self.hasher.update(new_bytes);
+    }
+
+    fn state(&self) -> [u8; 32] {
+        self.hasher.clone().finalize().into()
+    }
+
+    fn sample_field_element(&mut self) -> Fp4E {
+        let coefficients: Vec<FpE> = (0..4).map(|_| self.sample_base_field_element()).collect();
+        Fp4E::new([
+            Fp2E::new([coefficients[0], coefficients[1]]),
+            Fp2E::new([coefficients[2], coefficients[3]]),
+        ])
+    }
+
+    fn sample_u64(&mut self, upper_bound: u64) -> u64 {
+        let bytes = self.sample();
+        u64::from_be_bytes(bytes[..8].try_into().unwrap()) % upper_bound
+    }
+}
+
+/// Samples a point of the circle over the degree 4 extension that is not on the circle over
+/// `Mersenne31Field`, so that it is outside of every domain of the protocol.
+pub fn sample_circle_point(
+    transcript: &mut impl IsTranscript<Degree4ExtensionField>,
+) -> CirclePoint<Degree4ExtensionField> {
+    loop {
+        // Rational parametrization of the circle: t -> ((1 - t^2) / (1 + t^2), 2t / (1 + t^2)).
+        let t = transcript.sample_field_element();
+        let t_square = t.square();
+        let Ok(denominator) = (Fp4E::one() + &t_square).inv() else {
+            continue;
+        };
+        let x = (Fp4E::one() - t_square) * &denominator;
+        let y = t.double() * denominator;
+        if y.value()[1] != Fp2E::zero() {
+            return CirclePoint { x, y };
+        }
+    }
+}
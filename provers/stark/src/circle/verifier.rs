@@ -0,0 +1,275 @@
+use std::marker::PhantomData;
+
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::{
+    field::{
+        element::FieldElement, fields::mersenne31::extensions::Degree4ExtensionField,
+        traits::IsField,
+    },
+    traits::AsBytes,
+};
+use log::error;
+use sha3::Keccak256;
+
+use super::{
+    air::CircleAIR,
+    composition::{
+        check_parameters, evaluate_composition, evaluate_deep_composition, out_of_domain_frame,
+        out_of_domain_points, DeepCompositionParameters,
+    },
+    domain::coset_point,
+    fri::{fold_pair, x_fold_twiddle, y_fold_twiddle},
+    proof::{CirclePairOpening, CircleStarkProof},
+    transcript::sample_circle_point,
+};
+use crate::{
+    config::{BatchedMerkleTreeBackend, Commitment},
+    grinding,
+    proof::options::ProofOptions,
+    verifier::VerificationError,
+};
+
+type Fp4E = FieldElement<Degree4ExtensionField>;
+
+/// A Circle STARK verifier for AIRs over `Mersenne31Field`.
+pub struct CircleVerifier<A: CircleAIR> {
+    phantom: PhantomData<A>,
+}
+
+impl<A: CircleAIR> CircleVerifier<A> {
+    /// Verifies a proof generated by [`CircleProver`](super::prover::CircleProver), replaying
+    /// the prover's transcript from `transcript`.
+    pub fn verify(
+        proof: &CircleStarkProof,
+        pub_inputs: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        mut transcript: impl IsTranscript<Degree4ExtensionField>,
+    ) -> Result<(), VerificationError> {
+        let result = Self::verify_proof(proof, pub_inputs, proof_options, &mut transcript);
+        if let Err(error) = &result {
+            error!("Circle STARK proof rejected: {:?}", error);
+        }
+        result
+    }
+
+    fn verify_proof(
+        proof: &CircleStarkProof,
+        pub_inputs: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        transcript: &mut impl IsTranscript<Degree4ExtensionField>,
+    ) -> Result<(), VerificationError> {
+        if proof.query_openings.len() < proof_options.fri_number_of_queries {
+            return Err(VerificationError::InsufficientQueries {
+                expected: proof_options.fri_number_of_queries,
+                actual: proof.query_openings.len(),
+            });
+        }
+        let trace_length = proof.trace_length;
+        if !trace_length.is_power_of_two() || trace_length.trailing_zeros() > 30 {
+            return Err(VerificationError::MalformedProof(format!(
+                "unsupported trace length {trace_length}"
+            )));
+        }
+        let air = A::new(trace_length, pub_inputs, proof_options);
+        check_parameters(&air).map_err(VerificationError::MalformedProof)?;
+        Self::validate_proof_shape(&air, proof, proof_options)?;
+
+        let lde_size = trace_length * air.blowup_factor() as usize;
+        let lde_log_size = lde_size.trailing_zeros();
+        let offsets = &air.context().transition_offsets;
+
+        // Replay the rounds of the prover to recover the challenges.
+        transcript.append_bytes(&proof.lde_trace_merkle_root);
+        let boundary_constraints = air.boundary_constraints();
+        let transition_coeffs: Vec<Fp4E> = (0..air.num_transition_constraints())
+            .map(|_| transcript.sample_field_element())
+            .collect();
+        let boundary_coeffs: Vec<Fp4E> = (0..boundary_constraints.constraints.len())
+            .map(|_| transcript.sample_field_element())
+            .collect();
+        transcript.append_bytes(&proof.composition_poly_root);
+
+        let z = sample_circle_point(transcript);
+        for evaluation in proof.trace_ood_evaluations.iter().flatten() {
+            transcript.append_field_element(evaluation);
+        }
+        let trace_terms_gammas: Vec<Fp4E> = (0..offsets.len() * air.context().trace_columns)
+            .map(|_| transcript.sample_field_element())
+            .collect();
+        let composition_poly_gamma = transcript.sample_field_element();
+
+        let mut betas = vec![transcript.sample_field_element()];
+        for root in proof.fri_layers_merkle_roots.iter() {
+            transcript.append_bytes(root);
+            betas.push(transcript.sample_field_element());
+        }
+        transcript.append_field_element(&proof.fri_last_value);
+
+        let grinding_factor = proof_options.grinding_factor;
+        if grinding_factor > 0 {
+            let nonce = proof
+                .nonce
+                .filter(|nonce| {
+                    grinding::is_valid_nonce::<Keccak256>(
+                        &transcript.state(),
+                        *nonce,
+                        grinding_factor,
+                    )
+                })
+                .ok_or(VerificationError::InvalidGrindingNonce)?;
+            transcript.append_bytes(&nonce.to_be_bytes());
+        }
+
+        // The composition polynomial at ζ is not sent: it is determined by the evaluations of
+        // the trace around ζ.
+        let ood_points = out_of_domain_points(trace_length, &z, offsets);
+        let composition_poly_ood_evaluation = evaluate_composition(
+            &air,
+            &out_of_domain_frame(&proof.trace_ood_evaluations),
+            &z,
+            &boundary_constraints,
+            &transition_coeffs,
+            &boundary_coeffs,
+        );
+        let deep_parameters = DeepCompositionParameters {
+            z: &z,
+            ood_points: &ood_points,
+            trace_ood_evaluations: &proof.trace_ood_evaluations,
+            composition_poly_ood_evaluation: &composition_poly_ood_evaluation,
+            trace_terms_gammas: &trace_terms_gammas,
+            composition_poly_gamma: &composition_poly_gamma,
+        };
+
+        let num_columns = air.context().trace_columns;
+        for (query, opening) in proof.query_openings.iter().enumerate() {
+            let index = transcript.sample_u64((lde_size / 2) as u64) as usize;
+
+            if !verify_opening(&opening.trace, &proof.lde_trace_merkle_root, index) {
+                return Err(VerificationError::TraceOpening { query });
+            }
+            if !verify_opening(
+                &opening.composition_poly,
+                &proof.composition_poly_root,
+                index,
+            ) {
+                return Err(VerificationError::CompositionPolynomialOpening { query });
+            }
+
+            let point = coset_point(lde_log_size, index);
+            let point_sym = point.clone().conjugate();
+            let (trace_row, trace_row_sym) = opening.trace.values.split_at(num_columns);
+            let deep_evaluation = evaluate_deep_composition(
+                &point,
+                trace_row,
+                &opening.composition_poly.values[0],
+                &deep_parameters,
+            );
+            let deep_evaluation_sym = evaluate_deep_composition(
+                &point_sym,
+                trace_row_sym,
+                &opening.composition_poly.values[1],
+                &deep_parameters,
+            );
+
+            let mut value = fold_pair(
+                &deep_evaluation,
+                &deep_evaluation_sym,
+                &betas[0],
+                &y_fold_twiddle(&point),
+            );
+            let mut index = index;
+            let mut len = lde_size / 2;
+            for (layer, (layer_opening, root)) in opening
+                .fri_layers
+                .iter()
+                .zip(&proof.fri_layers_merkle_roots)
+                .enumerate()
+            {
+                let leaf = index.min(len - 1 - index);
+                let opened_value = if leaf == index {
+                    &layer_opening.values[0]
+                } else {
+                    &layer_opening.values[1]
+                };
+                if opened_value != &value || !verify_opening(layer_opening, root, leaf) {
+                    return Err(VerificationError::FriLayerOpening {
+                        query,
+                        layer: layer + 1,
+                    });
+                }
+                value = fold_pair(
+                    &layer_opening.values[0],
+                    &layer_opening.values[1],
+                    &betas[layer + 1],
+                    &x_fold_twiddle(len, leaf),
+                );
+                index = leaf;
+                len /= 2;
+            }
+
+            if value != proof.fri_last_value {
+                return Err(VerificationError::FriLastValueMismatch { query });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_proof_shape(
+        air: &A,
+        proof: &CircleStarkProof,
+        proof_options: &ProofOptions,
+    ) -> Result<(), VerificationError> {
+        let num_columns = air.context().trace_columns;
+        let num_offsets = air.context().transition_offsets.len();
+        if proof.trace_ood_evaluations.len() != num_offsets
+            || proof
+                .trace_ood_evaluations
+                .iter()
+                .any(|row| row.len() != num_columns)
+        {
+            return Err(VerificationError::MalformedProof(format!(
+                "expected {num_offsets} out-of-domain rows of {num_columns} evaluations"
+            )));
+        }
+
+        let num_layers = air.composition_poly_degree_bound().trailing_zeros() as usize - 1;
+        if proof.fri_layers_merkle_roots.len() != num_layers {
+            return Err(VerificationError::MalformedProof(format!(
+                "expected {num_layers} FRI layers, found {}",
+                proof.fri_layers_merkle_roots.len()
+            )));
+        }
+
+        if proof.query_openings.len() != proof_options.fri_number_of_queries
+            || proof.query_openings.iter().any(|opening| {
+                opening.trace.values.len() != 2 * num_columns
+                    || opening.composition_poly.values.len() != 2
+                    || opening.fri_layers.len() != num_layers
+                    || opening
+                        .fri_layers
+                        .iter()
+                        .any(|layer| layer.values.len() != 2)
+            })
+        {
+            return Err(VerificationError::MalformedProof(
+                "query openings do not match the proof options and the AIR".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn verify_opening<F: IsField>(
+    opening: &CirclePairOpening<F>,
+    root: &Commitment,
+    index: usize,
+) -> bool
+where
+    FieldElement<F>: AsBytes,
+    Vec<FieldElement<F>>: Sync + Send,
+{
+    opening
+        .proof
+        .verify::<BatchedMerkleTreeBackend<F>>(root, index, &opening.values)
+}
@@ -1,7 +1,16 @@
+use alloc::vec::Vec;
 use lambdaworks_crypto::merkle_tree::{
-    backends::types::{BatchKeccak256Backend, Keccak256Backend},
+    backends::types::{BatchKeccak256Backend, BatchSha3_256Backend, Keccak256Backend},
     merkle::MerkleTree,
+    traits::IsMerkleTreeBackend,
 };
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsField},
+    traits::AsBytes,
+};
+use sha3::{Keccak256, Sha3_256};
+
+use crate::grinding::IsGrindingHash;
 
 // Merkle Trees configuration
 
@@ -18,3 +27,47 @@ pub type Commitment = [u8; COMMITMENT_SIZE];
 
 pub type BatchedMerkleTreeBackend<F> = BatchKeccak256Backend<F>;
 pub type BatchedMerkleTree<F> = MerkleTree<BatchedMerkleTreeBackend<F>>;
+
+/// The hash used by the Merkle trees committing to the trace, the composition polynomial and the
+/// FRI layers of a proof, where `F` is the field of the trace and `E` its extension.
+/// Leaves are rows of field elements and nodes are `Commitment`s. The proof-of-work of the
+/// prover is computed with `GrindingHash`.
+pub trait IsCommitmentHash<F: IsField, E: IsField> {
+    /// Backend of the trees whose leaves are rows of the main and preprocessed traces.
+    type MainBackend: IsMerkleTreeBackend<Node = Commitment, Data = Vec<FieldElement<F>>>;
+    /// Backend of the trees whose leaves are rows of extension field elements: the auxiliary
+    /// trace, the composition polynomial parts and the FRI layers.
+    type ExtensionBackend: IsMerkleTreeBackend<Node = Commitment, Data = Vec<FieldElement<E>>>;
+    /// Hash of the proof-of-work done before the FRI folding challenges and the queries.
+    type GrindingHash: IsGrindingHash;
+}
+
+/// Keccak256 Merkle trees, the default commitments of the prover and verifier.
+pub struct Keccak256Commitments;
+
+impl<F, E> IsCommitmentHash<F, E> for Keccak256Commitments
+where
+    F: IsField,
+    E: IsField,
+    FieldElement<F>: AsBytes + Sync + Send,
+    FieldElement<E>: AsBytes + Sync + Send,
+{
+    type MainBackend = BatchedMerkleTreeBackend<F>;
+    type ExtensionBackend = BatchedMerkleTreeBackend<E>;
+    type GrindingHash = Keccak256;
+}
+
+/// Sha3-256 Merkle trees and proof-of-work.
+pub struct Sha3_256Commitments;
+
+impl<F, E> IsCommitmentHash<F, E> for Sha3_256Commitments
+where
+    F: IsField,
+    E: IsField,
+    FieldElement<F>: AsBytes + Sync + Send,
+    FieldElement<E>: AsBytes + Sync + Send,
+{
+    type MainBackend = BatchSha3_256Backend<F>;
+    type ExtensionBackend = BatchSha3_256Backend<E>;
+    type GrindingHash = Sha3_256;
+}
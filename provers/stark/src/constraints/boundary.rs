@@ -1,4 +1,4 @@
-use itertools::Itertools;
+use alloc::{collections::BTreeSet, vec::Vec};
 use lambdaworks_math::{
     field::{element::FieldElement, traits::IsField},
     polynomial::Polynomial,
@@ -87,18 +87,20 @@ impl<F: IsField> BoundaryConstraints<F> {
     }
 
     pub fn steps_for_boundary(&self) -> Vec<usize> {
+        let mut seen = BTreeSet::new();
         self.constraints
             .iter()
-            .unique_by(|elem| elem.step)
             .map(|v| v.step)
+            .filter(|step| seen.insert(*step))
             .collect()
     }
 
     pub fn cols_for_boundary(&self) -> Vec<usize> {
+        let mut seen = BTreeSet::new();
         self.constraints
             .iter()
-            .unique_by(|elem| elem.col)
             .map(|v| v.col)
+            .filter(|col| seen.insert(*col))
             .collect()
     }
 
@@ -5,6 +5,7 @@ use crate::domain::Domain;
 use crate::trace::LDETraceTable;
 use crate::traits::{TransitionEvaluationContext, AIR};
 use crate::{frame::Frame, prover::evaluate_polynomial_on_lde_domain};
+use alloc::vec::Vec;
 use itertools::Itertools;
 #[cfg(not(feature = "parallel"))]
 use lambdaworks_math::polynomial::Polynomial;
@@ -38,7 +39,7 @@ impl<A: AIR> ConstraintEvaluator<A> {
         transition_coefficients: &[FieldElement<A::FieldExtension>],
         boundary_coefficients: &[FieldElement<A::FieldExtension>],
         rap_challenges: &[FieldElement<A::FieldExtension>],
-    ) -> Vec<FieldElement<A::FieldExtension>>
+    ) -> Result<Vec<FieldElement<A::FieldExtension>>, FFTError>
     where
         FieldElement<A::Field>: AsBytes + Send + Sync,
         FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
@@ -79,8 +80,7 @@ impl<A: AIR> ConstraintEvaluator<A> {
                     &domain.coset_offset,
                 )
             })
-            .collect::<Result<Vec<Vec<FieldElement<A::Field>>>, FFTError>>()
-            .unwrap();
+            .collect::<Result<Vec<Vec<FieldElement<A::Field>>>, FFTError>>()?;
 
         #[cfg(feature = "instruments")]
         println!(
@@ -221,6 +221,6 @@ impl<A: AIR> ConstraintEvaluator<A> {
             timer.elapsed()
         );
 
-        evaluations_t
+        Ok(evaluations_t)
     }
 }
@@ -1,3 +1,4 @@
 pub mod boundary;
 pub mod evaluator;
+pub mod symbolic;
 pub mod transition;
@@ -0,0 +1,743 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::ops::{Add, Mul, Neg, Sub};
+
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsFFTField, IsField, IsSubFieldOf},
+};
+
+use crate::traits::TransitionEvaluationContext;
+
+use super::transition::TransitionConstraint;
+
+/// A cell of the evaluation frame.
+///
+/// - `step`: the index of the step inside the frame, as given by the transition offsets of the AIR.
+///   Step 0 is the current step, step 1 the next one and so on.
+/// - `row`: the row inside the step. It is always 0 when the AIR has `STEP_SIZE = 1`.
+/// - `col`: the column of the main or auxiliary trace.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TraceCell {
+    pub step: usize,
+    pub row: usize,
+    pub col: usize,
+}
+
+/// A symbolic expression over the cells of an evaluation frame, the periodic values and the
+/// RAP challenges.
+///
+/// Expressions are built with the constructors of this type and the usual arithmetic operators.
+/// They know their own degree and can be evaluated over both the prover and the verifier frames,
+/// so a constraint only needs to be written once:
+///
+/// ```ignore
+/// let a0 = Expr::main(0);
+/// let a1 = Expr::main_at(1, 0);
+/// let a2 = Expr::main_at(2, 0);
+/// let fib = a2 - a1 - a0;
+/// ```
+#[derive(Clone, Debug)]
+pub enum Expr<F: IsField> {
+    /// A cell of the main trace.
+    Main(TraceCell),
+    /// A cell of the auxiliary trace.
+    Aux(TraceCell),
+    /// The value of a periodic column at the current step.
+    Periodic(usize),
+    /// A challenge sampled to build the auxiliary trace.
+    Challenge(usize),
+    /// A constant of the base field.
+    Constant(FieldElement<F>),
+    Add(Box<Expr<F>>, Box<Expr<F>>),
+    Sub(Box<Expr<F>>, Box<Expr<F>>),
+    Mul(Box<Expr<F>>, Box<Expr<F>>),
+    Neg(Box<Expr<F>>),
+    Pow(Box<Expr<F>>, u64),
+}
+
+impl<F: IsField> Expr<F> {
+    /// The cell of column `col` of the main trace at the current step.
+    pub fn main(col: usize) -> Self {
+        Self::main_at(0, col)
+    }
+
+    /// The cell of column `col` of the main trace at the next step.
+    pub fn main_next(col: usize) -> Self {
+        Self::main_at(1, col)
+    }
+
+    /// The cell of column `col` of the main trace at the frame step `step`.
+    pub fn main_at(step: usize, col: usize) -> Self {
+        Self::main_at_row(step, 0, col)
+    }
+
+    /// The cell of column `col` of the main trace at the row `row` of the frame step `step`.
+    pub fn main_at_row(step: usize, row: usize, col: usize) -> Self {
+        Self::Main(TraceCell { step, row, col })
+    }
+
+    /// The cell of column `col` of the auxiliary trace at the current step.
+    pub fn aux(col: usize) -> Self {
+        Self::aux_at(0, col)
+    }
+
+    /// The cell of column `col` of the auxiliary trace at the next step.
+    pub fn aux_next(col: usize) -> Self {
+        Self::aux_at(1, col)
+    }
+
+    /// The cell of column `col` of the auxiliary trace at the frame step `step`.
+    pub fn aux_at(step: usize, col: usize) -> Self {
+        Self::aux_at_row(step, 0, col)
+    }
+
+    /// The cell of column `col` of the auxiliary trace at the row `row` of the frame step `step`.
+    pub fn aux_at_row(step: usize, row: usize, col: usize) -> Self {
+        Self::Aux(TraceCell { step, row, col })
+    }
+
+    /// The value of the periodic column `idx` at the current step.
+    pub fn periodic(idx: usize) -> Self {
+        Self::Periodic(idx)
+    }
+
+    /// The RAP challenge `idx`.
+    pub fn challenge(idx: usize) -> Self {
+        Self::Challenge(idx)
+    }
+
+    pub fn constant(value: FieldElement<F>) -> Self {
+        Self::Constant(value)
+    }
+
+    pub fn zero() -> Self {
+        Self::Constant(FieldElement::zero())
+    }
+
+    pub fn one() -> Self {
+        Self::Constant(FieldElement::one())
+    }
+
+    pub fn pow(self, exponent: u64) -> Self {
+        Self::Pow(Box::new(self), exponent)
+    }
+
+    pub fn square(self) -> Self {
+        self.pow(2)
+    }
+
+    /// The degree of the expression interpreting it as a multivariate polynomial on the
+    /// trace cells. Periodic values, challenges and constants have degree 0.
+    pub fn degree(&self) -> usize {
+        match self {
+            Self::Main(_) | Self::Aux(_) => 1,
+            Self::Periodic(_) | Self::Challenge(_) | Self::Constant(_) => 0,
+            Self::Add(a, b) | Self::Sub(a, b) => a.degree().max(b.degree()),
+            Self::Mul(a, b) => a.degree() + b.degree(),
+            Self::Neg(a) => a.degree(),
+            Self::Pow(a, exponent) => a.degree() * (*exponent as usize),
+        }
+    }
+
+    /// The largest frame step read by the expression, or `None` if it reads no trace cell.
+    pub fn max_step(&self) -> Option<usize> {
+        match self {
+            Self::Main(cell) | Self::Aux(cell) => Some(cell.step),
+            Self::Periodic(_) | Self::Challenge(_) | Self::Constant(_) => None,
+            Self::Add(a, b) | Self::Sub(a, b) | Self::Mul(a, b) => a.max_step().max(b.max_step()),
+            Self::Neg(a) | Self::Pow(a, _) => a.max_step(),
+        }
+    }
+
+    /// Evaluates the expression over the frame, periodic values and challenges of the
+    /// evaluation context. The result is the same for the prover and the verifier contexts.
+    pub fn evaluate<E>(
+        &self,
+        evaluation_context: &TransitionEvaluationContext<F, E>,
+    ) -> FieldElement<E>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        match evaluation_context {
+            TransitionEvaluationContext::Prover {
+                frame,
+                periodic_values,
+                rap_challenges,
+            } => self.evaluate_with(
+                &|cell: &TraceCell| {
+                    frame
+                        .get_evaluation_step(cell.step)
+                        .get_main_evaluation_element(cell.row, cell.col)
+                        .clone()
+                        .to_extension()
+                },
+                &|cell: &TraceCell| {
+                    frame
+                        .get_evaluation_step(cell.step)
+                        .get_aux_evaluation_element(cell.row, cell.col)
+                        .clone()
+                },
+                &|idx: usize| periodic_values[idx].clone().to_extension(),
+                rap_challenges,
+            ),
+            TransitionEvaluationContext::Verifier {
+                frame,
+                periodic_values,
+                rap_challenges,
+            } => self.evaluate_with(
+                &|cell: &TraceCell| {
+                    frame
+                        .get_evaluation_step(cell.step)
+                        .get_main_evaluation_element(cell.row, cell.col)
+                        .clone()
+                },
+                &|cell: &TraceCell| {
+                    frame
+                        .get_evaluation_step(cell.step)
+                        .get_aux_evaluation_element(cell.row, cell.col)
+                        .clone()
+                },
+                &|idx: usize| periodic_values[idx].clone(),
+                rap_challenges,
+            ),
+        }
+    }
+
+    fn evaluate_with<E>(
+        &self,
+        main: &dyn Fn(&TraceCell) -> FieldElement<E>,
+        aux: &dyn Fn(&TraceCell) -> FieldElement<E>,
+        periodic: &dyn Fn(usize) -> FieldElement<E>,
+        rap_challenges: &[FieldElement<E>],
+    ) -> FieldElement<E>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        let eval = |expr: &Expr<F>| expr.evaluate_with(main, aux, periodic, rap_challenges);
+        match self {
+            Self::Main(cell) => main(cell),
+            Self::Aux(cell) => aux(cell),
+            Self::Periodic(idx) => periodic(*idx),
+            Self::Challenge(idx) => rap_challenges[*idx].clone(),
+            Self::Constant(value) => value.clone().to_extension(),
+            Self::Add(a, b) => eval(a) + eval(b),
+            Self::Sub(a, b) => eval(a) - eval(b),
+            Self::Mul(a, b) => eval(a) * eval(b),
+            Self::Neg(a) => -eval(a),
+            Self::Pow(a, exponent) => eval(a).pow(*exponent),
+        }
+    }
+}
+
+impl<F: IsField> From<FieldElement<F>> for Expr<F> {
+    fn from(value: FieldElement<F>) -> Self {
+        Self::Constant(value)
+    }
+}
+
+impl<F: IsField> From<&Expr<F>> for Expr<F> {
+    fn from(expr: &Expr<F>) -> Self {
+        expr.clone()
+    }
+}
+
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, $variant:ident) => {
+        impl<F: IsField, R: Into<Expr<F>>> $trait<R> for Expr<F> {
+            type Output = Expr<F>;
+
+            fn $method(self, rhs: R) -> Expr<F> {
+                Expr::$variant(Box::new(self), Box::new(rhs.into()))
+            }
+        }
+
+        impl<F: IsField, R: Into<Expr<F>>> $trait<R> for &Expr<F> {
+            type Output = Expr<F>;
+
+            fn $method(self, rhs: R) -> Expr<F> {
+                Expr::$variant(Box::new(self.clone()), Box::new(rhs.into()))
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, Add);
+impl_binary_op!(Sub, sub, Sub);
+impl_binary_op!(Mul, mul, Mul);
+
+impl<F: IsField> Neg for Expr<F> {
+    type Output = Expr<F>;
+
+    fn neg(self) -> Expr<F> {
+        Expr::Neg(Box::new(self))
+    }
+}
+
+impl<F: IsField> Neg for &Expr<F> {
+    type Output = Expr<F>;
+
+    fn neg(self) -> Expr<F> {
+        Expr::Neg(Box::new(self.clone()))
+    }
+}
+
+/// A transition constraint defined by a symbolic expression that must evaluate to zero.
+///
+/// The degree of the constraint is derived from the expression and the evaluation is shared
+/// between the prover and the verifier. The zerofier is configured with the `with_*` methods,
+/// which have the same meaning as the corresponding methods of `TransitionConstraint`.
+#[derive(Clone, Debug)]
+pub struct SymbolicConstraint<F: IsField> {
+    expr: Expr<F>,
+    constraint_idx: usize,
+    degree: usize,
+    period: usize,
+    offset: usize,
+    exemptions_period: Option<usize>,
+    periodic_exemptions_offset: Option<usize>,
+    end_exemptions: usize,
+}
+
+impl<F: IsField> SymbolicConstraint<F> {
+    /// Creates a constraint applied on every step of the trace, without exemptions.
+    /// The constraint index is assigned when it is added to a `SymbolicConstraints` set.
+    pub fn new(expr: Expr<F>) -> Self {
+        let degree = expr.degree();
+        Self {
+            expr,
+            constraint_idx: 0,
+            degree,
+            period: 1,
+            offset: 0,
+            exemptions_period: None,
+            periodic_exemptions_offset: None,
+            end_exemptions: 0,
+        }
+    }
+
+    pub fn with_end_exemptions(mut self, end_exemptions: usize) -> Self {
+        self.end_exemptions = end_exemptions;
+        self
+    }
+
+    pub fn with_period(mut self, period: usize) -> Self {
+        self.period = period;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn with_periodic_exemptions(mut self, exemptions_period: usize, offset: usize) -> Self {
+        self.exemptions_period = Some(exemptions_period);
+        self.periodic_exemptions_offset = Some(offset);
+        self
+    }
+
+    pub fn expr(&self) -> &Expr<F> {
+        &self.expr
+    }
+}
+
+impl<F, E> TransitionConstraint<F, E> for SymbolicConstraint<F>
+where
+    F: IsSubFieldOf<E> + IsFFTField + Send + Sync,
+    E: IsField + Send + Sync,
+    FieldElement<F>: Send + Sync,
+{
+    fn degree(&self) -> usize {
+        self.degree
+    }
+
+    fn constraint_idx(&self) -> usize {
+        self.constraint_idx
+    }
+
+    fn evaluate(
+        &self,
+        evaluation_context: &TransitionEvaluationContext<F, E>,
+        transition_evaluations: &mut [FieldElement<E>],
+    ) {
+        transition_evaluations[self.constraint_idx] = self.expr.evaluate(evaluation_context);
+    }
+
+    fn period(&self) -> usize {
+        self.period
+    }
+
+    fn offset(&self) -> usize {
+        self.offset
+    }
+
+    fn exemptions_period(&self) -> Option<usize> {
+        self.exemptions_period
+    }
+
+    fn periodic_exemptions_offset(&self) -> Option<usize> {
+        self.periodic_exemptions_offset
+    }
+
+    fn end_exemptions(&self) -> usize {
+        self.end_exemptions
+    }
+}
+
+/// An ordered set of symbolic constraints. Constraint indexes are assigned in insertion order,
+/// and `build` produces the list of transition constraints returned by `AIR::transition_constraints`.
+#[derive(Clone, Debug)]
+pub struct SymbolicConstraints<F: IsField> {
+    constraints: Vec<SymbolicConstraint<F>>,
+}
+
+impl<F: IsField> Default for SymbolicConstraints<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: IsField> SymbolicConstraints<F> {
+    pub fn new() -> Self {
+        Self {
+            constraints: Vec::new(),
+        }
+    }
+
+    /// Adds a constraint to the set, assigning it the next constraint index.
+    pub fn with(mut self, mut constraint: SymbolicConstraint<F>) -> Self {
+        constraint.constraint_idx = self.constraints.len();
+        self.constraints.push(constraint);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.constraints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.constraints.is_empty()
+    }
+
+    /// Returns the frame offsets needed to evaluate all the constraints of the set,
+    /// to be used as `AirContext::transition_offsets`.
+    pub fn transition_offsets(&self) -> Vec<usize> {
+        let max_step = self
+            .constraints
+            .iter()
+            .filter_map(|c| c.expr.max_step())
+            .max()
+            .unwrap_or(0);
+        (0..=max_step).collect()
+    }
+
+    /// Returns the maximum degree among the constraints of the set.
+    pub fn max_degree(&self) -> usize {
+        self.constraints.iter().map(|c| c.degree).max().unwrap_or(0)
+    }
+
+    pub fn build<E>(self) -> Vec<Box<dyn TransitionConstraint<F, E>>>
+    where
+        F: IsSubFieldOf<E> + IsFFTField + Send + Sync + 'static,
+        E: IsField + Send + Sync + 'static,
+        FieldElement<F>: Send + Sync,
+    {
+        self.constraints
+            .into_iter()
+            .map(|c| Box::new(c) as Box<dyn TransitionConstraint<F, E>>)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constraints::boundary::{BoundaryConstraint, BoundaryConstraints},
+        context::AirContext,
+        examples::{fibonacci_rap::fibonacci_rap_trace, simple_fibonacci},
+        frame::Frame,
+        proof::options::ProofOptions,
+        prover::{IsStarkProver, Prover},
+        table::Table,
+        trace::TraceTable,
+        traits::AIR,
+        transcript::StoneProverTranscript,
+        verifier::{IsStarkVerifier, Verifier},
+        Felt252,
+    };
+    use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+    use lambdaworks_math::field::fields::{
+        fft_friendly::{
+            babybear::Babybear31PrimeField, quartic_babybear::Degree4BabyBearExtensionField,
+            stark_252_prime_field::Stark252PrimeField,
+        },
+        u64_prime_field::F17,
+    };
+
+    type FE17 = FieldElement<F17>;
+
+    #[test]
+    fn degree_is_computed_from_the_expression() {
+        let a = Expr::<F17>::main(0);
+        let b = Expr::<F17>::main_next(1);
+        let z = Expr::<F17>::aux(0);
+        let gamma = Expr::<F17>::challenge(0);
+
+        assert_eq!((&a + &b).degree(), 1);
+        assert_eq!((&a * &b).degree(), 2);
+        assert_eq!((&z * (&a + &gamma)).degree(), 2);
+        assert_eq!((&a * &b * &z - FE17::from(3)).degree(), 3);
+        assert_eq!(a.clone().pow(3).degree(), 3);
+        assert_eq!((Expr::periodic(0) * &a).degree(), 1);
+        assert_eq!(Expr::<F17>::constant(FE17::from(5)).degree(), 0);
+    }
+
+    #[test]
+    fn transition_offsets_cover_all_the_steps_used() {
+        let constraints = SymbolicConstraints::<F17>::new()
+            .with(SymbolicConstraint::new(Expr::main_next(0) - Expr::main(0)))
+            .with(SymbolicConstraint::new(
+                Expr::main_at(2, 0) - Expr::main_at(1, 0),
+            ));
+
+        assert_eq!(constraints.len(), 2);
+        assert_eq!(constraints.transition_offsets(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn evaluation_is_the_same_for_prover_and_verifier_frames() {
+        // Main trace over Babybear and auxiliary trace over its quartic extension.
+        type F = Babybear31PrimeField;
+        type E = Degree4BabyBearExtensionField;
+
+        let main = Table::<F>::new(
+            vec![
+                FieldElement::from(3),
+                FieldElement::from(5),
+                FieldElement::from(7),
+                FieldElement::from(11),
+            ],
+            2,
+        );
+        let aux = Table::<E>::new(
+            vec![
+                FieldElement::<F>::from(13).to_extension(),
+                FieldElement::<F>::from(17).to_extension(),
+            ],
+            1,
+        );
+        let prover_frame = Frame::new(
+            (0..2)
+                .map(|row| {
+                    crate::table::TableView::new(vec![main.get_row(row)], vec![aux.get_row(row)])
+                })
+                .collect(),
+        );
+        let main_extension = Table::<E>::new(
+            main.data.iter().map(|x| x.clone().to_extension()).collect(),
+            2,
+        );
+        let verifier_frame = Frame::new(
+            (0..2)
+                .map(|row| {
+                    crate::table::TableView::new(
+                        vec![main_extension.get_row(row)],
+                        vec![aux.get_row(row)],
+                    )
+                })
+                .collect(),
+        );
+        let challenges = vec![FieldElement::<E>::from(19)];
+        let periodic_values_prover = vec![FieldElement::<F>::from(2)];
+        let periodic_values_verifier = vec![FieldElement::<E>::from(2)];
+
+        // z' * (b + gamma) - z * (a + gamma) * s + 1
+        let expr = Expr::<F>::aux_next(0) * (Expr::main(1) + Expr::challenge(0))
+            - Expr::aux(0) * (Expr::main(0) + Expr::challenge(0)) * Expr::periodic(0)
+            + FieldElement::<F>::one();
+
+        let prover_eval = expr.evaluate(&TransitionEvaluationContext::new_prover(
+            &prover_frame,
+            &periodic_values_prover,
+            &challenges,
+        ));
+        let verifier_eval = expr.evaluate(&TransitionEvaluationContext::new_verifier(
+            &verifier_frame,
+            &periodic_values_verifier,
+            &challenges,
+        ));
+
+        let expected = FieldElement::<E>::from(17) * FieldElement::<E>::from(5 + 19)
+            - FieldElement::<E>::from(13)
+                * FieldElement::<E>::from(3 + 19)
+                * FieldElement::<E>::from(2)
+            + FieldElement::<E>::one();
+
+        assert_eq!(prover_eval, expected);
+        assert_eq!(verifier_eval, expected);
+    }
+
+    struct SymbolicFibonacciRAP {
+        context: AirContext,
+        trace_length: usize,
+        constraints: Vec<Box<dyn TransitionConstraint<Stark252PrimeField, Stark252PrimeField>>>,
+    }
+
+    impl AIR for SymbolicFibonacciRAP {
+        type Field = Stark252PrimeField;
+        type FieldExtension = Stark252PrimeField;
+        type PublicInputs = ();
+
+        const STEP_SIZE: usize = 1;
+        const AIR_ID: &'static str = "symbolic_test";
+
+        fn new(trace_length: usize, _pub_inputs: &(), proof_options: &ProofOptions) -> Self {
+            let fib = Expr::main_at(2, 0) - Expr::main_at(1, 0) - Expr::main(0);
+            let gamma = Expr::challenge(0);
+            let permutation = Expr::aux_next(0) * (Expr::main(1) + &gamma)
+                - Expr::aux(0) * (Expr::main(0) + &gamma);
+
+            let constraints = SymbolicConstraints::new()
+                .with(SymbolicConstraint::new(fib).with_end_exemptions(3 + 32 - 16 - 1))
+                .with(SymbolicConstraint::new(permutation).with_end_exemptions(1));
+
+            let context = AirContext {
+                proof_options: proof_options.clone(),
+                trace_columns: 3,
+                transition_offsets: constraints.transition_offsets(),
+                num_transition_constraints: constraints.len(),
+            };
+
+            Self {
+                context,
+                trace_length,
+                constraints: constraints.build(),
+            }
+        }
+
+        fn build_auxiliary_trace(
+            &self,
+            trace: &mut TraceTable<Self::Field, Self::FieldExtension>,
+            challenges: &[Felt252],
+        ) {
+            let gamma = &challenges[0];
+            let mut z = Felt252::one();
+            trace.set_aux(0, 0, z);
+            for i in 1..trace.num_rows() {
+                let not_perm = trace.get_main(i - 1, 0) + gamma;
+                let perm = trace.get_main(i - 1, 1) + gamma;
+                z = z * not_perm / perm;
+                trace.set_aux(i, 0, z);
+            }
+        }
+
+        fn build_rap_challenges(
+            &self,
+            transcript: &mut impl IsTranscript<Self::FieldExtension>,
+        ) -> Vec<Felt252> {
+            vec![transcript.sample_field_element()]
+        }
+
+        fn trace_layout(&self) -> (usize, usize) {
+            (2, 1)
+        }
+
+        fn composition_poly_degree_bound(&self) -> usize {
+            self.trace_length
+        }
+
+        fn boundary_constraints(
+            &self,
+            _rap_challenges: &[Felt252],
+        ) -> BoundaryConstraints<Stark252PrimeField> {
+            BoundaryConstraints::from_constraints(vec![
+                BoundaryConstraint::new_simple_main(0, Felt252::one()),
+                BoundaryConstraint::new_simple_main(1, Felt252::one()),
+                BoundaryConstraint::new_aux(0, 0, Felt252::one()),
+            ])
+        }
+
+        fn transition_constraints(
+            &self,
+        ) -> &Vec<Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>> {
+            &self.constraints
+        }
+
+        fn context(&self) -> &AirContext {
+            &self.context
+        }
+
+        fn trace_length(&self) -> usize {
+            self.trace_length
+        }
+
+        fn pub_inputs(&self) -> &() {
+            &()
+        }
+    }
+
+    #[test]
+    fn symbolic_constraints_match_handwritten_fibonacci_constraint() {
+        let trace = simple_fibonacci::fibonacci_trace([Felt252::one(), Felt252::one()], 8);
+        let air = simple_fibonacci::FibonacciAIR::new(
+            8,
+            &simple_fibonacci::FibonacciPublicInputs {
+                a0: Felt252::one(),
+                a1: Felt252::one(),
+            },
+            &ProofOptions::default_test_options(),
+        );
+        let symbolic = SymbolicConstraints::new()
+            .with(
+                SymbolicConstraint::new(Expr::main_at(2, 0) - Expr::main_at(1, 0) - Expr::main(0))
+                    .with_end_exemptions(2),
+            )
+            .build::<Stark252PrimeField>();
+
+        let frame = trace.main_table.into_frame(1, 1);
+        for step in 0..6 {
+            let window = Frame::new(
+                (step..step + 3)
+                    .map(|i| frame.get_evaluation_step(i).clone())
+                    .collect(),
+            );
+            let context = TransitionEvaluationContext::new_verifier(&window, &[], &[]);
+            let mut evaluations = vec![Felt252::zero()];
+            symbolic[0].evaluate(&context, &mut evaluations);
+
+            assert_eq!(air.compute_transition(&context), evaluations);
+        }
+        assert_eq!(
+            symbolic[0].degree(),
+            air.transition_constraints()[0].degree()
+        );
+        assert_eq!(
+            symbolic[0].end_exemptions(),
+            air.transition_constraints()[0].end_exemptions()
+        );
+    }
+
+    #[test]
+    fn prove_and_verify_air_with_symbolic_constraints() {
+        let mut trace = fibonacci_rap_trace([Felt252::one(), Felt252::one()], 16);
+        let proof_options = ProofOptions::default_test_options();
+
+        let proof = Prover::<SymbolicFibonacciRAP>::prove(
+            &mut trace,
+            &(),
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+        assert!(Verifier::<SymbolicFibonacciRAP>::verify(
+            &proof,
+            &(),
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .is_ok());
+    }
+}
@@ -1,4 +1,5 @@
-use std::ops::Div;
+use alloc::vec::Vec;
+use core::ops::Div;
 
 use crate::domain::Domain;
 use crate::prover::evaluate_polynomial_on_lde_domain;
@@ -162,7 +163,7 @@ where
                 .cycle()
                 .take(end_exemption_evaluations.len());
 
-            std::iter::zip(cycled_evaluations, end_exemption_evaluations)
+            core::iter::zip(cycled_evaluations, end_exemption_evaluations)
                 .map(|(eval, exemption_eval)| eval * exemption_eval)
                 .collect()
 
@@ -198,7 +199,7 @@ where
                 .cycle()
                 .take(end_exemption_evaluations.len());
 
-            std::iter::zip(cycled_evaluations, end_exemption_evaluations)
+            core::iter::zip(cycled_evaluations, end_exemption_evaluations)
                 .map(|(eval, exemption_eval)| eval * exemption_eval)
                 .collect()
         }
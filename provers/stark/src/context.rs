@@ -1,4 +1,5 @@
 use super::proof::options::ProofOptions;
+use alloc::vec::Vec;
 
 #[derive(Clone, Debug)]
 pub struct AirContext {
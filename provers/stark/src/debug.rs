@@ -1,15 +1,105 @@
 use super::domain::Domain;
 use super::traits::{TransitionEvaluationContext, AIR};
-use crate::{frame::Frame, trace::LDETraceTable};
+use crate::{
+    frame::Frame,
+    trace::{LDETraceTable, TraceTable},
+};
+use alloc::vec::Vec;
+use core::fmt;
 use lambdaworks_math::{
     field::{
         element::FieldElement,
-        traits::{IsFFTField, IsField},
+        traits::{IsFFTField, IsField, IsSubFieldOf},
     },
     polynomial::Polynomial,
 };
 use log::{error, info};
 
+/// A constraint of an AIR that does not hold on a given trace.
+#[derive(Clone, Debug)]
+pub enum ConstraintViolation<F, E>
+where
+    F: IsSubFieldOf<E>,
+    E: IsField,
+{
+    /// The value of the trace at `row` and `col` differs from the one required by the boundary
+    /// constraint with index `constraint_idx`.
+    Boundary {
+        constraint_idx: usize,
+        row: usize,
+        col: usize,
+        is_aux: bool,
+        expected: FieldElement<E>,
+        found: FieldElement<E>,
+    },
+    /// The transition constraint with index `constraint_idx` evaluates to a non-zero value on
+    /// the frame starting at `step`. `main_frame` and `aux_frame` hold the rows of the frame, in
+    /// the order given by the transition offsets of the AIR.
+    Transition {
+        constraint_idx: usize,
+        step: usize,
+        row: usize,
+        evaluation: FieldElement<E>,
+        main_frame: Vec<Vec<FieldElement<F>>>,
+        aux_frame: Vec<Vec<FieldElement<E>>>,
+    },
+}
+
+impl<F, E> fmt::Display for ConstraintViolation<F, E>
+where
+    F: IsSubFieldOf<E>,
+    E: IsField,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Boundary {
+                constraint_idx,
+                row,
+                col,
+                is_aux,
+                expected,
+                found,
+            } => {
+                let trace = if *is_aux { "aux" } else { "main" };
+                write!(
+                    f,
+                    "Boundary constraint {constraint_idx} violated at row {row} of {trace} column {col} - expected {expected:?}, found {found:?}"
+                )
+            }
+            Self::Transition {
+                constraint_idx,
+                step,
+                row,
+                evaluation,
+                main_frame,
+                aux_frame,
+            } => write!(
+                f,
+                "Transition constraint {constraint_idx} violated at step {step} (row {row}) - expected 0, got {evaluation:?}. Main frame: {main_frame:?}, aux frame: {aux_frame:?}"
+            ),
+        }
+    }
+}
+
+/// Checks every boundary and transition constraint of `air` against `trace`, returning the
+/// list of violations. An empty list means the trace is valid.
+///
+/// The preprocessed columns of the AIR are appended to the main columns of `trace`. For AIRs
+/// with an auxiliary trace, `trace` must already contain the auxiliary columns built with
+/// `rap_challenges`.
+pub fn check_trace<A: AIR>(
+    air: &A,
+    trace: &TraceTable<A::Field, A::FieldExtension>,
+    rap_challenges: &[FieldElement<A::FieldExtension>],
+) -> Vec<ConstraintViolation<A::Field, A::FieldExtension>> {
+    let mut main_columns = trace.columns_main();
+    main_columns.extend(air.preprocessed_columns());
+
+    let lde_trace = LDETraceTable::from_columns(main_columns, trace.columns_aux(), A::STEP_SIZE, 1);
+
+    check_lde_trace(air, &lde_trace, rap_challenges)
+}
+
 /// Validates that the trace is valid with respect to the supplied AIR constraints
 pub fn validate_trace<A: AIR>(
     air: &A,
@@ -19,7 +109,6 @@ pub fn validate_trace<A: AIR>(
     rap_challenges: &[FieldElement<A::FieldExtension>],
 ) -> bool {
     info!("Starting constraints validation over trace...");
-    let mut ret = true;
 
     let main_trace_columns: Vec<_> = main_trace_polys
         .iter()
@@ -44,54 +133,72 @@ pub fn validate_trace<A: AIR>(
     let lde_trace =
         LDETraceTable::from_columns(main_trace_columns, aux_trace_columns, A::STEP_SIZE, 1);
 
-    let periodic_columns: Vec<_> = air
-        .get_periodic_column_polynomials()
-        .iter()
-        .map(|poly| {
-            Polynomial::<FieldElement<A::Field>>::evaluate_fft::<A::Field>(
-                poly,
-                1,
-                Some(domain.interpolation_domain_size),
-            )
-            .unwrap()
-        })
-        .collect();
+    let violations = check_lde_trace(air, &lde_trace, rap_challenges);
+    for violation in violations.iter() {
+        error!("{violation}");
+    }
+
+    info!("Constraints validation check ended");
+    violations.is_empty()
+}
+
+/// Checks the constraints of `air` over `lde_trace`, which must be the trace itself, that is, an
+/// LDE with blowup factor 1.
+fn check_lde_trace<A: AIR>(
+    air: &A,
+    lde_trace: &LDETraceTable<A::Field, A::FieldExtension>,
+    rap_challenges: &[FieldElement<A::FieldExtension>],
+) -> Vec<ConstraintViolation<A::Field, A::FieldExtension>> {
+    let mut violations = Vec::new();
+
+    let periodic_columns = air.get_periodic_column_values();
 
     // --------- VALIDATE BOUNDARY CONSTRAINTS ------------
     air.boundary_constraints(rap_challenges)
         .constraints
         .iter()
-        .for_each(|constraint| {
+        .enumerate()
+        .for_each(|(constraint_idx, constraint)| {
             let col = constraint.col;
-            let step = constraint.step;
-            let boundary_value = constraint.value.clone();
+            let row = constraint.step;
+            let expected = constraint.value.clone().to_extension();
 
-            let trace_value = if !constraint.is_aux {
-                lde_trace.get_main(step, col).clone().to_extension()
+            let found = if !constraint.is_aux {
+                lde_trace.get_main(row, col).clone().to_extension()
             } else {
-                lde_trace.get_aux(step,  col).clone()
+                lde_trace.get_aux(row, col).clone()
             };
 
-            if boundary_value.clone().to_extension() != trace_value {
-                ret = false;
-                error!("Boundary constraint inconsistency - Expected value {:?} in step {} and column {}, found: {:?}", boundary_value, step, col, trace_value);
+            if expected != found {
+                violations.push(ConstraintViolation::Boundary {
+                    constraint_idx,
+                    row,
+                    col,
+                    is_aux: constraint.is_aux,
+                    expected,
+                    found,
+                });
             }
         });
 
     // --------- VALIDATE TRANSITION CONSTRAINTS -----------
     let n_transition_constraints = air.context().num_transition_constraints();
-    let exemption_steps: Vec<usize> = std::iter::repeat(lde_trace.num_steps())
+    let exemption_steps: Vec<usize> = core::iter::repeat(lde_trace.num_steps())
         .take(n_transition_constraints)
         .zip(air.transition_constraints())
-        .map(|(trace_steps, constraint)| trace_steps - constraint.end_exemptions())
+        .map(|(trace_steps, constraint)| {
+            trace_steps - constraint.end_exemptions() * constraint.period()
+        })
         .collect();
 
+    let num_offsets = air.context().transition_offsets.len();
+
     // Iterate over trace and compute transitions
     for step in 0..lde_trace.num_steps() {
-        let frame = Frame::read_step_from_lde(&lde_trace, step, &air.context().transition_offsets);
+        let frame = Frame::read_step_from_lde(lde_trace, step, &air.context().transition_offsets);
         let periodic_values: Vec<_> = periodic_columns
             .iter()
-            .map(|col| col[step].clone())
+            .map(|col| col[step % col.len()].clone())
             .collect();
         let transition_evaluation_context =
             TransitionEvaluationContext::new_prover(&frame, &periodic_values, rap_challenges);
@@ -101,19 +208,34 @@ pub fn validate_trace<A: AIR>(
         // the exemption steps corresponding to the transition, it should have zero as a
         // result
         evaluations.iter().enumerate().for_each(|(i, eval)| {
-            // Check that all the transition constraint evaluations of the trace are zero.
-            // We don't take into account the transition exemptions.
-            if step < exemption_steps[i] && eval != &FieldElement::zero() {
-                ret = false;
-                error!(
-                    "Inconsistent evaluation of transition {} in step {} - expected 0, got {:?}",
-                    i, step, eval
-                );
+            // Check that the transition constraint evaluations of the trace are zero at the
+            // rows each constraint applies to.
+            let constraint = &air.transition_constraints()[i];
+            let applies = step % constraint.period() == constraint.offset()
+                && constraint
+                    .exemptions_period()
+                    .zip(constraint.periodic_exemptions_offset())
+                    .map_or(true, |(period, offset)| step % period != offset);
+            if applies && step < exemption_steps[i] && eval != &FieldElement::zero() {
+                violations.push(ConstraintViolation::Transition {
+                    constraint_idx: i,
+                    step,
+                    row: lde_trace.step_to_row(step),
+                    evaluation: eval.clone(),
+                    main_frame: (0..num_offsets)
+                        .flat_map(|i| frame.get_evaluation_step(i).data.iter())
+                        .map(|row| row.to_vec())
+                        .collect(),
+                    aux_frame: (0..num_offsets)
+                        .flat_map(|i| frame.get_evaluation_step(i).aux_data.iter())
+                        .map(|row| row.to_vec())
+                        .collect(),
+                });
             }
         })
     }
-    info!("Constraints validation check ended");
-    ret
+
+    violations
 }
 
 pub fn check_boundary_polys_divisibility<F: IsFFTField>(
@@ -141,3 +263,91 @@ where
     let rows: Vec<Vec<FieldElement<F>>> = data.chunks(width).map(|c| c.to_vec()).collect();
     rows.iter().all(|r| r.len() == rows[0].len())
 }
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
+
+    use super::*;
+    use crate::{
+        examples::{
+            round_constants::{self, RoundConstantsAIR, RoundConstantsPublicInputs},
+            simple_fibonacci::{self, FibonacciAIR, FibonacciPublicInputs},
+        },
+        proof::options::ProofOptions,
+        Felt252,
+    };
+
+    fn fibonacci_air(trace_length: usize) -> FibonacciAIR<Stark252PrimeField> {
+        let pub_inputs = FibonacciPublicInputs {
+            a0: Felt252::one(),
+            a1: Felt252::one(),
+        };
+        FibonacciAIR::new(
+            trace_length,
+            &pub_inputs,
+            &ProofOptions::default_test_options(),
+        )
+    }
+
+    #[test]
+    fn check_trace_returns_no_violations_for_valid_trace() {
+        let trace = simple_fibonacci::fibonacci_trace([Felt252::one(), Felt252::one()], 8);
+        let air = fibonacci_air(trace.num_rows());
+
+        assert!(check_trace(&air, &trace, &[]).is_empty());
+    }
+
+    #[test]
+    fn check_trace_appends_preprocessed_columns() {
+        let trace = round_constants::round_constants_trace(Felt252::from(3), 8);
+        let pub_inputs = RoundConstantsPublicInputs {
+            input: Felt252::from(3),
+            output: *trace.get_main(7, 0),
+        };
+        let air = RoundConstantsAIR::new(8, &pub_inputs, &ProofOptions::default_test_options());
+
+        assert!(check_trace(&air, &trace, &[]).is_empty());
+    }
+
+    #[test]
+    fn check_trace_reports_each_violated_constraint() {
+        let mut trace = simple_fibonacci::fibonacci_trace([Felt252::one(), Felt252::one()], 8);
+        trace.set_main(1, 0, Felt252::from(2));
+        let air = fibonacci_air(trace.num_rows());
+
+        let violations = check_trace(&air, &trace, &[]);
+
+        let column = |values: &[u64]| -> Vec<Vec<Felt252>> {
+            values.iter().map(|v| vec![Felt252::from(*v)]).collect()
+        };
+        assert_eq!(violations.len(), 3);
+        assert!(matches!(
+            &violations[0],
+            ConstraintViolation::Boundary {
+                constraint_idx: 1,
+                row: 1,
+                col: 0,
+                is_aux: false,
+                expected,
+                found,
+            } if expected == &Felt252::one() && found == &Felt252::from(2)
+        ));
+        for (violation, (step, frame)) in violations[1..]
+            .iter()
+            .zip([(0, column(&[1, 2, 2])), (1, column(&[2, 2, 3]))])
+        {
+            assert!(matches!(
+                violation,
+                ConstraintViolation::Transition {
+                    constraint_idx: 0,
+                    step: s,
+                    row,
+                    evaluation,
+                    main_frame,
+                    ..
+                } if *s == step && *row == step && evaluation == &-Felt252::one() && main_frame == &frame
+            ));
+        }
+    }
+}
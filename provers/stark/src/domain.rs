@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 use lambdaworks_math::{
     fft::cpu::roots_of_unity::get_powers_of_primitive_root_coset,
     field::{element::FieldElement, traits::IsFFTField},
@@ -17,11 +18,22 @@ pub struct Domain<F: IsFFTField> {
 
 impl<F: IsFFTField> Domain<F> {
     pub fn new<A>(air: &A) -> Self
+    where
+        A: AIR<Field = F>,
+    {
+        let lde_size = air.trace_length() * air.options().blowup_factor as usize;
+        Self::new_with_lde_size(air, lde_size)
+    }
+
+    /// Returns the domain of `air` with an LDE domain of `lde_size` elements instead of
+    /// `trace_length * blowup_factor`. Traces of different lengths evaluated over domains with the
+    /// same `lde_size` and coset offset share their LDE domain.
+    pub(crate) fn new_with_lde_size<A>(air: &A, lde_size: usize) -> Self
     where
         A: AIR<Field = F>,
     {
         // Initial definitions
-        let blowup_factor = air.options().blowup_factor as usize;
+        let blowup_factor = lde_size / air.trace_length();
         let coset_offset = FieldElement::from(air.options().coset_offset);
         let interpolation_domain_size = air.trace_length();
         let root_order = air.trace_length().trailing_zeros();
@@ -34,13 +46,10 @@ impl<F: IsFFTField> Domain<F> {
         )
         .unwrap();
 
-        let lde_root_order = (air.trace_length() * blowup_factor).trailing_zeros();
-        let lde_roots_of_unity_coset = get_powers_of_primitive_root_coset(
-            lde_root_order as u64,
-            air.trace_length() * blowup_factor,
-            &coset_offset,
-        )
-        .unwrap();
+        let lde_root_order = lde_size.trailing_zeros();
+        let lde_roots_of_unity_coset =
+            get_powers_of_primitive_root_coset(lde_root_order as u64, lde_size, &coset_offset)
+                .unwrap();
 
         Self {
             root_order,
@@ -137,6 +137,7 @@ impl AIR for BitFlagsAIR {
     type PublicInputs = ();
 
     const STEP_SIZE: usize = 16;
+    const AIR_ID: &'static str = "bit_flags";
 
     fn new(
         trace_length: usize,
@@ -190,7 +191,7 @@ impl AIR for BitFlagsAIR {
     }
 
     fn trace_layout(&self) -> (usize, usize) {
-        (1, 0)
+        (2, 0)
     }
 
     fn pub_inputs(&self) -> &Self::PublicInputs {
@@ -0,0 +1,107 @@
+use crate::{
+    circle::air::CircleAIR,
+    constraints::boundary::{BoundaryConstraint, BoundaryConstraints},
+    context::AirContext,
+    frame::Frame,
+    proof::options::ProofOptions,
+    table::Table,
+};
+use lambdaworks_math::field::{
+    element::FieldElement,
+    fields::mersenne31::field::Mersenne31Field,
+    traits::{IsField, IsSubFieldOf},
+};
+
+/// The Fibonacci sequence over `Mersenne31Field`, proven with the Circle STARK prover.
+pub struct CircleFibonacciAIR {
+    context: AirContext,
+    trace_length: usize,
+    pub_inputs: CircleFibonacciPublicInputs,
+}
+
+#[derive(Clone, Debug)]
+pub struct CircleFibonacciPublicInputs {
+    pub a0: FieldElement<Mersenne31Field>,
+    pub a1: FieldElement<Mersenne31Field>,
+}
+
+impl CircleAIR for CircleFibonacciAIR {
+    type PublicInputs = CircleFibonacciPublicInputs;
+
+    fn new(
+        trace_length: usize,
+        pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Self {
+        let context = AirContext {
+            proof_options: proof_options.clone(),
+            trace_columns: 1,
+            transition_offsets: vec![0, 1, 2],
+            num_transition_constraints: 1,
+        };
+
+        Self {
+            context,
+            trace_length,
+            pub_inputs: pub_inputs.clone(),
+        }
+    }
+
+    fn context(&self) -> &AirContext {
+        &self.context
+    }
+
+    fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    fn pub_inputs(&self) -> &Self::PublicInputs {
+        &self.pub_inputs
+    }
+
+    fn boundary_constraints(&self) -> BoundaryConstraints<Mersenne31Field> {
+        let a0 = BoundaryConstraint::new_simple_main(0, self.pub_inputs.a0);
+        let a1 = BoundaryConstraint::new_simple_main(1, self.pub_inputs.a1);
+
+        BoundaryConstraints::from_constraints(vec![a0, a1])
+    }
+
+    fn compute_transition<K>(&self, frame: &Frame<K, K>) -> Vec<FieldElement<K>>
+    where
+        K: IsField,
+        Mersenne31Field: IsSubFieldOf<K>,
+    {
+        let a0 = frame
+            .get_evaluation_step(0)
+            .get_main_evaluation_element(0, 0);
+        let a1 = frame
+            .get_evaluation_step(1)
+            .get_main_evaluation_element(0, 0);
+        let a2 = frame
+            .get_evaluation_step(2)
+            .get_main_evaluation_element(0, 0);
+
+        vec![a2 - a1 - a0]
+    }
+
+    fn transition_end_exemptions(&self) -> Vec<usize> {
+        vec![2]
+    }
+
+    fn composition_poly_degree_bound(&self) -> usize {
+        2 * self.trace_length
+    }
+}
+
+pub fn circle_fibonacci_trace(
+    initial_values: [FieldElement<Mersenne31Field>; 2],
+    trace_length: usize,
+) -> Table<Mersenne31Field> {
+    let mut ret = initial_values.to_vec();
+
+    for i in 2..trace_length {
+        ret.push(ret[i - 1] + ret[i - 2]);
+    }
+
+    Table::from_columns(vec![ret])
+}
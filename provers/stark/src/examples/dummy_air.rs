@@ -145,6 +145,7 @@ impl AIR for DummyAIR {
     type PublicInputs = ();
 
     const STEP_SIZE: usize = 1;
+    const AIR_ID: &'static str = "dummy";
 
     fn new(
         trace_length: usize,
@@ -177,6 +177,7 @@ where
     type PublicInputs = PublicInputs<Self::Field>;
 
     const STEP_SIZE: usize = 1;
+    const AIR_ID: &'static str = "fibonacci_2_cols_shifted";
 
     fn new(
         trace_length: usize,
@@ -156,6 +156,7 @@ where
     type PublicInputs = FibonacciPublicInputs<Self::Field>;
 
     const STEP_SIZE: usize = 1;
+    const AIR_ID: &'static str = "fibonacci_2_columns";
 
     fn new(
         trace_length: usize,
@@ -14,7 +14,7 @@ use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
 use lambdaworks_math::{
     field::{element::FieldElement, traits::IsFFTField},
     helpers::resize_to_next_power_of_two,
-    traits::ByteConversion,
+    traits::{AsBytes, ByteConversion},
 };
 
 #[derive(Clone)]
@@ -164,6 +164,21 @@ where
     pub a1: FieldElement<F>,
 }
 
+impl<F> AsBytes for FibonacciRAPPublicInputs<F>
+where
+    F: IsFFTField,
+    FieldElement<F>: AsBytes,
+{
+    fn as_bytes(&self) -> Vec<u8> {
+        [
+            (self.steps as u64).to_be_bytes().to_vec(),
+            self.a0.as_bytes(),
+            self.a1.as_bytes(),
+        ]
+        .concat()
+    }
+}
+
 impl<F> AIR for FibonacciRAP<F>
 where
     F: IsFFTField + Send + Sync + 'static,
@@ -174,6 +189,7 @@ where
     type PublicInputs = FibonacciRAPPublicInputs<Self::Field>;
 
     const STEP_SIZE: usize = 1;
+    const AIR_ID: &'static str = "fibonacci_rap";
 
     fn new(
         trace_length: usize,
@@ -1,9 +1,11 @@
 pub mod bit_flags;
+pub mod circle_fibonacci;
 pub mod dummy_air;
 pub mod fibonacci_2_cols_shifted;
 pub mod fibonacci_2_columns;
 pub mod fibonacci_rap;
 pub mod quadratic_air;
 pub mod read_only_memory;
+pub mod round_constants;
 pub mod simple_fibonacci;
 pub mod simple_periodic_cols;
@@ -10,7 +10,10 @@ use crate::{
     trace::TraceTable,
     traits::{TransitionEvaluationContext, AIR},
 };
-use lambdaworks_math::field::{element::FieldElement, traits::IsFFTField};
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsFFTField},
+    traits::AsBytes,
+};
 
 #[derive(Clone)]
 struct QuadraticConstraint<F: IsFFTField> {
@@ -89,6 +92,16 @@ where
     pub a0: FieldElement<F>,
 }
 
+impl<F> AsBytes for QuadraticPublicInputs<F>
+where
+    F: IsFFTField,
+    FieldElement<F>: AsBytes,
+{
+    fn as_bytes(&self) -> Vec<u8> {
+        self.a0.as_bytes()
+    }
+}
+
 impl<F> AIR for QuadraticAIR<F>
 where
     F: IsFFTField + Send + Sync + 'static,
@@ -98,6 +111,7 @@ where
     type PublicInputs = QuadraticPublicInputs<Self::Field>;
 
     const STEP_SIZE: usize = 1;
+    const AIR_ID: &'static str = "quadratic";
 
     fn new(
         trace_length: usize,
@@ -249,6 +249,7 @@ where
     type PublicInputs = ReadOnlyPublicInputs<F>;
 
     const STEP_SIZE: usize = 1;
+    const AIR_ID: &'static str = "read_only_memory";
 
     fn new(
         trace_length: usize,
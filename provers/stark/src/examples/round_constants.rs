@@ -0,0 +1,210 @@
+use std::marker::PhantomData;
+
+use crate::{
+    constraints::{
+        boundary::{BoundaryConstraint, BoundaryConstraints},
+        transition::TransitionConstraint,
+    },
+    context::AirContext,
+    proof::options::ProofOptions,
+    trace::TraceTable,
+    traits::{TransitionEvaluationContext, AIR},
+};
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsFFTField},
+    traits::AsBytes,
+};
+
+pub struct RoundConstantConstraint<F: IsFFTField> {
+    phantom: PhantomData<F>,
+}
+impl<F: IsFFTField> RoundConstantConstraint<F> {
+    pub fn new() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+impl<F: IsFFTField> Default for RoundConstantConstraint<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F> TransitionConstraint<F, F> for RoundConstantConstraint<F>
+where
+    F: IsFFTField + Send + Sync,
+{
+    fn degree(&self) -> usize {
+        2
+    }
+
+    fn constraint_idx(&self) -> usize {
+        0
+    }
+
+    fn end_exemptions(&self) -> usize {
+        1
+    }
+
+    fn evaluate(
+        &self,
+        evaluation_context: &TransitionEvaluationContext<F, F>,
+        transition_evaluations: &mut [FieldElement<F>],
+    ) {
+        let frame = match evaluation_context {
+            TransitionEvaluationContext::Prover { frame, .. }
+            | TransitionEvaluationContext::Verifier { frame, .. } => frame,
+        };
+
+        let first_step = frame.get_evaluation_step(0);
+        let second_step = frame.get_evaluation_step(1);
+
+        let x0 = first_step.get_main_evaluation_element(0, 0);
+        // The preprocessed column comes right after the main trace column.
+        let c0 = first_step.get_main_evaluation_element(0, 1);
+        let x1 = second_step.get_main_evaluation_element(0, 0);
+
+        transition_evaluations[self.constraint_idx()] = x1 - x0.square() - c0;
+    }
+}
+
+/// A sequence that mixes in a fixed round constant at every step:
+///     x_{i+1} = x_i² + c_i
+/// The round constants `c_i` are not part of the witness. They are a preprocessed
+/// column of the AIR, committed to in the verifier key.
+///
+///   x (main)  |  c (preprocessed)
+///   x_0       |  c_0
+///   x_1       |  c_1
+///   ...       |  ...
+pub struct RoundConstantsAIR<F>
+where
+    F: IsFFTField,
+{
+    context: AirContext,
+    trace_length: usize,
+    pub_inputs: RoundConstantsPublicInputs<F>,
+    transition_constraints: Vec<Box<dyn TransitionConstraint<F, F>>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct RoundConstantsPublicInputs<F>
+where
+    F: IsFFTField,
+{
+    pub input: FieldElement<F>,
+    pub output: FieldElement<F>,
+}
+
+impl<F> AsBytes for RoundConstantsPublicInputs<F>
+where
+    F: IsFFTField,
+    FieldElement<F>: AsBytes,
+{
+    fn as_bytes(&self) -> Vec<u8> {
+        [self.input.as_bytes(), self.output.as_bytes()].concat()
+    }
+}
+
+impl<F> AIR for RoundConstantsAIR<F>
+where
+    F: IsFFTField + Send + Sync + 'static,
+{
+    type Field = F;
+    type FieldExtension = F;
+    type PublicInputs = RoundConstantsPublicInputs<Self::Field>;
+
+    const STEP_SIZE: usize = 1;
+    const AIR_ID: &'static str = "round_constants";
+
+    fn new(
+        trace_length: usize,
+        pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Self {
+        let transition_constraints: Vec<
+            Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>,
+        > = vec![Box::new(RoundConstantConstraint::new())];
+
+        let context = AirContext {
+            proof_options: proof_options.clone(),
+            trace_columns: 2,
+            transition_offsets: vec![0, 1],
+            num_transition_constraints: transition_constraints.len(),
+        };
+
+        Self {
+            pub_inputs: pub_inputs.clone(),
+            context,
+            trace_length,
+            transition_constraints,
+        }
+    }
+
+    fn composition_poly_degree_bound(&self) -> usize {
+        2 * self.trace_length()
+    }
+
+    fn boundary_constraints(
+        &self,
+        _rap_challenges: &[FieldElement<Self::FieldExtension>],
+    ) -> BoundaryConstraints<Self::Field> {
+        let input = BoundaryConstraint::new_simple_main(0, self.pub_inputs.input.clone());
+        let output = BoundaryConstraint::new_simple_main(
+            self.trace_length() - 1,
+            self.pub_inputs.output.clone(),
+        );
+
+        BoundaryConstraints::from_constraints(vec![input, output])
+    }
+
+    fn transition_constraints(
+        &self,
+    ) -> &Vec<Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>> {
+        &self.transition_constraints
+    }
+
+    fn preprocessed_columns(&self) -> Vec<Vec<FieldElement<Self::Field>>> {
+        vec![round_constants(self.trace_length)]
+    }
+
+    fn num_preprocessed_columns(&self) -> usize {
+        1
+    }
+
+    fn context(&self) -> &AirContext {
+        &self.context
+    }
+
+    fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    fn trace_layout(&self) -> (usize, usize) {
+        (1, 0)
+    }
+
+    fn pub_inputs(&self) -> &Self::PublicInputs {
+        &self.pub_inputs
+    }
+}
+
+/// Returns the round constants `c_i = i + 1` for a trace of length `trace_length`.
+pub fn round_constants<F: IsFFTField>(trace_length: usize) -> Vec<FieldElement<F>> {
+    (1..=trace_length as u64).map(FieldElement::from).collect()
+}
+
+pub fn round_constants_trace<F: IsFFTField>(
+    input: FieldElement<F>,
+    trace_length: usize,
+) -> TraceTable<F, F> {
+    let constants = round_constants::<F>(trace_length);
+    let mut ret = vec![input];
+    for c in constants.iter().take(trace_length - 1) {
+        let x = ret.last().unwrap();
+        ret.push(x.square() + c);
+    }
+
+    TraceTable::from_columns_main(vec![ret], 1)
+}
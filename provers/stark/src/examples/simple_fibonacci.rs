@@ -8,7 +8,10 @@ use crate::{
     trace::TraceTable,
     traits::{TransitionEvaluationContext, AIR},
 };
-use lambdaworks_math::field::{element::FieldElement, traits::IsFFTField};
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsFFTField},
+    traits::AsBytes,
+};
 use std::marker::PhantomData;
 
 #[derive(Clone)]
@@ -91,6 +94,16 @@ where
     pub a1: FieldElement<F>,
 }
 
+impl<F> AsBytes for FibonacciPublicInputs<F>
+where
+    F: IsFFTField,
+    FieldElement<F>: AsBytes,
+{
+    fn as_bytes(&self) -> Vec<u8> {
+        [self.a0.as_bytes(), self.a1.as_bytes()].concat()
+    }
+}
+
 impl<F> AIR for FibonacciAIR<F>
 where
     F: IsFFTField + Send + Sync + 'static,
@@ -100,6 +113,7 @@ where
     type PublicInputs = FibonacciPublicInputs<Self::Field>;
 
     const STEP_SIZE: usize = 1;
+    const AIR_ID: &'static str = "simple_fibonacci";
 
     fn new(
         trace_length: usize,
@@ -118,6 +118,7 @@ where
     type PublicInputs = SimplePeriodicPublicInputs<Self::Field>;
 
     const STEP_SIZE: usize = 1;
+    const AIR_ID: &'static str = "simple_periodic_cols";
 
     fn new(
         trace_length: usize,
@@ -1,4 +1,5 @@
 use crate::{table::TableView, trace::LDETraceTable};
+use alloc::vec::Vec;
 use itertools::Itertools;
 use lambdaworks_math::field::traits::{IsField, IsSubFieldOf};
 
@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 use lambdaworks_crypto::merkle_tree::{merkle::MerkleTree, traits::IsMerkleTreeBackend};
 use lambdaworks_math::{
     field::{element::FieldElement, traits::IsField},
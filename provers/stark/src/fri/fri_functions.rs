@@ -1,4 +1,5 @@
 use super::Polynomial;
+use alloc::vec::Vec;
 use lambdaworks_math::{
     field::{element::FieldElement, traits::IsField},
     polynomial,
@@ -1,8 +1,13 @@
+use alloc::vec::Vec;
+
 pub mod fri_commitment;
 pub mod fri_decommit;
 mod fri_functions;
+mod standalone;
 
 use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_crypto::merkle_tree::{merkle::MerkleTree, traits::IsMerkleTreeBackend};
+use lambdaworks_math::fft::errors::FFTError;
 use lambdaworks_math::field::traits::{IsFFTField, IsField};
 use lambdaworks_math::traits::AsBytes;
 use lambdaworks_math::{
@@ -13,35 +18,72 @@ pub use lambdaworks_math::{
     polynomial::Polynomial,
 };
 
-use crate::config::{BatchedMerkleTree, BatchedMerkleTreeBackend};
+use crate::config::Commitment;
+use crate::grinding::{self, IsGrindingHash};
 
 use self::fri_commitment::FriLayer;
 use self::fri_decommit::FriDecommitment;
 use self::fri_functions::fold_polynomial;
+pub use self::standalone::{
+    prove, prove_batch, verify, verify_batch, FriDomain, FriParameters, FriProof, FriProvingError,
+    FriQueryOpening, FriVerificationError,
+};
 
-pub fn commit_phase<F: IsFFTField + IsSubFieldOf<E>, E: IsField>(
+/// The reasons the commit phase of FRI can fail.
+#[derive(Debug)]
+pub enum FriCommitError {
+    /// An FFT needed to evaluate a layer failed.
+    FFTError(FFTError),
+    /// No proof-of-work nonce satisfies the commit phase grinding factor.
+    NonceNotFound(u8),
+    /// A layer has no evaluations to commit to.
+    EmptyCommitment,
+    /// The Merkle tree of a layer has no leaf at the position opened by a query.
+    MissingMerkleProof(usize),
+}
+
+impl From<FFTError> for FriCommitError {
+    fn from(error: FFTError) -> Self {
+        Self::FFTError(error)
+    }
+}
+
+/// Runs the commit phase of FRI on `p_0`, committing to each layer with a Merkle tree with
+/// backend `B`.
+///
+/// If `grinding_factor` is not zero, a proof of work of `grinding_factor` bits computed with `G`
+/// is appended to the transcript before each folding challenge. Returns the last value, the
+/// layers and the nonces of the proofs of work.
+#[allow(clippy::type_complexity)]
+pub fn commit_phase<F, E, B, G>(
     number_layers: usize,
     p_0: Polynomial<FieldElement<E>>,
     transcript: &mut impl IsTranscript<E>,
     coset_offset: &FieldElement<F>,
     domain_size: usize,
-) -> (
-    FieldElement<E>,
-    Vec<FriLayer<E, BatchedMerkleTreeBackend<E>>>,
-)
+    grinding_factor: u8,
+) -> Result<(FieldElement<E>, Vec<FriLayer<E, B>>, Vec<u64>), FriCommitError>
 where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
     FieldElement<F>: AsBytes + Sync + Send,
     FieldElement<E>: AsBytes + Sync + Send,
+    B: IsMerkleTreeBackend<Node = Commitment, Data = Vec<FieldElement<E>>>,
+    G: IsGrindingHash,
 {
     let mut domain_size = domain_size;
 
     let mut fri_layer_list = Vec::with_capacity(number_layers);
-    let mut current_layer: FriLayer<E, BatchedMerkleTreeBackend<E>>;
+    let mut current_layer: FriLayer<E, B>;
     let mut current_poly = p_0;
+    let mut nonces = Vec::new();
 
     let mut coset_offset = coset_offset.clone();
 
     for _ in 1..number_layers {
+        if grinding_factor > 0 {
+            nonces.push(grind::<E, G>(transcript, grinding_factor)?);
+        }
         // <<<< Receive challenge 𝜁ₖ₋₁
         let zeta = transcript.sample_field_element();
         coset_offset = coset_offset.square();
@@ -49,14 +91,17 @@ where
 
         // Compute layer polynomial and domain
         current_poly = FieldElement::<F>::from(2) * fold_polynomial(&current_poly, &zeta);
-        current_layer = new_fri_layer(&current_poly, &coset_offset, domain_size);
-        let new_data = &current_layer.merkle_tree.root;
-        fri_layer_list.push(current_layer.clone()); // TODO: remove this clone
+        current_layer = new_fri_layer(&current_poly, &coset_offset, domain_size)?;
+        let new_data = current_layer.merkle_tree.root;
+        fri_layer_list.push(current_layer);
 
         // >>>> Send commitment: [pₖ]
-        transcript.append_bytes(new_data);
+        transcript.append_bytes(&new_data);
     }
 
+    if grinding_factor > 0 {
+        nonces.push(grind::<E, G>(transcript, grinding_factor)?);
+    }
     // <<<< Receive challenge: 𝜁ₙ₋₁
     let zeta = transcript.sample_field_element();
 
@@ -71,58 +116,79 @@ where
     // >>>> Send value: pₙ
     transcript.append_field_element(&last_value);
 
-    (last_value, fri_layer_list)
+    Ok((last_value, fri_layer_list, nonces))
 }
 
-pub fn query_phase<F: IsField>(
-    fri_layers: &Vec<FriLayer<F, BatchedMerkleTreeBackend<F>>>,
+/// Finds a nonce for the proof of work of `grinding_factor` bits seeded with the state of the
+/// transcript, and appends it to the transcript.
+fn grind<E, G>(
+    transcript: &mut impl IsTranscript<E>,
+    grinding_factor: u8,
+) -> Result<u64, FriCommitError>
+where
+    E: IsField,
+    G: IsGrindingHash,
+{
+    let nonce = grinding::generate_nonce::<G>(&transcript.state(), grinding_factor)
+        .ok_or(FriCommitError::NonceNotFound(grinding_factor))?;
+    transcript.append_bytes(&nonce.to_be_bytes());
+    Ok(nonce)
+}
+
+pub fn query_phase<F, B>(
+    fri_layers: &Vec<FriLayer<F, B>>,
     iotas: &[usize],
-) -> Vec<FriDecommitment<F>>
+) -> Result<Vec<FriDecommitment<F>>, FriCommitError>
 where
+    F: IsField,
     FieldElement<F>: AsBytes + Sync + Send,
+    B: IsMerkleTreeBackend<Node = Commitment, Data = Vec<FieldElement<F>>>,
 {
-    if !fri_layers.is_empty() {
-        let query_list = iotas
-            .iter()
-            .map(|iota_s| {
-                let mut layers_evaluations_sym = Vec::new();
-                let mut layers_auth_paths_sym = Vec::new();
-
-                let mut index = *iota_s;
-                for layer in fri_layers {
-                    // symmetric element
-                    let evaluation_sym = layer.evaluation[index ^ 1].clone();
-                    let auth_path_sym = layer.merkle_tree.get_proof_by_pos(index >> 1).unwrap();
-                    layers_evaluations_sym.push(evaluation_sym);
-                    layers_auth_paths_sym.push(auth_path_sym);
-
-                    index >>= 1;
-                }
-
-                FriDecommitment {
-                    layers_auth_paths: layers_auth_paths_sym,
-                    layers_evaluations_sym,
-                }
-            })
-            .collect();
-
-        query_list
-    } else {
-        vec![]
+    if fri_layers.is_empty() {
+        return Ok(vec![]);
     }
+
+    iotas
+        .iter()
+        .map(|iota_s| {
+            let mut layers_evaluations_sym = Vec::new();
+            let mut layers_auth_paths_sym = Vec::new();
+
+            let mut index = *iota_s;
+            for layer in fri_layers {
+                // symmetric element
+                let evaluation_sym = layer.evaluation[index ^ 1].clone();
+                let auth_path_sym = layer
+                    .merkle_tree
+                    .get_proof_by_pos(index >> 1)
+                    .ok_or(FriCommitError::MissingMerkleProof(index >> 1))?;
+                layers_evaluations_sym.push(evaluation_sym);
+                layers_auth_paths_sym.push(auth_path_sym);
+
+                index >>= 1;
+            }
+
+            Ok(FriDecommitment {
+                layers_auth_paths: layers_auth_paths_sym,
+                layers_evaluations_sym,
+            })
+        })
+        .collect()
 }
 
-pub fn new_fri_layer<F: IsFFTField + IsSubFieldOf<E>, E: IsField>(
+pub fn new_fri_layer<F, E, B>(
     poly: &Polynomial<FieldElement<E>>,
     coset_offset: &FieldElement<F>,
     domain_size: usize,
-) -> crate::fri::fri_commitment::FriLayer<E, BatchedMerkleTreeBackend<E>>
+) -> Result<FriLayer<E, B>, FriCommitError>
 where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
     FieldElement<F>: AsBytes + Sync + Send,
     FieldElement<E>: AsBytes + Sync + Send,
+    B: IsMerkleTreeBackend<Node = Commitment, Data = Vec<FieldElement<E>>>,
 {
-    let mut evaluation =
-        Polynomial::evaluate_offset_fft(poly, 1, Some(domain_size), coset_offset).unwrap(); // TODO: return error
+    let mut evaluation = Polynomial::evaluate_offset_fft(poly, 1, Some(domain_size), coset_offset)?;
 
     in_place_bit_reverse_permute(&mut evaluation);
 
@@ -131,12 +197,12 @@ where
         to_commit.push(vec![chunk[0].clone(), chunk[1].clone()]);
     }
 
-    let merkle_tree = BatchedMerkleTree::build(&to_commit).unwrap();
+    let merkle_tree = MerkleTree::build(&to_commit).ok_or(FriCommitError::EmptyCommitment)?;
 
-    FriLayer::new(
+    Ok(FriLayer::new(
         &evaluation,
         merkle_tree,
         coset_offset.clone().to_extension(),
         domain_size,
-    )
+    ))
 }
@@ -0,0 +1,673 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use lambdaworks_crypto::{fiat_shamir::is_transcript::IsTranscript, merkle_tree::proof::Proof};
+use lambdaworks_math::{
+    fft::{
+        cpu::bit_reversing::{in_place_bit_reverse_permute, reverse_index},
+        errors::FFTError,
+    },
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsSubFieldOf},
+    },
+    polynomial::Polynomial,
+    traits::AsBytes,
+};
+use sha3::Keccak256;
+
+use super::{commit_phase, fri_decommit::FriDecommitment, query_phase, FriCommitError};
+use crate::config::{BatchedMerkleTree, BatchedMerkleTreeBackend, Commitment};
+
+/// The evaluation domain of the codewords: the coset `coset_offset * <ω>` of size `size`, with
+/// `ω` the primitive root of unity of order `size`. Codewords are given in natural order, that
+/// is, their `i`-th element is the evaluation at `coset_offset * ωⁱ`.
+#[derive(Clone, Debug)]
+pub struct FriDomain<F: IsFFTField> {
+    pub size: usize,
+    pub coset_offset: FieldElement<F>,
+}
+
+impl<F: IsFFTField> FriDomain<F> {
+    pub fn new(size: usize, coset_offset: FieldElement<F>) -> Self {
+        Self { size, coset_offset }
+    }
+}
+
+/// The parameters of a FRI proof.
+///
+/// - `blowup_factor`: the inverse of the rate of the Reed–Solomon code. Codewords are proven to
+///   be close to evaluations of polynomials of degree less than `domain.size / blowup_factor`.
+/// - `number_of_queries`: the number of queries of the query phase.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FriParameters {
+    pub blowup_factor: usize,
+    pub number_of_queries: usize,
+}
+
+impl FriParameters {
+    pub fn new(blowup_factor: usize, number_of_queries: usize) -> Self {
+        Self {
+            blowup_factor,
+            number_of_queries,
+        }
+    }
+
+    /// Returns the bound on the degree of the polynomials of the codewords over `domain`.
+    pub fn degree_bound<F: IsFFTField>(&self, domain: &FriDomain<F>) -> usize {
+        domain.size / self.blowup_factor
+    }
+}
+
+/// The openings sent for a single FRI query `ι`: the evaluations of every codeword at the pair
+/// of points `𝜐` and `-𝜐` of the domain determined by `ι`, and the decommitment of the
+/// following FRI layers.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FriQueryOpening<F: IsField> {
+    pub codewords_evaluations: Vec<FieldElement<F>>,
+    pub codewords_evaluations_sym: Vec<FieldElement<F>>,
+    pub codewords_auth_path: Proof<Commitment>,
+    pub layers_decommitment: FriDecommitment<F>,
+}
+
+/// A proof that one or more codewords are close to a Reed–Solomon code.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FriProof<F: IsField> {
+    // Commitment of the codewords, whose leaves hold all of them at a pair of points 𝜐 and -𝜐
+    pub codewords_merkle_root: Commitment,
+    // Commitments of the folded layers [pₖ]
+    pub layers_merkle_roots: Vec<Commitment>,
+    // pₙ
+    pub last_value: FieldElement<F>,
+    // One opening per query
+    pub query_openings: Vec<FriQueryOpening<F>>,
+}
+
+#[derive(Debug)]
+pub enum FriProvingError {
+    /// The domain, the parameters or the codewords are not consistent with each other.
+    InvalidParameters(String),
+    /// An FFT needed to interpolate or evaluate a polynomial failed.
+    FFTError(FFTError),
+    /// There are no codewords to commit to.
+    EmptyCommitment,
+}
+
+impl From<FFTError> for FriProvingError {
+    fn from(error: FFTError) -> Self {
+        Self::FFTError(error)
+    }
+}
+
+impl From<FriCommitError> for FriProvingError {
+    fn from(error: FriCommitError) -> Self {
+        match error {
+            FriCommitError::FFTError(error) => Self::FFTError(error),
+            FriCommitError::EmptyCommitment => Self::EmptyCommitment,
+            FriCommitError::NonceNotFound(bits) => {
+                Self::InvalidParameters(format!("no proof-of-work nonce of {bits} bits found"))
+            }
+            FriCommitError::MissingMerkleProof(pos) => {
+                Self::InvalidParameters(format!("no leaf at position {pos} to open"))
+            }
+        }
+    }
+}
+
+/// The reasons a FRI proof can be rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FriVerificationError {
+    /// The domain and the parameters are not valid, or the proof does not have the shape they
+    /// require.
+    MalformedProof(String),
+    /// The proof has fewer queries than required by the parameters.
+    InsufficientQueries { expected: usize, actual: usize },
+    /// The opening of the codewords failed for the query `query`.
+    CodewordsOpening { query: usize },
+    /// The opening of the FRI layer `layer` (starting from 1) failed for the query `query`.
+    LayerOpening { query: usize, layer: usize },
+    /// The value obtained after folding the query `query` does not match the last FRI value.
+    LastValueMismatch { query: usize },
+}
+
+/// Proves that `evaluations` is close to the evaluations over `domain` of a polynomial of degree
+/// less than `domain.size / params.blowup_factor`.
+pub fn prove<F, E>(
+    evaluations: &[FieldElement<E>],
+    domain: &FriDomain<F>,
+    params: &FriParameters,
+    transcript: &mut impl IsTranscript<E>,
+) -> Result<FriProof<E>, FriProvingError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<F>: AsBytes + Sync + Send,
+    FieldElement<E>: AsBytes + Sync + Send,
+{
+    prove_batch(&[evaluations.to_vec()], domain, params, transcript)
+}
+
+/// Verifies a proof generated by [`prove`].
+pub fn verify<F, E>(
+    proof: &FriProof<E>,
+    domain: &FriDomain<F>,
+    params: &FriParameters,
+    transcript: &mut impl IsTranscript<E>,
+) -> Result<(), FriVerificationError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<F>: AsBytes + Sync + Send,
+    FieldElement<E>: AsBytes + Sync + Send,
+{
+    verify_batch(proof, 1, domain, params, transcript)
+}
+
+/// Proves that every codeword of `codewords` is close to the evaluations over `domain` of a
+/// polynomial of degree less than `domain.size / params.blowup_factor`.
+///
+/// The codewords are committed together, and a single FRI proof is run on their linear
+/// combination with the powers `1, α, α², ...` of a challenge `α`.
+pub fn prove_batch<F, E>(
+    codewords: &[Vec<FieldElement<E>>],
+    domain: &FriDomain<F>,
+    params: &FriParameters,
+    transcript: &mut impl IsTranscript<E>,
+) -> Result<FriProof<E>, FriProvingError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<F>: AsBytes + Sync + Send,
+    FieldElement<E>: AsBytes + Sync + Send,
+{
+    check_parameters(domain, params).map_err(FriProvingError::InvalidParameters)?;
+    if codewords.is_empty() {
+        return Err(FriProvingError::EmptyCommitment);
+    }
+    if let Some(codeword) = codewords
+        .iter()
+        .find(|codeword| codeword.len() != domain.size)
+    {
+        return Err(FriProvingError::InvalidParameters(format!(
+            "codeword of length {} over a domain of size {}",
+            codeword.len(),
+            domain.size
+        )));
+    }
+
+    // Commit to all the codewords at once. The leaf `i` holds the evaluations at the positions
+    // `2i` and `2i + 1` of the bit-reversed domain, which are a pair of points 𝜐 and -𝜐.
+    let bit_reversed_codewords: Vec<Vec<FieldElement<E>>> = codewords
+        .iter()
+        .map(|codeword| {
+            let mut codeword = codeword.clone();
+            in_place_bit_reverse_permute(&mut codeword);
+            codeword
+        })
+        .collect();
+    let leaves: Vec<Vec<FieldElement<E>>> = (0..domain.size / 2)
+        .map(|i| codewords_leaf(&bit_reversed_codewords, i))
+        .collect();
+    let codewords_merkle_tree =
+        BatchedMerkleTree::<E>::build(&leaves).ok_or(FriProvingError::EmptyCommitment)?;
+    transcript.append_bytes(&codewords_merkle_tree.root);
+
+    // <<<< Receive challenge α
+    let alpha = transcript.sample_field_element();
+    let combined_codeword = linear_combination(codewords, &alpha, domain.size);
+    let p_0 = Polynomial::interpolate_offset_fft::<F>(&combined_codeword, &domain.coset_offset)?;
+
+    let number_of_layers = params.degree_bound(domain).trailing_zeros() as usize;
+    let (last_value, fri_layers, _) = commit_phase::<F, E, BatchedMerkleTreeBackend<E>, Keccak256>(
+        number_of_layers,
+        p_0,
+        transcript,
+        &domain.coset_offset,
+        domain.size,
+        0,
+    )?;
+
+    let iotas = sample_query_indexes(params.number_of_queries, domain, transcript);
+    let query_openings = iotas
+        .iter()
+        .zip(query_phase(&fri_layers, &iotas)?)
+        .map(|(iota, layers_decommitment)| {
+            let number_of_codewords = codewords.len();
+            let mut codewords_evaluations = codewords_leaf(&bit_reversed_codewords, *iota);
+            let codewords_evaluations_sym = codewords_evaluations.split_off(number_of_codewords);
+            Ok(FriQueryOpening {
+                codewords_evaluations,
+                codewords_evaluations_sym,
+                codewords_auth_path: codewords_merkle_tree
+                    .get_proof_by_pos(*iota)
+                    .ok_or(FriCommitError::MissingMerkleProof(*iota))?,
+                layers_decommitment,
+            })
+        })
+        .collect::<Result<Vec<_>, FriCommitError>>()?;
+
+    Ok(FriProof {
+        codewords_merkle_root: codewords_merkle_tree.root,
+        layers_merkle_roots: fri_layers
+            .iter()
+            .map(|layer| layer.merkle_tree.root)
+            .collect(),
+        last_value,
+        query_openings,
+    })
+}
+
+/// Verifies a proof generated by [`prove_batch`] for `number_of_codewords` codewords.
+pub fn verify_batch<F, E>(
+    proof: &FriProof<E>,
+    number_of_codewords: usize,
+    domain: &FriDomain<F>,
+    params: &FriParameters,
+    transcript: &mut impl IsTranscript<E>,
+) -> Result<(), FriVerificationError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<F>: AsBytes + Sync + Send,
+    FieldElement<E>: AsBytes + Sync + Send,
+{
+    check_parameters(domain, params).map_err(FriVerificationError::MalformedProof)?;
+    if proof.query_openings.len() < params.number_of_queries {
+        return Err(FriVerificationError::InsufficientQueries {
+            expected: params.number_of_queries,
+            actual: proof.query_openings.len(),
+        });
+    }
+    let number_of_layers = params.degree_bound(domain).trailing_zeros() as usize;
+    check_proof_shape(proof, number_of_codewords, number_of_layers, params)?;
+
+    // Replay the commit phase to recover the challenges.
+    transcript.append_bytes(&proof.codewords_merkle_root);
+    let alpha = transcript.sample_field_element();
+    let mut zetas = Vec::with_capacity(number_of_layers);
+    for root in proof.layers_merkle_roots.iter() {
+        zetas.push(transcript.sample_field_element());
+        transcript.append_bytes(root);
+    }
+    zetas.push(transcript.sample_field_element());
+    transcript.append_field_element(&proof.last_value);
+    let iotas = sample_query_indexes(params.number_of_queries, domain, transcript);
+
+    let mut evaluation_points_inv = iotas
+        .iter()
+        .map(|iota| query_challenge_to_evaluation_point(*iota, domain))
+        .collect::<Vec<FieldElement<F>>>();
+    FieldElement::inplace_batch_inverse(&mut evaluation_points_inv).map_err(|_| {
+        FriVerificationError::MalformedProof("coset offset must not be zero".to_string())
+    })?;
+
+    let alpha_powers: Vec<FieldElement<E>> =
+        core::iter::successors(Some(FieldElement::one()), |power| Some(power * &alpha))
+            .take(number_of_codewords)
+            .collect();
+
+    for (query, ((iota, opening), evaluation_point_inv)) in iotas
+        .iter()
+        .zip(&proof.query_openings)
+        .zip(evaluation_points_inv)
+        .enumerate()
+    {
+        let leaf = [
+            opening.codewords_evaluations.clone(),
+            opening.codewords_evaluations_sym.clone(),
+        ]
+        .concat();
+        if !opening
+            .codewords_auth_path
+            .verify::<BatchedMerkleTreeBackend<E>>(&proof.codewords_merkle_root, *iota, &leaf)
+        {
+            return Err(FriVerificationError::CodewordsOpening { query });
+        }
+
+        let p0_eval = inner_product(&opening.codewords_evaluations, &alpha_powers);
+        let p0_eval_sym = inner_product(&opening.codewords_evaluations_sym, &alpha_powers);
+        verify_query(
+            proof,
+            &zetas,
+            query,
+            *iota,
+            &opening.layers_decommitment,
+            evaluation_point_inv,
+            &p0_eval,
+            &p0_eval_sym,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Verifies the FRI layers of a single query, given the evaluations `p₀(𝜐)` and `p₀(-𝜐)` of the
+/// combined codeword and `𝜐⁻¹`.
+#[allow(clippy::too_many_arguments)]
+fn verify_query<F, E>(
+    proof: &FriProof<E>,
+    zetas: &[FieldElement<E>],
+    query: usize,
+    iota: usize,
+    decommitment: &FriDecommitment<E>,
+    evaluation_point_inv: FieldElement<F>,
+    p0_eval: &FieldElement<E>,
+    p0_eval_sym: &FieldElement<E>,
+) -> Result<(), FriVerificationError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<E>: AsBytes + Sync + Send,
+{
+    let evaluation_points_inv =
+        core::iter::successors(Some(evaluation_point_inv.square()), |evaluation_point| {
+            Some(evaluation_point.square())
+        });
+
+    // Reconstruct p₁(𝜐²)
+    let mut v =
+        (p0_eval + p0_eval_sym) + evaluation_point_inv * &zetas[0] * (p0_eval - p0_eval_sym);
+    let mut index = iota;
+
+    for (i, (((merkle_root, auth_path_sym), evaluation_sym), evaluation_point_inv)) in proof
+        .layers_merkle_roots
+        .iter()
+        .zip(&decommitment.layers_auth_paths)
+        .zip(&decommitment.layers_evaluations_sym)
+        .zip(evaluation_points_inv)
+        .enumerate()
+    {
+        // `v` is pᵢ(𝜐^(2ⁱ)) and `evaluation_sym` is pᵢ(−𝜐^(2ⁱ)).
+        let evaluations = if index % 2 == 1 {
+            vec![evaluation_sym.clone(), v.clone()]
+        } else {
+            vec![v.clone(), evaluation_sym.clone()]
+        };
+        if !auth_path_sym.verify::<BatchedMerkleTreeBackend<E>>(
+            merkle_root,
+            index >> 1,
+            &evaluations,
+        ) {
+            return Err(FriVerificationError::LayerOpening {
+                query,
+                layer: i + 1,
+            });
+        }
+
+        // Update `v` with next value pᵢ₊₁(𝜐^(2ⁱ⁺¹)).
+        v = (&v + evaluation_sym) + evaluation_point_inv * &zetas[i + 1] * (&v - evaluation_sym);
+        index >>= 1;
+    }
+
+    if v != proof.last_value {
+        return Err(FriVerificationError::LastValueMismatch { query });
+    }
+    Ok(())
+}
+
+fn check_parameters<F: IsFFTField>(
+    domain: &FriDomain<F>,
+    params: &FriParameters,
+) -> Result<(), String> {
+    if !domain.size.is_power_of_two() || u64::from(domain.size.trailing_zeros()) > F::TWO_ADICITY {
+        return Err(format!(
+            "domain size {} is not a power of two within the two-adicity of the field",
+            domain.size
+        ));
+    }
+    if domain.coset_offset == FieldElement::zero() {
+        return Err("coset offset must not be zero".to_string());
+    }
+    if !params.blowup_factor.is_power_of_two() || params.blowup_factor < 2 {
+        return Err(format!(
+            "blowup factor {} is not a power of two greater than 1",
+            params.blowup_factor
+        ));
+    }
+    if params.degree_bound(domain) < 2 {
+        return Err(format!(
+            "a domain of size {} with blowup factor {} leaves nothing to fold",
+            domain.size, params.blowup_factor
+        ));
+    }
+    if params.number_of_queries == 0 {
+        return Err("at least one query is needed".to_string());
+    }
+    Ok(())
+}
+
+fn check_proof_shape<E: IsField>(
+    proof: &FriProof<E>,
+    number_of_codewords: usize,
+    number_of_layers: usize,
+    params: &FriParameters,
+) -> Result<(), FriVerificationError> {
+    if number_of_codewords == 0 {
+        return Err(FriVerificationError::MalformedProof(
+            "a proof covers at least one codeword".to_string(),
+        ));
+    }
+    if proof.layers_merkle_roots.len() + 1 != number_of_layers {
+        return Err(FriVerificationError::MalformedProof(format!(
+            "expected {} FRI layers, found {}",
+            number_of_layers - 1,
+            proof.layers_merkle_roots.len()
+        )));
+    }
+    if proof.query_openings.len() != params.number_of_queries
+        || proof.query_openings.iter().any(|opening| {
+            opening.codewords_evaluations.len() != number_of_codewords
+                || opening.codewords_evaluations_sym.len() != number_of_codewords
+                || opening.layers_decommitment.layers_auth_paths.len() + 1 != number_of_layers
+                || opening.layers_decommitment.layers_evaluations_sym.len() + 1 != number_of_layers
+        })
+    {
+        return Err(FriVerificationError::MalformedProof(
+            "query openings do not match the parameters".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn sample_query_indexes<F: IsFFTField, E: IsField>(
+    number_of_queries: usize,
+    domain: &FriDomain<F>,
+    transcript: &mut impl IsTranscript<E>,
+) -> Vec<usize> {
+    (0..number_of_queries)
+        .map(|_| transcript.sample_u64((domain.size >> 1) as u64) as usize)
+        .collect()
+}
+
+/// Returns the point `𝜐` of the domain at the position `2ι` of the bit-reversed domain.
+fn query_challenge_to_evaluation_point<F: IsFFTField>(
+    iota: usize,
+    domain: &FriDomain<F>,
+) -> FieldElement<F> {
+    let primitive_root =
+        F::get_primitive_root_of_unity(domain.size.trailing_zeros() as u64).unwrap();
+    primitive_root.pow(reverse_index(iota * 2, domain.size as u64)) * &domain.coset_offset
+}
+
+/// Returns the evaluations of all codewords at the positions `2i` and `2i + 1`.
+fn codewords_leaf<E: IsField>(
+    bit_reversed_codewords: &[Vec<FieldElement<E>>],
+    i: usize,
+) -> Vec<FieldElement<E>> {
+    let evaluations = bit_reversed_codewords
+        .iter()
+        .map(|codeword| codeword[2 * i].clone());
+    let evaluations_sym = bit_reversed_codewords
+        .iter()
+        .map(|codeword| codeword[2 * i + 1].clone());
+    evaluations.chain(evaluations_sym).collect()
+}
+
+/// Returns `Σ αᵏ cₖ`, with `cₖ` the codewords.
+fn linear_combination<E: IsField>(
+    codewords: &[Vec<FieldElement<E>>],
+    alpha: &FieldElement<E>,
+    size: usize,
+) -> Vec<FieldElement<E>> {
+    codewords
+        .iter()
+        .rev()
+        .fold(vec![FieldElement::zero(); size], |acc, codeword| {
+            acc.iter()
+                .zip(codeword)
+                .map(|(acc, value)| acc * alpha + value)
+                .collect()
+        })
+}
+
+fn inner_product<E: IsField>(a: &[FieldElement<E>], b: &[FieldElement<E>]) -> FieldElement<E> {
+    a.iter()
+        .zip(b)
+        .fold(FieldElement::zero(), |acc, (a, b)| acc + a * b)
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
+
+    use super::*;
+    use crate::transcript::StoneProverTranscript;
+
+    type FE = FieldElement<Stark252PrimeField>;
+
+    fn domain() -> FriDomain<Stark252PrimeField> {
+        FriDomain::new(64, FE::from(3))
+    }
+
+    fn codeword(coefficients: &[FE], domain: &FriDomain<Stark252PrimeField>) -> Vec<FE> {
+        Polynomial::evaluate_offset_fft::<Stark252PrimeField>(
+            &Polynomial::new(coefficients),
+            1,
+            Some(domain.size),
+            &domain.coset_offset,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn low_degree_codeword_is_accepted() {
+        let domain = domain();
+        let params = FriParameters::new(4, 10);
+        let coefficients: Vec<FE> = (1..=16).map(FE::from).collect();
+        let evaluations = codeword(&coefficients, &domain);
+
+        let proof = prove(
+            &evaluations,
+            &domain,
+            &params,
+            &mut StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            verify(
+                &proof,
+                &domain,
+                &params,
+                &mut StoneProverTranscript::new(&[])
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn high_degree_codeword_is_rejected() {
+        let domain = domain();
+        let params = FriParameters::new(4, 10);
+        let coefficients: Vec<FE> = (1..=32).map(FE::from).collect();
+        let evaluations = codeword(&coefficients, &domain);
+
+        let proof = prove(
+            &evaluations,
+            &domain,
+            &params,
+            &mut StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+
+        assert!(verify(
+            &proof,
+            &domain,
+            &params,
+            &mut StoneProverTranscript::new(&[])
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn batch_is_rejected_if_any_codeword_has_high_degree() {
+        let domain = domain();
+        let params = FriParameters::new(4, 10);
+        let low: Vec<FE> = (1..=16).map(FE::from).collect();
+        let high: Vec<FE> = (1..=20).map(FE::from).collect();
+        let low_codewords = vec![codeword(&low, &domain), codeword(&low[..8], &domain)];
+        let mixed_codewords = vec![codeword(&low, &domain), codeword(&high, &domain)];
+
+        let proof = prove_batch(
+            &low_codewords,
+            &domain,
+            &params,
+            &mut StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+        assert_eq!(
+            verify_batch(
+                &proof,
+                2,
+                &domain,
+                &params,
+                &mut StoneProverTranscript::new(&[])
+            ),
+            Ok(())
+        );
+
+        let proof = prove_batch(
+            &mixed_codewords,
+            &domain,
+            &params,
+            &mut StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+        assert!(verify_batch(
+            &proof,
+            2,
+            &domain,
+            &params,
+            &mut StoneProverTranscript::new(&[])
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn tampered_codeword_opening_is_rejected() {
+        let domain = domain();
+        let params = FriParameters::new(4, 10);
+        let coefficients: Vec<FE> = (1..=16).map(FE::from).collect();
+        let evaluations = codeword(&coefficients, &domain);
+        let mut proof = prove(
+            &evaluations,
+            &domain,
+            &params,
+            &mut StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+
+        proof.query_openings[2].codewords_evaluations_sym[0] += FE::one();
+
+        assert_eq!(
+            verify(
+                &proof,
+                &domain,
+                &params,
+                &mut StoneProverTranscript::new(&[])
+            ),
+            Err(FriVerificationError::CodewordsOpening { query: 2 })
+        );
+    }
+}
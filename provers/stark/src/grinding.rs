@@ -1,9 +1,30 @@
 #[cfg(feature = "parallel")]
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
-use sha3::{Digest, Keccak256};
+use sha3::{Digest, Keccak256, Sha3_256};
 
 const PREFIX: [u8; 8] = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xed];
 
+/// Number of candidate nonces tested by each step of the search in `generate_nonce`.
+/// With the `parallel` feature, the candidates of a step are split between the threads.
+const NONCES_PER_STEP: u64 = 1 << 16;
+
+/// A hash with a 256-bit output used for the proof-of-work of the prover.
+pub trait IsGrindingHash {
+    fn hash(data: &[u8]) -> [u8; 32];
+}
+
+impl IsGrindingHash for Keccak256 {
+    fn hash(data: &[u8]) -> [u8; 32] {
+        Keccak256::digest(data).into()
+    }
+}
+
+impl IsGrindingHash for Sha3_256 {
+    fn hash(data: &[u8]) -> [u8; 32] {
+        Sha3_256::digest(data).into()
+    }
+}
+
 /// Checks if the bit-string `Hash(Hash(prefix || seed || grinding_factor) || nonce)`
 /// has at least `grinding_factor` zeros to the left.
 /// `prefix` is the bit-string `0x123456789abcded`
@@ -17,10 +38,10 @@ const PREFIX: [u8; 8] = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xed];
 /// # Returns
 ///
 /// `true` if the number of leading zeros is at least `grinding_factor`, and `false` otherwise.
-pub fn is_valid_nonce(seed: &[u8; 32], nonce: u64, grinding_factor: u8) -> bool {
-    let inner_hash = get_inner_hash(seed, grinding_factor);
+pub fn is_valid_nonce<G: IsGrindingHash>(seed: &[u8; 32], nonce: u64, grinding_factor: u8) -> bool {
+    let inner_hash = get_inner_hash::<G>(seed, grinding_factor);
     let limit = 1 << (64 - grinding_factor);
-    is_valid_nonce_for_inner_hash(&inner_hash, nonce, limit)
+    is_valid_nonce_for_inner_hash::<G>(&inner_hash, nonce, limit)
 }
 
 /// Performs grinding, returning a new nonce for the proof.
@@ -29,6 +50,10 @@ pub fn is_valid_nonce(seed: &[u8; 32], nonce: u64, grinding_factor: u8) -> bool
 /// to the left.
 /// `prefix` is the bit-string `0x123456789abcded`
 ///
+/// The candidates are tested in steps of `NONCES_PER_STEP` consecutive values, in parallel
+/// with the `parallel` feature. The smallest valid nonce is returned in any case, so the proof
+/// doesn't depend on the number of threads.
+///
 /// # Parameters
 ///
 /// * `seed`: the input seed,
@@ -37,30 +62,38 @@ pub fn is_valid_nonce(seed: &[u8; 32], nonce: u64, grinding_factor: u8) -> bool
 /// # Returns
 ///
 /// A `nonce` satisfying the required condition.
-pub fn generate_nonce(seed: &[u8; 32], grinding_factor: u8) -> Option<u64> {
-    let inner_hash = get_inner_hash(seed, grinding_factor);
+pub fn generate_nonce<G: IsGrindingHash>(seed: &[u8; 32], grinding_factor: u8) -> Option<u64> {
+    let inner_hash = get_inner_hash::<G>(seed, grinding_factor);
     let limit = 1 << (64 - grinding_factor);
 
-    #[cfg(not(feature = "parallel"))]
-    return (0..u64::MAX).find(|&candidate_nonce| {
-        is_valid_nonce_for_inner_hash(&inner_hash, candidate_nonce, limit)
-    });
+    (0..=u64::MAX / NONCES_PER_STEP).find_map(|step| {
+        let first = step * NONCES_PER_STEP;
+        let last = first + (NONCES_PER_STEP - 1);
+        let is_valid = |candidate_nonce: &u64| {
+            is_valid_nonce_for_inner_hash::<G>(&inner_hash, *candidate_nonce, limit)
+        };
+
+        #[cfg(not(feature = "parallel"))]
+        return (first..=last).find(is_valid);
 
-    #[cfg(feature = "parallel")]
-    return (0..u64::MAX).into_par_iter().find_any(|&candidate_nonce| {
-        is_valid_nonce_for_inner_hash(&inner_hash, candidate_nonce, limit)
-    });
+        #[cfg(feature = "parallel")]
+        return (first..=last).into_par_iter().find_first(is_valid);
+    })
 }
 
 /// Checks if the leftmost 8 bytes of `Hash(inner_hash || candidate_nonce)` are less than `limit`
 /// when interpreted as `u64`.
 #[inline(always)]
-fn is_valid_nonce_for_inner_hash(inner_hash: &[u8; 32], candidate_nonce: u64, limit: u64) -> bool {
+fn is_valid_nonce_for_inner_hash<G: IsGrindingHash>(
+    inner_hash: &[u8; 32],
+    candidate_nonce: u64,
+    limit: u64,
+) -> bool {
     let mut data = [0; 40];
     data[..32].copy_from_slice(inner_hash);
     data[32..].copy_from_slice(&candidate_nonce.to_be_bytes());
 
-    let digest = Keccak256::digest(data);
+    let digest = G::hash(&data);
 
     let seed_head = u64::from_be_bytes(digest[..8].try_into().unwrap());
     seed_head < limit
@@ -69,19 +102,20 @@ fn is_valid_nonce_for_inner_hash(inner_hash: &[u8; 32], candidate_nonce: u64, li
 /// Returns the bit-string constructed as
 /// Hash(prefix || seed || grinding_factor)
 /// `prefix` is the bit-string `0x123456789abcded`
-fn get_inner_hash(seed: &[u8; 32], grinding_factor: u8) -> [u8; 32] {
+fn get_inner_hash<G: IsGrindingHash>(seed: &[u8; 32], grinding_factor: u8) -> [u8; 32] {
     let mut inner_data = [0u8; 41];
     inner_data[0..8].copy_from_slice(&PREFIX);
     inner_data[8..40].copy_from_slice(seed);
     inner_data[40] = grinding_factor;
 
-    let digest = Keccak256::digest(inner_data);
-    digest[..32].try_into().unwrap()
+    G::hash(&inner_data)
 }
 
 #[cfg(test)]
 mod test {
-    use crate::grinding::is_valid_nonce;
+    use sha3::{Keccak256, Sha3_256};
+
+    use crate::grinding::{generate_nonce, is_valid_nonce};
 
     #[test]
     fn test_invalid_nonce_grinding_factor_6() {
@@ -93,7 +127,7 @@ mod test {
         ];
         let nonce = 4;
         let grinding_factor = 6;
-        assert!(!is_valid_nonce(&seed, nonce, grinding_factor));
+        assert!(!is_valid_nonce::<Keccak256>(&seed, nonce, grinding_factor));
     }
 
     #[test]
@@ -106,7 +140,7 @@ mod test {
         ];
         let nonce = 287;
         let grinding_factor = 9;
-        assert!(!is_valid_nonce(&seed, nonce, grinding_factor));
+        assert!(!is_valid_nonce::<Keccak256>(&seed, nonce, grinding_factor));
     }
 
     #[test]
@@ -117,7 +151,7 @@ mod test {
         ];
         let nonce = 0x5ba;
         let grinding_factor = 10;
-        assert!(is_valid_nonce(&seed, nonce, grinding_factor));
+        assert!(is_valid_nonce::<Keccak256>(&seed, nonce, grinding_factor));
     }
 
     #[test]
@@ -128,7 +162,7 @@ mod test {
         ];
         let nonce = 0x2c5db8;
         let grinding_factor = 20;
-        assert!(is_valid_nonce(&seed, nonce, grinding_factor));
+        assert!(is_valid_nonce::<Keccak256>(&seed, nonce, grinding_factor));
     }
 
     #[test]
@@ -142,7 +176,7 @@ mod test {
         ];
         let nonce = 0x2c5db8;
         let grinding_factor = 19;
-        assert!(!is_valid_nonce(&seed, nonce, grinding_factor));
+        assert!(!is_valid_nonce::<Keccak256>(&seed, nonce, grinding_factor));
     }
 
     #[test]
@@ -153,7 +187,7 @@ mod test {
         ];
         let nonce = 0x1ae839e1;
         let grinding_factor = 30;
-        assert!(is_valid_nonce(&seed, nonce, grinding_factor));
+        assert!(is_valid_nonce::<Keccak256>(&seed, nonce, grinding_factor));
     }
 
     #[test]
@@ -164,6 +198,40 @@ mod test {
         ];
         let nonce = 0x4cc3123f;
         let grinding_factor = 33;
-        assert!(is_valid_nonce(&seed, nonce, grinding_factor));
+        assert!(is_valid_nonce::<Keccak256>(&seed, nonce, grinding_factor));
+    }
+
+    #[test]
+    fn test_generate_nonce_returns_the_smallest_valid_nonce() {
+        let seed = [
+            37, 68, 26, 150, 139, 142, 66, 175, 33, 47, 199, 160, 9, 109, 79, 234, 135, 254, 39,
+            11, 225, 219, 206, 108, 224, 165, 25, 72, 189, 96, 218, 95,
+        ];
+        let grinding_factor = 10;
+        let nonce = generate_nonce::<Keccak256>(&seed, grinding_factor).unwrap();
+        assert_eq!(nonce, 0x5ba);
+        assert!((0..nonce).all(|nonce| !is_valid_nonce::<Keccak256>(
+            &seed,
+            nonce,
+            grinding_factor
+        )));
+    }
+
+    #[test]
+    fn test_generate_nonce_depends_on_the_hash() {
+        let seed = [
+            37, 68, 26, 150, 139, 142, 66, 175, 33, 47, 199, 160, 9, 109, 79, 234, 135, 254, 39,
+            11, 225, 219, 206, 108, 224, 165, 25, 72, 189, 96, 218, 95,
+        ];
+        let grinding_factor = 12;
+        let keccak_nonce = generate_nonce::<Keccak256>(&seed, grinding_factor).unwrap();
+        let sha3_nonce = generate_nonce::<Sha3_256>(&seed, grinding_factor).unwrap();
+
+        assert!(is_valid_nonce::<Sha3_256>(
+            &seed,
+            sha3_nonce,
+            grinding_factor
+        ));
+        assert_ne!(keccak_nonce, sha3_nonce);
     }
 }
@@ -1,19 +1,36 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#[macro_use]
+extern crate alloc;
+
 use lambdaworks_math::field::{
     element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
 };
 
+pub mod batch;
+#[cfg(feature = "std")]
+pub mod cairo;
+#[cfg(feature = "std")]
+pub mod circle;
 pub mod constraints;
 pub mod context;
 pub mod debug;
 pub mod domain;
+#[cfg(feature = "std")]
 pub mod examples;
 pub mod frame;
 pub mod fri;
 pub mod grinding;
+pub mod padding;
+pub mod preprocessed;
 pub mod proof;
 pub mod prover;
+#[cfg(feature = "std")]
+pub mod recursion;
+#[cfg(feature = "std")]
+pub mod streaming;
 pub mod table;
 pub mod trace;
+pub mod trace_io;
 pub mod traits;
 pub mod transcript;
 pub mod utils;
@@ -0,0 +1,804 @@
+//! Utilities to prove executions whose length is not a power of two.
+//!
+//! [`pad_trace`] extends an execution trace to the next power of two with the rows given by a
+//! [`PaddingRow`] policy, and [`PaddedAIR`] wraps the AIR of the execution so its transition
+//! constraints are not enforced on the padding rows. The length of the execution is part of the
+//! [`PaddedPublicInputs`] of the wrapped AIR, so it is bound to the proof. Depending on the
+//! [`PaddingMode`], the padding rows are disabled by a selector column added to the trace or by
+//! extending the end exemptions of every transition constraint.
+//!
+//! [`prove_padded`] and [`verify_padded`] do all of the above for the default prover and
+//! verifier, and check that the length of the execution is consistent with the proof.
+
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::marker::PhantomData;
+
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::{
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsSubFieldOf},
+    },
+    traits::AsBytes,
+};
+
+use crate::{
+    constraints::{
+        boundary::{BoundaryConstraint, BoundaryConstraints},
+        transition::TransitionConstraint,
+    },
+    context::AirContext,
+    proof::{options::ProofOptions, stark::StarkProof},
+    prover::{IsStarkProver, Prover, ProvingError},
+    trace::TraceTable,
+    traits::{TransitionEvaluationContext, AIR},
+    verifier::{IsStarkVerifier, VerificationError, Verifier},
+};
+
+/// The rows appended to a trace to pad it to a power of two.
+pub enum PaddingRow<F: IsField> {
+    /// Every padding row is a copy of the last row of the execution.
+    RepeatLast,
+    /// Every padding row is the given row.
+    Fixed(Vec<FieldElement<F>>),
+    /// Every padding row is obtained by applying a step that does nothing, for example a NOP
+    /// instruction advancing the program counter, to the previous row.
+    Nop(fn(&[FieldElement<F>]) -> Vec<FieldElement<F>>),
+}
+
+/// How the transition constraints of a [`PaddedAIR`] are disabled on the padding rows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaddingMode {
+    /// A main column is appended to the trace, holding one on the rows of the execution and zero
+    /// on the padding rows, and every transition constraint is multiplied by it. This raises the
+    /// degree of the constraints by one, and is only supported for AIRs with a step size of one
+    /// and no preprocessed columns.
+    Selector,
+    /// The padding rows are added to the end exemptions of every transition constraint. The
+    /// degree of the constraints doesn't change, but computing their zerofiers takes time
+    /// quadratic in the number of padding rows, so it suits traces that are almost a power of
+    /// two.
+    EndExemptions,
+}
+
+impl PaddingMode {
+    fn as_byte(&self) -> u8 {
+        match self {
+            Self::Selector => 0,
+            Self::EndExemptions => 1,
+        }
+    }
+}
+
+/// Errors found while padding or proving an execution, or while verifying its proof.
+#[derive(Debug)]
+pub enum PaddingError {
+    /// The trace has no rows.
+    EmptyTrace,
+    /// The step size is zero or doesn't divide the number of rows of the trace.
+    InvalidStepSize {
+        step_size: usize,
+        num_rows: usize,
+    },
+    /// A padding row doesn't have as many values as the trace has main columns.
+    RowWidthMismatch {
+        expected: usize,
+        found: usize,
+    },
+    /// The selector column is only supported for AIRs with a step size of one.
+    UnsupportedStepSize(usize),
+    /// The selector column would be placed where the AIR expects its preprocessed columns.
+    PreprocessedColumns,
+    /// The length of the execution is zero, larger than the trace or not a whole number of
+    /// steps.
+    InvalidRealLength {
+        real_length: usize,
+        trace_length: usize,
+    },
+    Proving(ProvingError),
+    Verification(VerificationError),
+}
+
+impl From<ProvingError> for PaddingError {
+    fn from(error: ProvingError) -> Self {
+        Self::Proving(error)
+    }
+}
+
+impl From<VerificationError> for PaddingError {
+    fn from(error: VerificationError) -> Self {
+        Self::Verification(error)
+    }
+}
+
+/// Pads the trace to the next power of two with rows given by `padding`, returning the number of
+/// rows of the execution. With [`PaddingMode::Selector`] the selector column is appended to the
+/// main columns. Auxiliary columns, if any, are padded with zeros, since they are built by the
+/// AIR after the main trace is committed.
+pub fn pad_trace<F, E>(
+    trace: &mut TraceTable<F, E>,
+    padding: &PaddingRow<F>,
+    mode: PaddingMode,
+) -> Result<usize, PaddingError>
+where
+    F: IsSubFieldOf<E> + IsFFTField,
+    E: IsField,
+{
+    let real_length = trace.num_rows();
+    if real_length == 0 {
+        return Err(PaddingError::EmptyTrace);
+    }
+    let step_size = trace.step_size;
+    if step_size == 0 || real_length % step_size != 0 {
+        return Err(PaddingError::InvalidStepSize {
+            step_size,
+            num_rows: real_length,
+        });
+    }
+    if mode == PaddingMode::Selector && step_size != 1 {
+        return Err(PaddingError::UnsupportedStepSize(step_size));
+    }
+
+    let padded_length = real_length.next_power_of_two();
+    let width = trace.num_main_columns;
+    for _ in real_length..padded_length {
+        let row = match padding {
+            PaddingRow::RepeatLast => trace.main_table.last_row().to_vec(),
+            PaddingRow::Fixed(row) => row.clone(),
+            PaddingRow::Nop(step) => step(trace.main_table.last_row()),
+        };
+        if row.len() != width {
+            return Err(PaddingError::RowWidthMismatch {
+                expected: width,
+                found: row.len(),
+            });
+        }
+        trace.main_table.append_row(&row);
+    }
+
+    let mut main_columns = trace.main_table.columns();
+    if mode == PaddingMode::Selector {
+        let mut selector = vec![FieldElement::<F>::one(); real_length];
+        selector.resize(padded_length, FieldElement::zero());
+        main_columns.push(selector);
+    }
+    let aux_columns = trace
+        .aux_table
+        .columns()
+        .into_iter()
+        .map(|mut column| {
+            column.resize(padded_length, FieldElement::zero());
+            column
+        })
+        .collect();
+
+    *trace = TraceTable::from_columns(main_columns, aux_columns, step_size);
+    Ok(real_length)
+}
+
+/// Public inputs of a [`PaddedAIR`]: the public inputs of the wrapped AIR and the number of rows
+/// of the execution before padding.
+pub struct PaddedPublicInputs<A: AIR> {
+    pub inner_public_inputs: A::PublicInputs,
+    pub real_length: usize,
+    pub mode: PaddingMode,
+}
+
+impl<A: AIR> Clone for PaddedPublicInputs<A>
+where
+    A::PublicInputs: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner_public_inputs: self.inner_public_inputs.clone(),
+            real_length: self.real_length,
+            mode: self.mode,
+        }
+    }
+}
+
+impl<A: AIR> AsBytes for PaddedPublicInputs<A>
+where
+    A::PublicInputs: AsBytes,
+{
+    fn as_bytes(&self) -> Vec<u8> {
+        // The identifier of the padded AIR is the same for every wrapped AIR, so the identifier
+        // of the wrapped AIR is bound here.
+        let inner_public_inputs = self.inner_public_inputs.as_bytes();
+        let air_id = A::AIR_ID;
+        let mut bytes = (air_id.len() as u64).to_be_bytes().to_vec();
+        bytes.extend_from_slice(air_id.as_bytes());
+        bytes.extend_from_slice(&(self.real_length as u64).to_be_bytes());
+        bytes.push(self.mode.as_byte());
+        bytes.extend_from_slice(&(inner_public_inputs.len() as u64).to_be_bytes());
+        bytes.extend_from_slice(&inner_public_inputs);
+        bytes
+    }
+}
+
+/// How a wrapped transition constraint is disabled on the padding rows.
+#[derive(Clone, Copy)]
+enum Gate {
+    /// The constraint is multiplied by the selector at the frame step `step`, the first row whose
+    /// constraint would be exempted in a trace ending at the execution.
+    Selector { step: usize, column: usize },
+    /// The constraint has `extra` end exemptions besides the ones of the wrapped constraint.
+    EndExemptions { extra: usize },
+}
+
+/// A transition constraint of the wrapped AIR, disabled on the padding rows.
+struct PaddedConstraint<A: AIR> {
+    air: Arc<A>,
+    idx: usize,
+    gate: Gate,
+}
+
+impl<A: AIR> PaddedConstraint<A> {
+    fn inner(&self) -> &dyn TransitionConstraint<A::Field, A::FieldExtension> {
+        self.air.transition_constraints()[self.idx].as_ref()
+    }
+}
+
+impl<A> TransitionConstraint<A::Field, A::FieldExtension> for PaddedConstraint<A>
+where
+    A: AIR + Send + Sync,
+{
+    fn degree(&self) -> usize {
+        match self.gate {
+            Gate::Selector { .. } => self.inner().degree() + 1,
+            Gate::EndExemptions { .. } => self.inner().degree(),
+        }
+    }
+
+    fn constraint_idx(&self) -> usize {
+        self.inner().constraint_idx()
+    }
+
+    fn evaluate(
+        &self,
+        evaluation_context: &TransitionEvaluationContext<A::Field, A::FieldExtension>,
+        transition_evaluations: &mut [FieldElement<A::FieldExtension>],
+    ) {
+        let inner = self.inner();
+        inner.evaluate(evaluation_context, transition_evaluations);
+
+        if let Gate::Selector { step, column } = self.gate {
+            let idx = inner.constraint_idx();
+            let evaluation = &transition_evaluations[idx];
+            transition_evaluations[idx] = match evaluation_context {
+                TransitionEvaluationContext::Prover { frame, .. } => {
+                    frame
+                        .get_evaluation_step(step)
+                        .get_main_evaluation_element(0, column)
+                        * evaluation
+                }
+                TransitionEvaluationContext::Verifier { frame, .. } => {
+                    frame
+                        .get_evaluation_step(step)
+                        .get_main_evaluation_element(0, column)
+                        * evaluation
+                }
+            };
+        }
+    }
+
+    fn period(&self) -> usize {
+        self.inner().period()
+    }
+
+    fn offset(&self) -> usize {
+        self.inner().offset()
+    }
+
+    fn exemptions_period(&self) -> Option<usize> {
+        self.inner().exemptions_period()
+    }
+
+    fn periodic_exemptions_offset(&self) -> Option<usize> {
+        self.inner().periodic_exemptions_offset()
+    }
+
+    fn end_exemptions(&self) -> usize {
+        match self.gate {
+            Gate::Selector { .. } => self.inner().end_exemptions(),
+            Gate::EndExemptions { extra } => self.inner().end_exemptions() + extra,
+        }
+    }
+}
+
+/// Constraints of the selector column `t`, which together with its boundary constraints force it
+/// to be one on the rows of the execution and zero on the padding rows.
+#[derive(Clone, Copy)]
+enum SelectorConstraintKind {
+    /// `t * (1 - t) = 0`.
+    Binary,
+    /// `t' * (1 - t) = 0`: once the selector is zero it stays zero.
+    Monotone { next_step: usize },
+}
+
+struct SelectorConstraint<F, E> {
+    kind: SelectorConstraintKind,
+    column: usize,
+    idx: usize,
+    phantom: PhantomData<(F, E)>,
+}
+
+impl<F, E> TransitionConstraint<F, E> for SelectorConstraint<F, E>
+where
+    F: IsSubFieldOf<E> + IsFFTField + Send + Sync,
+    E: IsField + Send + Sync,
+{
+    fn degree(&self) -> usize {
+        2
+    }
+
+    fn constraint_idx(&self) -> usize {
+        self.idx
+    }
+
+    fn end_exemptions(&self) -> usize {
+        match self.kind {
+            SelectorConstraintKind::Binary => 0,
+            SelectorConstraintKind::Monotone { .. } => 1,
+        }
+    }
+
+    fn evaluate(
+        &self,
+        evaluation_context: &TransitionEvaluationContext<F, E>,
+        transition_evaluations: &mut [FieldElement<E>],
+    ) {
+        let (t, next) = match evaluation_context {
+            TransitionEvaluationContext::Prover { frame, .. } => {
+                let value = |step: usize| {
+                    frame
+                        .get_evaluation_step(step)
+                        .get_main_evaluation_element(0, self.column)
+                        .clone()
+                        .to_extension()
+                };
+                match self.kind {
+                    SelectorConstraintKind::Binary => (value(0), None),
+                    SelectorConstraintKind::Monotone { next_step } => {
+                        (value(0), Some(value(next_step)))
+                    }
+                }
+            }
+            TransitionEvaluationContext::Verifier { frame, .. } => {
+                let value = |step: usize| {
+                    frame
+                        .get_evaluation_step(step)
+                        .get_main_evaluation_element(0, self.column)
+                        .clone()
+                };
+                match self.kind {
+                    SelectorConstraintKind::Binary => (value(0), None),
+                    SelectorConstraintKind::Monotone { next_step } => {
+                        (value(0), Some(value(next_step)))
+                    }
+                }
+            }
+        };
+
+        let one_minus_t = FieldElement::<E>::one() - &t;
+        transition_evaluations[self.idx] = match next {
+            None => t * one_minus_t,
+            Some(next) => next * one_minus_t,
+        };
+    }
+}
+
+/// Returns the index of `offset` in the transition offsets, appending it if it is missing so the
+/// steps read by the wrapped constraints keep their indices.
+fn offset_step(transition_offsets: &mut Vec<usize>, offset: usize) -> usize {
+    match transition_offsets.iter().position(|o| *o == offset) {
+        Some(step) => step,
+        None => {
+            transition_offsets.push(offset);
+            transition_offsets.len() - 1
+        }
+    }
+}
+
+/// AIR proving an execution of the AIR `A` padded to a power of two by [`pad_trace`]. The
+/// transition constraints of `A` are enforced on the rows of the execution only, and its boundary
+/// constraints are unchanged, so those on the last row of the trace only hold for
+/// [`PaddingRow::RepeatLast`] padding or for padding rows satisfying them.
+///
+/// The AIR trusts the length of the execution given in its public inputs: it must be a whole
+/// number of steps between one and the length of the trace, which [`verify_padded`] checks.
+/// With [`PaddingMode::Selector`] the wrapped AIR must not have preprocessed columns, which
+/// [`prove_padded`] and [`verify_padded`] check as well.
+pub struct PaddedAIR<A: AIR> {
+    inner: Arc<A>,
+    context: AirContext,
+    pub_inputs: PaddedPublicInputs<A>,
+    transition_constraints: Vec<Box<dyn TransitionConstraint<A::Field, A::FieldExtension>>>,
+}
+
+impl<A: AIR> PaddedAIR<A> {
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// The column of the selector, right after the main columns of the wrapped AIR.
+    fn selector_column(&self) -> usize {
+        self.inner.trace_layout().0
+    }
+}
+
+impl<A> AIR for PaddedAIR<A>
+where
+    A: AIR + Send + Sync + 'static,
+    A::PublicInputs: Clone,
+{
+    type Field = A::Field;
+    type FieldExtension = A::FieldExtension;
+    type PublicInputs = PaddedPublicInputs<A>;
+
+    const STEP_SIZE: usize = A::STEP_SIZE;
+    const AIR_ID: &'static str = "padded";
+    const EXTENSION_DEGREE: usize = A::EXTENSION_DEGREE;
+
+    fn new(
+        trace_length: usize,
+        pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Self {
+        let inner = Arc::new(A::new(
+            trace_length,
+            &pub_inputs.inner_public_inputs,
+            proof_options,
+        ));
+        let mut context = inner.context().clone();
+        let num_inner_constraints = inner.transition_constraints().len();
+        let padding_rows = trace_length.saturating_sub(pub_inputs.real_length);
+
+        let mut transition_constraints: Vec<
+            Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>,
+        > = Vec::with_capacity(num_inner_constraints + 2);
+        match pub_inputs.mode {
+            PaddingMode::Selector => {
+                let column = inner.trace_layout().0;
+                for idx in 0..num_inner_constraints {
+                    let constraint = &inner.transition_constraints()[idx];
+                    let offset = constraint.end_exemptions() * constraint.period();
+                    let step = offset_step(&mut context.transition_offsets, offset);
+                    transition_constraints.push(Box::new(PaddedConstraint {
+                        air: inner.clone(),
+                        idx,
+                        gate: Gate::Selector { step, column },
+                    }));
+                }
+                let next_step = offset_step(&mut context.transition_offsets, 1);
+                for kind in [
+                    SelectorConstraintKind::Binary,
+                    SelectorConstraintKind::Monotone { next_step },
+                ] {
+                    transition_constraints.push(Box::new(SelectorConstraint {
+                        kind,
+                        column,
+                        idx: transition_constraints.len(),
+                        phantom: PhantomData,
+                    }));
+                }
+                context.trace_columns += 1;
+            }
+            PaddingMode::EndExemptions => {
+                for idx in 0..num_inner_constraints {
+                    let period = inner.transition_constraints()[idx].period();
+                    transition_constraints.push(Box::new(PaddedConstraint {
+                        air: inner.clone(),
+                        idx,
+                        gate: Gate::EndExemptions {
+                            extra: (padding_rows + period - 1) / period,
+                        },
+                    }));
+                }
+            }
+        }
+        context.num_transition_constraints = transition_constraints.len();
+
+        Self {
+            inner,
+            context,
+            pub_inputs: pub_inputs.clone(),
+            transition_constraints,
+        }
+    }
+
+    fn build_auxiliary_trace(
+        &self,
+        main_trace: &mut TraceTable<Self::Field, Self::FieldExtension>,
+        rap_challenges: &[FieldElement<Self::FieldExtension>],
+    ) where
+        Self::FieldExtension: IsFFTField,
+    {
+        self.inner.build_auxiliary_trace(main_trace, rap_challenges)
+    }
+
+    fn build_rap_challenges(
+        &self,
+        transcript: &mut impl IsTranscript<Self::FieldExtension>,
+    ) -> Vec<FieldElement<Self::FieldExtension>> {
+        self.inner.build_rap_challenges(transcript)
+    }
+
+    fn trace_layout(&self) -> (usize, usize) {
+        let (main_columns, aux_columns) = self.inner.trace_layout();
+        match self.pub_inputs.mode {
+            PaddingMode::Selector => (main_columns + 1, aux_columns),
+            PaddingMode::EndExemptions => (main_columns, aux_columns),
+        }
+    }
+
+    fn preprocessed_columns(&self) -> Vec<Vec<FieldElement<Self::Field>>> {
+        self.inner.preprocessed_columns()
+    }
+
+    fn num_preprocessed_columns(&self) -> usize {
+        self.inner.num_preprocessed_columns()
+    }
+
+    fn composition_poly_degree_bound(&self) -> usize {
+        let bound = self.inner.composition_poly_degree_bound();
+        match self.pub_inputs.mode {
+            // Multiplying by the selector raises the degree of the constraints by one, which
+            // adds up to a trace length to the degree of the composition polynomial.
+            PaddingMode::Selector => (bound + self.trace_length()).next_power_of_two(),
+            PaddingMode::EndExemptions => bound,
+        }
+    }
+
+    fn boundary_constraints(
+        &self,
+        rap_challenges: &[FieldElement<Self::FieldExtension>],
+    ) -> BoundaryConstraints<Self::FieldExtension> {
+        let mut constraints = self.inner.boundary_constraints(rap_challenges);
+        if self.pub_inputs.mode == PaddingMode::Selector {
+            let column = self.selector_column();
+            let real_length = self.pub_inputs.real_length;
+            if let Some(last_real_row) = real_length.checked_sub(1) {
+                constraints.constraints.push(BoundaryConstraint::new_main(
+                    column,
+                    last_real_row,
+                    FieldElement::one(),
+                ));
+            }
+            if real_length < self.trace_length() {
+                constraints.constraints.push(BoundaryConstraint::new_main(
+                    column,
+                    real_length,
+                    FieldElement::zero(),
+                ));
+            }
+        }
+        constraints
+    }
+
+    fn context(&self) -> &AirContext {
+        &self.context
+    }
+
+    fn trace_length(&self) -> usize {
+        self.inner.trace_length()
+    }
+
+    fn pub_inputs(&self) -> &Self::PublicInputs {
+        &self.pub_inputs
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<FieldElement<Self::Field>>> {
+        self.inner.get_periodic_column_values()
+    }
+
+    fn transition_constraints(
+        &self,
+    ) -> &Vec<Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>> {
+        &self.transition_constraints
+    }
+}
+
+/// Checks that the length of the execution is a whole number of steps between one and the length
+/// of the trace.
+fn check_real_length<A: AIR>(real_length: usize, trace_length: usize) -> Result<(), PaddingError> {
+    if real_length == 0 || real_length > trace_length || real_length % A::STEP_SIZE != 0 {
+        return Err(PaddingError::InvalidRealLength {
+            real_length,
+            trace_length,
+        });
+    }
+    Ok(())
+}
+
+/// Checks that the selector column can be appended to the main columns of `air`. The frames
+/// place the preprocessed columns right after the main ones, so the selector would take the
+/// place of the first preprocessed column.
+fn check_selector_support<A: AIR>(air: &A) -> Result<(), PaddingError> {
+    if A::STEP_SIZE != 1 {
+        return Err(PaddingError::UnsupportedStepSize(A::STEP_SIZE));
+    }
+    if air.num_preprocessed_columns() != 0 {
+        return Err(PaddingError::PreprocessedColumns);
+    }
+    Ok(())
+}
+
+/// Pads the execution trace of the AIR `A` with [`pad_trace`] and proves it with the default
+/// prover, returning the proof together with the public inputs it is verified against.
+#[allow(clippy::type_complexity)]
+pub fn prove_padded<A>(
+    trace: &mut TraceTable<A::Field, A::FieldExtension>,
+    inner_public_inputs: &A::PublicInputs,
+    padding: &PaddingRow<A::Field>,
+    mode: PaddingMode,
+    proof_options: &ProofOptions,
+    transcript: impl IsTranscript<A::FieldExtension>,
+) -> Result<
+    (
+        StarkProof<A::Field, A::FieldExtension>,
+        PaddedPublicInputs<A>,
+    ),
+    PaddingError,
+>
+where
+    A: AIR + Send + Sync + 'static,
+    A::PublicInputs: Clone + AsBytes + Send + Sync,
+    A::FieldExtension: IsFFTField,
+    FieldElement<A::Field>: AsBytes + Send + Sync,
+    FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+{
+    let real_length = pad_trace(trace, padding, mode)?;
+    let pub_inputs = PaddedPublicInputs {
+        inner_public_inputs: inner_public_inputs.clone(),
+        real_length,
+        mode,
+    };
+    if mode == PaddingMode::Selector {
+        let air = A::new(trace.num_rows(), inner_public_inputs, proof_options);
+        check_selector_support(&air)?;
+    }
+
+    let proof =
+        Prover::<PaddedAIR<A>>::prove_statement(trace, &pub_inputs, proof_options, transcript)?;
+    Ok((proof, pub_inputs))
+}
+
+/// Verifies the proof of a padded execution of the AIR `A`, checking that the length of the
+/// execution in the public inputs is consistent with the length of the trace.
+pub fn verify_padded<A>(
+    proof: &StarkProof<A::Field, A::FieldExtension>,
+    pub_inputs: &PaddedPublicInputs<A>,
+    proof_options: &ProofOptions,
+    transcript: impl IsTranscript<A::FieldExtension>,
+) -> Result<(), PaddingError>
+where
+    A: AIR + Send + Sync + 'static,
+    A::PublicInputs: Clone + AsBytes,
+    FieldElement<A::Field>: AsBytes + Send + Sync,
+    FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+{
+    check_real_length::<A>(pub_inputs.real_length, proof.trace_length)?;
+    if pub_inputs.mode == PaddingMode::Selector {
+        let air = A::new(
+            proof.trace_length,
+            &pub_inputs.inner_public_inputs,
+            proof_options,
+        );
+        check_selector_support(&air)?;
+    }
+    Verifier::<PaddedAIR<A>>::verify_statement(proof, pub_inputs, proof_options, transcript)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
+
+    type FE = FieldElement<Stark252PrimeField>;
+
+    fn trace(values: &[u64]) -> TraceTable<Stark252PrimeField, Stark252PrimeField> {
+        let column = values.iter().map(|v| FE::from(*v)).collect();
+        let other = values.iter().map(|v| FE::from(2 * *v)).collect();
+        TraceTable::from_columns_main(vec![column, other], 1)
+    }
+
+    fn column(trace: &TraceTable<Stark252PrimeField, Stark252PrimeField>, col: usize) -> Vec<FE> {
+        trace.get_column_main(col)
+    }
+
+    fn felts(values: &[u64]) -> Vec<FE> {
+        values.iter().map(|v| FE::from(*v)).collect()
+    }
+
+    #[test]
+    fn repeat_last_pads_to_the_next_power_of_two() {
+        let mut trace = trace(&[1, 2, 3, 4, 5]);
+        let real_length = pad_trace(
+            &mut trace,
+            &PaddingRow::RepeatLast,
+            PaddingMode::EndExemptions,
+        )
+        .unwrap();
+        assert_eq!(real_length, 5);
+        assert_eq!(trace.num_main_columns, 2);
+        assert_eq!(column(&trace, 0), felts(&[1, 2, 3, 4, 5, 5, 5, 5]));
+        assert_eq!(column(&trace, 1), felts(&[2, 4, 6, 8, 10, 10, 10, 10]));
+    }
+
+    #[test]
+    fn fixed_row_and_selector_column() {
+        let mut trace = trace(&[1, 2, 3]);
+        let padding = PaddingRow::Fixed(vec![FE::from(7), FE::from(9)]);
+        let real_length = pad_trace(&mut trace, &padding, PaddingMode::Selector).unwrap();
+        assert_eq!(real_length, 3);
+        assert_eq!(trace.num_main_columns, 3);
+        assert_eq!(column(&trace, 0), felts(&[1, 2, 3, 7]));
+        assert_eq!(column(&trace, 1), felts(&[2, 4, 6, 9]));
+        assert_eq!(column(&trace, 2), felts(&[1, 1, 1, 0]));
+    }
+
+    #[test]
+    fn nop_row_is_applied_to_the_previous_row() {
+        let mut trace = trace(&[1, 2, 3, 4, 5]);
+        let padding = PaddingRow::Nop(|row| vec![row[0] + FE::one(), row[1]]);
+        pad_trace(&mut trace, &padding, PaddingMode::EndExemptions).unwrap();
+        assert_eq!(column(&trace, 0), felts(&[1, 2, 3, 4, 5, 6, 7, 8]));
+        assert_eq!(column(&trace, 1), felts(&[2, 4, 6, 8, 10, 10, 10, 10]));
+    }
+
+    #[test]
+    fn trace_of_power_of_two_length_is_not_padded() {
+        let mut trace = trace(&[1, 2, 3, 4]);
+        let real_length =
+            pad_trace(&mut trace, &PaddingRow::RepeatLast, PaddingMode::Selector).unwrap();
+        assert_eq!(real_length, 4);
+        assert_eq!(column(&trace, 2), felts(&[1, 1, 1, 1]));
+    }
+
+    #[test]
+    fn invalid_traces_and_rows_are_rejected() {
+        let mut empty = TraceTable::<Stark252PrimeField, Stark252PrimeField>::from_columns_main(
+            vec![vec![]],
+            1,
+        );
+        assert!(matches!(
+            pad_trace(&mut empty, &PaddingRow::RepeatLast, PaddingMode::Selector),
+            Err(PaddingError::EmptyTrace)
+        ));
+
+        let padding = PaddingRow::Fixed(vec![FE::one()]);
+        assert!(matches!(
+            pad_trace(&mut trace(&[1, 2, 3]), &padding, PaddingMode::Selector),
+            Err(PaddingError::RowWidthMismatch {
+                expected: 2,
+                found: 1
+            })
+        ));
+
+        let mut two_row_steps = trace(&[1, 2, 3, 4, 5, 6]);
+        two_row_steps.step_size = 2;
+        assert!(matches!(
+            pad_trace(
+                &mut two_row_steps,
+                &PaddingRow::RepeatLast,
+                PaddingMode::Selector
+            ),
+            Err(PaddingError::UnsupportedStepSize(2))
+        ));
+        let mut odd_steps = trace(&[1, 2, 3]);
+        odd_steps.step_size = 2;
+        assert!(matches!(
+            pad_trace(
+                &mut odd_steps,
+                &PaddingRow::RepeatLast,
+                PaddingMode::EndExemptions
+            ),
+            Err(PaddingError::InvalidStepSize {
+                step_size: 2,
+                num_rows: 3
+            })
+        ));
+    }
+}
@@ -0,0 +1,145 @@
+use alloc::{format, vec::Vec};
+use lambdaworks_crypto::merkle_tree::{merkle::MerkleTree, traits::IsMerkleTreeBackend};
+use lambdaworks_math::{
+    fft::cpu::bit_reversing::in_place_bit_reverse_permute,
+    field::{element::FieldElement, traits::IsFFTField},
+    polynomial::Polynomial,
+    traits::AsBytes,
+};
+
+use crate::{
+    config::{BatchedMerkleTreeBackend, Commitment, IsCommitmentHash, Keccak256Commitments},
+    domain::Domain,
+    prover::{evaluate_polynomial_on_lde_domain, ProvingError},
+    trace::columns2rows,
+    traits::AIR,
+    verifier::VerificationError,
+};
+
+/// The preprocessed (fixed) columns of an AIR together with the data the prover needs to open
+/// them. These columns only depend on the AIR and the trace length, so they can be committed to
+/// once at setup time and reused for every proof.
+pub struct PreprocessedTrace<F, B = BatchedMerkleTreeBackend<F>>
+where
+    F: IsFFTField,
+    FieldElement<F>: AsBytes + Send + Sync,
+    B: IsMerkleTreeBackend<Node = Commitment, Data = Vec<FieldElement<F>>>,
+{
+    /// The polynomials interpolating the preprocessed columns over the trace domain.
+    pub(crate) polys: Vec<Polynomial<FieldElement<F>>>,
+    /// The evaluations of `polys` over the LDE domain, in natural order.
+    pub(crate) lde_evaluations: Vec<Vec<FieldElement<F>>>,
+    /// The Merkle tree built over the bit-reversed rows of `lde_evaluations`.
+    pub(crate) merkle_tree: MerkleTree<B>,
+}
+
+impl<F, B> PreprocessedTrace<F, B>
+where
+    F: IsFFTField,
+    FieldElement<F>: AsBytes + Send + Sync,
+    B: IsMerkleTreeBackend<Node = Commitment, Data = Vec<FieldElement<F>>>,
+{
+    /// Interpolates and commits to the preprocessed columns of `air` over `domain`.
+    /// Returns `None` if the AIR has no preprocessed columns, and an error if the columns don't
+    /// match the number declared by `AIR::num_preprocessed_columns` and the trace length.
+    pub fn new<A: AIR<Field = F>>(
+        air: &A,
+        domain: &Domain<F>,
+    ) -> Result<Option<Self>, ProvingError> {
+        if air.num_preprocessed_columns() == 0 {
+            return Ok(None);
+        }
+
+        let columns = air.preprocessed_columns();
+        if columns.len() != air.num_preprocessed_columns()
+            || columns
+                .iter()
+                .any(|column| column.len() != air.trace_length())
+        {
+            return Err(ProvingError::InvalidAirLayout(format!(
+                "expected {} preprocessed columns of length {}",
+                air.num_preprocessed_columns(),
+                air.trace_length()
+            )));
+        }
+
+        let polys = columns
+            .iter()
+            .map(|column| Polynomial::interpolate_fft::<F>(column))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let lde_evaluations = polys
+            .iter()
+            .map(|poly| {
+                evaluate_polynomial_on_lde_domain(
+                    poly,
+                    domain.blowup_factor,
+                    domain.interpolation_domain_size,
+                    &domain.coset_offset,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut lde_permuted = lde_evaluations.clone();
+        for col in lde_permuted.iter_mut() {
+            in_place_bit_reverse_permute(col);
+        }
+        let merkle_tree =
+            MerkleTree::build(&columns2rows(lde_permuted)).ok_or(ProvingError::EmptyCommitment)?;
+
+        Ok(Some(Self {
+            polys,
+            lde_evaluations,
+            merkle_tree,
+        }))
+    }
+
+    /// Returns the commitment to the preprocessed columns.
+    pub fn commitment(&self) -> Commitment {
+        self.merkle_tree.root
+    }
+
+    /// Returns the number of preprocessed columns.
+    pub fn num_columns(&self) -> usize {
+        self.polys.len()
+    }
+}
+
+/// The data a verifier needs ahead of time to check proofs of a given AIR and trace length.
+/// It holds the commitment to the preprocessed columns, if any, so that the verifier can check
+/// their openings without recomputing them.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct VerifierKey {
+    pub preprocessed_commitment: Option<Commitment>,
+}
+
+impl VerifierKey {
+    /// Runs the setup for `air`, committing to its preprocessed columns.
+    pub fn new<A: AIR>(air: &A) -> Result<Self, VerificationError>
+    where
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+    {
+        Self::with_commitment_hash::<A, Keccak256Commitments>(air)
+    }
+
+    /// Runs the setup for `air`, committing to its preprocessed columns with the hash `H`.
+    /// AIRs without preprocessed columns get the empty key without building any domain.
+    pub fn with_commitment_hash<A, H>(air: &A) -> Result<Self, VerificationError>
+    where
+        A: AIR,
+        H: IsCommitmentHash<A::Field, A::FieldExtension>,
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+    {
+        if air.num_preprocessed_columns() == 0 {
+            return Ok(Self::default());
+        }
+
+        let domain = Domain::new(air);
+        let preprocessed = PreprocessedTrace::<A::Field, H::MainBackend>::new(air, &domain)
+            .map_err(|error| VerificationError::InvalidSetup(format!("{error:?}")))?;
+        Ok(Self {
+            preprocessed_commitment: preprocessed.map(|preprocessed| preprocessed.commitment()),
+        })
+    }
+}
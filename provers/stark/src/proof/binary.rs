@@ -0,0 +1,705 @@
+use alloc::vec::Vec;
+use lambdaworks_crypto::merkle_tree::proof::Proof;
+use lambdaworks_math::{
+    field::{
+        element::FieldElement,
+        traits::{IsField, IsSubFieldOf},
+    },
+    traits::ByteConversion,
+};
+
+use crate::{
+    config::{Commitment, COMMITMENT_SIZE},
+    fri::fri_decommit::FriDecommitment,
+    table::Table,
+};
+
+use super::stark::{DeepPolynomialOpening, PolynomialOpenings, StarkProof};
+
+/// Bytes at the start of every proof encoded with [`StarkProof::to_bytes`].
+pub const PROOF_MAGIC: [u8; 4] = *b"LWSP";
+
+/// Version of the binary encoding written by [`StarkProof::to_bytes`].
+pub const PROOF_FORMAT_VERSION: u8 = 3;
+
+/// Logarithm of the largest blowup factor of a proof, which is a `u8` in `ProofOptions`. The
+/// encoding doesn't hold the blowup factor, so the Merkle trees of a proof of a trace of length
+/// `n` are at most `n * 2^MAX_LOG_BLOWUP_FACTOR` leaves wide.
+const MAX_LOG_BLOWUP_FACTOR: u32 = 7;
+
+/// Flags of a DEEP composition polynomial opening telling which optional trace openings it has.
+const AUX_TRACE_OPENING: u8 = 1;
+const PREPROCESSED_TRACE_OPENING: u8 = 1 << 1;
+
+/// Errors found while decoding a proof encoded with [`StarkProof::to_bytes`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProofEncodingError {
+    /// The bytes don't start with [`PROOF_MAGIC`].
+    InvalidMagic,
+    /// The proof was encoded with a version of the format this decoder doesn't know.
+    UnsupportedVersion(u8),
+    /// The size of the field elements of the proof is not the size of the elements of the
+    /// fields it is decoded into.
+    ElementSizeMismatch { expected: usize, found: usize },
+    /// The proof ended before all its elements were read.
+    UnexpectedEndOfProof,
+    /// Bytes were left after reading the whole proof.
+    TrailingBytes(usize),
+    /// A length or an integer doesn't fit in the type it is decoded into.
+    IntegerOverflow,
+    /// A byte that can only take a few values has another one.
+    InvalidTag(u8),
+    /// A field element can't be decoded from its bytes.
+    InvalidFieldElement,
+    /// A field element is not written in its canonical form, i.e. its value is not below the
+    /// modulus of its field.
+    NonCanonicalFieldElement,
+    /// A table has rows but no columns.
+    InvalidTableDimensions,
+    /// An authentication path refers to nodes that are not in a previous path.
+    InvalidPathReference,
+    /// An authentication path is longer than the height of the Merkle trees of the proof.
+    InvalidPathLength(usize),
+}
+
+/// The number of bytes taken by each component of a proof encoded with [`StarkProof::to_bytes`].
+/// The bytes of the lengths and flags of a component are counted as part of it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProofSizeBreakdown {
+    /// Magic, version, sizes of the field elements and trace length.
+    pub header: usize,
+    /// Merkle roots of the trace, the composition polynomial and the FRI layers.
+    pub commitments: usize,
+    /// Out-of-domain evaluations of the trace and the composition polynomial parts.
+    pub ood_evaluations: usize,
+    /// The value of the last FRI layer.
+    pub fri_last_value: usize,
+    /// The nonces of the commit phase grinding and the grinding nonce.
+    pub nonce: usize,
+    /// Evaluations of the main, auxiliary and preprocessed trace at the queries.
+    pub trace_openings: usize,
+    /// Evaluations of the composition polynomial parts at the queries.
+    pub composition_poly_openings: usize,
+    /// Authentication paths of the trace openings.
+    pub trace_authentication_paths: usize,
+    /// Authentication paths of the composition polynomial openings.
+    pub composition_poly_authentication_paths: usize,
+    /// Evaluations of the FRI layers at the queries.
+    pub fri_layers_evaluations: usize,
+    /// Authentication paths of the FRI layers evaluations.
+    pub fri_layers_authentication_paths: usize,
+}
+
+impl ProofSizeBreakdown {
+    /// The size in bytes of the whole encoded proof.
+    pub fn total(&self) -> usize {
+        self.header
+            + self.commitments
+            + self.ood_evaluations
+            + self.fri_last_value
+            + self.nonce
+            + self.trace_openings
+            + self.composition_poly_openings
+            + self.trace_authentication_paths
+            + self.composition_poly_authentication_paths
+            + self.fri_layers_evaluations
+            + self.fri_layers_authentication_paths
+    }
+}
+
+fn element_size<F>() -> usize
+where
+    F: IsField,
+    FieldElement<F>: ByteConversion,
+{
+    FieldElement::<F>::zero().to_bytes_be().len()
+}
+
+impl<F, E> StarkProof<F, E>
+where
+    F: IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<F>: ByteConversion,
+    FieldElement<E>: ByteConversion,
+{
+    /// Encodes the proof in a compact binary format that doesn't depend on any serializer.
+    ///
+    /// After a header with the format version, every variable-length component is prefixed
+    /// by its length. The authentication paths of each Merkle tree are batched: the nodes a
+    /// path shares with a previous path of the same tree, such as the ones close to the root,
+    /// are replaced by a reference to that path. Field elements are written as the big-endian
+    /// bytes of their canonical representative.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.encode().0
+    }
+
+    /// Decodes a proof encoded with [`StarkProof::to_bytes`]. Every length is checked against
+    /// the remaining bytes, every field element must be below the modulus of its field, and the
+    /// whole input must be consumed.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofEncodingError> {
+        let mut reader = ProofReader { bytes };
+        let proof = reader.read_proof()?;
+        if !reader.bytes.is_empty() {
+            return Err(ProofEncodingError::TrailingBytes(reader.bytes.len()));
+        }
+        Ok(proof)
+    }
+
+    /// Reports the number of bytes taken by each component of the encoded proof.
+    pub fn proof_size_breakdown(&self) -> ProofSizeBreakdown {
+        self.encode().1
+    }
+
+    fn encode(&self) -> (Vec<u8>, ProofSizeBreakdown) {
+        let mut writer = ProofWriter::default();
+        let mut breakdown = ProofSizeBreakdown::default();
+
+        writer.write_bytes(&PROOF_MAGIC);
+        writer.write_bytes(&[PROOF_FORMAT_VERSION]);
+        writer.write_length(element_size::<F>());
+        writer.write_length(element_size::<E>());
+        writer.write_length(self.trace_length);
+        breakdown.header = writer.section_size();
+
+        writer.write_bytes(&self.lde_trace_main_merkle_root);
+        writer.write_option(&self.lde_trace_aux_merkle_root, |writer, root| {
+            writer.write_bytes(root)
+        });
+        writer.write_bytes(&self.composition_poly_root);
+        writer.write_length(self.fri_layers_merkle_roots.len());
+        for root in self.fri_layers_merkle_roots.iter() {
+            writer.write_bytes(root);
+        }
+        breakdown.commitments = writer.section_size();
+
+        writer.write_length(self.trace_ood_evaluations.width);
+        writer.write_length(self.trace_ood_evaluations.height);
+        writer.write_field_elements(&self.trace_ood_evaluations.data);
+        writer.write_vec(&self.composition_poly_parts_ood_evaluation);
+        breakdown.ood_evaluations = writer.section_size();
+
+        writer.write_field_element(&self.fri_last_value);
+        breakdown.fri_last_value = writer.section_size();
+
+        writer.write_length(self.commit_phase_nonces.len());
+        for nonce in self.commit_phase_nonces.iter() {
+            writer.write_bytes(&nonce.to_be_bytes());
+        }
+        writer.write_option(&self.nonce, |writer, nonce| {
+            writer.write_bytes(&nonce.to_be_bytes())
+        });
+        breakdown.nonce = writer.section_size();
+
+        let openings = &self.deep_poly_openings;
+        writer.write_length(openings.len());
+        for opening in openings.iter() {
+            let mut flags = 0;
+            if opening.aux_trace_polys.is_some() {
+                flags |= AUX_TRACE_OPENING;
+            }
+            if opening.preprocessed_trace_polys.is_some() {
+                flags |= PREPROCESSED_TRACE_OPENING;
+            }
+            writer.write_bytes(&[flags]);
+            writer.write_evaluations(&opening.main_trace_polys);
+            if let Some(aux_trace_polys) = &opening.aux_trace_polys {
+                writer.write_evaluations(aux_trace_polys);
+            }
+            if let Some(preprocessed_trace_polys) = &opening.preprocessed_trace_polys {
+                writer.write_evaluations(preprocessed_trace_polys);
+            }
+        }
+        breakdown.trace_openings = writer.section_size();
+
+        for opening in openings.iter() {
+            writer.write_evaluations(&opening.composition_poly);
+        }
+        breakdown.composition_poly_openings = writer.section_size();
+
+        writer.write_paths(openings.iter().flat_map(|opening| {
+            [
+                &opening.main_trace_polys.proof,
+                &opening.main_trace_polys.proof_sym,
+            ]
+        }));
+        writer.write_paths(
+            openings
+                .iter()
+                .filter_map(|opening| opening.aux_trace_polys.as_ref())
+                .flat_map(|openings| [&openings.proof, &openings.proof_sym]),
+        );
+        writer.write_paths(
+            openings
+                .iter()
+                .filter_map(|opening| opening.preprocessed_trace_polys.as_ref())
+                .flat_map(|openings| [&openings.proof, &openings.proof_sym]),
+        );
+        breakdown.trace_authentication_paths = writer.section_size();
+
+        writer.write_paths(openings.iter().flat_map(|opening| {
+            [
+                &opening.composition_poly.proof,
+                &opening.composition_poly.proof_sym,
+            ]
+        }));
+        breakdown.composition_poly_authentication_paths = writer.section_size();
+
+        writer.write_length(self.query_list.len());
+        for decommitment in self.query_list.iter() {
+            writer.write_length(decommitment.layers_auth_paths.len());
+            writer.write_vec(&decommitment.layers_evaluations_sym);
+        }
+        breakdown.fri_layers_evaluations = writer.section_size();
+
+        let number_of_layers = self
+            .query_list
+            .iter()
+            .map(|decommitment| decommitment.layers_auth_paths.len())
+            .max()
+            .unwrap_or(0);
+        for layer in 0..number_of_layers {
+            writer.write_paths(
+                self.query_list
+                    .iter()
+                    .filter_map(|decommitment| decommitment.layers_auth_paths.get(layer)),
+            );
+        }
+        breakdown.fri_layers_authentication_paths = writer.section_size();
+
+        (writer.output, breakdown)
+    }
+}
+
+/// Writes the elements of a proof in the order they are read by `ProofReader`.
+#[derive(Default)]
+struct ProofWriter {
+    output: Vec<u8>,
+    section_start: usize,
+}
+
+impl ProofWriter {
+    /// Returns the number of bytes written since the last call.
+    fn section_size(&mut self) -> usize {
+        let size = self.output.len() - self.section_start;
+        self.section_start = self.output.len();
+        size
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        self.output.extend_from_slice(bytes);
+    }
+
+    /// Writes `length` as an unsigned LEB128 integer.
+    fn write_length(&mut self, length: usize) {
+        let mut value = length as u64;
+        while value >= 0x80 {
+            self.output.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.output.push(value as u8);
+    }
+
+    fn write_option<T>(&mut self, value: &Option<T>, write: impl FnOnce(&mut Self, &T)) {
+        match value {
+            Some(value) => {
+                self.output.push(1);
+                write(self, value);
+            }
+            None => self.output.push(0),
+        }
+    }
+
+    fn write_field_element<F>(&mut self, element: &FieldElement<F>)
+    where
+        F: IsField,
+        FieldElement<F>: ByteConversion,
+    {
+        self.write_bytes(&element.to_bytes_be());
+    }
+
+    fn write_field_elements<F>(&mut self, elements: &[FieldElement<F>])
+    where
+        F: IsField,
+        FieldElement<F>: ByteConversion,
+    {
+        for element in elements.iter() {
+            self.write_field_element(element);
+        }
+    }
+
+    fn write_vec<F>(&mut self, elements: &[FieldElement<F>])
+    where
+        F: IsField,
+        FieldElement<F>: ByteConversion,
+    {
+        self.write_length(elements.len());
+        self.write_field_elements(elements);
+    }
+
+    fn write_evaluations<F>(&mut self, openings: &PolynomialOpenings<F>)
+    where
+        F: IsField,
+        FieldElement<F>: ByteConversion,
+    {
+        self.write_vec(&openings.evaluations);
+        self.write_vec(&openings.evaluations_sym);
+    }
+
+    /// Writes the authentication paths of a batch of leaves of the same Merkle tree.
+    ///
+    /// Two leaves whose indexes agree in all but their last `k` bits have the same nodes in
+    /// their authentication paths from level `k` upwards. Each path is written as the index of a
+    /// previous path it shares the most trailing nodes with (plus one, or zero if there is none),
+    /// the number of shared nodes, and the nodes that are not shared, from the leaf upwards.
+    fn write_paths<'a>(&mut self, paths: impl Iterator<Item = &'a Proof<Commitment>>) {
+        let paths: Vec<_> = paths.collect();
+        self.write_length(paths.len());
+        for (i, path) in paths.iter().enumerate() {
+            let (reference, shared) = paths[..i]
+                .iter()
+                .enumerate()
+                .map(|(j, previous)| {
+                    let shared = path
+                        .merkle_path
+                        .iter()
+                        .rev()
+                        .zip(previous.merkle_path.iter().rev())
+                        .take_while(|(node, previous_node)| node == previous_node)
+                        .count();
+                    (j + 1, shared)
+                })
+                .filter(|(_, shared)| *shared > 0)
+                .max_by_key(|(j, shared)| (*shared, core::cmp::Reverse(*j)))
+                .unwrap_or((0, 0));
+            let own_nodes = &path.merkle_path[..path.merkle_path.len() - shared];
+
+            self.write_length(reference);
+            self.write_length(shared);
+            self.write_length(own_nodes.len());
+            for node in own_nodes.iter() {
+                self.write_bytes(node);
+            }
+        }
+    }
+}
+
+/// The evaluations of a polynomial opening at a point and at its symmetric.
+type Evaluations<F> = (Vec<FieldElement<F>>, Vec<FieldElement<F>>);
+
+/// Pairs the evaluations of a polynomial opening with the next two authentication paths.
+fn openings<F: IsField>(
+    (evaluations, evaluations_sym): Evaluations<F>,
+    paths: &mut impl Iterator<Item = Proof<Commitment>>,
+) -> PolynomialOpenings<F> {
+    PolynomialOpenings {
+        proof: paths.next().expect("a path was read for each evaluation"),
+        proof_sym: paths.next().expect("a path was read for each evaluation"),
+        evaluations,
+        evaluations_sym,
+    }
+}
+
+/// Reads the elements of a proof in the order they are written by `ProofWriter`. Every read
+/// consumes at least one byte, so the lengths read can't make the reader loop or allocate
+/// beyond the size of the input.
+struct ProofReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ProofReader<'a> {
+    fn read_proof<F, E>(&mut self) -> Result<StarkProof<F, E>, ProofEncodingError>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+        FieldElement<F>: ByteConversion,
+        FieldElement<E>: ByteConversion,
+    {
+        if self.read_bytes(PROOF_MAGIC.len())? != PROOF_MAGIC {
+            return Err(ProofEncodingError::InvalidMagic);
+        }
+        let version = self.read_byte()?;
+        if version != PROOF_FORMAT_VERSION {
+            return Err(ProofEncodingError::UnsupportedVersion(version));
+        }
+        self.check_element_size::<F>()?;
+        self.check_element_size::<E>()?;
+        let trace_length = self.read_length()?;
+        let log_trace_length = usize::BITS - trace_length.saturating_sub(1).leading_zeros();
+        let max_path_length = (log_trace_length + MAX_LOG_BLOWUP_FACTOR).min(u64::BITS) as usize;
+
+        let lde_trace_main_merkle_root = self.read_commitment()?;
+        let lde_trace_aux_merkle_root = self.read_option(Self::read_commitment)?;
+        let composition_poly_root = self.read_commitment()?;
+        let number_of_fri_layers = self.read_length()?;
+        let fri_layers_merkle_roots = (0..number_of_fri_layers)
+            .map(|_| self.read_commitment())
+            .collect::<Result<_, _>>()?;
+
+        let width = self.read_length()?;
+        let height = self.read_length()?;
+        let size = width
+            .checked_mul(height)
+            .ok_or(ProofEncodingError::IntegerOverflow)?;
+        let trace_ood_evaluations = Table::new(self.read_field_elements(size)?, width);
+        if trace_ood_evaluations.height != height {
+            return Err(ProofEncodingError::InvalidTableDimensions);
+        }
+        let composition_poly_parts_ood_evaluation = self.read_vec()?;
+        let fri_last_value = self.read_field_element()?;
+        let number_of_commit_phase_nonces = self.read_length()?;
+        let commit_phase_nonces = (0..number_of_commit_phase_nonces)
+            .map(|_| self.read_u64())
+            .collect::<Result<_, _>>()?;
+        let nonce = self.read_option(Self::read_u64)?;
+
+        let number_of_openings = self.read_length()?;
+        let mut trace_evaluations = Vec::new();
+        for _ in 0..number_of_openings {
+            let flags = self.read_byte()?;
+            if flags & !(AUX_TRACE_OPENING | PREPROCESSED_TRACE_OPENING) != 0 {
+                return Err(ProofEncodingError::InvalidTag(flags));
+            }
+            let main = self.read_evaluations::<F>()?;
+            let aux = (flags & AUX_TRACE_OPENING != 0)
+                .then(|| self.read_evaluations::<E>())
+                .transpose()?;
+            let preprocessed = (flags & PREPROCESSED_TRACE_OPENING != 0)
+                .then(|| self.read_evaluations::<F>())
+                .transpose()?;
+            trace_evaluations.push((main, aux, preprocessed));
+        }
+        let composition_evaluations = (0..number_of_openings)
+            .map(|_| self.read_evaluations::<E>())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let number_of_aux_openings = trace_evaluations
+            .iter()
+            .filter(|(_, aux, _)| aux.is_some())
+            .count();
+        let number_of_preprocessed_openings = trace_evaluations
+            .iter()
+            .filter(|(_, _, preprocessed)| preprocessed.is_some())
+            .count();
+        let mut main_paths = self
+            .read_paths(2 * number_of_openings, max_path_length)?
+            .into_iter();
+        let mut aux_paths = self
+            .read_paths(2 * number_of_aux_openings, max_path_length)?
+            .into_iter();
+        let mut preprocessed_paths = self
+            .read_paths(2 * number_of_preprocessed_openings, max_path_length)?
+            .into_iter();
+        let mut composition_paths = self
+            .read_paths(2 * number_of_openings, max_path_length)?
+            .into_iter();
+
+        let deep_poly_openings = trace_evaluations
+            .into_iter()
+            .zip(composition_evaluations)
+            .map(
+                |((main, aux, preprocessed), composition)| DeepPolynomialOpening {
+                    composition_poly: openings(composition, &mut composition_paths),
+                    main_trace_polys: openings(main, &mut main_paths),
+                    aux_trace_polys: aux.map(|aux| openings(aux, &mut aux_paths)),
+                    preprocessed_trace_polys: preprocessed
+                        .map(|preprocessed| openings(preprocessed, &mut preprocessed_paths)),
+                },
+            )
+            .collect();
+
+        let number_of_queries = self.read_length()?;
+        let mut layers_evaluations = Vec::new();
+        for _ in 0..number_of_queries {
+            let number_of_layers = self.read_length()?;
+            layers_evaluations.push((number_of_layers, self.read_vec()?));
+        }
+        let max_number_of_layers = layers_evaluations
+            .iter()
+            .map(|(number_of_layers, _)| *number_of_layers)
+            .max()
+            .unwrap_or(0);
+        let mut layers_auth_paths = vec![Vec::new(); number_of_queries];
+        for layer in 0..max_number_of_layers {
+            let queries: Vec<_> = layers_evaluations
+                .iter()
+                .enumerate()
+                .filter(|(_, (number_of_layers, _))| layer < *number_of_layers)
+                .map(|(query, _)| query)
+                .collect();
+            let paths = self.read_paths(queries.len(), max_path_length)?;
+            for (query, path) in queries.into_iter().zip(paths) {
+                layers_auth_paths[query].push(path);
+            }
+        }
+        let query_list = layers_auth_paths
+            .into_iter()
+            .zip(layers_evaluations)
+            .map(
+                |(layers_auth_paths, (_, layers_evaluations_sym))| FriDecommitment {
+                    layers_auth_paths,
+                    layers_evaluations_sym,
+                },
+            )
+            .collect();
+
+        Ok(StarkProof {
+            trace_length,
+            lde_trace_main_merkle_root,
+            lde_trace_aux_merkle_root,
+            trace_ood_evaluations,
+            composition_poly_root,
+            composition_poly_parts_ood_evaluation,
+            fri_layers_merkle_roots,
+            fri_last_value,
+            query_list,
+            deep_poly_openings,
+            commit_phase_nonces,
+            nonce,
+        })
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], ProofEncodingError> {
+        if self.bytes.len() < len {
+            return Err(ProofEncodingError::UnexpectedEndOfProof);
+        }
+        let (read, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(read)
+    }
+
+    fn read_byte(&mut self) -> Result<u8, ProofEncodingError> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    /// Reads an unsigned LEB128 integer, rejecting encodings longer than needed.
+    fn read_length(&mut self) -> Result<usize, ProofEncodingError> {
+        let mut value: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_byte()?;
+            let bits = u64::from(byte & 0x7f);
+            if (bits << shift) >> shift != bits || (byte == 0 && shift > 0) {
+                return Err(ProofEncodingError::IntegerOverflow);
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return usize::try_from(value).map_err(|_| ProofEncodingError::IntegerOverflow);
+            }
+        }
+        Err(ProofEncodingError::IntegerOverflow)
+    }
+
+    fn read_option<T>(
+        &mut self,
+        read: impl FnOnce(&mut Self) -> Result<T, ProofEncodingError>,
+    ) -> Result<Option<T>, ProofEncodingError> {
+        match self.read_byte()? {
+            0 => Ok(None),
+            1 => read(self).map(Some),
+            tag => Err(ProofEncodingError::InvalidTag(tag)),
+        }
+    }
+
+    fn check_element_size<F>(&mut self) -> Result<(), ProofEncodingError>
+    where
+        F: IsField,
+        FieldElement<F>: ByteConversion,
+    {
+        let expected = element_size::<F>();
+        let found = self.read_length()?;
+        if found != expected {
+            return Err(ProofEncodingError::ElementSizeMismatch { expected, found });
+        }
+        Ok(())
+    }
+
+    fn read_u64(&mut self) -> Result<u64, ProofEncodingError> {
+        let mut value = [0; 8];
+        value.copy_from_slice(self.read_bytes(8)?);
+        Ok(u64::from_be_bytes(value))
+    }
+
+    fn read_commitment(&mut self) -> Result<Commitment, ProofEncodingError> {
+        let mut commitment = [0; COMMITMENT_SIZE];
+        commitment.copy_from_slice(self.read_bytes(COMMITMENT_SIZE)?);
+        Ok(commitment)
+    }
+
+    fn read_field_element<F>(&mut self) -> Result<FieldElement<F>, ProofEncodingError>
+    where
+        F: IsField,
+        FieldElement<F>: ByteConversion,
+    {
+        let bytes = self.read_bytes(element_size::<F>())?;
+        let element = FieldElement::<F>::from_bytes_be(bytes)
+            .map_err(|_| ProofEncodingError::InvalidFieldElement)?;
+        // Decoding reduces the value, so a value that is not below the modulus is only caught
+        // by encoding it back.
+        if element.to_bytes_be() != bytes {
+            return Err(ProofEncodingError::NonCanonicalFieldElement);
+        }
+        Ok(element)
+    }
+
+    fn read_field_elements<F>(
+        &mut self,
+        count: usize,
+    ) -> Result<Vec<FieldElement<F>>, ProofEncodingError>
+    where
+        F: IsField,
+        FieldElement<F>: ByteConversion,
+    {
+        (0..count).map(|_| self.read_field_element()).collect()
+    }
+
+    fn read_vec<F>(&mut self) -> Result<Vec<FieldElement<F>>, ProofEncodingError>
+    where
+        F: IsField,
+        FieldElement<F>: ByteConversion,
+    {
+        let count = self.read_length()?;
+        self.read_field_elements(count)
+    }
+
+    fn read_evaluations<F>(&mut self) -> Result<Evaluations<F>, ProofEncodingError>
+    where
+        F: IsField,
+        FieldElement<F>: ByteConversion,
+    {
+        Ok((self.read_vec()?, self.read_vec()?))
+    }
+
+    /// Reads `count` authentication paths written by `ProofWriter::write_paths`, none of them
+    /// longer than `max_path_length`.
+    fn read_paths(
+        &mut self,
+        count: usize,
+        max_path_length: usize,
+    ) -> Result<Vec<Proof<Commitment>>, ProofEncodingError> {
+        if self.read_length()? != count {
+            return Err(ProofEncodingError::InvalidPathReference);
+        }
+        let mut paths: Vec<Proof<Commitment>> = Vec::new();
+        for i in 0..count {
+            let reference = self.read_length()?;
+            let shared = self.read_length()?;
+            let number_of_own_nodes = self.read_length()?;
+            let path_length = number_of_own_nodes.saturating_add(shared);
+            if path_length > max_path_length {
+                return Err(ProofEncodingError::InvalidPathLength(path_length));
+            }
+            let mut merkle_path = (0..number_of_own_nodes)
+                .map(|_| self.read_commitment())
+                .collect::<Result<Vec<_>, _>>()?;
+            match reference.checked_sub(1) {
+                Some(j) if j < i && shared > 0 && shared <= paths[j].merkle_path.len() => {
+                    let previous = &paths[j].merkle_path;
+                    merkle_path.extend_from_slice(&previous[previous.len() - shared..]);
+                }
+                None if shared == 0 => {}
+                _ => return Err(ProofEncodingError::InvalidPathReference),
+            }
+            paths.push(Proof { merkle_path });
+        }
+        Ok(paths)
+    }
+}
@@ -1,3 +1,8 @@
+pub mod binary;
 pub mod errors;
 pub mod options;
+#[cfg(feature = "std")]
+pub mod security;
 pub mod stark;
+#[cfg(feature = "std")]
+pub mod stone;
@@ -1,5 +1,11 @@
-use super::errors::InsecureOptionError;
+#[cfg(feature = "std")]
+use super::{errors::InsecureOptionError, security::SecurityParameters};
+#[cfg(feature = "std")]
+use crate::traits::AIR;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
 use lambdaworks_math::field::traits::IsPrimeField;
+use lambdaworks_math::traits::AsBytes;
 
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::wasm_bindgen;
@@ -13,12 +19,30 @@ pub enum SecurityLevel {
     Provable128Bits,
 }
 
+#[cfg(feature = "std")]
+impl SecurityLevel {
+    /// Returns the number of bits of the level and whether they must be provable.
+    fn target(&self) -> (usize, bool) {
+        match self {
+            SecurityLevel::Conjecturable80Bits => (80, false),
+            SecurityLevel::Conjecturable100Bits => (100, false),
+            SecurityLevel::Conjecturable128Bits => (128, false),
+            SecurityLevel::Provable80Bits => (80, true),
+            SecurityLevel::Provable100Bits => (100, true),
+            SecurityLevel::Provable128Bits => (128, true),
+        }
+    }
+}
+
 /// The options for the proof
 ///
 /// - `blowup_factor`: the blowup factor for the trace
 /// - `fri_number_of_queries`: the number of queries for the FRI layer
 /// - `coset_offset`: the offset for the coset
 /// - `grinding_factor`: the number of leading zeros that we want for the Hash(hash || nonce)
+///   computed before sampling the FRI queries
+/// - `commit_phase_grinding_factor`: the number of leading zeros of the proof-of-work computed
+///   before sampling each FRI folding challenge. Zero disables it.
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Debug)]
 pub struct ProofOptions {
@@ -26,14 +50,10 @@ pub struct ProofOptions {
     pub fri_number_of_queries: usize,
     pub coset_offset: u64,
     pub grinding_factor: u8,
+    pub commit_phase_grinding_factor: u8,
 }
 
 impl ProofOptions {
-    // TODO: Make it work for extended fields
-    const EXTENSION_DEGREE: usize = 1;
-    // Estimated maximum domain size. 2^40 = 1 TB
-    const NUM_BITS_MAX_DOMAIN_SIZE: usize = 40;
-
     /// See section 5.10.1 of https://eprint.iacr.org/2021/582.pdf
     pub fn new_secure(security_level: SecurityLevel, coset_offset: u64) -> Self {
         match security_level {
@@ -42,41 +62,117 @@ impl ProofOptions {
                 fri_number_of_queries: 31,
                 coset_offset,
                 grinding_factor: 20,
+                commit_phase_grinding_factor: 0,
             },
             SecurityLevel::Conjecturable100Bits => ProofOptions {
                 blowup_factor: 4,
                 fri_number_of_queries: 41,
                 coset_offset,
                 grinding_factor: 20,
+                commit_phase_grinding_factor: 0,
             },
             SecurityLevel::Conjecturable128Bits => ProofOptions {
                 blowup_factor: 4,
                 fri_number_of_queries: 55,
                 coset_offset,
                 grinding_factor: 20,
+                commit_phase_grinding_factor: 0,
             },
             SecurityLevel::Provable80Bits => ProofOptions {
                 blowup_factor: 4,
                 fri_number_of_queries: 80,
                 coset_offset,
                 grinding_factor: 20,
+                commit_phase_grinding_factor: 0,
             },
             SecurityLevel::Provable100Bits => ProofOptions {
                 blowup_factor: 4,
                 fri_number_of_queries: 104,
                 coset_offset,
                 grinding_factor: 20,
+                commit_phase_grinding_factor: 0,
             },
             SecurityLevel::Provable128Bits => ProofOptions {
                 blowup_factor: 4,
                 fri_number_of_queries: 140,
                 coset_offset,
                 grinding_factor: 20,
+                commit_phase_grinding_factor: 0,
             },
         }
     }
 
-    /// Checks security of proof options given 128 bits of security
+    /// Default proof options used for testing purposes.
+    /// These options should never be used in production.
+    pub fn default_test_options() -> Self {
+        Self {
+            blowup_factor: 4,
+            fri_number_of_queries: 3,
+            coset_offset: 3,
+            grinding_factor: 1,
+            commit_phase_grinding_factor: 0,
+        }
+    }
+}
+
+/// Security estimates rely on floating point logarithms, which are only available with `std`.
+#[cfg(feature = "std")]
+impl ProofOptions {
+    // Estimated maximum domain size. 2^40 = 1 TB
+    const NUM_BITS_MAX_DOMAIN_SIZE: usize = 40;
+    // Grinding factor and maximum number of queries used by `new_secure_for_air`
+    const PRESET_GRINDING_FACTOR: u8 = 20;
+    const MAX_PRESET_QUERIES: usize = 512;
+
+    /// Returns the proof options with the fewest FRI queries reaching `security_level` for
+    /// proofs of `trace_length` rows of the AIR `A`, taking into account the size of its field
+    /// and the degree of the extension used for challenges.
+    pub fn new_secure_for_air<A>(
+        security_level: SecurityLevel,
+        trace_length: usize,
+        coset_offset: u64,
+    ) -> Result<Self, InsecureOptionError>
+    where
+        A: AIR,
+        A::Field: IsPrimeField,
+    {
+        let (security_target, provable) = security_level.target();
+        let mut error = InsecureOptionError::FieldSize;
+
+        for blowup_factor in [4, 8, 16, 32] {
+            let options = ProofOptions {
+                blowup_factor,
+                fri_number_of_queries: 0,
+                coset_offset,
+                grinding_factor: Self::PRESET_GRINDING_FACTOR,
+                commit_phase_grinding_factor: 0,
+            };
+            let params =
+                SecurityParameters::new::<A::Field>(A::EXTENSION_DEGREE, trace_length, &options);
+            if params.challenge_security_bits() <= security_target {
+                continue;
+            }
+            error = InsecureOptionError::LowSecurityBits;
+
+            for fri_number_of_queries in 1..=Self::MAX_PRESET_QUERIES {
+                let params = SecurityParameters {
+                    fri_number_of_queries,
+                    ..params.clone()
+                };
+                if Self::security_bits(&params, provable) >= security_target {
+                    return Ok(ProofOptions {
+                        fri_number_of_queries,
+                        ..options
+                    });
+                }
+            }
+        }
+
+        Err(error)
+    }
+
+    /// Checks conjectured security of proof options given a target of `security_target` bits,
+    /// for any trace length, when challenges are sampled from `F` itself.
     pub fn new_with_checked_security<F: IsPrimeField>(
         blowup_factor: u8,
         fri_number_of_queries: usize,
@@ -84,26 +180,19 @@ impl ProofOptions {
         grinding_factor: u8,
         security_target: u8,
     ) -> Result<Self, InsecureOptionError> {
-        Self::check_field_security::<F>(security_target)?;
-
-        let num_bits_blowup_factor = blowup_factor.trailing_zeros() as usize;
-
-        if security_target as usize
-            >= grinding_factor as usize + num_bits_blowup_factor * fri_number_of_queries - 1
-        {
-            return Err(InsecureOptionError::LowSecurityBits);
-        }
-
-        Ok(ProofOptions {
+        Self::new_with_checked_security_in_extension::<F>(
             blowup_factor,
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
-        })
+            0,
+            1,
+            security_target,
+        )
     }
 
-    /// Checks provable security of proof options given 128 bits of security
-    /// This is an approximation. It's stricter than the formula in the paper.
+    /// Checks provable security of proof options given a target of `security_target` bits,
+    /// for any trace length, when challenges are sampled from `F` itself.
     /// See https://eprint.iacr.org/2021/582.pdf
     pub fn new_with_checked_provable_security<F: IsPrimeField>(
         blowup_factor: u8,
@@ -112,55 +201,117 @@ impl ProofOptions {
         grinding_factor: u8,
         security_target: u8,
     ) -> Result<Self, InsecureOptionError> {
-        Self::check_field_security::<F>(security_target)?;
-
-        let num_bits_blowup_factor = blowup_factor.leading_zeros() as usize;
+        Self::new_with_checked_provable_security_in_extension::<F>(
+            blowup_factor,
+            fri_number_of_queries,
+            coset_offset,
+            grinding_factor,
+            0,
+            1,
+            security_target,
+        )
+    }
 
-        if (security_target as usize)
-            < grinding_factor as usize + num_bits_blowup_factor * fri_number_of_queries / 2
-        {
-            return Err(InsecureOptionError::LowSecurityBits);
-        }
+    /// Checks conjectured security of proof options given a target of `security_target` bits,
+    /// for any trace length, when challenges are sampled from the extension of degree
+    /// `extension_degree` of `F`.
+    pub fn new_with_checked_security_in_extension<F: IsPrimeField>(
+        blowup_factor: u8,
+        fri_number_of_queries: usize,
+        coset_offset: u64,
+        grinding_factor: u8,
+        commit_phase_grinding_factor: u8,
+        extension_degree: usize,
+        security_target: u8,
+    ) -> Result<Self, InsecureOptionError> {
+        let options = ProofOptions {
+            blowup_factor,
+            fri_number_of_queries,
+            coset_offset,
+            grinding_factor,
+            commit_phase_grinding_factor,
+        };
+        options.check_security::<F>(extension_degree, security_target, false)?;
+        Ok(options)
+    }
 
-        Ok(ProofOptions {
+    /// Checks provable security of proof options given a target of `security_target` bits,
+    /// for any trace length, when challenges are sampled from the extension of degree
+    /// `extension_degree` of `F`.
+    /// See https://eprint.iacr.org/2021/582.pdf
+    pub fn new_with_checked_provable_security_in_extension<F: IsPrimeField>(
+        blowup_factor: u8,
+        fri_number_of_queries: usize,
+        coset_offset: u64,
+        grinding_factor: u8,
+        commit_phase_grinding_factor: u8,
+        extension_degree: usize,
+        security_target: u8,
+    ) -> Result<Self, InsecureOptionError> {
+        let options = ProofOptions {
             blowup_factor,
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
-        })
+            commit_phase_grinding_factor,
+        };
+        options.check_security::<F>(extension_degree, security_target, true)?;
+        Ok(options)
     }
 
-    fn check_field_security<F: IsPrimeField>(
+    /// Checks the security of the options for the largest LDE domain supported.
+    fn check_security<F: IsPrimeField>(
+        &self,
+        extension_degree: usize,
         security_target: u8,
+        provable: bool,
     ) -> Result<(), InsecureOptionError> {
-        if F::field_bit_size() * Self::EXTENSION_DEGREE
-            <= security_target as usize + Self::NUM_BITS_MAX_DOMAIN_SIZE
-        {
+        let trace_length = (1 << Self::NUM_BITS_MAX_DOMAIN_SIZE) / self.blowup_factor as usize;
+        let params = SecurityParameters::new::<F>(extension_degree, trace_length, self);
+
+        if params.challenge_security_bits() <= security_target as usize {
             return Err(InsecureOptionError::FieldSize);
         }
+        if Self::security_bits(&params, provable) < security_target as usize {
+            return Err(InsecureOptionError::LowSecurityBits);
+        }
 
         Ok(())
     }
 
-    /// Default proof options used for testing purposes.
-    /// These options should never be used in production.
-    pub fn default_test_options() -> Self {
-        Self {
-            blowup_factor: 4,
-            fri_number_of_queries: 3,
-            coset_offset: 3,
-            grinding_factor: 1,
+    fn security_bits(params: &SecurityParameters, provable: bool) -> usize {
+        if provable {
+            params.provable_security_bits()
+        } else {
+            params.conjectured_security_bits()
         }
     }
 }
 
+impl AsBytes for ProofOptions {
+    fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.blowup_factor];
+        bytes.extend((self.fri_number_of_queries as u64).to_be_bytes());
+        bytes.extend(self.coset_offset.to_be_bytes());
+        bytes.push(self.grinding_factor);
+        bytes.push(self.commit_phase_grinding_factor);
+        bytes
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use lambdaworks_math::field::fields::{
-        fft_friendly::stark_252_prime_field::Stark252PrimeField, u64_prime_field::F17,
+        fft_friendly::{
+            babybear_u32::Babybear31PrimeField, stark_252_prime_field::Stark252PrimeField,
+        },
+        u64_prime_field::F17,
     };
 
-    use crate::proof::{errors::InsecureOptionError, options::SecurityLevel};
+    use crate::{
+        examples::simple_fibonacci::FibonacciAIR,
+        proof::{errors::InsecureOptionError, options::SecurityLevel},
+    };
 
     use super::ProofOptions;
 
@@ -171,6 +322,7 @@ mod tests {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            ..
         } = ProofOptions::new_secure(SecurityLevel::Conjecturable128Bits, 1);
 
         let u64_options = ProofOptions::new_with_checked_security::<F17>(
@@ -191,6 +343,7 @@ mod tests {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            ..
         } = ProofOptions::new_secure(SecurityLevel::Conjecturable128Bits, 1);
 
         let secure_options = ProofOptions::new_with_checked_security::<Stark252PrimeField>(
@@ -211,6 +364,7 @@ mod tests {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            ..
         } = ProofOptions::new_secure(SecurityLevel::Conjecturable128Bits, 1);
 
         let insecure_options = ProofOptions::new_with_checked_security::<Stark252PrimeField>(
@@ -234,6 +388,7 @@ mod tests {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            ..
         } = ProofOptions::new_secure(SecurityLevel::Conjecturable100Bits, 1);
 
         let secure_options = ProofOptions::new_with_checked_security::<Stark252PrimeField>(
@@ -254,6 +409,7 @@ mod tests {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            ..
         } = ProofOptions::new_secure(SecurityLevel::Conjecturable80Bits, 1);
 
         let secure_options = ProofOptions::new_with_checked_security::<Stark252PrimeField>(
@@ -266,4 +422,95 @@ mod tests {
 
         assert!(secure_options.is_ok());
     }
+
+    #[test]
+    fn generated_stark_proof_options_for_128_provable_bits_are_provably_secure() {
+        let ProofOptions {
+            blowup_factor,
+            fri_number_of_queries,
+            coset_offset,
+            grinding_factor,
+            ..
+        } = ProofOptions::new_secure(SecurityLevel::Provable128Bits, 1);
+
+        let secure_options = ProofOptions::new_with_checked_provable_security::<Stark252PrimeField>(
+            blowup_factor,
+            fri_number_of_queries,
+            coset_offset,
+            grinding_factor,
+            128,
+        );
+        let insecure_options = ProofOptions::new_with_checked_provable_security::<Stark252PrimeField>(
+            blowup_factor,
+            fri_number_of_queries / 2,
+            coset_offset,
+            grinding_factor,
+            128,
+        );
+
+        assert!(secure_options.is_ok());
+        assert!(matches!(
+            insecure_options,
+            Err(InsecureOptionError::LowSecurityBits)
+        ));
+    }
+
+    #[test]
+    fn small_field_options_are_secure_in_a_quartic_extension() {
+        let ProofOptions {
+            blowup_factor,
+            fri_number_of_queries,
+            coset_offset,
+            grinding_factor,
+            commit_phase_grinding_factor,
+        } = ProofOptions::new_secure(SecurityLevel::Conjecturable80Bits, 1);
+
+        let base_options = ProofOptions::new_with_checked_security::<Babybear31PrimeField>(
+            blowup_factor,
+            fri_number_of_queries,
+            coset_offset,
+            grinding_factor,
+            80,
+        );
+        let quartic_options =
+            ProofOptions::new_with_checked_security_in_extension::<Babybear31PrimeField>(
+                blowup_factor,
+                fri_number_of_queries,
+                coset_offset,
+                grinding_factor,
+                commit_phase_grinding_factor,
+                4,
+                80,
+            );
+
+        assert!(matches!(base_options, Err(InsecureOptionError::FieldSize)));
+        assert!(quartic_options.is_ok());
+    }
+
+    #[test]
+    fn preset_options_for_air_match_the_conjectured_presets() {
+        let options = ProofOptions::new_secure_for_air::<FibonacciAIR<Stark252PrimeField>>(
+            SecurityLevel::Conjecturable128Bits,
+            1 << 20,
+            3,
+        )
+        .unwrap();
+
+        assert_eq!(options.blowup_factor, 4);
+        assert_eq!(
+            options.fri_number_of_queries,
+            ProofOptions::new_secure(SecurityLevel::Conjecturable128Bits, 3).fri_number_of_queries
+        );
+    }
+
+    #[test]
+    fn preset_options_for_air_over_small_field_without_extension_are_insecure() {
+        let options = ProofOptions::new_secure_for_air::<FibonacciAIR<Babybear31PrimeField>>(
+            SecurityLevel::Conjecturable100Bits,
+            1 << 20,
+            3,
+        );
+
+        assert!(matches!(options, Err(InsecureOptionError::FieldSize)));
+    }
 }
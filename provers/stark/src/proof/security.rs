@@ -0,0 +1,224 @@
+use lambdaworks_math::field::traits::IsPrimeField;
+
+use super::options::ProofOptions;
+use crate::config::COMMITMENT_SIZE;
+
+/// The output size in bits of the hash used for the commitments of the prover.
+pub const DEFAULT_HASH_OUTPUT_BITS: usize = 8 * COMMITMENT_SIZE;
+
+/// Smallest and largest values of the Johnson proximity parameter `m` tried when computing
+/// the provable security. See section 5.10.1 of https://eprint.iacr.org/2021/582.pdf
+const MIN_PROXIMITY_PARAMETER: usize = 3;
+const MAX_PROXIMITY_PARAMETER: usize = 100;
+
+/// The parameters of a STARK instance that determine its soundness.
+///
+/// - `base_field_bits`: the size in bits of the field of the trace
+/// - `extension_degree`: the degree of the extension from which the verifier samples challenges
+/// - `trace_length`: the number of rows of the trace
+/// - `blowup_factor`: the blowup factor of the low degree extension
+/// - `fri_number_of_queries`: the number of FRI queries
+/// - `grinding_factor`: the number of bits of proof of work before the FRI queries
+/// - `commit_phase_grinding_factor`: the number of bits of proof of work before each FRI folding
+///   challenge
+/// - `hash_output_bits`: the output size in bits of the hash used for the commitments
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SecurityParameters {
+    pub base_field_bits: usize,
+    pub extension_degree: usize,
+    pub trace_length: usize,
+    pub blowup_factor: usize,
+    pub fri_number_of_queries: usize,
+    pub grinding_factor: usize,
+    pub commit_phase_grinding_factor: usize,
+    pub hash_output_bits: usize,
+}
+
+impl SecurityParameters {
+    /// Returns the parameters of proofs over the field `F` with the given extension degree,
+    /// trace length and proof options, committed with the default hash.
+    pub fn new<F: IsPrimeField>(
+        extension_degree: usize,
+        trace_length: usize,
+        proof_options: &ProofOptions,
+    ) -> Self {
+        Self {
+            base_field_bits: F::field_bit_size(),
+            extension_degree,
+            trace_length,
+            blowup_factor: proof_options.blowup_factor as usize,
+            fri_number_of_queries: proof_options.fri_number_of_queries,
+            grinding_factor: proof_options.grinding_factor as usize,
+            commit_phase_grinding_factor: proof_options.commit_phase_grinding_factor as usize,
+            hash_output_bits: DEFAULT_HASH_OUTPUT_BITS,
+        }
+    }
+
+    /// Returns the number of bits of security of the field from which challenges are sampled,
+    /// once the size of the LDE domain is accounted for.
+    pub fn field_security_bits(&self) -> usize {
+        let field_bits = self.base_field_bits * self.extension_degree;
+        field_bits.saturating_sub(self.lde_domain_size_bits() as usize)
+    }
+
+    /// Returns the number of bits of security of the challenges sampled by the verifier before
+    /// the FRI queries. The FRI folding challenges gain the bits of the commit phase grinding,
+    /// while the out-of-domain and composition challenges, sampled before any proof of work,
+    /// are bounded by the degree of the composition polynomial over the size of the field.
+    pub fn challenge_security_bits(&self) -> usize {
+        let field_bits = self.base_field_bits * self.extension_degree;
+        let deep_security =
+            field_bits.saturating_sub(self.composition_degree_bits().ceil() as usize);
+        let commit_phase_security = self.field_security_bits() + self.commit_phase_grinding_factor;
+
+        deep_security.min(commit_phase_security)
+    }
+
+    /// Returns the bits of security under the ethSTARK conjecture on the soundness of FRI.
+    /// See section 5.10.1 of https://eprint.iacr.org/2021/582.pdf
+    pub fn conjectured_security_bits(&self) -> usize {
+        let query_security = self.blowup_factor.trailing_zeros() as usize
+            * self.fri_number_of_queries
+            + self.grinding_factor;
+
+        self.challenge_security_bits()
+            .min(query_security)
+            .saturating_sub(1)
+            .min(self.collision_resistance_bits())
+    }
+
+    /// Returns the bits of security proven in the list-decoding regime, following Theorem 8 of
+    /// https://eprint.iacr.org/2021/582.pdf. The errors of the DEEP and ALI steps assume a
+    /// composition polynomial of degree at most twice the trace length, opened at two points.
+    pub fn provable_security_bits(&self) -> usize {
+        let bits = (MIN_PROXIMITY_PARAMETER..=MAX_PROXIMITY_PARAMETER)
+            .map(|m| self.provable_security_bits_for_proximity_parameter(m as f64))
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        (bits.max(0.0).floor() as usize).min(self.collision_resistance_bits())
+    }
+
+    fn provable_security_bits_for_proximity_parameter(&self, m: f64) -> f64 {
+        let field_bits = (self.base_field_bits * self.extension_degree) as f64;
+        let rate = 1.0 / self.blowup_factor as f64;
+        let list_size = (m + 0.5) / rate.sqrt();
+        let alpha = (1.0 + 0.5 / m) * rate.sqrt();
+
+        // Base-2 logarithms of each error term.
+        let ali_error = list_size.log2() - field_bits;
+        let deep_error = list_size.log2() + self.composition_degree_bits() + 1.0 - field_bits;
+        let fri_commit_error = 7.0 * (m + 0.5).log2() - 3.0_f64.log2() - 1.5 * rate.log2()
+            + 2.0 * self.lde_domain_size_bits()
+            - field_bits
+            - self.commit_phase_grinding_factor as f64;
+        let fri_query_error =
+            self.fri_number_of_queries as f64 * alpha.log2() - self.grinding_factor as f64;
+
+        let total_error: f64 = [ali_error, deep_error, fri_commit_error, fri_query_error]
+            .iter()
+            .map(|error| error.exp2())
+            .sum();
+        -total_error.log2()
+    }
+
+    /// The composition polynomial has degree at most twice the trace length.
+    fn composition_degree_bits(&self) -> f64 {
+        (2.0 * self.trace_length as f64).log2()
+    }
+
+    fn lde_domain_size_bits(&self) -> f64 {
+        ((self.trace_length * self.blowup_factor) as f64).log2()
+    }
+
+    fn collision_resistance_bits(&self) -> usize {
+        self.hash_output_bits / 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::field::fields::{
+        fft_friendly::{
+            babybear_u32::Babybear31PrimeField, stark_252_prime_field::Stark252PrimeField,
+        },
+        mersenne31::field::Mersenne31Field,
+    };
+
+    use super::*;
+    use crate::proof::options::SecurityLevel;
+
+    #[test]
+    fn conjectured_security_is_bounded_by_the_queries() {
+        let options = ProofOptions {
+            fri_number_of_queries: 30,
+            ..ProofOptions::new_secure(SecurityLevel::Conjecturable128Bits, 3)
+        };
+        let params = SecurityParameters::new::<Stark252PrimeField>(1, 1 << 20, &options);
+
+        // 2 bits per query times 30 queries plus 20 bits of grinding, minus one.
+        assert_eq!(params.conjectured_security_bits(), 79);
+    }
+
+    #[test]
+    fn conjectured_security_is_bounded_by_the_hash() {
+        let options = ProofOptions::new_secure(SecurityLevel::Provable128Bits, 3);
+        let params = SecurityParameters {
+            hash_output_bits: 160,
+            ..SecurityParameters::new::<Stark252PrimeField>(1, 1 << 20, &options)
+        };
+
+        assert_eq!(params.conjectured_security_bits(), 80);
+    }
+
+    #[test]
+    fn small_fields_need_an_extension_to_be_secure() {
+        let options = ProofOptions::new_secure(SecurityLevel::Conjecturable100Bits, 3);
+
+        let base = SecurityParameters::new::<Babybear31PrimeField>(1, 1 << 20, &options);
+        let quartic = SecurityParameters::new::<Babybear31PrimeField>(4, 1 << 20, &options);
+
+        // 31 bits of field minus 22 bits of LDE domain, minus one.
+        assert_eq!(base.conjectured_security_bits(), 8);
+        assert_eq!(quartic.conjectured_security_bits(), 101);
+        assert!(quartic.provable_security_bits() < quartic.conjectured_security_bits());
+    }
+
+    #[test]
+    fn provable_security_is_limited_by_the_field_for_quartic_extensions_of_small_fields() {
+        let options = ProofOptions::new_secure(SecurityLevel::Provable128Bits, 3);
+
+        let mersenne = SecurityParameters::new::<Mersenne31Field>(4, 1 << 20, &options);
+        let stark252 = SecurityParameters::new::<Stark252PrimeField>(1, 1 << 20, &options);
+
+        assert!(mersenne.provable_security_bits() < 100);
+        assert_eq!(stark252.provable_security_bits(), 128);
+    }
+
+    #[test]
+    fn commit_phase_grinding_is_bounded_by_the_out_of_domain_challenge() {
+        let options = ProofOptions::new_secure(SecurityLevel::Conjecturable128Bits, 3);
+        let without_grinding =
+            SecurityParameters::new::<Babybear31PrimeField>(4, 1 << 20, &options);
+        let with_grinding = SecurityParameters {
+            commit_phase_grinding_factor: 20,
+            ..without_grinding.clone()
+        };
+
+        // 124 bits of field minus 22 bits of LDE domain, minus one.
+        assert_eq!(without_grinding.conjectured_security_bits(), 101);
+        // 124 bits of field minus 21 bits of composition polynomial degree, minus one.
+        assert_eq!(with_grinding.conjectured_security_bits(), 102);
+    }
+
+    #[test]
+    fn commit_phase_grinding_increases_provable_security_of_small_fields() {
+        let options = ProofOptions::new_secure(SecurityLevel::Provable128Bits, 3);
+        let without_grinding = SecurityParameters::new::<Mersenne31Field>(4, 1 << 20, &options);
+        let with_grinding = SecurityParameters {
+            commit_phase_grinding_factor: 20,
+            ..without_grinding.clone()
+        };
+
+        assert!(with_grinding.provable_security_bits() > without_grinding.provable_security_bits());
+    }
+}
@@ -1,4 +1,8 @@
-use std::collections::{BTreeSet, HashMap, HashSet};
+use alloc::{
+    borrow::ToOwned,
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
 
 use lambdaworks_crypto::merkle_tree::proof::Proof;
 use lambdaworks_math::{
@@ -35,6 +39,7 @@ pub struct DeepPolynomialOpening<F: IsSubFieldOf<E>, E: IsField> {
     pub composition_poly: PolynomialOpenings<E>,
     pub main_trace_polys: PolynomialOpenings<F>,
     pub aux_trace_polys: Option<PolynomialOpenings<E>>,
+    pub preprocessed_trace_polys: Option<PolynomialOpenings<F>>,
 }
 
 pub type DeepPolynomialOpenings<F, E> = Vec<DeepPolynomialOpening<F, E>>;
@@ -64,6 +69,9 @@ pub struct StarkProof<F: IsSubFieldOf<E>, E: IsField> {
     // Open(H₁(D_LDE, 𝜐ᵢ), Open(H₂(D_LDE, 𝜐ᵢ), Open(tⱼ(D_LDE), 𝜐ᵢ)
     // Open(H₁(D_LDE, -𝜐ᵢ), Open(H₂(D_LDE, -𝜐ᵢ), Open(tⱼ(D_LDE), -𝜐ᵢ)
     pub deep_poly_openings: DeepPolynomialOpenings<F, E>,
+    // nonces obtained from grinding before each folding challenge, if the commit phase
+    // grinding factor is not zero
+    pub commit_phase_nonces: Vec<u64>,
     // nonce obtained from grinding
     pub nonce: Option<u64>,
 }
@@ -206,7 +214,7 @@ impl StoneCompatibleSerializer {
             .zip(fri_query_indexes.iter())
             .collect();
         // Remove repeated values
-        let mut seen = HashSet::new();
+        let mut seen = BTreeSet::new();
         fri_first_layer_openings.retain(|&(_, index)| seen.insert(index));
         // Sort by increasing value of query
         fri_first_layer_openings.sort_by(|a, b| a.1.cmp(b.1));
@@ -321,8 +329,8 @@ impl StoneCompatibleSerializer {
         fri_query_indexes: &[usize],
         output: &mut Vec<u8>,
     ) {
-        let mut fri_layers_evaluations: HashMap<(u64, usize, usize), FieldElement<_>> =
-            HashMap::new();
+        let mut fri_layers_evaluations: BTreeMap<(u64, usize, usize), FieldElement<_>> =
+            BTreeMap::new();
         for (decommitment, query_index) in proof.query_list.iter().zip(fri_query_indexes.iter()) {
             let mut query_layer_index = *query_index;
             for (i, element) in decommitment.layers_evaluations_sym.iter().enumerate() {
@@ -418,7 +426,7 @@ impl StoneCompatibleSerializer {
         leaf_indexes: &[usize],
     ) -> Vec<Commitment> {
         debug_assert_eq!(leaf_indexes.len(), authentication_paths.len());
-        let mut merkle_tree: HashMap<(usize, usize), Commitment> = HashMap::new();
+        let mut merkle_tree: BTreeMap<(usize, usize), Commitment> = BTreeMap::new();
         for (index_previous_layer, path) in leaf_indexes.iter().zip(authentication_paths.iter()) {
             let mut node_index = *index_previous_layer;
             for (tree_level, node) in path.merkle_path.iter().enumerate() {
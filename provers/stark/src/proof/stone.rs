@@ -0,0 +1,704 @@
+//! Parsing of the proofs generated by the Stone prover.
+//!
+//! Only the configuration that the verifier of this crate can check is supported: every FRI
+//! layer after the first one folds the previous layer once, that is, `fri_step_list` is
+//! `[0, 1, ..., 1]`, and FRI runs until the last layer is constant, that is,
+//! `last_layer_degree_bound` is 1. This is the format written by
+//! [`StoneCompatibleSerializer`](super::stark::StoneCompatibleSerializer). Proofs with layers
+//! folded more than once or with a last layer polynomial are rejected with
+//! [`StoneProofError::UnsupportedParameters`].
+
+use std::collections::{BTreeSet, HashMap};
+
+use lambdaworks_crypto::merkle_tree::{proof::Proof, traits::IsMerkleTreeBackend};
+use lambdaworks_math::{
+    field::{
+        element::FieldElement,
+        fields::{
+            fft_friendly::stark_252_prime_field::{
+                MontgomeryConfigStark252PrimeField, Stark252PrimeField,
+            },
+            montgomery_backed_prime_fields::IsModulus,
+        },
+    },
+    traits::{AsBytes, ByteConversion},
+    unsigned_integer::element::U256,
+};
+
+use crate::{
+    config::{BatchedMerkleTreeBackend, Commitment, COMMITMENT_SIZE},
+    domain::Domain,
+    fri::fri_decommit::FriDecommitment,
+    table::Table,
+    traits::AIR,
+    transcript::StoneProverTranscript,
+    verifier::{IsStarkVerifier, Verifier},
+    Felt252,
+};
+
+use super::{
+    options::ProofOptions,
+    stark::{DeepPolynomialOpening, PolynomialOpenings, StarkProof},
+};
+
+/// Size in bytes of a field element in a Stone proof.
+const FIELD_ELEMENT_SIZE: usize = 32;
+
+/// Errors found while reading a proof generated by the Stone prover.
+#[derive(Debug, PartialEq, Eq)]
+pub enum StoneProofError {
+    /// The JSON document is not a proof output by the Stone prover.
+    InvalidJson(String),
+    /// The `proof_hex` field is not an hexadecimal string.
+    InvalidHex,
+    /// The proof parameters can't be expressed as `ProofOptions`.
+    UnsupportedParameters(String),
+    /// The AIR has elements that the Stone proof format doesn't encode.
+    UnsupportedAir(String),
+    /// The proof ended before all its elements were read.
+    UnexpectedEndOfProof,
+    /// Bytes were left after reading the whole proof.
+    TrailingBytes(usize),
+    /// A field element is not smaller than the modulus of the field.
+    NonCanonicalFieldElement,
+}
+
+/// The parameters of the FRI protocol in a Stone proof.
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct StoneFriParameters {
+    /// The number of times each layer is folded, starting from the first one.
+    pub fri_step_list: Vec<u32>,
+    /// The degree bound of the polynomial sent in clear after the last layer.
+    pub last_layer_degree_bound: u64,
+    pub n_queries: usize,
+    pub proof_of_work_bits: u8,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct StoneStarkParameters {
+    pub fri: StoneFriParameters,
+    /// The logarithm of the blowup factor.
+    pub log_n_cosets: u32,
+}
+
+/// The `proof_parameters` section of the output of the Stone prover.
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct StoneProofParameters {
+    pub stark: StoneStarkParameters,
+    pub channel_hash: Option<String>,
+    pub commitment_hash: Option<String>,
+    #[serde(default)]
+    pub use_extension_field: bool,
+}
+
+impl StoneProofParameters {
+    /// The offset of the LDE coset used by Stone, the generator of the multiplicative group of
+    /// the field.
+    const COSET_OFFSET: u64 = 3;
+    /// The only hash supported both for the transcript and the commitments.
+    const HASH: &'static str = "keccak256";
+
+    /// Returns the `ProofOptions` matching these parameters.
+    pub fn proof_options(&self) -> Result<ProofOptions, StoneProofError> {
+        self.check_supported()?;
+        let fri = &self.stark.fri;
+        Ok(ProofOptions {
+            blowup_factor: 1 << self.stark.log_n_cosets,
+            fri_number_of_queries: fri.n_queries,
+            coset_offset: Self::COSET_OFFSET,
+            grinding_factor: fri.proof_of_work_bits,
+            commit_phase_grinding_factor: 0,
+        })
+    }
+
+    /// Returns the length of the trace of the proof. FRI folds the DEEP composition polynomial,
+    /// of degree less than the trace length, `sum(fri_step_list)` times down to the last layer,
+    /// so this is `2^(sum(fri_step_list) + log2(last_layer_degree_bound))`.
+    pub fn trace_length(&self) -> Result<usize, StoneProofError> {
+        self.check_supported()?;
+        let fri = &self.stark.fri;
+        let log_trace_length =
+            fri.fri_step_list.iter().sum::<u32>() + fri.last_layer_degree_bound.trailing_zeros();
+        Ok(1 << log_trace_length)
+    }
+
+    fn check_supported(&self) -> Result<(), StoneProofError> {
+        let unsupported = |message: String| Err(StoneProofError::UnsupportedParameters(message));
+
+        for hash in [&self.channel_hash, &self.commitment_hash]
+            .into_iter()
+            .flatten()
+        {
+            if hash != Self::HASH {
+                return unsupported(format!("hash {hash} is not {}", Self::HASH));
+            }
+        }
+        if self.use_extension_field {
+            return unsupported("extension fields are not supported".to_string());
+        }
+        if self.stark.log_n_cosets >= u8::BITS {
+            return unsupported(format!(
+                "blowup factor 2^{} is too large",
+                self.stark.log_n_cosets
+            ));
+        }
+
+        let fri = &self.stark.fri;
+        // The first layer is the DEEP composition polynomial itself, and every other layer halves
+        // the degree of the previous one.
+        match fri.fri_step_list.split_first() {
+            Some((0, steps)) if !steps.is_empty() && steps.iter().all(|step| *step == 1) => {}
+            _ => {
+                return unsupported(format!(
+                    "FRI step list {:?} is not of the form [0, 1, ..., 1]",
+                    fri.fri_step_list
+                ))
+            }
+        }
+        if fri.fri_step_list.len() >= usize::BITS as usize {
+            return unsupported(format!(
+                "{} FRI steps are too many",
+                fri.fri_step_list.len()
+            ));
+        }
+        if !fri.last_layer_degree_bound.is_power_of_two() {
+            return unsupported(format!(
+                "last layer degree bound {} is not a power of two",
+                fri.last_layer_degree_bound
+            ));
+        }
+        // The last layer is sent as a single value, not as the coefficients of a polynomial.
+        if fri.last_layer_degree_bound != 1 {
+            return unsupported(format!(
+                "last layer degree bound {} is not 1",
+                fri.last_layer_degree_bound
+            ));
+        }
+        if fri.n_queries == 0 {
+            return unsupported("the number of queries is 0".to_string());
+        }
+        if fri.proof_of_work_bits >= 64 {
+            return unsupported(format!(
+                "{} proof of work bits are too many",
+                fri.proof_of_work_bits
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct StoneProofJson {
+    proof_hex: String,
+    proof_parameters: StoneProofParameters,
+}
+
+/// A proof output by the Stone prover (https://github.com/starkware-libs/stone-prover/).
+pub struct StoneProof {
+    pub parameters: StoneProofParameters,
+    pub proof_bytes: Vec<u8>,
+}
+
+impl StoneProof {
+    /// Reads the parameters and the bytes of the proof from the JSON document output by the
+    /// Stone prover. Other sections, such as the annotations, are ignored.
+    pub fn from_json(json: &str) -> Result<Self, StoneProofError> {
+        let proof: StoneProofJson = serde_json::from_str(json)
+            .map_err(|error| StoneProofError::InvalidJson(error.to_string()))?;
+        Ok(Self {
+            parameters: proof.proof_parameters,
+            proof_bytes: decode_hex(&proof.proof_hex)?,
+        })
+    }
+
+    /// Reconstructs the proof of a statement of the AIR `A` with public inputs `public_inputs`,
+    /// and returns it together with the options it was generated with.
+    pub fn parse<A>(
+        &self,
+        public_inputs: &A::PublicInputs,
+    ) -> Result<
+        (
+            StarkProof<Stark252PrimeField, Stark252PrimeField>,
+            ProofOptions,
+        ),
+        StoneProofError,
+    >
+    where
+        A: AIR<Field = Stark252PrimeField, FieldExtension = Stark252PrimeField>,
+        A::PublicInputs: AsBytes,
+    {
+        let options = self.parameters.proof_options()?;
+        let proof = StoneCompatibleDeserializer::deserialize_proof::<A>(
+            &self.proof_bytes,
+            self.parameters.trace_length()?,
+            public_inputs,
+            &options,
+        )?;
+        Ok((proof, options))
+    }
+}
+
+fn decode_hex(hex: &str) -> Result<Vec<u8>, StoneProofError> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return Err(StoneProofError::InvalidHex);
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| StoneProofError::InvalidHex))
+        .collect()
+}
+
+/// Reads the elements of a proof in the order they are written by `StoneCompatibleSerializer`.
+struct ProofReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ProofReader<'a> {
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], StoneProofError> {
+        if self.bytes.len() < len {
+            return Err(StoneProofError::UnexpectedEndOfProof);
+        }
+        let (read, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(read)
+    }
+
+    fn read_commitment(&mut self) -> Result<Commitment, StoneProofError> {
+        let mut commitment = [0; COMMITMENT_SIZE];
+        commitment.copy_from_slice(self.read_bytes(COMMITMENT_SIZE)?);
+        Ok(commitment)
+    }
+
+    fn read_commitments(&mut self, count: usize) -> Result<Vec<Commitment>, StoneProofError> {
+        (0..count).map(|_| self.read_commitment()).collect()
+    }
+
+    /// Reads a field element, which Stone writes in Montgomery form.
+    fn read_field_element(&mut self) -> Result<Felt252, StoneProofError> {
+        let value = U256::from_bytes_be(self.read_bytes(FIELD_ELEMENT_SIZE)?)
+            .map_err(|_| StoneProofError::UnexpectedEndOfProof)?;
+        if value >= MontgomeryConfigStark252PrimeField::MODULUS {
+            return Err(StoneProofError::NonCanonicalFieldElement);
+        }
+        Ok(FieldElement::from_raw(value))
+    }
+
+    fn read_field_elements(&mut self, count: usize) -> Result<Vec<Felt252>, StoneProofError> {
+        (0..count).map(|_| self.read_field_element()).collect()
+    }
+
+    fn read_u64(&mut self) -> Result<u64, StoneProofError> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.read_bytes(8)?);
+        Ok(u64::from_be_bytes(bytes))
+    }
+
+    /// Reads the merged authentication paths of the leaves at `leaf_indexes` of a Merkle tree of
+    /// height `tree_height`, and returns the authentication path of each of them. This is the
+    /// inverse of `StoneCompatibleSerializer::merge_authentication_paths`: the nodes that are not
+    /// sent are the ones computed from the leaves, which are given in `leaves`.
+    fn read_authentication_paths(
+        &mut self,
+        leaves: &HashMap<usize, Vec<Felt252>>,
+        leaf_indexes: &[usize],
+        tree_height: usize,
+    ) -> Result<Vec<Proof<Commitment>>, StoneProofError> {
+        type Backend = BatchedMerkleTreeBackend<Stark252PrimeField>;
+
+        let mut merkle_tree: HashMap<(usize, usize), Commitment> = leaves
+            .iter()
+            .map(|(index, leaf)| ((0, *index), Backend::hash_data(leaf)))
+            .collect();
+        let mut level_indexes: BTreeSet<usize> = leaf_indexes.iter().copied().collect();
+        for tree_level in 0..tree_height {
+            for node_index in level_indexes.iter() {
+                let sibling_index = node_index ^ 1;
+                if !level_indexes.contains(&sibling_index) {
+                    merkle_tree.insert((tree_level, sibling_index), self.read_commitment()?);
+                }
+            }
+            level_indexes = level_indexes.iter().map(|index| *index >> 1).collect();
+            for parent_index in level_indexes.iter() {
+                let parent = Backend::hash_new_parent(
+                    &merkle_tree[&(tree_level, parent_index * 2)],
+                    &merkle_tree[&(tree_level, parent_index * 2 + 1)],
+                );
+                merkle_tree.insert((tree_level + 1, *parent_index), parent);
+            }
+        }
+
+        Ok(leaf_indexes
+            .iter()
+            .map(|index| Proof {
+                merkle_path: (0..tree_height)
+                    .map(|tree_level| merkle_tree[&(tree_level, (index >> tree_level) ^ 1)])
+                    .collect(),
+            })
+            .collect())
+    }
+}
+
+/// Deserializer of proofs in the format of the Stone prover, the inverse of
+/// [`StoneCompatibleSerializer`](super::stark::StoneCompatibleSerializer).
+pub struct StoneCompatibleDeserializer;
+
+impl StoneCompatibleDeserializer {
+    /// Reconstructs the proof serialized in `bytes` for a trace of length `trace_length`.
+    /// As the proof only holds the elements the verifier can't compute, the transcript is
+    /// replayed to recover the FRI queries, and the values and Merkle nodes that are not sent
+    /// are computed from the other elements of the proof.
+    pub fn deserialize_proof<A>(
+        bytes: &[u8],
+        trace_length: usize,
+        public_inputs: &A::PublicInputs,
+        options: &ProofOptions,
+    ) -> Result<StarkProof<Stark252PrimeField, Stark252PrimeField>, StoneProofError>
+    where
+        A: AIR<Field = Stark252PrimeField, FieldExtension = Stark252PrimeField>,
+        A::PublicInputs: AsBytes,
+    {
+        let lde_size = (options.blowup_factor as usize)
+            .checked_mul(trace_length)
+            .filter(|lde_size| trace_length > 1 && lde_size.is_power_of_two())
+            .ok_or_else(|| {
+                StoneProofError::UnsupportedParameters(format!(
+                    "unsupported trace length {trace_length} and blowup factor {}",
+                    options.blowup_factor
+                ))
+            })?;
+        let lde_log_size = lde_size.trailing_zeros() as usize;
+
+        let air = A::new(trace_length, public_inputs, options);
+        if air.num_preprocessed_columns() > 0 {
+            return Err(StoneProofError::UnsupportedAir(
+                "preprocessed columns are not supported".to_string(),
+            ));
+        }
+        let domain = Domain::<Stark252PrimeField>::new(&air);
+        let mut reader = ProofReader { bytes };
+
+        // Commitments and out-of-domain evaluations
+        let lde_trace_main_merkle_root = reader.read_commitment()?;
+        let lde_trace_aux_merkle_root = if air.has_trace_interaction() {
+            Some(reader.read_commitment()?)
+        } else {
+            None
+        };
+        let composition_poly_root = reader.read_commitment()?;
+
+        let width = air.context().trace_columns;
+        let height = air.context().transition_offsets.len() * A::STEP_SIZE;
+        let mut trace_ood_evaluations = vec![Felt252::zero(); width * height];
+        for column in 0..width {
+            for row in 0..height {
+                trace_ood_evaluations[row * width + column] = reader.read_field_element()?;
+            }
+        }
+        let number_of_parts = air.composition_poly_degree_bound() / trace_length;
+        let composition_poly_parts_ood_evaluation = reader.read_field_elements(number_of_parts)?;
+
+        let number_of_fri_layers = (domain.root_order as usize).saturating_sub(1);
+        let fri_layers_merkle_roots = reader.read_commitments(number_of_fri_layers)?;
+        let fri_last_value = reader.read_field_element()?;
+        let nonce = if options.grinding_factor > 0 {
+            Some(reader.read_u64()?)
+        } else {
+            None
+        };
+
+        let mut proof = StarkProof {
+            trace_length,
+            lde_trace_main_merkle_root,
+            lde_trace_aux_merkle_root,
+            trace_ood_evaluations: Table::new(trace_ood_evaluations, width),
+            composition_poly_root,
+            composition_poly_parts_ood_evaluation,
+            fri_layers_merkle_roots,
+            fri_last_value,
+            query_list: Vec::new(),
+            deep_poly_openings: Vec::new(),
+            commit_phase_nonces: Vec::new(),
+            nonce,
+        };
+        let challenges = Verifier::<A>::step_1_replay_rounds_and_recover_challenges(
+            &air,
+            &proof,
+            &domain,
+            &mut StoneProverTranscript::new(&public_inputs.as_bytes()),
+        );
+        let iotas = &challenges.iotas;
+
+        proof.deep_poly_openings =
+            Self::read_fri_query_phase_first_layer(&air, &mut reader, iotas, lde_log_size)?;
+
+        // The values of the first inner layer at the queries are the folds of the DEEP
+        // composition polynomial at the opened points.
+        let (deep_poly_evaluations, deep_poly_evaluations_sym) =
+            Verifier::<A>::reconstruct_deep_composition_poly_evaluations_for_all_queries(
+                &challenges,
+                &domain,
+                &proof,
+            );
+        let mut evaluation_points_inverse: Vec<Felt252> = iotas
+            .iter()
+            .map(|iota| Verifier::<A>::query_challenge_to_evaluation_point(*iota, &domain))
+            .collect();
+        FieldElement::inplace_batch_inverse(&mut evaluation_points_inverse)
+            .expect("points of the LDE coset are not zero");
+        let values: Vec<Felt252> = deep_poly_evaluations
+            .iter()
+            .zip(&deep_poly_evaluations_sym)
+            .zip(&evaluation_points_inverse)
+            .map(|((p0_eval, p0_eval_sym), evaluation_point_inverse)| {
+                (p0_eval + p0_eval_sym)
+                    + evaluation_point_inverse * challenges.zetas[0] * (p0_eval - p0_eval_sym)
+            })
+            .collect();
+
+        proof.query_list = Self::read_fri_query_phase_inner_layers(
+            &mut reader,
+            iotas,
+            values,
+            evaluation_points_inverse,
+            &challenges.zetas,
+            number_of_fri_layers,
+            lde_log_size,
+        )?;
+
+        if !reader.bytes.is_empty() {
+            return Err(StoneProofError::TrailingBytes(reader.bytes.len()));
+        }
+        Ok(proof)
+    }
+
+    /// Reads the openings of the trace and the composition polynomial parts at each query, as
+    /// written by `StoneCompatibleSerializer::append_fri_query_phase_first_layer`.
+    fn read_fri_query_phase_first_layer<A>(
+        air: &A,
+        reader: &mut ProofReader,
+        fri_query_indexes: &[usize],
+        lde_log_size: usize,
+    ) -> Result<Vec<DeepPolynomialOpening<Stark252PrimeField, Stark252PrimeField>>, StoneProofError>
+    where
+        A: AIR<Field = Stark252PrimeField, FieldExtension = Stark252PrimeField>,
+    {
+        let (num_main_columns, num_aux_columns) = air.trace_layout();
+        let number_of_parts = air.composition_poly_degree_bound() / air.trace_length();
+        let unique_indexes: BTreeSet<usize> = fri_query_indexes.iter().copied().collect();
+
+        // BT_{i_1} | BT_{i_2} | ... | BT_{i_k}
+        let mut main_leaves = HashMap::new();
+        let mut aux_leaves = HashMap::new();
+        for index in unique_indexes.iter() {
+            main_leaves.insert(index * 2, reader.read_field_elements(num_main_columns)?);
+            aux_leaves.insert(index * 2, reader.read_field_elements(num_aux_columns)?);
+            main_leaves.insert(index * 2 + 1, reader.read_field_elements(num_main_columns)?);
+            aux_leaves.insert(index * 2 + 1, reader.read_field_elements(num_aux_columns)?);
+        }
+
+        // TraceMergedPaths
+        let fri_trace_query_indexes: Vec<_> = fri_query_indexes
+            .iter()
+            .flat_map(|query| vec![query * 2, query * 2 + 1])
+            .collect();
+        let main_paths = reader.read_authentication_paths(
+            &main_leaves,
+            &fri_trace_query_indexes,
+            lde_log_size,
+        )?;
+        let aux_paths = if air.has_trace_interaction() {
+            Some(reader.read_authentication_paths(
+                &aux_leaves,
+                &fri_trace_query_indexes,
+                lde_log_size,
+            )?)
+        } else {
+            None
+        };
+
+        // BH_{i_1} | BH_{i_2} | ... | BH_{i_k}
+        let mut composition_leaves = HashMap::new();
+        for index in unique_indexes.iter() {
+            composition_leaves.insert(*index, reader.read_field_elements(2 * number_of_parts)?);
+        }
+
+        // CompositionMergedPaths
+        let composition_paths = reader.read_authentication_paths(
+            &composition_leaves,
+            fri_query_indexes,
+            lde_log_size - 1,
+        )?;
+
+        let openings =
+            |leaves: &HashMap<usize, Vec<Felt252>>, paths: &[Proof<Commitment>], i: usize| {
+                let index = fri_query_indexes[i];
+                PolynomialOpenings {
+                    proof: paths[2 * i].clone(),
+                    proof_sym: paths[2 * i + 1].clone(),
+                    evaluations: leaves[&(index * 2)].clone(),
+                    evaluations_sym: leaves[&(index * 2 + 1)].clone(),
+                }
+            };
+        Ok(fri_query_indexes
+            .iter()
+            .enumerate()
+            .map(|(i, index)| {
+                let (evaluations, evaluations_sym) =
+                    composition_leaves[index].split_at(number_of_parts);
+                DeepPolynomialOpening {
+                    composition_poly: PolynomialOpenings {
+                        proof: composition_paths[i].clone(),
+                        proof_sym: composition_paths[i].clone(),
+                        evaluations: evaluations.to_vec(),
+                        evaluations_sym: evaluations_sym.to_vec(),
+                    },
+                    main_trace_polys: openings(&main_leaves, &main_paths, i),
+                    aux_trace_polys: aux_paths
+                        .as_ref()
+                        .map(|aux_paths| openings(&aux_leaves, aux_paths, i)),
+                    preprocessed_trace_polys: None,
+                }
+            })
+            .collect())
+    }
+
+    /// Reads the openings of the inner layers of FRI, as written by
+    /// `StoneCompatibleSerializer::append_fri_query_phase_inner_layers`. The values of each
+    /// layer at the queries, `values`, are not sent: they are folded from the values of the
+    /// previous layer as the verifier does, starting from the first inner layer.
+    fn read_fri_query_phase_inner_layers(
+        reader: &mut ProofReader,
+        fri_query_indexes: &[usize],
+        mut values: Vec<Felt252>,
+        mut evaluation_points_inverse: Vec<Felt252>,
+        zetas: &[Felt252],
+        number_of_fri_layers: usize,
+        lde_log_size: usize,
+    ) -> Result<Vec<FriDecommitment<Stark252PrimeField>>, StoneProofError> {
+        let mut query_list: Vec<_> = fri_query_indexes
+            .iter()
+            .map(|_| FriDecommitment {
+                layers_auth_paths: Vec::new(),
+                layers_evaluations_sym: Vec::new(),
+            })
+            .collect();
+
+        let mut indexes = fri_query_indexes.to_owned();
+        for i in 0..number_of_fri_layers {
+            // The elements of Y_i, computed from the previous layer
+            let mut layer: HashMap<(usize, usize), Felt252> = indexes
+                .iter()
+                .zip(&values)
+                .map(|(index, value)| ((index >> 1, index % 2), *value))
+                .collect();
+            let reconstructed_row_col: BTreeSet<_> = layer.keys().copied().collect();
+            let reconstructed_row_col_sym: BTreeSet<_> = reconstructed_row_col
+                .iter()
+                .map(|(row, col)| (*row, 1 - col))
+                .collect();
+
+            // Z_i
+            for row_col in reconstructed_row_col_sym.difference(&reconstructed_row_col) {
+                layer.insert(*row_col, reader.read_field_element()?);
+            }
+
+            // MergedPathsLayer_i
+            let leaves: HashMap<usize, Vec<Felt252>> = reconstructed_row_col
+                .iter()
+                .map(|(row, _)| (*row, vec![layer[&(*row, 0)], layer[&(*row, 1)]]))
+                .collect();
+            let rows: Vec<usize> = indexes.iter().map(|index| index >> 1).collect();
+            let paths = reader.read_authentication_paths(&leaves, &rows, lde_log_size - i - 2)?;
+
+            for ((((decommitment, index), value), evaluation_point_inverse), path) in query_list
+                .iter_mut()
+                .zip(indexes.iter_mut())
+                .zip(values.iter_mut())
+                .zip(evaluation_points_inverse.iter_mut())
+                .zip(paths)
+            {
+                let evaluation_sym = layer[&(*index >> 1, (*index + 1) % 2)];
+                *evaluation_point_inverse = evaluation_point_inverse.square();
+                *value = (*value + evaluation_sym)
+                    + *evaluation_point_inverse * zetas[i + 1] * (*value - evaluation_sym);
+                *index >>= 1;
+                decommitment.layers_evaluations_sym.push(evaluation_sym);
+                decommitment.layers_auth_paths.push(path);
+            }
+        }
+        Ok(query_list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parameters_json(fri_step_list: &str, last_layer_degree_bound: u64) -> String {
+        format!(
+            r#"{{
+                "channel_hash": "keccak256",
+                "commitment_hash": "keccak256",
+                "stark": {{
+                    "fri": {{
+                        "fri_step_list": {fri_step_list},
+                        "last_layer_degree_bound": {last_layer_degree_bound},
+                        "n_queries": 18,
+                        "proof_of_work_bits": 24
+                    }},
+                    "log_n_cosets": 4
+                }},
+                "use_extension_field": false
+            }}"#
+        )
+    }
+
+    #[test]
+    fn proof_parameters_are_mapped_to_proof_options() {
+        let parameters: StoneProofParameters =
+            serde_json::from_str(&parameters_json("[0, 1, 1, 1]", 1)).unwrap();
+
+        let options = parameters.proof_options().unwrap();
+        assert_eq!(options.blowup_factor, 16);
+        assert_eq!(options.fri_number_of_queries, 18);
+        assert_eq!(options.coset_offset, 3);
+        assert_eq!(options.grinding_factor, 24);
+        assert_eq!(parameters.trace_length().unwrap(), 8);
+    }
+
+    #[test]
+    fn fri_steps_folding_more_than_once_are_not_supported() {
+        let parameters: StoneProofParameters =
+            serde_json::from_str(&parameters_json("[0, 4, 4, 3]", 64)).unwrap();
+
+        assert!(matches!(
+            parameters.proof_options(),
+            Err(StoneProofError::UnsupportedParameters(_))
+        ));
+    }
+
+    #[test]
+    fn last_layer_polynomials_are_not_supported() {
+        let parameters: StoneProofParameters =
+            serde_json::from_str(&parameters_json("[0, 1, 1, 1]", 4)).unwrap();
+
+        assert!(matches!(
+            parameters.trace_length(),
+            Err(StoneProofError::UnsupportedParameters(_))
+        ));
+    }
+
+    #[test]
+    fn proof_hex_must_be_hexadecimal() {
+        let json = format!(
+            r#"{{"proof_hex": "0x0g", "proof_parameters": {}}}"#,
+            parameters_json("[0, 1]", 1)
+        );
+
+        assert_eq!(
+            StoneProof::from_json(&json).err(),
+            Some(StoneProofError::InvalidHex)
+        );
+    }
+}
@@ -1,8 +1,16 @@
-use std::marker::PhantomData;
+use alloc::{
+    borrow::{Cow, ToOwned},
+    string::String,
+    vec::Vec,
+};
+use core::marker::PhantomData;
 #[cfg(feature = "instruments")]
 use std::time::Instant;
 
 use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_crypto::merkle_tree::{
+    merkle::MerkleTree, proof::Proof, traits::IsMerkleTreeBackend,
+};
 use lambdaworks_math::fft::cpu::bit_reversing::{in_place_bit_reverse_permute, reverse_index};
 use lambdaworks_math::fft::errors::FFTError;
 
@@ -20,81 +28,164 @@ use rayon::prelude::{IndexedParallelIterator, IntoParallelRefIterator, ParallelI
 #[cfg(debug_assertions)]
 use crate::debug::validate_trace;
 use crate::fri;
+use crate::preprocessed::PreprocessedTrace;
 use crate::proof::stark::{DeepPolynomialOpenings, PolynomialOpenings};
 use crate::table::Table;
 use crate::trace::{columns2rows, LDETraceTable};
+use crate::transcript::bind_statement;
 
-use super::config::{BatchedMerkleTree, Commitment};
+use super::config::{BatchedMerkleTreeBackend, Commitment, IsCommitmentHash, Keccak256Commitments};
 use super::constraints::evaluator::ConstraintEvaluator;
 use super::domain::Domain;
+use super::fri::fri_commitment::FriLayer;
 use super::fri::fri_decommit::FriDecommitment;
+use super::fri::FriCommitError;
 use super::grinding;
 use super::proof::options::ProofOptions;
 use super::proof::stark::{DeepPolynomialOpening, StarkProof};
 use super::trace::TraceTable;
 use super::traits::AIR;
 
-/// A default STARK prover implementing `IsStarkProver`.
-pub struct Prover<A: AIR> {
-    phantom: PhantomData<A>,
+/// A default STARK prover implementing `IsStarkProver`, committing with Keccak256.
+pub type Prover<A> = StarkProver<A, Keccak256Commitments>;
+
+/// A STARK prover implementing `IsStarkProver` whose Merkle trees use the hash `H`.
+pub struct StarkProver<A: AIR, H> {
+    phantom: PhantomData<(A, H)>,
 }
 
-impl<A: AIR> IsStarkProver<A> for Prover<A> {}
+impl<A, H> IsStarkProver<A, H> for StarkProver<A, H>
+where
+    A: AIR,
+    H: IsCommitmentHash<A::Field, A::FieldExtension>,
+{
+}
 
 #[derive(Debug)]
 pub enum ProvingError {
     WrongParameter(String),
     EmptyCommitment,
+    /// The length of the trace is zero or not a power of two.
+    TraceLengthNotPowerOfTwo(usize),
+    /// The length of the trace differs from the one of the AIR.
+    TraceLengthMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    /// The trace does not have the number of main and auxiliary columns declared by
+    /// `AIR::trace_layout`.
+    TraceColumnsMismatch {
+        expected: (usize, usize),
+        actual: (usize, usize),
+    },
+    /// The AIR is not consistent with itself, e.g. its context and its layout disagree.
+    InvalidAirLayout(String),
+    /// The proof options cannot be used with the field of the AIR and the trace length.
+    InvalidProofOptions(String),
+    /// An FFT needed to interpolate or evaluate a polynomial failed.
+    FFTError(FFTError),
+    /// No proof-of-work nonce satisfies the grinding factor.
+    NonceNotFound(u8),
+    /// A Merkle tree has no leaf at the position opened by a query.
+    MissingMerkleProof(usize),
+}
+
+impl From<FFTError> for ProvingError {
+    fn from(error: FFTError) -> Self {
+        Self::FFTError(error)
+    }
+}
+
+impl From<FriCommitError> for ProvingError {
+    fn from(error: FriCommitError) -> Self {
+        match error {
+            FriCommitError::FFTError(error) => Self::FFTError(error),
+            FriCommitError::NonceNotFound(grinding_factor) => Self::NonceNotFound(grinding_factor),
+            FriCommitError::EmptyCommitment => Self::EmptyCommitment,
+            FriCommitError::MissingMerkleProof(pos) => Self::MissingMerkleProof(pos),
+        }
+    }
+}
+
+/// Returns the authentication path of the leaf at `pos` of `tree`.
+pub(crate) fn merkle_proof<B>(
+    tree: &MerkleTree<B>,
+    pos: usize,
+) -> Result<Proof<Commitment>, ProvingError>
+where
+    B: IsMerkleTreeBackend<Node = Commitment>,
+{
+    tree.get_proof_by_pos(pos)
+        .ok_or(ProvingError::MissingMerkleProof(pos))
 }
 
 /// A container for the intermediate results of the commitments to a trace table, main or auxiliary in case of RAP,
 /// in the first round of the STARK Prove protocol.
-pub struct Round1CommitmentData<F>
+pub struct Round1CommitmentData<F, B = BatchedMerkleTreeBackend<F>>
 where
     F: IsField,
     FieldElement<F>: AsBytes + Send + Sync,
+    B: IsMerkleTreeBackend,
 {
     /// The result of the interpolation of the columns of the trace table.
     pub(crate) trace_polys: Vec<Polynomial<FieldElement<F>>>,
     /// The Merkle trees constructed to obtain the commitment of the entire trace table.
-    pub(crate) lde_trace_merkle_tree: BatchedMerkleTree<F>,
+    pub(crate) lde_trace_merkle_tree: MerkleTree<B>,
     /// The root of the Merkle tree in `lde_trace_merkle_tree`.
     pub(crate) lde_trace_merkle_root: Commitment,
 }
 
 /// A container for the results of the first round of the STARK Prove protocol.
-pub struct Round1<A>
+pub struct Round1<A, H = Keccak256Commitments>
 where
     A: AIR,
+    H: IsCommitmentHash<A::Field, A::FieldExtension>,
     FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
     FieldElement<A::Field>: AsBytes + Sync + Send,
 {
     /// The table of evaluations over the LDE of the main and auxiliary trace tables.
     pub(crate) lde_trace: LDETraceTable<A::Field, A::FieldExtension>,
     /// The intermediate results of the commitment to the main trace table.
-    pub(crate) main: Round1CommitmentData<A::Field>,
+    pub(crate) main: Round1CommitmentData<A::Field, H::MainBackend>,
     /// The intermediate results of the commitment to the auxiliary trace table in case of RAP.
-    pub(crate) aux: Option<Round1CommitmentData<A::FieldExtension>>,
+    pub(crate) aux: Option<Round1CommitmentData<A::FieldExtension, H::ExtensionBackend>>,
+    /// The preprocessed columns of the AIR, if any.
+    pub(crate) preprocessed: Option<PreprocessedTrace<A::Field, H::MainBackend>>,
     /// The challenges of the RAP round.
     pub(crate) rap_challenges: Vec<FieldElement<A::FieldExtension>>,
 }
 
-impl<A> Round1<A>
+impl<A, H> Round1<A, H>
 where
     A: AIR,
+    H: IsCommitmentHash<A::Field, A::FieldExtension>,
     FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
     FieldElement<A::Field>: AsBytes + Sync + Send,
 {
-    /// Returns the full list of the polynomials interpolating the trace. It includes both
-    /// main and auxiliary trace polynomials. The main trace polynomials are casted to
-    /// polynomials with coefficients over `Self::FieldExtension`.
+    /// Returns the polynomials interpolating the main trace followed by the ones interpolating
+    /// the preprocessed columns, in the order they appear in the evaluation frames.
+    fn main_and_preprocessed_trace_polys(&self) -> Cow<'_, [Polynomial<FieldElement<A::Field>>]> {
+        match &self.preprocessed {
+            None => Cow::Borrowed(&self.main.trace_polys),
+            Some(preprocessed) => Cow::Owned(
+                self.main
+                    .trace_polys
+                    .iter()
+                    .chain(&preprocessed.polys)
+                    .cloned()
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Returns the full list of the polynomials interpolating the trace. It includes the main,
+    /// preprocessed and auxiliary trace polynomials. The main and preprocessed trace polynomials
+    /// are casted to polynomials with coefficients over `Self::FieldExtension`.
     fn all_trace_polys(&self) -> Vec<Polynomial<FieldElement<A::FieldExtension>>> {
         let mut trace_polys: Vec<_> = self
-            .main
-            .trace_polys
-            .clone()
-            .into_iter()
-            .map(|poly| poly.to_extension())
+            .main_and_preprocessed_trace_polys()
+            .iter()
+            .map(|poly| poly.clone().to_extension())
             .collect();
 
         if let Some(aux) = &self.aux {
@@ -105,17 +196,18 @@ where
 }
 
 /// A container for the results of the second round of the STARK Prove protocol.
-pub struct Round2<F>
+pub struct Round2<F, B = BatchedMerkleTreeBackend<F>>
 where
     F: IsField,
     FieldElement<F>: AsBytes + Sync + Send,
+    B: IsMerkleTreeBackend,
 {
     /// The list of polynomials `H₀, ..., Hₙ` such that `H = ∑ᵢXⁱH(Xⁿ)`, where H is the composition polynomial.
     pub(crate) composition_poly_parts: Vec<Polynomial<FieldElement<F>>>,
     /// Evaluations of the composition polynomial parts over the LDE domain.
     pub(crate) lde_composition_poly_evaluations: Vec<Vec<FieldElement<F>>>,
     /// The Merkle tree built to compute the commitment to the composition polynomial parts.
-    pub(crate) composition_poly_merkle_tree: BatchedMerkleTree<F>,
+    pub(crate) composition_poly_merkle_tree: MerkleTree<B>,
     /// The commitment to the composition polynomial parts.
     pub(crate) composition_poly_root: Commitment,
 }
@@ -123,9 +215,9 @@ where
 /// A container for the results of the third round of the STARK Prove protocol.
 pub struct Round3<F: IsField> {
     /// Evaluations of the trace polynomials, main ans auxiliary, at the out-of-domain challenge.
-    trace_ood_evaluations: Table<F>,
+    pub(crate) trace_ood_evaluations: Table<F>,
     /// Evaluations of the composition polynomial parts at the out-of-domain challenge.
-    composition_poly_parts_ood_evaluation: Vec<FieldElement<F>>,
+    pub(crate) composition_poly_parts_ood_evaluation: Vec<FieldElement<F>>,
 }
 
 /// A container for the results of the fourth round of the STARK Prove protocol.
@@ -142,6 +234,8 @@ pub struct Round4<F: IsSubFieldOf<E>, E: IsField> {
     query_list: Vec<FriDecommitment<E>>,
     /// The proof of work nonce.
     nonce: Option<u64>,
+    /// The nonces of the proofs of work done before each FRI folding challenge.
+    commit_phase_nonces: Vec<u64>,
 }
 
 /// Returns the evaluations of the polynomial `p` over the lde domain defined by the given
@@ -169,15 +263,19 @@ where
 /// https://lambdaclass.github.io/lambdaworks/starks/protocol.html
 /// The default implementation is complete and is compatible with Stone prover
 /// https://github.com/starkware-libs/stone-prover
-pub trait IsStarkProver<A: AIR> {
+pub trait IsStarkProver<A, H = Keccak256Commitments>
+where
+    A: AIR,
+    H: IsCommitmentHash<A::Field, A::FieldExtension>,
+{
     /// Returns the Merkle tree and the commitment to the vectors `vectors`.
     fn batch_commit_main(
         vectors: &[Vec<FieldElement<A::Field>>],
-    ) -> Option<(BatchedMerkleTree<A::Field>, Commitment)>
+    ) -> Option<(MerkleTree<H::MainBackend>, Commitment)>
     where
         FieldElement<A::Field>: AsBytes + Sync + Send,
     {
-        let tree = BatchedMerkleTree::build(vectors)?;
+        let tree = MerkleTree::build(vectors)?;
 
         let commitment = tree.root;
         Some((tree, commitment))
@@ -186,12 +284,12 @@ pub trait IsStarkProver<A: AIR> {
     /// Returns the Merkle tree and the commitment to the vectors `vectors`.
     fn batch_commit_extension(
         vectors: &[Vec<FieldElement<A::FieldExtension>>],
-    ) -> Option<(BatchedMerkleTree<A::FieldExtension>, Commitment)>
+    ) -> Option<(MerkleTree<H::ExtensionBackend>, Commitment)>
     where
         FieldElement<A::Field>: AsBytes + Sync + Send,
         FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
     {
-        let tree = BatchedMerkleTree::build(vectors)?;
+        let tree = MerkleTree::build(vectors)?;
 
         let commitment = tree.root;
         Some((tree, commitment))
@@ -209,12 +307,15 @@ pub trait IsStarkProver<A: AIR> {
         trace: &TraceTable<A::Field, A::FieldExtension>,
         domain: &Domain<A::Field>,
         transcript: &mut impl IsTranscript<A::FieldExtension>,
-    ) -> Option<(
-        Vec<Polynomial<FieldElement<A::Field>>>,
-        Vec<Vec<FieldElement<A::Field>>>,
-        BatchedMerkleTree<A::Field>,
-        Commitment,
-    )>
+    ) -> Result<
+        (
+            Vec<Polynomial<FieldElement<A::Field>>>,
+            Vec<Vec<FieldElement<A::Field>>>,
+            MerkleTree<H::MainBackend>,
+            Commitment,
+        ),
+        ProvingError,
+    >
     where
         FieldElement<A::Field>: AsBytes + Send + Sync,
         // FieldElement<E>: AsBytes + Send + Sync,
@@ -222,11 +323,11 @@ pub trait IsStarkProver<A: AIR> {
         A::Field: IsSubFieldOf<A::FieldExtension>,
     {
         // Interpolate columns of `trace`.
-        let trace_polys = trace.compute_trace_polys_main::<A::Field>();
+        let trace_polys = trace.compute_trace_polys_main::<A::Field>()?;
 
         // Evaluate those polynomials t_j on the large domain D_LDE.
         let lde_trace_evaluations =
-            Self::compute_lde_trace_evaluations::<A::Field>(&trace_polys, domain);
+            Self::compute_lde_trace_evaluations::<A::Field>(&trace_polys, domain)?;
 
         let mut lde_trace_permuted = lde_trace_evaluations.clone();
         for col in lde_trace_permuted.iter_mut() {
@@ -237,12 +338,13 @@ pub trait IsStarkProver<A: AIR> {
         let lde_trace_permuted_rows = columns2rows(lde_trace_permuted);
 
         let (lde_trace_merkle_tree, lde_trace_merkle_root) =
-            Self::batch_commit_main(&lde_trace_permuted_rows)?;
+            Self::batch_commit_main(&lde_trace_permuted_rows)
+                .ok_or(ProvingError::EmptyCommitment)?;
 
         // >>>> Send commitment.
         transcript.append_bytes(&lde_trace_merkle_root);
 
-        Some((
+        Ok((
             trace_polys,
             lde_trace_evaluations,
             lde_trace_merkle_tree,
@@ -262,22 +364,25 @@ pub trait IsStarkProver<A: AIR> {
         trace: &TraceTable<A::Field, A::FieldExtension>,
         domain: &Domain<A::Field>,
         transcript: &mut impl IsTranscript<A::FieldExtension>,
-    ) -> Option<(
-        Vec<Polynomial<FieldElement<A::FieldExtension>>>,
-        Vec<Vec<FieldElement<A::FieldExtension>>>,
-        BatchedMerkleTree<A::FieldExtension>,
-        Commitment,
-    )>
+    ) -> Result<
+        (
+            Vec<Polynomial<FieldElement<A::FieldExtension>>>,
+            Vec<Vec<FieldElement<A::FieldExtension>>>,
+            MerkleTree<H::ExtensionBackend>,
+            Commitment,
+        ),
+        ProvingError,
+    >
     where
         FieldElement<A::Field>: AsBytes + Send + Sync,
         FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
         A::Field: IsSubFieldOf<A::FieldExtension> + IsFFTField,
     {
         // Interpolate columns of `trace`.
-        let trace_polys = trace.compute_trace_polys_aux::<A::Field>();
+        let trace_polys = trace.compute_trace_polys_aux::<A::Field>()?;
 
         // Evaluate those polynomials t_j on the large domain D_LDE.
-        let lde_trace_evaluations = Self::compute_lde_trace_evaluations(&trace_polys, domain);
+        let lde_trace_evaluations = Self::compute_lde_trace_evaluations(&trace_polys, domain)?;
 
         let mut lde_trace_permuted = lde_trace_evaluations.clone();
         for col in lde_trace_permuted.iter_mut() {
@@ -288,12 +393,13 @@ pub trait IsStarkProver<A: AIR> {
         let lde_trace_permuted_rows = columns2rows(lde_trace_permuted);
 
         let (lde_trace_merkle_tree, lde_trace_merkle_root) =
-            Self::batch_commit_extension(&lde_trace_permuted_rows)?;
+            Self::batch_commit_extension(&lde_trace_permuted_rows)
+                .ok_or(ProvingError::EmptyCommitment)?;
 
         // >>>> Send commitment.
         transcript.append_bytes(&lde_trace_merkle_root);
 
-        Some((
+        Ok((
             trace_polys,
             lde_trace_evaluations,
             lde_trace_merkle_tree,
@@ -306,7 +412,7 @@ pub trait IsStarkProver<A: AIR> {
     fn compute_lde_trace_evaluations<E>(
         trace_polys: &[Polynomial<FieldElement<E>>],
         domain: &Domain<A::Field>,
-    ) -> Vec<Vec<FieldElement<E>>>
+    ) -> Result<Vec<Vec<FieldElement<E>>>, FFTError>
     where
         FieldElement<E>: Send + Sync,
         FieldElement<A::Field>: Send + Sync,
@@ -328,7 +434,6 @@ pub trait IsStarkProver<A: AIR> {
                 )
             })
             .collect::<Result<Vec<Vec<FieldElement<E>>>, FFTError>>()
-            .unwrap()
     }
 
     /// Returns the result of the first round of the STARK Prove protocol.
@@ -337,19 +442,23 @@ pub trait IsStarkProver<A: AIR> {
         trace: &mut TraceTable<A::Field, A::FieldExtension>,
         domain: &Domain<A::Field>,
         transcript: &mut impl IsTranscript<A::FieldExtension>,
-    ) -> Result<Round1<A>, ProvingError>
+    ) -> Result<Round1<A, H>, ProvingError>
     where
         FieldElement<A::Field>: AsBytes + Send + Sync,
         A::FieldExtension: IsFFTField,
         FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
     {
-        let Some((trace_polys, evaluations, main_merkle_tree, main_merkle_root)) =
-            Self::interpolate_and_commit_main(trace, domain, transcript)
-        else {
-            return Err(ProvingError::EmptyCommitment);
-        };
+        // The preprocessed columns are fixed by the AIR, so their commitment is part of the
+        // verifier key. It is appended to the transcript to bind it to the rest of the proof.
+        let preprocessed = PreprocessedTrace::<A::Field, H::MainBackend>::new(air, domain)?;
+        if let Some(preprocessed) = &preprocessed {
+            transcript.append_bytes(&preprocessed.commitment());
+        }
+
+        let (trace_polys, mut evaluations, main_merkle_tree, main_merkle_root) =
+            Self::interpolate_and_commit_main(trace, domain, transcript)?;
 
-        let main = Round1CommitmentData::<A::Field> {
+        let main = Round1CommitmentData {
             trace_polys,
             lde_trace_merkle_tree: main_merkle_tree,
             lde_trace_merkle_root: main_merkle_root,
@@ -358,17 +467,17 @@ pub trait IsStarkProver<A: AIR> {
         let rap_challenges = air.build_rap_challenges(transcript);
         let (aux, aux_evaluations) = if air.has_trace_interaction() {
             air.build_auxiliary_trace(trace, &rap_challenges);
-            let Some((
-                aux_trace_polys,
-                aux_trace_polys_evaluations,
-                aux_merkle_tree,
-                aux_merkle_root,
-            )) = Self::interpolate_and_commit_aux(trace, domain, transcript)
-            else {
-                return Err(ProvingError::EmptyCommitment);
-            };
+            let (num_main_columns, num_aux_columns) = air.trace_layout();
+            if trace.num_aux_columns != num_aux_columns {
+                return Err(ProvingError::TraceColumnsMismatch {
+                    expected: (num_main_columns, num_aux_columns),
+                    actual: (trace.num_main_columns, trace.num_aux_columns),
+                });
+            }
+            let (aux_trace_polys, aux_trace_polys_evaluations, aux_merkle_tree, aux_merkle_root) =
+                Self::interpolate_and_commit_aux(trace, domain, transcript)?;
             let aux_evaluations = aux_trace_polys_evaluations;
-            let aux = Some(Round1CommitmentData::<A::FieldExtension> {
+            let aux = Some(Round1CommitmentData {
                 trace_polys: aux_trace_polys,
                 lde_trace_merkle_tree: aux_merkle_tree,
                 lde_trace_merkle_root: aux_merkle_root,
@@ -378,6 +487,11 @@ pub trait IsStarkProver<A: AIR> {
             (None, Vec::new())
         };
 
+        // The preprocessed columns are placed right after the main trace columns in the frames.
+        if let Some(preprocessed) = &preprocessed {
+            evaluations.extend_from_slice(&preprocessed.lde_evaluations);
+        }
+
         let lde_trace = LDETraceTable::from_columns(
             evaluations,
             aux_evaluations,
@@ -389,6 +503,7 @@ pub trait IsStarkProver<A: AIR> {
             lde_trace,
             main,
             aux,
+            preprocessed,
             rap_challenges,
         })
     }
@@ -397,7 +512,7 @@ pub trait IsStarkProver<A: AIR> {
     /// composition polynomial.
     fn commit_composition_polynomial(
         lde_composition_poly_parts_evaluations: &[Vec<FieldElement<A::FieldExtension>>],
-    ) -> Option<(BatchedMerkleTree<A::FieldExtension>, Commitment)>
+    ) -> Option<(MerkleTree<H::ExtensionBackend>, Commitment)>
     where
         FieldElement<A::Field>: AsBytes + Sync + Send,
         FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
@@ -428,10 +543,10 @@ pub trait IsStarkProver<A: AIR> {
     fn round_2_compute_composition_polynomial(
         air: &A,
         domain: &Domain<A::Field>,
-        round_1_result: &Round1<A>,
+        round_1_result: &Round1<A, H>,
         transition_coefficients: &[FieldElement<A::FieldExtension>],
         boundary_coefficients: &[FieldElement<A::FieldExtension>],
-    ) -> Result<Round2<A::FieldExtension>, ProvingError>
+    ) -> Result<Round2<A::FieldExtension, H::ExtensionBackend>, ProvingError>
     where
         A: Send + Sync,
         FieldElement<A::Field>: AsBytes + Send + Sync,
@@ -446,17 +561,16 @@ pub trait IsStarkProver<A: AIR> {
             transition_coefficients,
             boundary_coefficients,
             &round_1_result.rap_challenges,
-        );
+        )?;
 
         // Get coefficients of the composition poly H
         let composition_poly =
-            Polynomial::interpolate_offset_fft(&constraint_evaluations, &domain.coset_offset)
-                .unwrap();
+            Polynomial::interpolate_offset_fft(&constraint_evaluations, &domain.coset_offset)?;
 
         let number_of_parts = air.composition_poly_degree_bound() / air.trace_length();
         let composition_poly_parts = composition_poly.break_in_parts(number_of_parts);
 
-        let lde_composition_poly_parts_evaluations: Vec<_> = composition_poly_parts
+        let lde_composition_poly_parts_evaluations = composition_poly_parts
             .iter()
             .map(|part| {
                 evaluate_polynomial_on_lde_domain(
@@ -465,9 +579,8 @@ pub trait IsStarkProver<A: AIR> {
                     domain.interpolation_domain_size,
                     &domain.coset_offset,
                 )
-                .unwrap()
             })
-            .collect();
+            .collect::<Result<Vec<_>, FFTError>>()?;
 
         let Some((composition_poly_merkle_tree, composition_poly_root)) =
             Self::commit_composition_polynomial(&lde_composition_poly_parts_evaluations)
@@ -487,8 +600,8 @@ pub trait IsStarkProver<A: AIR> {
     fn round_3_evaluate_polynomials_in_out_of_domain_element(
         air: &A,
         domain: &Domain<A::Field>,
-        round_1_result: &Round1<A>,
-        round_2_result: &Round2<A::FieldExtension>,
+        round_1_result: &Round1<A, H>,
+        round_2_result: &Round2<A::FieldExtension, H::ExtensionBackend>,
         z: &FieldElement<A::FieldExtension>,
     ) -> Round3<A::FieldExtension>
     where
@@ -514,7 +627,7 @@ pub trait IsStarkProver<A: AIR> {
         // polynomial and `g` is the primitive root of unity used when interpolating `t`.
         let trace_ood_evaluations =
             crate::trace::get_trace_evaluations::<A::Field, A::FieldExtension>(
-                &round_1_result.main.trace_polys,
+                &round_1_result.main_and_preprocessed_trace_polys(),
                 round_1_result
                     .aux
                     .as_ref()
@@ -536,12 +649,70 @@ pub trait IsStarkProver<A: AIR> {
     fn round_4_compute_and_run_fri_on_the_deep_composition_polynomial(
         air: &A,
         domain: &Domain<A::Field>,
-        round_1_result: &Round1<A>,
-        round_2_result: &Round2<A::FieldExtension>,
+        round_1_result: &Round1<A, H>,
+        round_2_result: &Round2<A::FieldExtension, H::ExtensionBackend>,
+        round_3_result: &Round3<A::FieldExtension>,
+        z: &FieldElement<A::FieldExtension>,
+        transcript: &mut impl IsTranscript<A::FieldExtension>,
+    ) -> Result<Round4<A::Field, A::FieldExtension>, ProvingError>
+    where
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+    {
+        let (fri_last_value, fri_layers, commit_phase_nonces, nonce, iotas) =
+            Self::run_fri_on_the_deep_composition_polynomial(
+                air,
+                domain,
+                &round_1_result.all_trace_polys(),
+                &round_2_result.composition_poly_parts,
+                round_3_result,
+                z,
+                transcript,
+            )?;
+
+        let query_list = fri::query_phase(&fri_layers, &iotas)?;
+
+        let fri_layers_merkle_roots: Vec<_> = fri_layers
+            .iter()
+            .map(|layer| layer.merkle_tree.root)
+            .collect();
+
+        let deep_poly_openings =
+            Self::open_deep_composition_poly(domain, round_1_result, round_2_result, &iotas)?;
+
+        Ok(Round4 {
+            fri_last_value,
+            fri_layers_merkle_roots,
+            deep_poly_openings,
+            query_list,
+            nonce,
+            commit_phase_nonces,
+        })
+    }
+
+    /// Computes the DEEP composition polynomial of `trace_polys` and `composition_poly_parts`,
+    /// runs the FRI commit phase on it and the grinding, and samples the query indexes.
+    /// Returns the last value of FRI, its layers, the commit phase nonces, the grinding nonce and
+    /// the query indexes.
+    #[allow(clippy::type_complexity, clippy::too_many_arguments)]
+    fn run_fri_on_the_deep_composition_polynomial(
+        air: &A,
+        domain: &Domain<A::Field>,
+        trace_polys: &[Polynomial<FieldElement<A::FieldExtension>>],
+        composition_poly_parts: &[Polynomial<FieldElement<A::FieldExtension>>],
         round_3_result: &Round3<A::FieldExtension>,
         z: &FieldElement<A::FieldExtension>,
         transcript: &mut impl IsTranscript<A::FieldExtension>,
-    ) -> Round4<A::Field, A::FieldExtension>
+    ) -> Result<
+        (
+            FieldElement<A::FieldExtension>,
+            Vec<FriLayer<A::FieldExtension, H::ExtensionBackend>>,
+            Vec<u64>,
+            Option<u64>,
+            Vec<usize>,
+        ),
+        ProvingError,
+    >
     where
         FieldElement<A::Field>: AsBytes + Send + Sync,
         FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
@@ -551,7 +722,7 @@ pub trait IsStarkProver<A: AIR> {
 
         let gamma = transcript.sample_field_element();
 
-        let n_terms_composition_poly = round_2_result.lde_composition_poly_evaluations.len();
+        let n_terms_composition_poly = composition_poly_parts.len();
         let num_terms_trace =
             air.context().transition_offsets.len() * A::STEP_SIZE * air.context().trace_columns;
 
@@ -573,8 +744,8 @@ pub trait IsStarkProver<A: AIR> {
 
         // Compute p₀ (deep composition polynomial)
         let deep_composition_poly = Self::compute_deep_composition_poly(
-            &round_1_result.all_trace_polys(),
-            round_2_result,
+            trace_polys,
+            composition_poly_parts,
             round_3_result,
             z,
             &domain.trace_primitive_root,
@@ -585,20 +756,23 @@ pub trait IsStarkProver<A: AIR> {
         let domain_size = domain.lde_roots_of_unity_coset.len();
 
         // FRI commit and query phases
-        let (fri_last_value, fri_layers) = fri::commit_phase::<A::Field, A::FieldExtension>(
-            domain.root_order as usize,
-            deep_composition_poly,
-            transcript,
-            &coset_offset,
-            domain_size,
-        );
+        let (fri_last_value, fri_layers, commit_phase_nonces) =
+            fri::commit_phase::<A::Field, A::FieldExtension, H::ExtensionBackend, H::GrindingHash>(
+                domain.root_order as usize,
+                deep_composition_poly,
+                transcript,
+                &coset_offset,
+                domain_size,
+                air.context().proof_options.commit_phase_grinding_factor,
+            )?;
 
         // grinding: generate nonce and append it to the transcript
         let security_bits = air.context().proof_options.grinding_factor;
         let mut nonce = None;
         if security_bits > 0 {
-            let nonce_value = grinding::generate_nonce(&transcript.state(), security_bits)
-                .expect("nonce not found");
+            let nonce_value =
+                grinding::generate_nonce::<H::GrindingHash>(&transcript.state(), security_bits)
+                    .ok_or(ProvingError::NonceNotFound(security_bits))?;
             transcript.append_bytes(&nonce_value.to_be_bytes());
             nonce = Some(nonce_value);
         }
@@ -606,23 +780,13 @@ pub trait IsStarkProver<A: AIR> {
         let number_of_queries = air.options().fri_number_of_queries;
         let iotas = Self::sample_query_indexes(number_of_queries, domain, transcript);
 
-        let query_list = fri::query_phase(&fri_layers, &iotas);
-
-        let fri_layers_merkle_roots: Vec<_> = fri_layers
-            .iter()
-            .map(|layer| layer.merkle_tree.root)
-            .collect();
-
-        let deep_poly_openings =
-            Self::open_deep_composition_poly(domain, round_1_result, round_2_result, &iotas);
-
-        Round4 {
+        Ok((
             fri_last_value,
-            fri_layers_merkle_roots,
-            deep_poly_openings,
-            query_list,
+            fri_layers,
+            commit_phase_nonces,
             nonce,
-        }
+            iotas,
+        ))
     }
 
     fn sample_query_indexes(
@@ -642,7 +806,7 @@ pub trait IsStarkProver<A: AIR> {
     #[allow(clippy::too_many_arguments)]
     fn compute_deep_composition_poly(
         trace_polys: &[Polynomial<FieldElement<A::FieldExtension>>],
-        round_2_result: &Round2<A::FieldExtension>,
+        composition_poly_parts: &[Polynomial<FieldElement<A::FieldExtension>>],
         round_3_result: &Round3<A::FieldExtension>,
         z: &FieldElement<A::FieldExtension>,
         primitive_root: &FieldElement<A::Field>,
@@ -653,11 +817,11 @@ pub trait IsStarkProver<A: AIR> {
         FieldElement<A::Field>: AsBytes + Send + Sync,
         FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
     {
-        let z_power = z.pow(round_2_result.composition_poly_parts.len());
+        let z_power = z.pow(composition_poly_parts.len());
 
         // ∑ᵢ 𝛾ᵢ ( Hᵢ − Hᵢ(z^N) ) / ( X − z^N )
         let mut h_terms = Polynomial::zero();
-        for (i, part) in round_2_result.composition_poly_parts.iter().enumerate() {
+        for (i, part) in composition_poly_parts.iter().enumerate() {
             // h_i_eval is the evaluation of the i-th part of the composition polynomial at z^N,
             // where N is the number of parts of the composition polynomial.
             let h_i_eval = &round_3_result.composition_poly_parts_ood_evaluation[i];
@@ -750,17 +914,15 @@ pub trait IsStarkProver<A: AIR> {
     /// at the domain value corresponding to the FRI query challenge `index` and its symmetric
     /// element.
     fn open_composition_poly(
-        composition_poly_merkle_tree: &BatchedMerkleTree<A::FieldExtension>,
+        composition_poly_merkle_tree: &MerkleTree<H::ExtensionBackend>,
         lde_composition_poly_evaluations: &[Vec<FieldElement<A::FieldExtension>>],
         index: usize,
-    ) -> PolynomialOpenings<A::FieldExtension>
+    ) -> Result<PolynomialOpenings<A::FieldExtension>, ProvingError>
     where
         FieldElement<A::Field>: AsBytes + Sync + Send,
         FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
     {
-        let proof = composition_poly_merkle_tree
-            .get_proof_by_pos(index)
-            .unwrap();
+        let proof = merkle_proof(composition_poly_merkle_tree, index)?;
 
         let lde_composition_poly_parts_evaluation: Vec<_> = lde_composition_poly_evaluations
             .iter()
@@ -772,7 +934,7 @@ pub trait IsStarkProver<A: AIR> {
             })
             .collect();
 
-        PolynomialOpenings {
+        Ok(PolynomialOpenings {
             proof: proof.clone(),
             proof_sym: proof,
             evaluations: lde_composition_poly_parts_evaluation
@@ -785,47 +947,48 @@ pub trait IsStarkProver<A: AIR> {
                 .skip(1)
                 .step_by(2)
                 .collect(),
-        }
+        })
     }
 
     /// Computes values and validity proofs of the evaluations of the trace polynomials
     /// at the domain value corresponding to the FRI query challenge `index` and its symmetric
     /// element.
-    fn open_trace_polys<E>(
+    fn open_trace_polys<E, B>(
         domain: &Domain<A::Field>,
-        tree: &BatchedMerkleTree<E>,
+        tree: &MerkleTree<B>,
         lde_trace: &Table<E>,
         challenge: usize,
-    ) -> PolynomialOpenings<E>
+    ) -> Result<PolynomialOpenings<E>, ProvingError>
     where
         FieldElement<A::Field>: AsBytes + Sync + Send,
         FieldElement<E>: AsBytes + Sync + Send,
         A::Field: IsSubFieldOf<E>,
         E: IsField,
+        B: IsMerkleTreeBackend<Node = Commitment>,
     {
         let domain_size = domain.lde_roots_of_unity_coset.len();
 
         let index = challenge * 2;
         let index_sym = challenge * 2 + 1;
-        PolynomialOpenings {
-            proof: tree.get_proof_by_pos(index).unwrap(),
-            proof_sym: tree.get_proof_by_pos(index_sym).unwrap(),
+        Ok(PolynomialOpenings {
+            proof: merkle_proof(tree, index)?,
+            proof_sym: merkle_proof(tree, index_sym)?,
             evaluations: lde_trace
                 .get_row(reverse_index(index, domain_size as u64))
                 .to_vec(),
             evaluations_sym: lde_trace
                 .get_row(reverse_index(index_sym, domain_size as u64))
                 .to_vec(),
-        }
+        })
     }
 
     /// Open the deep composition polynomial on a list of indexes and their symmetric elements.
     fn open_deep_composition_poly(
         domain: &Domain<A::Field>,
-        round_1_result: &Round1<A>,
-        round_2_result: &Round2<A::FieldExtension>,
+        round_1_result: &Round1<A, H>,
+        round_2_result: &Round2<A::FieldExtension, H::ExtensionBackend>,
         indexes_to_open: &[usize],
-    ) -> DeepPolynomialOpenings<A::Field, A::FieldExtension>
+    ) -> Result<DeepPolynomialOpenings<A::Field, A::FieldExtension>, ProvingError>
     where
         FieldElement<A::Field>: AsBytes + Send + Sync,
         FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
@@ -833,41 +996,171 @@ pub trait IsStarkProver<A: AIR> {
         let mut openings = Vec::new();
 
         for index in indexes_to_open.iter() {
-            let main_trace_opening = Self::open_trace_polys::<A::Field>(
+            let mut main_trace_opening = Self::open_trace_polys::<A::Field, _>(
                 domain,
                 &round_1_result.main.lde_trace_merkle_tree,
                 &round_1_result.lde_trace.main_table,
                 *index,
-            );
+            )?;
+
+            // The main table of the LDE holds the preprocessed columns after the main ones, but
+            // they are committed separately.
+            let preprocessed_trace_polys = round_1_result
+                .preprocessed
+                .as_ref()
+                .map(|preprocessed| -> Result<_, ProvingError> {
+                    let num_main_columns =
+                        main_trace_opening.evaluations.len() - preprocessed.num_columns();
+                    Ok(PolynomialOpenings {
+                        proof: merkle_proof(&preprocessed.merkle_tree, index * 2)?,
+                        proof_sym: merkle_proof(&preprocessed.merkle_tree, index * 2 + 1)?,
+                        evaluations: main_trace_opening.evaluations.split_off(num_main_columns),
+                        evaluations_sym: main_trace_opening
+                            .evaluations_sym
+                            .split_off(num_main_columns),
+                    })
+                })
+                .transpose()?;
 
             let composition_openings = Self::open_composition_poly(
                 &round_2_result.composition_poly_merkle_tree,
                 &round_2_result.lde_composition_poly_evaluations,
                 *index,
-            );
+            )?;
 
-            let aux_trace_polys = round_1_result.aux.as_ref().map(|aux| {
-                Self::open_trace_polys::<A::FieldExtension>(
-                    domain,
-                    &aux.lde_trace_merkle_tree,
-                    &round_1_result.lde_trace.aux_table,
-                    *index,
-                )
-            });
+            let aux_trace_polys = round_1_result
+                .aux
+                .as_ref()
+                .map(|aux| {
+                    Self::open_trace_polys::<A::FieldExtension, _>(
+                        domain,
+                        &aux.lde_trace_merkle_tree,
+                        &round_1_result.lde_trace.aux_table,
+                        *index,
+                    )
+                })
+                .transpose()?;
 
             openings.push(DeepPolynomialOpening {
                 composition_poly: composition_openings,
                 main_trace_polys: main_trace_opening,
                 aux_trace_polys,
+                preprocessed_trace_polys,
+            });
+        }
+
+        Ok(openings)
+    }
+
+    /// Checks that `proof_options` can be used to prove a trace of length `trace_length` over
+    /// `A::Field`. This must hold before building the AIR and its domain.
+    fn validate_proof_options(
+        trace_length: usize,
+        proof_options: &ProofOptions,
+    ) -> Result<(), ProvingError> {
+        if !trace_length.is_power_of_two() {
+            return Err(ProvingError::TraceLengthNotPowerOfTwo(trace_length));
+        }
+
+        let blowup_factor = proof_options.blowup_factor as usize;
+        if !blowup_factor.is_power_of_two() {
+            return Err(ProvingError::InvalidProofOptions(format!(
+                "blowup factor {blowup_factor} is not a power of two"
+            )));
+        }
+
+        let lde_size = trace_length
+            .checked_mul(blowup_factor)
+            .filter(|lde_size| u64::from(lde_size.trailing_zeros()) <= A::Field::TWO_ADICITY)
+            .ok_or_else(|| {
+                ProvingError::InvalidProofOptions(format!(
+                    "an LDE domain of size {trace_length} * {blowup_factor} exceeds the two-adicity of the field"
+                ))
+            })?;
+
+        // The LDE coset must be disjoint from the trace domain, otherwise the zerofiers vanish on it.
+        let coset_offset = FieldElement::<A::Field>::from(proof_options.coset_offset);
+        if coset_offset == FieldElement::zero() || coset_offset.pow(lde_size) == FieldElement::one()
+        {
+            return Err(ProvingError::InvalidProofOptions(format!(
+                "coset offset {} belongs to the LDE domain",
+                proof_options.coset_offset
+            )));
+        }
+
+        if proof_options.grinding_factor >= 64 {
+            return Err(ProvingError::InvalidProofOptions(format!(
+                "grinding factor {} must be smaller than 64",
+                proof_options.grinding_factor
+            )));
+        }
+        if proof_options.commit_phase_grinding_factor >= 64 {
+            return Err(ProvingError::InvalidProofOptions(format!(
+                "commit phase grinding factor {} must be smaller than 64",
+                proof_options.commit_phase_grinding_factor
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that `trace` has the shape declared by `air` and that the layout of `air` is
+    /// consistent with itself and with its proof options.
+    fn validate_trace_and_air(
+        air: &A,
+        trace: &TraceTable<A::Field, A::FieldExtension>,
+    ) -> Result<(), ProvingError> {
+        let trace_length = trace.num_rows();
+        if air.trace_length() != trace_length {
+            return Err(ProvingError::TraceLengthMismatch {
+                expected: air.trace_length(),
+                actual: trace_length,
+            });
+        }
+        if trace_length % A::STEP_SIZE != 0 {
+            return Err(ProvingError::InvalidAirLayout(format!(
+                "trace length {trace_length} is not a multiple of the step size {}",
+                A::STEP_SIZE
+            )));
+        }
+
+        let (num_main_columns, num_aux_columns) = air.trace_layout();
+        if trace.num_main_columns != num_main_columns {
+            return Err(ProvingError::TraceColumnsMismatch {
+                expected: (num_main_columns, num_aux_columns),
+                actual: (trace.num_main_columns, trace.num_aux_columns),
             });
         }
 
-        openings
+        // The preprocessed columns themselves are checked when they are committed to.
+        let expected_trace_columns =
+            num_main_columns + air.num_preprocessed_columns() + num_aux_columns;
+        if air.context().trace_columns != expected_trace_columns {
+            return Err(ProvingError::InvalidAirLayout(format!(
+                "the context declares {} trace columns but the layout has {expected_trace_columns}",
+                air.context().trace_columns
+            )));
+        }
+
+        let degree_bound = air.composition_poly_degree_bound();
+        if degree_bound < trace_length || degree_bound % trace_length != 0 {
+            return Err(ProvingError::InvalidAirLayout(format!(
+                "composition polynomial degree bound {degree_bound} is not a multiple of the trace length {trace_length}"
+            )));
+        }
+        if degree_bound > trace_length * air.blowup_factor() as usize {
+            return Err(ProvingError::InvalidProofOptions(format!(
+                "blowup factor {} is too small for a composition polynomial degree bound of {degree_bound}",
+                air.blowup_factor()
+            )));
+        }
+
+        Ok(())
     }
 
-    // FIXME remove unwrap() calls and return errors
     /// Generates a STARK proof for the trace `main_trace` with public inputs `pub_inputs`.
     /// Warning: the transcript must be safely initializated before passing it to this method.
+    /// Use `prove_statement` to have it initialized from the statement being proven.
     fn prove(
         trace: &mut TraceTable<A::Field, A::FieldExtension>,
         pub_inputs: &A::PublicInputs,
@@ -886,7 +1179,9 @@ pub trait IsStarkProver<A: AIR> {
         #[cfg(feature = "instruments")]
         let timer0 = Instant::now();
 
+        Self::validate_proof_options(trace.num_rows(), proof_options)?;
         let air = A::new(trace.num_rows(), pub_inputs, proof_options);
+        Self::validate_trace_and_air(&air, trace)?;
         let domain = Domain::new(&air);
 
         #[cfg(feature = "instruments")]
@@ -909,7 +1204,7 @@ pub trait IsStarkProver<A: AIR> {
         #[cfg(debug_assertions)]
         validate_trace(
             &air,
-            &round_1_result.main.trace_polys,
+            &round_1_result.main_and_preprocessed_trace_polys(),
             round_1_result
                 .aux
                 .as_ref()
@@ -1028,7 +1323,7 @@ pub trait IsStarkProver<A: AIR> {
             &round_3_result,
             &z,
             &mut transcript,
-        );
+        )?;
 
         #[cfg(feature = "instruments")]
         let elapsed4 = timer4.elapsed();
@@ -1071,12 +1366,36 @@ pub trait IsStarkProver<A: AIR> {
             // Open(H₁(D_LDE, 𝜐₀), Open(H₂(D_LDE, 𝜐₀), Open(tⱼ(D_LDE), 𝜐₀)
             // Open(H₁(D_LDE, -𝜐ᵢ), Open(H₂(D_LDE, -𝜐ᵢ), Open(tⱼ(D_LDE), -𝜐ᵢ)
             deep_poly_openings: round_4_result.deep_poly_openings,
+            // nonces obtained from grinding before the folding challenges
+            commit_phase_nonces: round_4_result.commit_phase_nonces,
             // nonce obtained from grinding
             nonce: round_4_result.nonce,
 
             trace_length: air.trace_length(),
         })
     }
+
+    /// Generates a STARK proof for `trace` with public inputs `pub_inputs`, binding the transcript
+    /// to the statement being proven. Before running the protocol, the AIR identity, the proof
+    /// options, the trace length and the public inputs are absorbed into `transcript`, which
+    /// must be freshly created. Proofs generated this way must be checked with
+    /// [`IsStarkVerifier::verify_statement`](crate::verifier::IsStarkVerifier::verify_statement).
+    fn prove_statement(
+        trace: &mut TraceTable<A::Field, A::FieldExtension>,
+        pub_inputs: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        mut transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> Result<StarkProof<A::Field, A::FieldExtension>, ProvingError>
+    where
+        A: Send + Sync,
+        A::PublicInputs: AsBytes,
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        A::FieldExtension: IsFFTField,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+    {
+        bind_statement::<A>(&mut transcript, trace.num_rows(), pub_inputs, proof_options);
+        Self::prove(trace, pub_inputs, proof_options, transcript)
+    }
 }
 
 #[cfg(test)]
@@ -1095,7 +1414,11 @@ mod tests {
             fibonacci_2_cols_shifted::{self, Fibonacci2ColsShifted},
             simple_fibonacci::{self, FibonacciPublicInputs},
         },
-        proof::options::ProofOptions,
+        proof::{
+            options::ProofOptions,
+            stark::StoneCompatibleSerializer,
+            stone::{StoneCompatibleDeserializer, StoneProof, StoneProofError},
+        },
         transcript::StoneProverTranscript,
         verifier::{Challenges, IsStarkVerifier, Verifier},
         Felt252,
@@ -1127,6 +1450,7 @@ mod tests {
             fri_number_of_queries: 1,
             coset_offset,
             grinding_factor,
+            commit_phase_grinding_factor: 0,
         };
 
         let domain = Domain::new(&simple_fibonacci::FibonacciAIR::new(
@@ -1162,7 +1486,9 @@ mod tests {
 
         let trace_length = trace.num_rows();
 
-        let trace_polys = trace.compute_trace_polys_main::<Stark252PrimeField>();
+        let trace_polys = trace
+            .compute_trace_polys_main::<Stark252PrimeField>()
+            .unwrap();
         let coset_offset = Felt252::from(3);
         let blowup_factor: usize = 2;
         let domain_size = 8;
@@ -1205,13 +1531,12 @@ mod tests {
         }
     }
 
-    fn proof_parts_stone_compatibility_case_1() -> (
-        StarkProof<Stark252PrimeField, Stark252PrimeField>,
+    fn statement_stone_compatibility_case_1() -> (
+        TraceTable<Stark252PrimeField, Stark252PrimeField>,
         fibonacci_2_cols_shifted::PublicInputs<Stark252PrimeField>,
         ProofOptions,
-        [u8; 4],
     ) {
-        let mut trace = fibonacci_2_cols_shifted::compute_trace(FieldElement::one(), 4);
+        let trace = fibonacci_2_cols_shifted::compute_trace(FieldElement::one(), 4);
 
         let claimed_index = 3;
         let col = 0;
@@ -1226,7 +1551,16 @@ mod tests {
             claimed_value,
             claimed_index,
         };
+        (trace, pub_inputs, proof_options)
+    }
 
+    fn proof_parts_stone_compatibility_case_1() -> (
+        StarkProof<Stark252PrimeField, Stark252PrimeField>,
+        fibonacci_2_cols_shifted::PublicInputs<Stark252PrimeField>,
+        ProofOptions,
+        [u8; 4],
+    ) {
+        let (mut trace, pub_inputs, proof_options) = statement_stone_compatibility_case_1();
         let transcript_init_seed = [0xca, 0xfe, 0xca, 0xfe];
 
         let proof = Prover::<Fibonacci2ColsShifted<_>>::prove(
@@ -1266,7 +1600,8 @@ mod tests {
             &public_inputs,
             &options,
             StoneProverTranscript::new(&seed)
-        ));
+        )
+        .is_ok());
     }
 
     #[test]
@@ -1602,13 +1937,12 @@ mod tests {
         );
     }
 
-    fn proof_parts_stone_compatibility_case_2() -> (
-        StarkProof<Stark252PrimeField, Stark252PrimeField>,
+    fn statement_stone_compatibility_case_2() -> (
+        TraceTable<Stark252PrimeField, Stark252PrimeField>,
         fibonacci_2_cols_shifted::PublicInputs<Stark252PrimeField>,
         ProofOptions,
-        [u8; 4],
     ) {
-        let mut trace = fibonacci_2_cols_shifted::compute_trace(FieldElement::from(12345), 512);
+        let trace = fibonacci_2_cols_shifted::compute_trace(FieldElement::from(12345), 512);
 
         let claimed_index = 420;
         let col = 0;
@@ -1623,7 +1957,16 @@ mod tests {
             claimed_value,
             claimed_index,
         };
+        (trace, pub_inputs, proof_options)
+    }
 
+    fn proof_parts_stone_compatibility_case_2() -> (
+        StarkProof<Stark252PrimeField, Stark252PrimeField>,
+        fibonacci_2_cols_shifted::PublicInputs<Stark252PrimeField>,
+        ProofOptions,
+        [u8; 4],
+    ) {
+        let (mut trace, pub_inputs, proof_options) = statement_stone_compatibility_case_2();
         let transcript_init_seed = [0xfa, 0xfa, 0xfa, 0xee];
 
         let proof = Prover::<Fibonacci2ColsShifted<_>>::prove(
@@ -1693,4 +2036,136 @@ mod tests {
             decode_hex("f12f159b548ca2c571a270870d43e7ec2ead78b3e93b635738c31eb9bcda3dda").unwrap()
         );
     }
+
+    /// Proves the statement with the transcript seeded by the public inputs, as expected by
+    /// the Stone serializer, and returns the serialized proof and the proof parsed back from it.
+    fn stone_serialization_round_trip(
+        mut trace: TraceTable<Stark252PrimeField, Stark252PrimeField>,
+        pub_inputs: &fibonacci_2_cols_shifted::PublicInputs<Stark252PrimeField>,
+        proof_options: &ProofOptions,
+    ) -> (Vec<u8>, StarkProof<Stark252PrimeField, Stark252PrimeField>) {
+        let proof = Prover::<Fibonacci2ColsShifted<_>>::prove(
+            &mut trace,
+            pub_inputs,
+            proof_options,
+            StoneProverTranscript::new(&pub_inputs.as_bytes()),
+        )
+        .unwrap();
+        let bytes = StoneCompatibleSerializer::serialize_proof::<Fibonacci2ColsShifted<_>>(
+            &proof,
+            pub_inputs,
+            proof_options,
+        );
+        let parsed_proof =
+            StoneCompatibleDeserializer::deserialize_proof::<Fibonacci2ColsShifted<_>>(
+                &bytes,
+                proof.trace_length,
+                pub_inputs,
+                proof_options,
+            )
+            .unwrap();
+        (bytes, parsed_proof)
+    }
+
+    fn assert_stone_serialization_round_trip(
+        trace: TraceTable<Stark252PrimeField, Stark252PrimeField>,
+        pub_inputs: fibonacci_2_cols_shifted::PublicInputs<Stark252PrimeField>,
+        proof_options: ProofOptions,
+    ) {
+        let (bytes, parsed_proof) =
+            stone_serialization_round_trip(trace, &pub_inputs, &proof_options);
+
+        assert_eq!(
+            StoneCompatibleSerializer::serialize_proof::<Fibonacci2ColsShifted<_>>(
+                &parsed_proof,
+                &pub_inputs,
+                &proof_options,
+            ),
+            bytes
+        );
+        assert!(Verifier::<Fibonacci2ColsShifted<_>>::verify(
+            &parsed_proof,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&pub_inputs.as_bytes())
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn stone_compatibility_case_1_serialization_round_trip() {
+        let (trace, pub_inputs, proof_options) = statement_stone_compatibility_case_1();
+        assert_stone_serialization_round_trip(trace, pub_inputs, proof_options);
+    }
+
+    #[test]
+    fn stone_compatibility_case_2_serialization_round_trip() {
+        let (trace, pub_inputs, proof_options) = statement_stone_compatibility_case_2();
+        assert_stone_serialization_round_trip(trace, pub_inputs, proof_options);
+    }
+
+    #[test]
+    fn stone_compatibility_case_2_serialization_round_trip_with_many_queries_and_grinding() {
+        let (trace, pub_inputs, mut proof_options) = statement_stone_compatibility_case_2();
+        proof_options.fri_number_of_queries = 30;
+        proof_options.grinding_factor = 4;
+        assert_stone_serialization_round_trip(trace, pub_inputs, proof_options);
+    }
+
+    #[test]
+    fn stone_compatibility_case_1_proof_is_parsed_from_stone_json() {
+        let (trace, pub_inputs, proof_options) = statement_stone_compatibility_case_1();
+        let (bytes, _) = stone_serialization_round_trip(trace, &pub_inputs, &proof_options);
+        let proof_hex: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+        let json = format!(
+            r#"{{
+                "annotations": [],
+                "proof_hex": "0x{proof_hex}",
+                "proof_parameters": {{
+                    "channel_hash": "keccak256",
+                    "commitment_hash": "keccak256",
+                    "stark": {{
+                        "fri": {{
+                            "fri_step_list": [0, 1, 1],
+                            "last_layer_degree_bound": 1,
+                            "n_queries": 1,
+                            "proof_of_work_bits": 0
+                        }},
+                        "log_n_cosets": 2
+                    }},
+                    "use_extension_field": false
+                }}
+            }}"#
+        );
+
+        let (proof, options) = StoneProof::from_json(&json)
+            .unwrap()
+            .parse::<Fibonacci2ColsShifted<_>>(&pub_inputs)
+            .unwrap();
+
+        assert_eq!(proof.trace_length, 4);
+        assert_eq!(options.blowup_factor, proof_options.blowup_factor);
+        assert_eq!(options.coset_offset, proof_options.coset_offset);
+        assert!(Verifier::<Fibonacci2ColsShifted<_>>::verify(
+            &proof,
+            &pub_inputs,
+            &options,
+            StoneProverTranscript::new(&pub_inputs.as_bytes())
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn stone_compatibility_case_1_truncated_proof_is_rejected() {
+        let (trace, pub_inputs, proof_options) = statement_stone_compatibility_case_1();
+        let (bytes, _) = stone_serialization_round_trip(trace, &pub_inputs, &proof_options);
+
+        let result = StoneCompatibleDeserializer::deserialize_proof::<Fibonacci2ColsShifted<_>>(
+            &bytes[..bytes.len() - 1],
+            4,
+            &pub_inputs,
+            &proof_options,
+        );
+        assert!(matches!(result, Err(StoneProofError::UnexpectedEndOfProof)));
+    }
 }
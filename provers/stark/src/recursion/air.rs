@@ -0,0 +1,528 @@
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::traits::AsBytes;
+
+use crate::{
+    constraints::{
+        boundary::{BoundaryConstraint, BoundaryConstraints},
+        transition::TransitionConstraint,
+    },
+    context::AirContext,
+    proof::options::ProofOptions,
+    table::TableView,
+    trace::TraceTable,
+    traits::{TransitionEvaluationContext, AIR},
+    transcript::bind_statement,
+    Felt252, PrimeField,
+};
+
+use super::{
+    circuit::{mix, Circuit, Round, Row, NUM_WIRES},
+    transcript::PoseidonTranscript,
+    verifier_circuit::{build_verifier_circuit, ProofShape},
+    RecursiveAIR,
+};
+
+// Main columns: the wires of each row, the S-box of each wire plus its round constant, and the
+// memory cells of the row sorted by address.
+pub(crate) const WIRES: usize = 0;
+pub(crate) const SBOX: usize = WIRES + NUM_WIRES;
+pub(crate) const SORTED_ADDRS: usize = SBOX + NUM_WIRES;
+pub(crate) const SORTED_VALUES: usize = SORTED_ADDRS + NUM_WIRES;
+pub(crate) const NUM_MAIN_COLUMNS: usize = SORTED_VALUES + NUM_WIRES;
+
+// Preprocessed columns, placed after the main columns in the evaluation frames: the selectors
+// of the gate and the rounds, the round constants and the address of the variable of each wire.
+const Q_L: usize = NUM_MAIN_COLUMNS;
+const Q_R: usize = Q_L + 1;
+const Q_M: usize = Q_L + 2;
+const Q_O: usize = Q_L + 3;
+const Q_C: usize = Q_L + 4;
+const Q_FULL: usize = Q_L + 5;
+const Q_PARTIAL: usize = Q_L + 6;
+const ROUND_CONSTANTS: usize = Q_L + 7;
+const ADDRS: usize = ROUND_CONSTANTS + NUM_WIRES;
+const NUM_PREPROCESSED_COLUMNS: usize = ADDRS + NUM_WIRES - NUM_MAIN_COLUMNS;
+
+// Auxiliary columns: the cumulative product of the memory argument after each cell.
+pub(crate) const PERMUTATION: usize = 0;
+const NUM_AUX_COLUMNS: usize = NUM_WIRES;
+
+#[derive(Clone, Copy, Debug)]
+enum ConstraintKind {
+    /// `q_l * a + q_r * b + q_m * a * b + q_o * c + q_c = 0`.
+    Gate,
+    /// The S-box column `k` holds the cube of the wire `k` plus its round constant.
+    SBox(usize),
+    FullRound(usize),
+    PartialRound(usize),
+    /// Sorted addresses increase by at most one, between the slot `k - 1` and `k` of a row, or
+    /// between the last slot of a row and the first one of the next row for `k = 0`.
+    MemoryContinuity(usize),
+    /// Sorted cells with the same address have the same value.
+    MemorySingleValue(usize),
+    MemoryPermutation(usize),
+}
+
+impl ConstraintKind {
+    fn reads_next_row(&self) -> bool {
+        matches!(
+            self,
+            Self::FullRound(_)
+                | Self::PartialRound(_)
+                | Self::MemoryContinuity(0)
+                | Self::MemorySingleValue(0)
+                | Self::MemoryPermutation(0)
+        )
+    }
+}
+
+struct CircuitConstraint {
+    kind: ConstraintKind,
+    idx: usize,
+}
+
+impl CircuitConstraint {
+    fn evaluate_kind(
+        &self,
+        current: &TableView<PrimeField, PrimeField>,
+        next: &TableView<PrimeField, PrimeField>,
+        rap_challenges: &[Felt252],
+    ) -> Felt252 {
+        let c = |col: usize| *current.get_main_evaluation_element(0, col);
+        let n = |col: usize| *next.get_main_evaluation_element(0, col);
+        let one = Felt252::one();
+
+        match self.kind {
+            ConstraintKind::Gate => {
+                let (a, b) = (c(WIRES), c(WIRES + 1));
+                c(Q_L) * a + c(Q_R) * b + c(Q_M) * a * b + c(Q_O) * c(WIRES + 2) + c(Q_C)
+            }
+            ConstraintKind::SBox(k) => {
+                let x = c(WIRES + k) + c(ROUND_CONSTANTS + k);
+                x.square() * x - c(SBOX + k)
+            }
+            ConstraintKind::FullRound(k) => {
+                let state = mix([c(SBOX), c(SBOX + 1), c(SBOX + 2)]);
+                c(Q_FULL) * (n(WIRES + k) - state[k])
+            }
+            ConstraintKind::PartialRound(k) => {
+                let state = mix([c(WIRES), c(WIRES + 1), c(SBOX + 2)]);
+                c(Q_PARTIAL) * (n(WIRES + k) - state[k])
+            }
+            ConstraintKind::MemoryContinuity(k) => {
+                let (previous, current) = sorted_pair(&c, &n, SORTED_ADDRS, k);
+                let delta = current - previous;
+                delta * (delta - one)
+            }
+            ConstraintKind::MemorySingleValue(k) => {
+                let (previous_address, address) = sorted_pair(&c, &n, SORTED_ADDRS, k);
+                let (previous_value, value) = sorted_pair(&c, &n, SORTED_VALUES, k);
+                (value - previous_value) * (address - previous_address - one)
+            }
+            ConstraintKind::MemoryPermutation(k) => {
+                let (z, alpha) = (&rap_challenges[0], &rap_challenges[1]);
+                let (row, previous_product) = if k == 0 {
+                    (next, aux(current, PERMUTATION + NUM_WIRES - 1))
+                } else {
+                    (current, aux(current, PERMUTATION + k - 1))
+                };
+                let main = |col: usize| *row.get_main_evaluation_element(0, col);
+                let unsorted = z - (main(ADDRS + k) + alpha * main(WIRES + k));
+                let sorted = z - (main(SORTED_ADDRS + k) + alpha * main(SORTED_VALUES + k));
+                aux(row, PERMUTATION + k) * sorted - previous_product * unsorted
+            }
+        }
+    }
+}
+
+fn aux(row: &TableView<PrimeField, PrimeField>, col: usize) -> Felt252 {
+    *row.get_aux_evaluation_element(0, col)
+}
+
+/// Returns the values of the sorted slots `k - 1` and `k` of the columns starting at
+/// `first_col`, where the slot before the first one is the last slot of the current row and the
+/// first one is taken from the next row.
+fn sorted_pair(
+    current: &impl Fn(usize) -> Felt252,
+    next: &impl Fn(usize) -> Felt252,
+    first_col: usize,
+    k: usize,
+) -> (Felt252, Felt252) {
+    if k == 0 {
+        (current(first_col + NUM_WIRES - 1), next(first_col))
+    } else {
+        (current(first_col + k - 1), current(first_col + k))
+    }
+}
+
+impl TransitionConstraint<PrimeField, PrimeField> for CircuitConstraint {
+    fn degree(&self) -> usize {
+        match self.kind {
+            ConstraintKind::Gate | ConstraintKind::SBox(_) => 3,
+            _ => 2,
+        }
+    }
+
+    fn constraint_idx(&self) -> usize {
+        self.idx
+    }
+
+    fn end_exemptions(&self) -> usize {
+        self.kind.reads_next_row() as usize
+    }
+
+    fn evaluate(
+        &self,
+        evaluation_context: &TransitionEvaluationContext<PrimeField, PrimeField>,
+        transition_evaluations: &mut [Felt252],
+    ) {
+        let (frame, rap_challenges) = match evaluation_context {
+            TransitionEvaluationContext::Prover {
+                frame,
+                rap_challenges,
+                ..
+            }
+            | TransitionEvaluationContext::Verifier {
+                frame,
+                rap_challenges,
+                ..
+            } => (frame, rap_challenges),
+        };
+
+        let current = frame.get_evaluation_step(0);
+        let next = frame.get_evaluation_step(1);
+
+        transition_evaluations[self.constraint_idx()] =
+            self.evaluate_kind(current, next, rap_challenges);
+    }
+}
+
+/// The statement proven by a recursive proof: there is a valid proof, with Poseidon commitments
+/// and a [`PoseidonTranscript`], of an execution of the inner AIR `A` with these public inputs,
+/// trace length and proof options.
+pub struct RecursionPublicInputs<A: AIR> {
+    pub inner_public_inputs: A::PublicInputs,
+    pub inner_trace_length: usize,
+    pub inner_proof_options: ProofOptions,
+}
+
+impl<A: AIR> Clone for RecursionPublicInputs<A>
+where
+    A::PublicInputs: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner_public_inputs: self.inner_public_inputs.clone(),
+            inner_trace_length: self.inner_trace_length,
+            inner_proof_options: self.inner_proof_options.clone(),
+        }
+    }
+}
+
+impl<A: AIR> AsBytes for RecursionPublicInputs<A>
+where
+    A::PublicInputs: AsBytes,
+{
+    fn as_bytes(&self) -> Vec<u8> {
+        let inner_public_inputs = self.inner_public_inputs.as_bytes();
+        let mut bytes = (self.inner_trace_length as u64).to_be_bytes().to_vec();
+        bytes.extend_from_slice(&self.inner_proof_options.as_bytes());
+        bytes.extend_from_slice(&(inner_public_inputs.len() as u64).to_be_bytes());
+        bytes.extend_from_slice(&inner_public_inputs);
+        bytes
+    }
+}
+
+/// Returns the inner AIR of a recursive statement.
+pub(crate) fn inner_air<A: AIR>(pub_inputs: &RecursionPublicInputs<A>) -> A {
+    A::new(
+        pub_inputs.inner_trace_length,
+        &pub_inputs.inner_public_inputs,
+        &pub_inputs.inner_proof_options,
+    )
+}
+
+/// Returns the values of the public inputs of the verifier circuit of a recursive statement:
+/// the values of the boundary constraints of the inner AIR and the state of the transcript of
+/// the inner proof after binding its statement.
+pub(crate) fn circuit_public_inputs<A>(
+    inner_air: &A,
+    pub_inputs: &RecursionPublicInputs<A>,
+) -> Vec<Felt252>
+where
+    A: RecursiveAIR,
+    A::PublicInputs: AsBytes,
+{
+    let mut transcript = PoseidonTranscript::new(&[]);
+    bind_statement::<A>(
+        &mut transcript,
+        pub_inputs.inner_trace_length,
+        &pub_inputs.inner_public_inputs,
+        &pub_inputs.inner_proof_options,
+    );
+    // Binding the statement ends with a byte string, which leaves nothing pending.
+    let state = transcript.sponge_state().unwrap();
+
+    inner_air
+        .boundary_constraints(&[])
+        .constraints
+        .iter()
+        .map(|constraint| constraint.value)
+        .chain(state)
+        .collect()
+}
+
+/// AIR proving that a proof of the inner AIR `A` is valid, by proving the execution of a circuit
+/// running its verifier, see [`build_verifier_circuit`].
+///
+/// The circuit is proven with a PLONK-like arithmetization: every row of the trace holds the
+/// three wires of a row of the circuit, constrained by the arithmetic gate and the Poseidon round
+/// selected by the preprocessed columns, and the copy constraints between wires holding the same
+/// variable are enforced with a memory argument over the preprocessed addresses of the wires.
+/// The public inputs of the circuit are the values of the wire `a` of the rows following the
+/// first one, which holds the variable zero.
+pub struct RecursionAIR<A: AIR> {
+    context: AirContext,
+    trace_length: usize,
+    pub_inputs: RecursionPublicInputs<A>,
+    circuit: Circuit,
+    shape: ProofShape,
+    circuit_public_inputs: Vec<Felt252>,
+    transition_constraints: Vec<Box<dyn TransitionConstraint<PrimeField, PrimeField>>>,
+}
+
+impl<A: AIR> RecursionAIR<A> {
+    pub fn circuit(&self) -> &Circuit {
+        &self.circuit
+    }
+
+    pub(crate) fn proof_shape(&self) -> &ProofShape {
+        &self.shape
+    }
+
+    pub(crate) fn circuit_public_inputs(&self) -> &[Felt252] {
+        &self.circuit_public_inputs
+    }
+
+    /// Returns the trace of the circuit for the values `witness` of its variables.
+    pub fn build_trace(&self, witness: &[Felt252]) -> TraceTable<PrimeField, PrimeField> {
+        let rows = self.padded_rows();
+        let mut columns: Vec<_> = (0..NUM_MAIN_COLUMNS)
+            .map(|_| Vec::with_capacity(self.trace_length))
+            .collect();
+        let mut cells = Vec::with_capacity(rows.len() * NUM_WIRES);
+
+        for row in rows.iter() {
+            for (k, var) in row.wires.iter().enumerate() {
+                let value = witness[var.index()];
+                let x = value + row.round_constants[k];
+                columns[WIRES + k].push(value);
+                columns[SBOX + k].push(x.square() * x);
+                cells.push((var.index(), value));
+            }
+        }
+        cells.sort_by_key(|(address, _)| *address);
+        for (i, (address, value)) in cells.into_iter().enumerate() {
+            columns[SORTED_ADDRS + i % NUM_WIRES].push(Felt252::from(address as u64));
+            columns[SORTED_VALUES + i % NUM_WIRES].push(value);
+        }
+
+        let aux_columns = vec![vec![Felt252::zero(); self.trace_length]; NUM_AUX_COLUMNS];
+        TraceTable::from_columns(columns, aux_columns, 1)
+    }
+
+    /// The rows of the circuit, padded or truncated to the length of the trace.
+    fn padded_rows(&self) -> Vec<Row> {
+        let mut rows = self.circuit.rows.clone();
+        rows.resize(self.trace_length, Row::padding());
+        rows
+    }
+}
+
+impl<A> AIR for RecursionAIR<A>
+where
+    A: RecursiveAIR,
+    A::PublicInputs: Clone + AsBytes,
+{
+    type Field = PrimeField;
+    type FieldExtension = PrimeField;
+    type PublicInputs = RecursionPublicInputs<A>;
+
+    const STEP_SIZE: usize = 1;
+    const AIR_ID: &'static str = "recursion";
+
+    fn new(
+        trace_length: usize,
+        pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Self {
+        let kinds = [ConstraintKind::Gate]
+            .into_iter()
+            .chain((0..NUM_WIRES).map(ConstraintKind::SBox))
+            .chain((0..NUM_WIRES).map(ConstraintKind::FullRound))
+            .chain((0..NUM_WIRES).map(ConstraintKind::PartialRound))
+            .chain((0..NUM_WIRES).flat_map(|k| {
+                [
+                    ConstraintKind::MemoryContinuity(k),
+                    ConstraintKind::MemorySingleValue(k),
+                    ConstraintKind::MemoryPermutation(k),
+                ]
+            }));
+
+        let transition_constraints: Vec<
+            Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>,
+        > = kinds
+            .enumerate()
+            .map(|(idx, kind)| {
+                Box::new(CircuitConstraint { kind, idx })
+                    as Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>
+            })
+            .collect();
+
+        let context = AirContext {
+            proof_options: proof_options.clone(),
+            trace_columns: NUM_MAIN_COLUMNS + NUM_PREPROCESSED_COLUMNS + NUM_AUX_COLUMNS,
+            transition_offsets: vec![0, 1],
+            num_transition_constraints: transition_constraints.len(),
+        };
+
+        let inner_air = inner_air(pub_inputs);
+        let (circuit, shape) = build_verifier_circuit(&inner_air);
+        let circuit_public_inputs = circuit_public_inputs(&inner_air, pub_inputs);
+
+        Self {
+            context,
+            trace_length,
+            pub_inputs: pub_inputs.clone(),
+            circuit,
+            shape,
+            circuit_public_inputs,
+            transition_constraints,
+        }
+    }
+
+    fn build_auxiliary_trace(
+        &self,
+        trace: &mut TraceTable<Self::Field, Self::FieldExtension>,
+        challenges: &[Felt252],
+    ) {
+        let (z, alpha) = (&challenges[0], &challenges[1]);
+        let preprocessed = self.preprocessed_columns();
+        let addresses = &preprocessed[ADDRS - NUM_MAIN_COLUMNS..][..NUM_WIRES];
+
+        let mut unsorted = Vec::with_capacity(trace.num_rows() * NUM_WIRES);
+        let mut sorted = Vec::with_capacity(trace.num_rows() * NUM_WIRES);
+        for row in 0..trace.num_rows() {
+            for (k, column) in addresses.iter().enumerate() {
+                let main = |col: usize| *trace.get_main(row, col);
+                let address = column[row];
+                unsorted.push(z - (address + alpha * main(WIRES + k)));
+                sorted.push(z - (main(SORTED_ADDRS + k) + alpha * main(SORTED_VALUES + k)));
+            }
+        }
+        Felt252::inplace_batch_inverse(&mut sorted).unwrap();
+
+        let mut product = Felt252::one();
+        for (i, (unsorted, sorted_inverse)) in unsorted.iter().zip(&sorted).enumerate() {
+            product = product * unsorted * sorted_inverse;
+            trace.set_aux(i / NUM_WIRES, PERMUTATION + i % NUM_WIRES, product);
+        }
+    }
+
+    fn build_rap_challenges(
+        &self,
+        transcript: &mut impl IsTranscript<Self::Field>,
+    ) -> Vec<Felt252> {
+        vec![
+            transcript.sample_field_element(),
+            transcript.sample_field_element(),
+        ]
+    }
+
+    fn trace_layout(&self) -> (usize, usize) {
+        (NUM_MAIN_COLUMNS, NUM_AUX_COLUMNS)
+    }
+
+    fn preprocessed_columns(&self) -> Vec<Vec<Felt252>> {
+        let rows = self.padded_rows();
+        let column = |f: &dyn Fn(&Row) -> Felt252| rows.iter().map(f).collect::<Vec<_>>();
+        let selector = |round: Round| {
+            column(&|row: &Row| {
+                if row.round == round {
+                    Felt252::one()
+                } else {
+                    Felt252::zero()
+                }
+            })
+        };
+
+        let mut columns = vec![
+            column(&|row: &Row| row.q_l),
+            column(&|row: &Row| row.q_r),
+            column(&|row: &Row| row.q_m),
+            column(&|row: &Row| row.q_o),
+            column(&|row: &Row| row.q_c),
+            selector(Round::Full),
+            selector(Round::Partial),
+        ];
+        for k in 0..NUM_WIRES {
+            columns.push(column(&|row: &Row| row.round_constants[k]));
+        }
+        for k in 0..NUM_WIRES {
+            columns.push(column(&|row: &Row| {
+                Felt252::from(row.wires[k].index() as u64)
+            }));
+        }
+        columns
+    }
+
+    fn num_preprocessed_columns(&self) -> usize {
+        NUM_PREPROCESSED_COLUMNS
+    }
+
+    /// Besides fixing the public inputs of the circuit and the ends of the memory argument, the
+    /// boundary constraints include two contradicting ones when the trace is too short to hold
+    /// the circuit, so that no proof is accepted for a truncated circuit.
+    fn boundary_constraints(&self, _rap_challenges: &[Felt252]) -> BoundaryConstraints<PrimeField> {
+        let last = self.trace_length - 1;
+        let mut constraints = vec![
+            BoundaryConstraint::new_main(WIRES, 0, Felt252::zero()),
+            BoundaryConstraint::new_main(SORTED_ADDRS, 0, Felt252::zero()),
+            BoundaryConstraint::new_main(SORTED_VALUES, 0, Felt252::zero()),
+            BoundaryConstraint::new_aux(PERMUTATION, 0, Felt252::one()),
+            BoundaryConstraint::new_aux(PERMUTATION + NUM_WIRES - 1, last, Felt252::one()),
+        ];
+        constraints.extend(
+            self.circuit_public_inputs
+                .iter()
+                .enumerate()
+                .map(|(i, value)| BoundaryConstraint::new_main(WIRES, 1 + i, *value)),
+        );
+        if self.circuit.num_rows() > self.trace_length {
+            constraints.push(BoundaryConstraint::new_main(WIRES, 0, Felt252::one()));
+        }
+        BoundaryConstraints::from_constraints(constraints)
+    }
+
+    fn transition_constraints(
+        &self,
+    ) -> &Vec<Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>> {
+        &self.transition_constraints
+    }
+
+    fn context(&self) -> &AirContext {
+        &self.context
+    }
+
+    fn composition_poly_degree_bound(&self) -> usize {
+        2 * self.trace_length()
+    }
+
+    fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    fn pub_inputs(&self) -> &Self::PublicInputs {
+        &self.pub_inputs
+    }
+}
@@ -0,0 +1,621 @@
+use std::collections::HashMap;
+
+use lambdaworks_crypto::hash::poseidon::{
+    parameters::PermutationParameters, starknet::PoseidonCairoStark252,
+};
+
+use crate::Felt252;
+
+use super::errors::CircuitError;
+
+/// Number of wires of a row of the circuit.
+pub const NUM_WIRES: usize = 3;
+
+/// Number of rounds of the Poseidon permutation.
+pub(crate) const NUM_ROUNDS: usize =
+    PoseidonCairoStark252::N_FULL_ROUNDS + PoseidonCairoStark252::N_PARTIAL_ROUNDS;
+
+/// A variable of a circuit. The variable 0 always holds zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Var(usize);
+
+impl Var {
+    pub const ZERO: Var = Var(0);
+
+    /// The address of the variable in the memory argument of the `RecursionAIR`.
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+/// How a row relates its wires to the wires of the next row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Round {
+    None,
+    /// A full round of Poseidon: the S-box is applied to the three wires.
+    Full,
+    /// A partial round of Poseidon: the S-box is applied to the wire `c` only.
+    Partial,
+}
+
+/// A row of the circuit. Its wires satisfy the gate
+/// `q_l * a + q_r * b + q_m * a * b + q_o * c + q_c = 0`, and when `round` is not `None` the wires
+/// of the next row are the result of the Poseidon round with constants `round_constants`.
+#[derive(Clone, Debug)]
+pub(crate) struct Row {
+    pub(crate) wires: [Var; NUM_WIRES],
+    pub(crate) q_l: Felt252,
+    pub(crate) q_r: Felt252,
+    pub(crate) q_m: Felt252,
+    pub(crate) q_o: Felt252,
+    pub(crate) q_c: Felt252,
+    pub(crate) round: Round,
+    pub(crate) round_constants: [Felt252; NUM_WIRES],
+}
+
+impl Row {
+    fn new(wires: [Var; NUM_WIRES]) -> Self {
+        Self {
+            wires,
+            q_l: Felt252::zero(),
+            q_r: Felt252::zero(),
+            q_m: Felt252::zero(),
+            q_o: Felt252::zero(),
+            q_c: Felt252::zero(),
+            round: Round::None,
+            round_constants: [Felt252::zero(); NUM_WIRES],
+        }
+    }
+
+    /// A row with every wire holding zero and no constraints, used to pad the circuit.
+    pub(crate) fn padding() -> Self {
+        Self::new([Var::ZERO; NUM_WIRES])
+    }
+
+    fn gate_evaluation(&self, values: &[Felt252]) -> Felt252 {
+        let [a, b, c] = self.wires.map(|var| values[var.0]);
+        self.q_l * a + self.q_r * b + self.q_m * a * b + self.q_o * c + self.q_c
+    }
+}
+
+/// The Poseidon round relating the wires of `row` to the ones of the next row.
+pub(crate) fn apply_round(row: &Row, state: [Felt252; NUM_WIRES]) -> [Felt252; NUM_WIRES] {
+    let cube = |x: Felt252| x.square() * x;
+    let [rc0, rc1, rc2] = row.round_constants;
+    let [a, b, c] = state;
+    match row.round {
+        Round::None => state,
+        Round::Full => mix([cube(a + rc0), cube(b + rc1), cube(c + rc2)]),
+        Round::Partial => mix([a, b, cube(c + rc2)]),
+    }
+}
+
+/// The linear layer of the Poseidon permutation of Starknet.
+pub(crate) fn mix<T>(state: [T; NUM_WIRES]) -> [T; NUM_WIRES]
+where
+    T: Copy + std::ops::Add<Output = T> + std::ops::Sub<Output = T>,
+{
+    let [a, b, c] = state;
+    let t = a + b + c;
+    [t + a + a, t - b - b, t - c - c - c]
+}
+
+/// The ways the witness generation computes the values of the variables that are not inputs.
+#[derive(Clone, Debug)]
+enum WitnessStep {
+    /// Solves the gate of the row for its wire `c`.
+    Gate(usize),
+    /// Computes the rows of the permutation starting at the row, from the state of that row.
+    Permutation(usize),
+    Inverse {
+        value: Var,
+        inverse: Var,
+    },
+    /// Little-endian binary decomposition of a value.
+    Bits {
+        value: Var,
+        bits: Vec<Var>,
+    },
+}
+
+/// Builds a [`Circuit`] by adding gates and gadgets on variables.
+pub struct CircuitBuilder {
+    rows: Vec<Row>,
+    num_vars: usize,
+    steps: Vec<WitnessStep>,
+    public_inputs: Vec<Var>,
+    inputs: Vec<Var>,
+    constants: HashMap<[u64; 4], Var>,
+}
+
+impl Default for CircuitBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CircuitBuilder {
+    pub fn new() -> Self {
+        let mut constants = HashMap::new();
+        constants.insert(Felt252::zero().representative().limbs, Var::ZERO);
+        Self {
+            rows: vec![Row::padding()],
+            num_vars: 1,
+            steps: Vec::new(),
+            public_inputs: Vec::new(),
+            inputs: Vec::new(),
+            constants,
+        }
+    }
+
+    fn new_var(&mut self) -> Var {
+        self.num_vars += 1;
+        Var(self.num_vars - 1)
+    }
+
+    /// Adds a public input. Its value is placed in the wire `a` of the row `1 + i` for the i-th
+    /// public input, so public inputs must be added before any other row.
+    pub fn public_input(&mut self) -> Var {
+        assert_eq!(
+            self.rows.len(),
+            1 + self.public_inputs.len(),
+            "public inputs must be added before any gate"
+        );
+        let var = self.new_var();
+        self.rows.push(Row::new([var, Var::ZERO, Var::ZERO]));
+        self.public_inputs.push(var);
+        var
+    }
+
+    /// Adds a private input, whose value is given to the witness generation.
+    pub fn input(&mut self) -> Var {
+        let var = self.new_var();
+        self.inputs.push(var);
+        var
+    }
+
+    pub fn inputs(&mut self, n: usize) -> Vec<Var> {
+        (0..n).map(|_| self.input()).collect()
+    }
+
+    /// Returns a variable holding `q_l * a + q_r * b + q_m * a * b + q_c`.
+    pub fn gate(
+        &mut self,
+        a: Var,
+        b: Var,
+        q_l: Felt252,
+        q_r: Felt252,
+        q_m: Felt252,
+        q_c: Felt252,
+    ) -> Var {
+        let c = self.new_var();
+        self.rows.push(Row {
+            q_l,
+            q_r,
+            q_m,
+            q_o: -Felt252::one(),
+            q_c,
+            ..Row::new([a, b, c])
+        });
+        self.steps.push(WitnessStep::Gate(self.rows.len() - 1));
+        c
+    }
+
+    pub fn constant(&mut self, value: Felt252) -> Var {
+        let key = value.representative().limbs;
+        if let Some(var) = self.constants.get(&key) {
+            return *var;
+        }
+        let zero = Felt252::zero();
+        let var = self.gate(Var::ZERO, Var::ZERO, zero, zero, zero, value);
+        self.constants.insert(key, var);
+        var
+    }
+
+    pub fn add(&mut self, a: Var, b: Var) -> Var {
+        let (one, zero) = (Felt252::one(), Felt252::zero());
+        self.gate(a, b, one, one, zero, zero)
+    }
+
+    pub fn sub(&mut self, a: Var, b: Var) -> Var {
+        let (one, zero) = (Felt252::one(), Felt252::zero());
+        self.gate(a, b, one, -one, zero, zero)
+    }
+
+    pub fn mul(&mut self, a: Var, b: Var) -> Var {
+        let (one, zero) = (Felt252::one(), Felt252::zero());
+        self.gate(a, b, zero, zero, one, zero)
+    }
+
+    /// Returns a variable holding `k_a * a + k_b * b`.
+    pub fn linear_combination(&mut self, a: Var, k_a: Felt252, b: Var, k_b: Felt252) -> Var {
+        let zero = Felt252::zero();
+        self.gate(a, b, k_a, k_b, zero, zero)
+    }
+
+    pub fn add_constant(&mut self, a: Var, constant: Felt252) -> Var {
+        let zero = Felt252::zero();
+        self.gate(a, Var::ZERO, Felt252::one(), zero, zero, constant)
+    }
+
+    pub fn mul_constant(&mut self, a: Var, constant: Felt252) -> Var {
+        let zero = Felt252::zero();
+        self.gate(a, Var::ZERO, constant, zero, zero, zero)
+    }
+
+    /// Returns a variable holding `constant - a`.
+    pub fn constant_sub(&mut self, constant: Felt252, a: Var) -> Var {
+        let zero = Felt252::zero();
+        self.gate(a, Var::ZERO, -Felt252::one(), zero, zero, constant)
+    }
+
+    pub fn assert_equal(&mut self, a: Var, b: Var) {
+        self.rows.push(Row {
+            q_l: Felt252::one(),
+            q_r: -Felt252::one(),
+            ..Row::new([a, b, Var::ZERO])
+        });
+    }
+
+    pub fn assert_bool(&mut self, a: Var) {
+        self.rows.push(Row {
+            q_l: -Felt252::one(),
+            q_m: Felt252::one(),
+            ..Row::new([a, a, Var::ZERO])
+        });
+    }
+
+    /// Returns the inverse of `a`, and makes the circuit unsatisfiable if `a` is zero.
+    pub fn inverse(&mut self, a: Var) -> Var {
+        let inverse = self.new_var();
+        self.steps.push(WitnessStep::Inverse { value: a, inverse });
+        self.rows.push(Row {
+            q_m: Felt252::one(),
+            q_c: -Felt252::one(),
+            ..Row::new([a, inverse, Var::ZERO])
+        });
+        inverse
+    }
+
+    pub fn div(&mut self, a: Var, b: Var) -> Var {
+        let inverse = self.inverse(b);
+        self.mul(a, inverse)
+    }
+
+    /// Returns `base^exponent` for a constant exponent.
+    pub fn pow(&mut self, base: Var, exponent: u64) -> Var {
+        let mut result = self.constant(Felt252::one());
+        for bit in (0..u64::BITS - exponent.leading_zeros()).rev() {
+            result = self.mul(result, result);
+            if (exponent >> bit) & 1 == 1 {
+                result = self.mul(result, base);
+            }
+        }
+        result
+    }
+
+    /// Returns the `num_bits` least significant bits of `a`, and makes the circuit unsatisfiable
+    /// if `a` doesn't fit in `num_bits` bits. For the decomposition to be unique, `num_bits`
+    /// must be at most 251.
+    pub fn to_bits(&mut self, a: Var, num_bits: usize) -> Vec<Var> {
+        let bits: Vec<Var> = (0..num_bits).map(|_| self.new_var()).collect();
+        self.steps.push(WitnessStep::Bits {
+            value: a,
+            bits: bits.clone(),
+        });
+
+        let mut accumulator = Var::ZERO;
+        let mut power = Felt252::one();
+        for bit in bits.iter() {
+            self.assert_bool(*bit);
+            accumulator = self.linear_combination(*bit, power, accumulator, Felt252::one());
+            power = power + power;
+        }
+        self.assert_equal(accumulator, a);
+        bits
+    }
+
+    /// Returns `(a, b)` if `bit` is 0 and `(b, a)` if `bit` is 1, where `bit` is boolean.
+    pub fn conditional_swap(&mut self, bit: Var, a: Var, b: Var) -> (Var, Var) {
+        let difference = self.sub(b, a);
+        let delta = self.mul(bit, difference);
+        (self.add(a, delta), self.sub(b, delta))
+    }
+
+    /// Returns the Poseidon permutation of Starknet applied to `state`. It takes one row per round,
+    /// holding the state before the round, and one last row holding the output.
+    pub fn permutation(&mut self, state: [Var; NUM_WIRES]) -> [Var; NUM_WIRES] {
+        let first_row = self.rows.len();
+        let half_full_rounds = PoseidonCairoStark252::N_FULL_ROUNDS / 2;
+        let mut index = 0;
+        let mut wires = state;
+        for round in 0..NUM_ROUNDS {
+            let is_partial = round >= half_full_rounds
+                && round < half_full_rounds + PoseidonCairoStark252::N_PARTIAL_ROUNDS;
+            let mut row = Row::new(wires);
+            if is_partial {
+                row.round = Round::Partial;
+                row.round_constants[2] = PoseidonCairoStark252::ROUND_CONSTANTS[index];
+                index += 1;
+            } else {
+                row.round = Round::Full;
+                row.round_constants
+                    .clone_from_slice(&PoseidonCairoStark252::ROUND_CONSTANTS[index..index + 3]);
+                index += 3;
+            }
+            self.rows.push(row);
+            wires = [self.new_var(), self.new_var(), self.new_var()];
+        }
+        self.rows.push(Row::new(wires));
+        self.steps.push(WitnessStep::Permutation(first_row));
+        wires
+    }
+
+    /// Returns the Poseidon hash of Starknet of two elements.
+    pub fn hash(&mut self, a: Var, b: Var) -> Var {
+        let two = self.constant(Felt252::from(2));
+        self.permutation([a, b, two])[0]
+    }
+
+    /// Returns the Poseidon hash of Starknet of a sequence of elements.
+    pub fn hash_many(&mut self, inputs: &[Var]) -> Var {
+        let one = self.constant(Felt252::one());
+        let mut values = inputs.to_vec();
+        values.push(one);
+        if values.len() % 2 == 1 {
+            values.push(Var::ZERO);
+        }
+
+        let mut state = [Var::ZERO; NUM_WIRES];
+        for block in values.chunks(2) {
+            state[0] = self.add_unless_zero(state[0], block[0]);
+            state[1] = self.add_unless_zero(state[1], block[1]);
+            state = self.permutation(state);
+        }
+        state[0]
+    }
+
+    /// Returns `a + b`, without adding a gate when one of them is the zero variable.
+    pub(crate) fn add_unless_zero(&mut self, a: Var, b: Var) -> Var {
+        match (a, b) {
+            (Var::ZERO, _) => b,
+            (_, Var::ZERO) => a,
+            _ => self.add(a, b),
+        }
+    }
+
+    /// Returns the root of a Poseidon Merkle tree from the hash of a leaf, its authentication
+    /// path and the bits of its index, least significant first.
+    pub fn merkle_root(&mut self, leaf_hash: Var, path: &[Var], index_bits: &[Var]) -> Var {
+        debug_assert_eq!(path.len(), index_bits.len());
+        path.iter()
+            .zip(index_bits)
+            .fold(leaf_hash, |node, (sibling, bit)| {
+                let (left, right) = self.conditional_swap(*bit, node, *sibling);
+                self.hash(left, right)
+            })
+    }
+
+    /// Returns the circuit. Variables that are not held by any wire are placed in rows without
+    /// constraints, so that the addresses of the memory argument are contiguous.
+    pub fn build(mut self) -> Circuit {
+        let mut is_used = vec![false; self.num_vars];
+        for row in self.rows.iter() {
+            for var in row.wires {
+                is_used[var.0] = true;
+            }
+        }
+        for (index, _) in is_used.iter().enumerate().filter(|(_, used)| !**used) {
+            self.rows.push(Row::new([Var(index), Var::ZERO, Var::ZERO]));
+        }
+
+        Circuit {
+            rows: self.rows,
+            num_vars: self.num_vars,
+            steps: self.steps,
+            public_inputs: self.public_inputs,
+            inputs: self.inputs,
+        }
+    }
+}
+
+/// An arithmetic circuit over `Stark252PrimeField`, proven with the
+/// [`RecursionAIR`](super::air::RecursionAIR). It is a sequence of rows of three wires, each row being
+/// either an arithmetic gate or a round of the Poseidon permutation, and wires holding the same
+/// variable are required to hold the same value.
+#[derive(Clone, Debug)]
+pub struct Circuit {
+    pub(crate) rows: Vec<Row>,
+    num_vars: usize,
+    steps: Vec<WitnessStep>,
+    public_inputs: Vec<Var>,
+    inputs: Vec<Var>,
+}
+
+impl Circuit {
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn num_public_inputs(&self) -> usize {
+        self.public_inputs.len()
+    }
+
+    /// The length of the trace proving the circuit.
+    pub fn trace_length(&self) -> usize {
+        self.rows.len().next_power_of_two()
+    }
+
+    /// Returns the values of all the variables of the circuit given the values of its public and
+    /// private inputs, in the order they were added, and checks that they satisfy every gate.
+    pub fn generate_witness(
+        &self,
+        public_inputs: &[Felt252],
+        inputs: &[Felt252],
+    ) -> Result<Vec<Felt252>, CircuitError> {
+        for (expected, actual) in [
+            (self.public_inputs.len(), public_inputs.len()),
+            (self.inputs.len(), inputs.len()),
+        ] {
+            if expected != actual {
+                return Err(CircuitError::WrongNumberOfInputs { expected, actual });
+            }
+        }
+
+        let mut values = vec![Felt252::zero(); self.num_vars];
+        for (var, value) in self
+            .public_inputs
+            .iter()
+            .zip(public_inputs)
+            .chain(self.inputs.iter().zip(inputs))
+        {
+            values[var.0] = *value;
+        }
+
+        for step in self.steps.iter() {
+            match step {
+                WitnessStep::Gate(row) => {
+                    let row = &self.rows[*row];
+                    let [a, b, _] = row.wires.map(|var| values[var.0]);
+                    // Gates added by the builder have `q_o = -1`.
+                    values[row.wires[2].0] = row.q_l * a + row.q_r * b + row.q_m * a * b + row.q_c;
+                }
+                WitnessStep::Permutation(first_row) => {
+                    let rounds = &self.rows[*first_row..=*first_row + NUM_ROUNDS];
+                    let mut state = rounds[0].wires.map(|var| values[var.0]);
+                    for (row, next) in rounds.iter().zip(&rounds[1..]) {
+                        state = apply_round(row, state);
+                        for (var, value) in next.wires.iter().zip(state) {
+                            values[var.0] = value;
+                        }
+                    }
+                }
+                WitnessStep::Inverse { value, inverse } => {
+                    values[inverse.0] = values[value.0].inv().unwrap_or_else(|_| Felt252::zero());
+                }
+                WitnessStep::Bits { value, bits } => {
+                    let limbs = values[value.0].representative().limbs;
+                    for (i, bit) in bits.iter().enumerate() {
+                        let limb = limbs[3 - i / 64];
+                        values[bit.0] = Felt252::from((limb >> (i % 64)) & 1);
+                    }
+                }
+            }
+        }
+
+        match self
+            .rows
+            .iter()
+            .position(|row| row.gate_evaluation(&values) != Felt252::zero())
+        {
+            Some(row) => Err(CircuitError::UnsatisfiedGate(row)),
+            None => Ok(values),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_crypto::hash::poseidon::Poseidon;
+
+    use super::*;
+
+    #[test]
+    fn poseidon_gadgets_match_the_native_hash() {
+        let mut builder = CircuitBuilder::new();
+        let inputs = builder.inputs(3);
+        let hash = builder.hash(inputs[0], inputs[1]);
+        let hash_many = builder.hash_many(&inputs);
+        let circuit = builder.build();
+
+        let values = [Felt252::from(3), Felt252::from(5), Felt252::from(8)];
+        let witness = circuit.generate_witness(&[], &values).unwrap();
+
+        assert_eq!(
+            witness[hash.index()],
+            PoseidonCairoStark252::hash(&values[0], &values[1])
+        );
+        assert_eq!(
+            witness[hash_many.index()],
+            PoseidonCairoStark252::hash_many(&values)
+        );
+    }
+
+    #[test]
+    fn merkle_root_swaps_the_children_according_to_the_index() {
+        let mut builder = CircuitBuilder::new();
+        let leaf = builder.input();
+        let path = builder.inputs(2);
+        let index_bits = [builder.input(), builder.input()];
+        for bit in index_bits {
+            builder.assert_bool(bit);
+        }
+        let root = builder.merkle_root(leaf, &path, &index_bits);
+        let circuit = builder.build();
+
+        let (leaf, siblings) = (Felt252::from(1), [Felt252::from(2), Felt252::from(3)]);
+        // The leaf is the right child at the first level and the left one at the second level.
+        let expected = PoseidonCairoStark252::hash(
+            &PoseidonCairoStark252::hash(&siblings[0], &leaf),
+            &siblings[1],
+        );
+        let inputs = [
+            leaf,
+            siblings[0],
+            siblings[1],
+            Felt252::one(),
+            Felt252::zero(),
+        ];
+        let witness = circuit.generate_witness(&[], &inputs).unwrap();
+        assert_eq!(witness[root.index()], expected);
+
+        let non_boolean = [
+            leaf,
+            siblings[0],
+            siblings[1],
+            Felt252::from(2),
+            Felt252::zero(),
+        ];
+        assert!(matches!(
+            circuit.generate_witness(&[], &non_boolean),
+            Err(CircuitError::UnsatisfiedGate(_))
+        ));
+    }
+
+    #[test]
+    fn witness_generation_checks_the_gates() {
+        let mut builder = CircuitBuilder::new();
+        let public = builder.public_input();
+        let input = builder.input();
+        let bits = builder.to_bits(input, 4);
+        let inverse = builder.inverse(input);
+        let product = builder.mul(public, inverse);
+        let one = builder.constant(Felt252::one());
+        builder.assert_equal(product, one);
+        let circuit = builder.build();
+
+        let witness = circuit
+            .generate_witness(&[Felt252::from(11)], &[Felt252::from(11)])
+            .unwrap();
+        let bit_values: Vec<Felt252> = bits.iter().map(|bit| witness[bit.index()]).collect();
+        assert_eq!(bit_values, [1, 1, 0, 1].map(Felt252::from));
+
+        assert!(circuit
+            .generate_witness(&[Felt252::from(12)], &[Felt252::from(11)])
+            .is_err());
+        // 16 doesn't fit in 4 bits.
+        assert!(circuit
+            .generate_witness(&[Felt252::from(16)], &[Felt252::from(16)])
+            .is_err());
+        assert!(circuit
+            .generate_witness(&[Felt252::zero()], &[Felt252::zero()])
+            .is_err());
+        assert_eq!(
+            circuit.generate_witness(&[], &[Felt252::one()]),
+            Err(CircuitError::WrongNumberOfInputs {
+                expected: 1,
+                actual: 0
+            })
+        );
+    }
+}
@@ -0,0 +1,29 @@
+use crate::{prover::ProvingError, verifier::VerificationError};
+
+/// The reasons a circuit can't be given a satisfying witness.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitError {
+    /// The number of values given for the public or private inputs differs from the number of
+    /// inputs of the circuit.
+    WrongNumberOfInputs { expected: usize, actual: usize },
+    /// The gate of the given row doesn't hold for the values of the inputs.
+    UnsatisfiedGate(usize),
+}
+
+#[derive(Debug)]
+pub enum RecursionError {
+    /// The inner AIR uses a feature the verifier circuit doesn't support.
+    UnsupportedAir(String),
+    /// The inner proof options use a feature the verifier circuit doesn't support.
+    UnsupportedProofOptions(String),
+    /// The inner proof doesn't have the shape expected by the verifier circuit.
+    MalformedProof(String),
+    /// The inner proof was rejected by the STARK verifier.
+    InvalidInnerProof(VerificationError),
+    /// The inner proof doesn't satisfy the verifier circuit.
+    Circuit(CircuitError),
+    /// The proof of the verifier circuit couldn't be generated.
+    Proving(ProvingError),
+    /// The proof of the verifier circuit was rejected by the STARK verifier.
+    Verification(VerificationError),
+}
@@ -0,0 +1,98 @@
+use lambdaworks_crypto::{
+    hash::poseidon::{starknet::PoseidonCairoStark252, Poseidon},
+    merkle_tree::traits::IsMerkleTreeBackend,
+};
+use lambdaworks_math::traits::ByteConversion;
+
+use crate::{
+    config::{Commitment, IsCommitmentHash},
+    Felt252, PrimeField,
+};
+
+/// Merkle tree backend hashing rows of `Felt252` with the Poseidon hash of Starknet.
+/// A leaf is the `hash_many` of its row and a parent the `hash` of its two children. Nodes are
+/// stored as the big-endian bytes of the canonical representative of the field element.
+#[derive(Clone, Default)]
+pub struct BatchPoseidonBackend;
+
+impl IsMerkleTreeBackend for BatchPoseidonBackend {
+    type Node = Commitment;
+    type Data = Vec<Felt252>;
+
+    fn hash_data(input: &Vec<Felt252>) -> Commitment {
+        field_element_to_commitment(&PoseidonCairoStark252::hash_many(input))
+    }
+
+    fn hash_new_parent(left: &Commitment, right: &Commitment) -> Commitment {
+        field_element_to_commitment(&PoseidonCairoStark252::hash(
+            &commitment_to_field_element(left),
+            &commitment_to_field_element(right),
+        ))
+    }
+}
+
+/// Poseidon Merkle trees over `Stark252PrimeField`. Proofs committed with them can be verified
+/// inside an arithmetic circuit, see [`RecursionAIR`](super::air::RecursionAIR).
+pub struct PoseidonCommitments;
+
+impl IsCommitmentHash<PrimeField, PrimeField> for PoseidonCommitments {
+    type MainBackend = BatchPoseidonBackend;
+    type ExtensionBackend = BatchPoseidonBackend;
+    // Recursion doesn't support grinding, see `check_supported`.
+    type GrindingHash = sha3::Keccak256;
+}
+
+/// Returns the node of a Poseidon Merkle tree holding `element`.
+pub fn field_element_to_commitment(element: &Felt252) -> Commitment {
+    let mut commitment = [0u8; 32];
+    commitment.copy_from_slice(&element.to_bytes_be());
+    commitment
+}
+
+/// Returns the field element held by a node of a Poseidon Merkle tree, reducing it modulo the
+/// prime when the bytes are not canonical.
+pub fn commitment_to_field_element(commitment: &Commitment) -> Felt252 {
+    // Any 32 bytes fit in a `U256`, so the conversion cannot fail.
+    Felt252::from_bytes_be(commitment).unwrap()
+}
+
+/// Returns the field element held by `commitment` if its bytes are the canonical encoding of it.
+pub fn canonical_commitment_to_field_element(commitment: &Commitment) -> Option<Felt252> {
+    let element = commitment_to_field_element(commitment);
+    (field_element_to_commitment(&element) == *commitment).then_some(element)
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_crypto::merkle_tree::merkle::MerkleTree;
+
+    use super::*;
+
+    #[test]
+    fn non_canonical_commitments_are_reduced() {
+        let commitment = [0xff; 32];
+        let element = commitment_to_field_element(&commitment);
+
+        assert_ne!(field_element_to_commitment(&element), commitment);
+        assert!(canonical_commitment_to_field_element(&commitment).is_none());
+        assert_eq!(
+            canonical_commitment_to_field_element(&field_element_to_commitment(&element)),
+            Some(element)
+        );
+    }
+
+    #[test]
+    fn poseidon_merkle_tree_openings_verify() {
+        let leaves: Vec<Vec<Felt252>> = (0..8u64)
+            .map(|i| vec![Felt252::from(i), Felt252::from(i * i)])
+            .collect();
+        let tree = MerkleTree::<BatchPoseidonBackend>::build(&leaves).unwrap();
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.get_proof_by_pos(index).unwrap();
+            assert!(proof.verify::<BatchPoseidonBackend>(&tree.root, index, leaf));
+            assert!(!proof.verify::<BatchPoseidonBackend>(&tree.root, index ^ 1, leaf));
+        }
+        assert!(canonical_commitment_to_field_element(&tree.root).is_some());
+    }
+}
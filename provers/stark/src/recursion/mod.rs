@@ -0,0 +1,176 @@
+//! Recursive verification of STARK proofs over `Stark252PrimeField`.
+//!
+//! An inner proof, committed with Poseidon Merkle trees ([`hash::PoseidonCommitments`]) and
+//! generated with a Poseidon transcript ([`transcript::PoseidonTranscript`]), is verified by an
+//! arithmetic circuit that replays the transcript, checks the out-of-domain evaluation of the
+//! composition polynomial, the Merkle paths of the openings and the FRI folding of every query.
+//! The execution of that circuit is then proven with the [`air::RecursionAIR`], so that the
+//! outer proof attests the validity of the inner one.
+//!
+//! The inner AIR must implement [`RecursiveAIR`] to evaluate its transition constraints in the
+//! circuit, and can't use auxiliary, preprocessed or periodic columns. Inner proofs with grinding
+//! are not supported.
+
+pub mod air;
+pub mod circuit;
+pub mod errors;
+pub mod hash;
+pub mod transcript;
+mod verifier_circuit;
+
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::traits::AsBytes;
+
+use crate::{
+    examples::simple_fibonacci::FibonacciAIR,
+    proof::{options::ProofOptions, stark::StarkProof},
+    prover::{IsStarkProver, Prover},
+    traits::AIR,
+    verifier::{IsStarkVerifier, StarkVerifier, Verifier},
+    PrimeField,
+};
+
+use self::{
+    air::{inner_air, RecursionAIR, RecursionPublicInputs},
+    circuit::{CircuitBuilder, Var},
+    errors::RecursionError,
+    hash::PoseidonCommitments,
+    transcript::PoseidonTranscript,
+    verifier_circuit::{build_verifier_circuit, proof_inputs},
+};
+
+/// An AIR over `Stark252PrimeField` whose proofs can be verified recursively.
+pub trait RecursiveAIR: AIR<Field = PrimeField, FieldExtension = PrimeField> {
+    /// Adds to `builder` the evaluations of the transition constraints on the out-of-domain
+    /// frame, where `frame[i][col]` holds the evaluation of the column `col` at the i-th row of
+    /// the frame. The evaluations are returned indexed by `constraint_idx`, as
+    /// `AIR::compute_transition` does.
+    fn evaluate_transitions_in_circuit(
+        &self,
+        builder: &mut CircuitBuilder,
+        frame: &[Vec<Var>],
+    ) -> Vec<Var>;
+}
+
+/// The transition constraint of the Fibonacci example, `a2 - a1 - a0`, so that its proofs can be
+/// verified recursively.
+impl RecursiveAIR for FibonacciAIR<PrimeField> {
+    fn evaluate_transitions_in_circuit(
+        &self,
+        builder: &mut CircuitBuilder,
+        frame: &[Vec<Var>],
+    ) -> Vec<Var> {
+        let (a0, a1, a2) = (frame[0][0], frame[1][0], frame[2][0]);
+        let difference = builder.sub(a2, a1);
+        vec![builder.sub(difference, a0)]
+    }
+}
+
+/// Checks that the verifier circuit supports the inner AIR `air`.
+fn check_supported<A: RecursiveAIR>(air: &A) -> Result<(), RecursionError> {
+    let unsupported = |feature: &str| Err(RecursionError::UnsupportedAir(feature.to_string()));
+    if air.has_trace_interaction() {
+        return unsupported("auxiliary trace columns");
+    }
+    if air.num_preprocessed_columns() > 0 {
+        return unsupported("preprocessed columns");
+    }
+    if !air.get_periodic_column_values().is_empty() {
+        return unsupported("periodic columns");
+    }
+    if air
+        .transition_constraints()
+        .iter()
+        .any(|constraint| constraint.exemptions_period().is_some())
+    {
+        return unsupported("periodic exemptions of transition constraints");
+    }
+
+    let options = air.options();
+    let trace_length = air.trace_length();
+    let blowup_factor = options.blowup_factor as usize;
+    if options.grinding_factor > 0 || options.commit_phase_grinding_factor > 0 {
+        return Err(RecursionError::UnsupportedProofOptions(
+            "grinding".to_string(),
+        ));
+    }
+    if !trace_length.is_power_of_two()
+        || trace_length < 2
+        || !blowup_factor.is_power_of_two()
+        || blowup_factor < 2
+    {
+        return Err(RecursionError::UnsupportedProofOptions(format!(
+            "trace length {trace_length} with blowup factor {blowup_factor}"
+        )));
+    }
+    Ok(())
+}
+
+/// Proves that `inner_proof` is a valid proof of the inner AIR `A` for `inner_public_inputs`,
+/// generated with [`IsStarkProver::prove_statement`] using [`PoseidonCommitments`] and
+/// `PoseidonTranscript::new(&[])`. Returns the outer proof together with the public inputs it is
+/// verified against.
+pub fn prove_recursive<A>(
+    inner_proof: &StarkProof<PrimeField, PrimeField>,
+    inner_public_inputs: &A::PublicInputs,
+    inner_proof_options: &ProofOptions,
+    proof_options: &ProofOptions,
+    transcript: impl IsTranscript<PrimeField>,
+) -> Result<(StarkProof<PrimeField, PrimeField>, RecursionPublicInputs<A>), RecursionError>
+where
+    A: RecursiveAIR + Send + Sync,
+    A::PublicInputs: Clone + AsBytes + Send + Sync,
+{
+    StarkVerifier::<A, PoseidonCommitments>::verify_statement(
+        inner_proof,
+        inner_public_inputs,
+        inner_proof_options,
+        PoseidonTranscript::new(&[]),
+    )
+    .map_err(RecursionError::InvalidInnerProof)?;
+
+    let pub_inputs = RecursionPublicInputs {
+        inner_public_inputs: inner_public_inputs.clone(),
+        inner_trace_length: inner_proof.trace_length,
+        inner_proof_options: inner_proof_options.clone(),
+    };
+    let inner_air = inner_air(&pub_inputs);
+    check_supported(&inner_air)?;
+
+    let (circuit, _) = build_verifier_circuit(&inner_air);
+    let air = RecursionAIR::new(circuit.trace_length(), &pub_inputs, proof_options);
+    let witness = air
+        .circuit()
+        .generate_witness(
+            air.circuit_public_inputs(),
+            &proof_inputs(air.proof_shape(), inner_proof)?,
+        )
+        .map_err(RecursionError::Circuit)?;
+    let mut trace = air.build_trace(&witness);
+
+    let proof = Prover::<RecursionAIR<A>>::prove_statement(
+        &mut trace,
+        &pub_inputs,
+        proof_options,
+        transcript,
+    )
+    .map_err(RecursionError::Proving)?;
+    Ok((proof, pub_inputs))
+}
+
+/// Verifies a proof generated with [`prove_recursive`], which attests that there is a valid
+/// proof of the inner AIR `A` for the statement in `pub_inputs`.
+pub fn verify_recursive<A>(
+    proof: &StarkProof<PrimeField, PrimeField>,
+    pub_inputs: &RecursionPublicInputs<A>,
+    proof_options: &ProofOptions,
+    transcript: impl IsTranscript<PrimeField>,
+) -> Result<(), RecursionError>
+where
+    A: RecursiveAIR,
+    A::PublicInputs: Clone + AsBytes,
+{
+    check_supported(&inner_air(pub_inputs))?;
+    Verifier::<RecursionAIR<A>>::verify_statement(proof, pub_inputs, proof_options, transcript)
+        .map_err(RecursionError::Verification)
+}
@@ -0,0 +1,167 @@
+use crate::{Felt252, PrimeField};
+use lambdaworks_crypto::{
+    fiat_shamir::is_transcript::IsTranscript,
+    hash::poseidon::{starknet::PoseidonCairoStark252, Poseidon},
+};
+
+use super::hash::{
+    canonical_commitment_to_field_element, commitment_to_field_element, field_element_to_commitment,
+};
+
+/// Number of field elements absorbed by each permutation of the sponge.
+pub(crate) const RATE: usize = 2;
+
+/// A transcript implementing `IsTranscript` over `Stark252PrimeField` with a Poseidon sponge of
+/// width 3 and rate 2. Field elements are absorbed as they are, which makes the transcript cheap
+/// to replay inside an arithmetic circuit.
+///
+/// Byte strings are absorbed as the field element `2 * len + mode` followed by their chunks.
+/// When the length is a multiple of 32 and every 32-byte big-endian chunk is a canonical field
+/// element, as is the case for the roots of Poseidon Merkle trees, `mode` is 0 and each chunk is
+/// one element. Otherwise `mode` is 1 and the bytes are split into chunks of 31 bytes.
+/// The sponge is permuted after each byte string, and the capacity is incremented by one plus
+/// the number of pending elements before each sampled element.
+#[derive(Clone)]
+pub struct PoseidonTranscript {
+    state: [Felt252; 3],
+    pending: Vec<Felt252>,
+}
+
+impl PoseidonTranscript {
+    pub fn new(data: &[u8]) -> Self {
+        let mut transcript = Self {
+            state: [Felt252::zero(); 3],
+            pending: Vec::with_capacity(RATE),
+        };
+        transcript.append_bytes(data);
+        transcript
+    }
+
+    /// Returns the state of the sponge, or `None` if there are elements not absorbed yet.
+    pub(crate) fn sponge_state(&self) -> Option<[Felt252; 3]> {
+        self.pending.is_empty().then_some(self.state)
+    }
+
+    fn absorb(&mut self, element: Felt252) {
+        if self.pending.len() == RATE {
+            self.flush();
+        }
+        self.pending.push(element);
+    }
+
+    fn flush(&mut self) {
+        for (state, element) in self.state.iter_mut().zip(self.pending.drain(..)) {
+            *state += element;
+        }
+        PoseidonCairoStark252::hades_permutation(&mut self.state);
+    }
+}
+
+/// Returns the field elements absorbed by [`PoseidonTranscript`] for the byte string `bytes`.
+pub(crate) fn bytes_to_field_elements(bytes: &[u8]) -> Vec<Felt252> {
+    let canonical_chunks: Option<Vec<Felt252>> = if bytes.len() % 32 == 0 {
+        bytes
+            .chunks(32)
+            .map(|chunk| canonical_commitment_to_field_element(chunk.try_into().unwrap()))
+            .collect()
+    } else {
+        None
+    };
+
+    let length = Felt252::from(bytes.len() as u64);
+    match canonical_chunks {
+        Some(chunks) => core::iter::once(length + length).chain(chunks).collect(),
+        None => core::iter::once(length + length + Felt252::one())
+            .chain(bytes.chunks(31).map(|chunk| {
+                let mut padded = [0u8; 32];
+                padded[32 - chunk.len()..].copy_from_slice(chunk);
+                commitment_to_field_element(&padded)
+            }))
+            .collect(),
+    }
+}
+
+impl IsTranscript<PrimeField> for PoseidonTranscript {
+    fn append_field_element(&mut self, element: &Felt252) {
+        self.absorb(*element);
+    }
+
+    fn append_bytes(&mut self, new_bytes: &[u8]) {
+        for element in bytes_to_field_elements(new_bytes) {
+            self.absorb(element);
+        }
+        self.flush();
+    }
+
+    fn state(&self) -> [u8; 32] {
+        let elements: Vec<Felt252> = self.state.iter().chain(&self.pending).copied().collect();
+        field_element_to_commitment(&PoseidonCairoStark252::hash_many(&elements))
+    }
+
+    fn sample_field_element(&mut self) -> Felt252 {
+        let increment = Felt252::from(self.pending.len() as u64 + 1);
+        for (state, element) in self.state.iter_mut().zip(self.pending.drain(..)) {
+            *state += element;
+        }
+        self.state[2] += increment;
+        PoseidonCairoStark252::hades_permutation(&mut self.state);
+        self.state[0]
+    }
+
+    fn sample_u64(&mut self, upper_bound: u64) -> u64 {
+        self.sample_field_element().representative().limbs[3] % upper_bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transcripts_with_the_same_history_agree() {
+        let mut transcript_1 = PoseidonTranscript::new(b"statement");
+        let mut transcript_2 = PoseidonTranscript::new(b"statement");
+        for transcript in [&mut transcript_1, &mut transcript_2] {
+            transcript.append_field_element(&Felt252::from(7));
+            transcript.append_bytes(&[1, 2, 3]);
+        }
+
+        assert_eq!(transcript_1.state(), transcript_2.state());
+        assert_eq!(
+            transcript_1.sample_field_element(),
+            transcript_2.sample_field_element()
+        );
+    }
+
+    #[test]
+    fn pending_elements_change_the_samples() {
+        let mut transcript_1 = PoseidonTranscript::new(&[]);
+        let mut transcript_2 = PoseidonTranscript::new(&[]);
+        transcript_2.append_field_element(&Felt252::zero());
+
+        assert_ne!(transcript_1.state(), transcript_2.state());
+        assert_ne!(
+            transcript_1.sample_field_element(),
+            transcript_2.sample_field_element()
+        );
+    }
+
+    #[test]
+    fn byte_encodings_are_unambiguous() {
+        let element = Felt252::from(5);
+        let canonical = field_element_to_commitment(&element);
+
+        assert_eq!(
+            bytes_to_field_elements(&canonical),
+            vec![Felt252::from(64), element]
+        );
+        assert_ne!(
+            bytes_to_field_elements(&[0xff; 32])[0],
+            bytes_to_field_elements(&canonical)[0]
+        );
+        assert_ne!(
+            bytes_to_field_elements(&[0, 1]),
+            bytes_to_field_elements(&[1])
+        );
+    }
+}
@@ -0,0 +1,622 @@
+use lambdaworks_crypto::merkle_tree::proof::Proof;
+use lambdaworks_math::field::traits::IsFFTField;
+
+use crate::{config::Commitment, proof::stark::StarkProof, traits::AIR, Felt252, PrimeField};
+
+use super::{
+    circuit::{Circuit, CircuitBuilder, Var, NUM_WIRES},
+    errors::RecursionError,
+    hash::{canonical_commitment_to_field_element, commitment_to_field_element},
+    transcript::RATE,
+    RecursiveAIR,
+};
+
+/// Number of bits of the decomposition of the elements sampled to get the query indexes. Every
+/// canonical element smaller than `2^251` has a unique decomposition, and the sample is larger
+/// with a negligible probability, in which case the recursive proof can't be generated.
+const SAMPLE_BITS: usize = 251;
+
+/// The sizes of the parts of a proof of an inner AIR, which determine the layout of the verifier
+/// circuit together with the constraints of the AIR.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ProofShape {
+    trace_width: usize,
+    ood_height: usize,
+    num_parts: usize,
+    num_fri_layers: usize,
+    lde_log_size: usize,
+    num_queries: usize,
+}
+
+impl ProofShape {
+    pub(crate) fn new<A: AIR>(air: &A) -> Self {
+        let trace_length = air.trace_length();
+        let lde_size = trace_length * air.blowup_factor() as usize;
+        Self {
+            trace_width: air.context().trace_columns,
+            ood_height: air.context().transition_offsets.len() * A::STEP_SIZE,
+            num_parts: air.composition_poly_degree_bound() / trace_length,
+            num_fri_layers: (trace_length.trailing_zeros() as usize).saturating_sub(1),
+            lde_log_size: lde_size.trailing_zeros() as usize,
+            num_queries: air.options().fri_number_of_queries,
+        }
+    }
+
+    /// Depth of the trees committing to the FRI layer `layer`, starting from 0 for the first
+    /// layer after the deep composition polynomial.
+    fn fri_layer_depth(&self, layer: usize) -> usize {
+        self.lde_log_size - 2 - layer
+    }
+}
+
+/// The variables holding the openings of a query of the inner proof.
+struct QueryVars {
+    main_evaluations: Vec<Var>,
+    main_evaluations_sym: Vec<Var>,
+    main_path: Vec<Var>,
+    main_path_sym: Vec<Var>,
+    composition_evaluations: Vec<Var>,
+    composition_evaluations_sym: Vec<Var>,
+    composition_path: Vec<Var>,
+    fri_evaluations_sym: Vec<Var>,
+    fri_paths: Vec<Vec<Var>>,
+}
+
+impl QueryVars {
+    fn allocate(builder: &mut CircuitBuilder, shape: &ProofShape) -> Self {
+        Self {
+            main_evaluations: builder.inputs(shape.trace_width),
+            main_evaluations_sym: builder.inputs(shape.trace_width),
+            main_path: builder.inputs(shape.lde_log_size),
+            main_path_sym: builder.inputs(shape.lde_log_size),
+            composition_evaluations: builder.inputs(shape.num_parts),
+            composition_evaluations_sym: builder.inputs(shape.num_parts),
+            composition_path: builder.inputs(shape.lde_log_size - 1),
+            fri_evaluations_sym: builder.inputs(shape.num_fri_layers),
+            fri_paths: (0..shape.num_fri_layers)
+                .map(|layer| builder.inputs(shape.fri_layer_depth(layer)))
+                .collect(),
+        }
+    }
+}
+
+/// The variables holding the inner proof, allocated in the order of [`proof_inputs`].
+struct ProofVars {
+    main_root: Var,
+    composition_root: Var,
+    fri_roots: Vec<Var>,
+    /// The out-of-domain evaluations of the trace, indexed by row and then by column.
+    trace_ood: Vec<Vec<Var>>,
+    composition_ood: Vec<Var>,
+    fri_last_value: Var,
+    queries: Vec<QueryVars>,
+}
+
+impl ProofVars {
+    fn allocate(builder: &mut CircuitBuilder, shape: &ProofShape) -> Self {
+        Self {
+            main_root: builder.input(),
+            composition_root: builder.input(),
+            fri_roots: builder.inputs(shape.num_fri_layers),
+            trace_ood: (0..shape.ood_height)
+                .map(|_| builder.inputs(shape.trace_width))
+                .collect(),
+            composition_ood: builder.inputs(shape.num_parts),
+            fri_last_value: builder.input(),
+            queries: (0..shape.num_queries)
+                .map(|_| QueryVars::allocate(builder, shape))
+                .collect(),
+        }
+    }
+}
+
+/// Replays a [`PoseidonTranscript`](super::transcript::PoseidonTranscript) in a circuit.
+struct TranscriptGadget {
+    state: [Var; NUM_WIRES],
+    pending: Vec<Var>,
+}
+
+impl TranscriptGadget {
+    fn absorb(&mut self, builder: &mut CircuitBuilder, element: Var) {
+        if self.pending.len() == RATE {
+            self.flush(builder);
+        }
+        self.pending.push(element);
+    }
+
+    fn add_pending(&mut self, builder: &mut CircuitBuilder) {
+        for (i, element) in self.pending.drain(..).enumerate() {
+            self.state[i] = builder.add_unless_zero(self.state[i], element);
+        }
+    }
+
+    fn flush(&mut self, builder: &mut CircuitBuilder) {
+        self.add_pending(builder);
+        self.state = builder.permutation(self.state);
+    }
+
+    fn append_field_element(&mut self, builder: &mut CircuitBuilder, element: Var) {
+        self.absorb(builder, element);
+    }
+
+    /// Appends the root of a Poseidon Merkle tree, whose bytes are the canonical encoding of the
+    /// element held by `root`.
+    fn append_commitment(&mut self, builder: &mut CircuitBuilder, root: Var) {
+        let prefix = builder.constant(Felt252::from(64));
+        self.absorb(builder, prefix);
+        self.absorb(builder, root);
+        self.flush(builder);
+    }
+
+    fn sample_field_element(&mut self, builder: &mut CircuitBuilder) -> Var {
+        let increment = Felt252::from(self.pending.len() as u64 + 1);
+        self.add_pending(builder);
+        self.state[2] = builder.add_constant(self.state[2], increment);
+        self.state = builder.permutation(self.state);
+        self.state[0]
+    }
+}
+
+/// Returns the powers `1, x, ..., x^(n - 1)`.
+fn powers(builder: &mut CircuitBuilder, x: Var, n: usize) -> Vec<Var> {
+    let mut powers = vec![builder.constant(Felt252::one())];
+    while powers.len() < n {
+        let power = builder.mul(*powers.last().unwrap(), x);
+        powers.push(power);
+    }
+    powers.truncate(n);
+    powers
+}
+
+/// Returns `sum_i a_i * b_i`.
+fn inner_product(builder: &mut CircuitBuilder, a: &[Var], b: &[Var]) -> Var {
+    a.iter().zip(b).fold(Var::ZERO, |accumulator, (a, b)| {
+        let term = builder.mul(*a, *b);
+        builder.add_unless_zero(accumulator, term)
+    })
+}
+
+/// Builds the circuit running the STARK verifier on a proof of `air` made with Poseidon
+/// commitments and a [`PoseidonTranscript`](super::transcript::PoseidonTranscript).
+///
+/// The public inputs of the circuit are the values of the boundary constraints of `air`
+/// followed by the state of the transcript after binding the statement, so the layout of the
+/// circuit depends only on the shape of the proof and on the constraints of the AIR.
+/// The out-of-domain challenge is the first element sampled for it: the native verifier samples
+/// again if it falls in the trace or LDE domains, which happens with negligible probability.
+pub(crate) fn build_verifier_circuit<A: RecursiveAIR>(air: &A) -> (Circuit, ProofShape) {
+    let shape = ProofShape::new(air);
+    let mut builder = CircuitBuilder::new();
+
+    let boundary_constraints = air.boundary_constraints(&[]).constraints;
+    let boundary_values: Vec<Var> = boundary_constraints
+        .iter()
+        .map(|_| builder.public_input())
+        .collect();
+    let state = [(); NUM_WIRES].map(|_| builder.public_input());
+    let proof = ProofVars::allocate(&mut builder, &shape);
+    let b = &mut builder;
+
+    // Replay the rounds of the protocol to recover the challenges.
+    let mut transcript = TranscriptGadget {
+        state,
+        pending: Vec::with_capacity(RATE),
+    };
+    transcript.append_commitment(b, proof.main_root);
+    let beta = transcript.sample_field_element(b);
+    transcript.append_commitment(b, proof.composition_root);
+    let z = transcript.sample_field_element(b);
+    for col in 0..shape.trace_width {
+        for row in proof.trace_ood.iter() {
+            transcript.append_field_element(b, row[col]);
+        }
+    }
+    for element in proof.composition_ood.iter() {
+        transcript.append_field_element(b, *element);
+    }
+    let gamma = transcript.sample_field_element(b);
+    let mut zetas = Vec::with_capacity(shape.num_fri_layers + 1);
+    for root in proof.fri_roots.iter() {
+        zetas.push(transcript.sample_field_element(b));
+        transcript.append_commitment(b, *root);
+    }
+    zetas.push(transcript.sample_field_element(b));
+    transcript.append_field_element(b, proof.fri_last_value);
+    let iotas_bits: Vec<Vec<Var>> = (0..shape.num_queries)
+        .map(|_| {
+            let sample = transcript.sample_field_element(b);
+            let mut bits = b.to_bits(sample, SAMPLE_BITS);
+            bits.truncate(shape.lde_log_size - 1);
+            bits
+        })
+        .collect();
+
+    // Check the out-of-domain evaluation of the composition polynomial.
+    let trace_length = air.trace_length();
+    let g = air.trace_primitive_root();
+    let num_transition_constraints = air.num_transition_constraints();
+    let betas = powers(
+        b,
+        beta,
+        num_transition_constraints + boundary_constraints.len(),
+    );
+
+    let mut composition_evaluation = Var::ZERO;
+    for ((constraint, value), beta) in boundary_constraints
+        .iter()
+        .zip(&boundary_values)
+        .zip(&betas[num_transition_constraints..])
+    {
+        let numerator = b.sub(proof.trace_ood[0][constraint.col], *value);
+        let denominator = b.add_constant(z, -g.pow(constraint.step));
+        let quotient = b.div(numerator, denominator);
+        let term = b.mul(quotient, *beta);
+        composition_evaluation = b.add_unless_zero(composition_evaluation, term);
+    }
+
+    let transition_evaluations = air.evaluate_transitions_in_circuit(b, &proof.trace_ood);
+    for constraint in air.transition_constraints().iter() {
+        let period = constraint.period();
+        let offset = g.pow(constraint.offset() * trace_length / period);
+        let z_power = b.pow(z, (trace_length / period) as u64);
+        let denominator = b.add_constant(z_power, -offset);
+        let mut zerofier = b.inverse(denominator);
+        for exemption in 1..=constraint.end_exemptions() {
+            let root = g.pow(trace_length - exemption * period);
+            let factor = b.add_constant(z, -root);
+            zerofier = b.mul(zerofier, factor);
+        }
+        let idx = constraint.constraint_idx();
+        let term = b.mul(transition_evaluations[idx], betas[idx]);
+        let term = b.mul(term, zerofier);
+        composition_evaluation = b.add_unless_zero(composition_evaluation, term);
+    }
+
+    let claimed_evaluation =
+        proof
+            .composition_ood
+            .iter()
+            .rev()
+            .fold(Var::ZERO, |accumulator, part| {
+                let shifted = b.mul(accumulator, z);
+                b.add(shifted, *part)
+            });
+    b.assert_equal(claimed_evaluation, composition_evaluation);
+
+    // Coefficients of the deep composition polynomial.
+    let num_trace_terms = shape.ood_height * shape.trace_width;
+    let gammas = powers(b, gamma, num_trace_terms + shape.num_parts);
+    let (trace_coefficients, composition_coefficients) = gammas.split_at(num_trace_terms);
+    let shifted_z: Vec<Var> = (0..shape.ood_height)
+        .map(|row| b.mul_constant(z, g.pow(row)))
+        .collect();
+    let z_power_parts = b.pow(z, shape.num_parts as u64);
+    let deep = DeepComposition {
+        trace_ood: &proof.trace_ood,
+        composition_ood: &proof.composition_ood,
+        trace_coefficients,
+        composition_coefficients,
+        shifted_z: &shifted_z,
+        z_power_parts,
+    };
+
+    let lde_root = PrimeField::get_primitive_root_of_unity(shape.lde_log_size as u64).unwrap();
+    let coset_offset = air.coset_offset();
+    let one = b.constant(Felt252::one());
+    for (query, bits) in proof.queries.iter().zip(&iotas_bits) {
+        // Openings of the trace and the composition polynomial.
+        let main_bits: Vec<Var> = core::iter::once(Var::ZERO).chain(bits.clone()).collect();
+        let main_bits_sym: Vec<Var> = core::iter::once(one).chain(bits.clone()).collect();
+        for (evaluations, path, index_bits) in [
+            (&query.main_evaluations, &query.main_path, &main_bits),
+            (
+                &query.main_evaluations_sym,
+                &query.main_path_sym,
+                &main_bits_sym,
+            ),
+        ] {
+            let leaf = b.hash_many(evaluations);
+            let root = b.merkle_root(leaf, path, index_bits);
+            b.assert_equal(root, proof.main_root);
+        }
+        let composition_leaf: Vec<Var> = query
+            .composition_evaluations
+            .iter()
+            .chain(&query.composition_evaluations_sym)
+            .copied()
+            .collect();
+        let leaf = b.hash_many(&composition_leaf);
+        let root = b.merkle_root(leaf, &query.composition_path, bits);
+        b.assert_equal(root, proof.composition_root);
+
+        // The evaluation point is `h * w^j`, where `j` is the bit reversal of `2 * iota`.
+        let mut x = b.constant(coset_offset);
+        for (t, bit) in bits.iter().enumerate() {
+            let root = lde_root.pow(1u64 << (shape.lde_log_size - 2 - t));
+            let zero = Felt252::zero();
+            let factor = b.gate(
+                *bit,
+                Var::ZERO,
+                root - Felt252::one(),
+                zero,
+                zero,
+                Felt252::one(),
+            );
+            x = b.mul(x, factor);
+        }
+        let x_sym = b.mul_constant(x, -Felt252::one());
+
+        let p0 = deep.evaluate(
+            b,
+            x,
+            &query.main_evaluations,
+            &query.composition_evaluations,
+        );
+        let p0_sym = deep.evaluate(
+            b,
+            x_sym,
+            &query.main_evaluations_sym,
+            &query.composition_evaluations_sym,
+        );
+
+        // FRI folding.
+        let mut x_inv = b.inverse(x);
+        let mut v = fold(b, p0, p0_sym, x_inv, zetas[0]);
+        for (layer, ((root, path), evaluation_sym)) in proof
+            .fri_roots
+            .iter()
+            .zip(&query.fri_paths)
+            .zip(&query.fri_evaluations_sym)
+            .enumerate()
+        {
+            let (left, right) = b.conditional_swap(bits[layer], v, *evaluation_sym);
+            let leaf = b.hash_many(&[left, right]);
+            let computed_root = b.merkle_root(leaf, path, &bits[layer + 1..]);
+            b.assert_equal(computed_root, *root);
+
+            x_inv = b.mul(x_inv, x_inv);
+            v = fold(b, v, *evaluation_sym, x_inv, zetas[layer + 1]);
+        }
+        b.assert_equal(v, proof.fri_last_value);
+    }
+
+    (builder.build(), shape)
+}
+
+/// Returns `(p + p_sym) + x_inv * zeta * (p - p_sym)`.
+fn fold(builder: &mut CircuitBuilder, p: Var, p_sym: Var, x_inv: Var, zeta: Var) -> Var {
+    let sum = builder.add(p, p_sym);
+    let difference = builder.sub(p, p_sym);
+    let coefficient = builder.mul(x_inv, zeta);
+    let term = builder.mul(coefficient, difference);
+    builder.add(sum, term)
+}
+
+/// The data needed to evaluate the deep composition polynomial from the openings of a query.
+struct DeepComposition<'a> {
+    trace_ood: &'a [Vec<Var>],
+    composition_ood: &'a [Var],
+    /// Coefficients of the trace terms, indexed by column and then by row.
+    trace_coefficients: &'a [Var],
+    composition_coefficients: &'a [Var],
+    /// The points `g^k * z` where the trace is evaluated out of domain.
+    shifted_z: &'a [Var],
+    z_power_parts: Var,
+}
+
+impl DeepComposition<'_> {
+    fn evaluate(
+        &self,
+        builder: &mut CircuitBuilder,
+        x: Var,
+        trace_evaluations: &[Var],
+        composition_evaluations: &[Var],
+    ) -> Var {
+        let height = self.shifted_z.len();
+        let denominators: Vec<Var> = self
+            .shifted_z
+            .iter()
+            .map(|shifted_z| {
+                let difference = builder.sub(x, *shifted_z);
+                builder.inverse(difference)
+            })
+            .collect();
+
+        let mut result = Var::ZERO;
+        for (col, evaluation) in trace_evaluations.iter().enumerate() {
+            for (row, denominator) in denominators.iter().enumerate() {
+                let difference = builder.sub(*evaluation, self.trace_ood[row][col]);
+                let quotient = builder.mul(difference, *denominator);
+                let term = builder.mul(quotient, self.trace_coefficients[col * height + row]);
+                result = builder.add_unless_zero(result, term);
+            }
+        }
+
+        let differences: Vec<Var> = composition_evaluations
+            .iter()
+            .zip(self.composition_ood)
+            .map(|(evaluation, ood)| builder.sub(*evaluation, *ood))
+            .collect();
+        let numerator = inner_product(builder, &differences, self.composition_coefficients);
+        let denominator = builder.sub(x, self.z_power_parts);
+        let composition_term = builder.div(numerator, denominator);
+        builder.add_unless_zero(result, composition_term)
+    }
+}
+
+/// Returns the values of the private inputs of the verifier circuit for `proof`, checking that
+/// the proof has the shape expected by the circuit.
+pub(crate) fn proof_inputs(
+    shape: &ProofShape,
+    proof: &StarkProof<PrimeField, PrimeField>,
+) -> Result<Vec<Felt252>, RecursionError> {
+    let malformed = |message: &str| RecursionError::MalformedProof(message.to_string());
+    let root = |commitment: &Commitment| {
+        canonical_commitment_to_field_element(commitment)
+            .ok_or_else(|| malformed("a Merkle root is not a canonical field element"))
+    };
+    let expect_length = |values: &[Felt252], length: usize, name: &str| {
+        if values.len() == length {
+            Ok(values.to_vec())
+        } else {
+            Err(malformed(&format!("{name} have an unexpected length")))
+        }
+    };
+    let path = |proof: &Proof<Commitment>, depth: usize| {
+        if proof.merkle_path.len() == depth {
+            // The Merkle tree backend reduces the nodes of the paths modulo the prime.
+            Ok(proof
+                .merkle_path
+                .iter()
+                .map(commitment_to_field_element)
+                .collect::<Vec<_>>())
+        } else {
+            Err(malformed("a Merkle path has an unexpected length"))
+        }
+    };
+
+    let ood = &proof.trace_ood_evaluations;
+    if ood.width != shape.trace_width || ood.height != shape.ood_height {
+        return Err(malformed(
+            "unexpected shape of the out-of-domain evaluations",
+        ));
+    }
+    if proof.fri_layers_merkle_roots.len() != shape.num_fri_layers {
+        return Err(malformed("unexpected number of FRI layers"));
+    }
+    if proof.query_list.len() < shape.num_queries
+        || proof.deep_poly_openings.len() < shape.num_queries
+    {
+        return Err(malformed("not enough queries"));
+    }
+
+    let mut values = vec![
+        root(&proof.lde_trace_main_merkle_root)?,
+        root(&proof.composition_poly_root)?,
+    ];
+    for fri_root in proof.fri_layers_merkle_roots.iter() {
+        values.push(root(fri_root)?);
+    }
+    values.extend(expect_length(
+        &ood.data,
+        shape.ood_height * shape.trace_width,
+        "out-of-domain evaluations",
+    )?);
+    values.extend(expect_length(
+        &proof.composition_poly_parts_ood_evaluation,
+        shape.num_parts,
+        "composition polynomial parts",
+    )?);
+    values.push(proof.fri_last_value);
+
+    for (opening, decommitment) in proof
+        .deep_poly_openings
+        .iter()
+        .zip(&proof.query_list)
+        .take(shape.num_queries)
+    {
+        let main = &opening.main_trace_polys;
+        let composition = &opening.composition_poly;
+        values.extend(expect_length(
+            &main.evaluations,
+            shape.trace_width,
+            "trace openings",
+        )?);
+        values.extend(expect_length(
+            &main.evaluations_sym,
+            shape.trace_width,
+            "trace openings",
+        )?);
+        values.extend(path(&main.proof, shape.lde_log_size)?);
+        values.extend(path(&main.proof_sym, shape.lde_log_size)?);
+        values.extend(expect_length(
+            &composition.evaluations,
+            shape.num_parts,
+            "composition polynomial openings",
+        )?);
+        values.extend(expect_length(
+            &composition.evaluations_sym,
+            shape.num_parts,
+            "composition polynomial openings",
+        )?);
+        values.extend(path(&composition.proof, shape.lde_log_size - 1)?);
+        values.extend(expect_length(
+            &decommitment.layers_evaluations_sym,
+            shape.num_fri_layers,
+            "FRI evaluations",
+        )?);
+        if decommitment.layers_auth_paths.len() != shape.num_fri_layers {
+            return Err(malformed("unexpected number of FRI authentication paths"));
+        }
+        for (layer, auth_path) in decommitment.layers_auth_paths.iter().enumerate() {
+            values.extend(path(auth_path, shape.fri_layer_depth(layer))?);
+        }
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        examples::simple_fibonacci::{fibonacci_trace, FibonacciAIR, FibonacciPublicInputs},
+        proof::options::ProofOptions,
+        prover::{IsStarkProver, StarkProver},
+        recursion::{
+            air::{circuit_public_inputs, RecursionPublicInputs},
+            hash::PoseidonCommitments,
+            transcript::PoseidonTranscript,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn verifier_circuit_is_satisfied_only_by_valid_proofs() {
+        let pub_inputs = FibonacciPublicInputs {
+            a0: Felt252::one(),
+            a1: Felt252::from(2),
+        };
+        let proof_options = ProofOptions {
+            blowup_factor: 4,
+            fri_number_of_queries: 1,
+            coset_offset: 3,
+            grinding_factor: 0,
+            commit_phase_grinding_factor: 0,
+        };
+        let mut trace = fibonacci_trace([pub_inputs.a0, pub_inputs.a1], 8);
+        let proof = StarkProver::<FibonacciAIR<PrimeField>, PoseidonCommitments>::prove_statement(
+            &mut trace,
+            &pub_inputs,
+            &proof_options,
+            PoseidonTranscript::new(&[]),
+        )
+        .unwrap();
+
+        let recursion_pub_inputs = RecursionPublicInputs::<FibonacciAIR<PrimeField>> {
+            inner_public_inputs: pub_inputs,
+            inner_trace_length: proof.trace_length,
+            inner_proof_options: proof_options.clone(),
+        };
+        let air = FibonacciAIR::new(8, &recursion_pub_inputs.inner_public_inputs, &proof_options);
+        let (circuit, shape) = build_verifier_circuit(&air);
+        let public_inputs = circuit_public_inputs(&air, &recursion_pub_inputs);
+        let inputs = proof_inputs(&shape, &proof).unwrap();
+        assert!(circuit.generate_witness(&public_inputs, &inputs).is_ok());
+
+        // Every value of the proof is checked by the circuit.
+        for i in 0..inputs.len() {
+            let mut tampered = inputs.clone();
+            tampered[i] += Felt252::one();
+            assert!(
+                circuit.generate_witness(&public_inputs, &tampered).is_err(),
+                "input {i} is not checked"
+            );
+        }
+
+        let mut wrong_public_inputs = public_inputs;
+        wrong_public_inputs[1] += Felt252::one();
+        assert!(circuit
+            .generate_witness(&wrong_public_inputs, &inputs)
+            .is_err());
+    }
+}
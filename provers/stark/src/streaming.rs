@@ -0,0 +1,749 @@
+//! A memory-bounded variant of the STARK prover.
+//!
+//! The default prover keeps the evaluations of every trace column over the LDE domain in memory,
+//! which takes `blowup_factor` times the memory of the trace. [`StreamingProver`] instead keeps
+//! only the polynomials interpolating the trace and evaluates them over the LDE domain in chunks
+//! of rows whose size is bounded by a memory budget:
+//!
+//! - The Merkle trees of the trace and the composition polynomial are built from leaves hashed
+//!   one chunk at a time.
+//! - The constraints are evaluated one chunk at a time, only over the subset of the LDE domain
+//!   needed to interpolate the composition polynomial.
+//! - The openings are computed by evaluating the polynomials at the queried points.
+//!
+//! The chunks are consecutive rows of the LDE in bit-reversed order, the order of the leaves of
+//! the Merkle trees. Each of them is a coset of a subgroup of the LDE domain, so it is evaluated
+//! with one FFT of its size per column. Chunks are never smaller than the trace, which keeps the
+//! frames of the transition constraints inside a single chunk.
+//!
+//! The proofs are identical to the ones of [`Prover`](crate::prover::Prover) with the same
+//! commitment hash, and are checked with the regular verifier. The DEEP composition polynomial,
+//! the FRI layers and the preprocessed columns are handled as in the default prover: their size
+//! doesn't depend on the width of the trace.
+
+use std::marker::PhantomData;
+
+use itertools::izip;
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_crypto::merkle_tree::{merkle::MerkleTree, traits::IsMerkleTreeBackend};
+use lambdaworks_math::fft::cpu::bit_reversing::{in_place_bit_reverse_permute, reverse_index};
+use lambdaworks_math::fft::errors::FFTError;
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsFFTField, IsField, IsSubFieldOf},
+};
+use lambdaworks_math::polynomial::Polynomial;
+use lambdaworks_math::traits::AsBytes;
+use log::info;
+#[cfg(feature = "parallel")]
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+use crate::config::{Commitment, IsCommitmentHash, Keccak256Commitments};
+#[cfg(debug_assertions)]
+use crate::debug::validate_trace;
+use crate::domain::Domain;
+use crate::frame::Frame;
+use crate::fri;
+use crate::preprocessed::PreprocessedTrace;
+use crate::proof::options::ProofOptions;
+use crate::proof::stark::{DeepPolynomialOpening, PolynomialOpenings, StarkProof};
+use crate::prover::{
+    merkle_proof, IsStarkProver, ProvingError, Round1CommitmentData, Round3, StarkProver,
+};
+use crate::trace::{columns2rows, get_trace_evaluations, LDETraceTable, TraceTable};
+use crate::traits::{TransitionEvaluationContext, AIR};
+use crate::transcript::bind_statement;
+
+/// A STARK prover whose memory usage for the evaluations over the LDE domain is bounded by a
+/// budget, see the [module documentation](self).
+pub struct StreamingProver<A: AIR, H = Keccak256Commitments> {
+    memory_budget: usize,
+    phantom: PhantomData<(A, H)>,
+}
+
+/// The LDE domain split into chunks of `chunk_size` consecutive rows in bit-reversed order.
+/// The chunk `c` is the coset `h * w^r * <w^n>` of the LDE domain `h * <w>`, where `n` is the
+/// number of chunks and `r` the bit-reversal of `c`.
+struct LdeChunks<'a, F: IsFFTField> {
+    domain: &'a Domain<F>,
+    chunk_size: usize,
+}
+
+impl<'a, F: IsFFTField> LdeChunks<'a, F> {
+    fn new(domain: &'a Domain<F>, chunk_size: usize) -> Self {
+        Self { domain, chunk_size }
+    }
+
+    fn num_chunks(&self) -> usize {
+        self.domain.lde_roots_of_unity_coset.len() / self.chunk_size
+    }
+
+    /// Returns the index in the LDE domain, in natural order, of the first point of `chunk`.
+    fn first_index(&self, chunk: usize) -> usize {
+        reverse_index(chunk, self.num_chunks() as u64)
+    }
+
+    /// Returns the points of `chunk` in natural order.
+    fn points(&self, chunk: usize) -> Vec<FieldElement<F>> {
+        let (first, step) = (self.first_index(chunk), self.num_chunks());
+        (0..self.chunk_size)
+            .map(|k| self.domain.lde_roots_of_unity_coset[first + k * step].clone())
+            .collect()
+    }
+
+    /// Returns the evaluations of `poly` over the points of `chunk` in natural order.
+    fn evaluate<E>(
+        &self,
+        poly: &Polynomial<FieldElement<E>>,
+        chunk: usize,
+    ) -> Result<Vec<FieldElement<E>>, FFTError>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        let offset = &self.domain.lde_roots_of_unity_coset[self.first_index(chunk)];
+        let mut scaled = poly.scale(offset);
+        // The points are the roots of unity of order `chunk_size` once scaled, so the
+        // coefficients can be reduced modulo `X^chunk_size - 1`.
+        if scaled.coeff_len() > self.chunk_size {
+            let mut folded = vec![FieldElement::zero(); self.chunk_size];
+            for (i, coefficient) in scaled.coefficients().iter().enumerate() {
+                folded[i % self.chunk_size] += coefficient.clone();
+            }
+            scaled = Polynomial::new(&folded);
+        }
+        Polynomial::evaluate_fft::<F>(&scaled, 1, Some(self.chunk_size))
+    }
+
+    /// Returns the rows of the evaluations of `polys` over `chunk`, in bit-reversed order.
+    fn bit_reversed_rows<E>(
+        &self,
+        polys: &[Polynomial<FieldElement<E>>],
+        chunk: usize,
+    ) -> Result<Vec<Vec<FieldElement<E>>>, FFTError>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        let mut columns = polys
+            .iter()
+            .map(|poly| self.evaluate(poly, chunk))
+            .collect::<Result<Vec<_>, _>>()?;
+        for column in columns.iter_mut() {
+            in_place_bit_reverse_permute(column);
+        }
+        Ok(columns2rows(columns))
+    }
+}
+
+/// The zerofier of a transition constraint, see `TransitionConstraint::evaluate_zerofier`.
+struct Zerofier<F: IsFFTField> {
+    period: usize,
+    offset: usize,
+    /// The period and offset of the periodic exemptions, if any.
+    exemptions: Option<(usize, usize)>,
+    end_exemptions: usize,
+    end_exemptions_poly: Polynomial<FieldElement<F>>,
+}
+
+impl<F: IsFFTField> Zerofier<F> {
+    /// Returns the evaluations of the zerofier over the points of `chunk`, as computed by
+    /// `TransitionConstraint::zerofier_evaluations_on_extended_domain`.
+    fn evaluate(
+        &self,
+        chunks: &LdeChunks<F>,
+        chunk: usize,
+    ) -> Result<Vec<FieldElement<F>>, FFTError> {
+        let trace_length = chunks.domain.interpolation_domain_size;
+        let root = &chunks.domain.trace_primitive_root;
+        let points = chunks.points(chunk);
+
+        let shift = root.pow(self.offset * trace_length / self.period);
+        let mut evaluations: Vec<_> = points
+            .iter()
+            .map(|x| x.pow(trace_length / self.period) - &shift)
+            .collect();
+        FieldElement::inplace_batch_inverse(&mut evaluations).unwrap();
+
+        if let Some((exemptions_period, exemptions_offset)) = self.exemptions {
+            let shift = root.pow(trace_length * exemptions_offset / exemptions_period);
+            for (evaluation, x) in evaluations.iter_mut().zip(&points) {
+                *evaluation = &*evaluation * (x.pow(trace_length / exemptions_period) - &shift);
+            }
+        }
+
+        let end_exemptions = chunks.evaluate(&self.end_exemptions_poly, chunk)?;
+        Ok(evaluations
+            .iter()
+            .zip(end_exemptions)
+            .map(|(evaluation, end_exemption)| evaluation * end_exemption)
+            .collect())
+    }
+}
+
+/// The zerofiers of the transition constraints. Constraints with the same zerofier share it.
+struct TransitionZerofiers<F: IsFFTField> {
+    zerofiers: Vec<Zerofier<F>>,
+    /// The index in `zerofiers` of the zerofier of each transition constraint.
+    constraint_zerofiers: Vec<usize>,
+}
+
+impl<F: IsFFTField> TransitionZerofiers<F> {
+    fn new<A: AIR<Field = F>>(air: &A, domain: &Domain<F>) -> Self
+    where
+        F: IsSubFieldOf<A::FieldExtension> + Send + Sync,
+    {
+        let mut zerofiers: Vec<Zerofier<F>> = Vec::new();
+        let mut constraint_zerofiers = vec![0; air.num_transition_constraints()];
+
+        for constraint in air.transition_constraints() {
+            let (period, offset, end_exemptions) = (
+                constraint.period(),
+                constraint.offset(),
+                constraint.end_exemptions(),
+            );
+            let exemptions = constraint
+                .exemptions_period()
+                .map(|period| (period, constraint.periodic_exemptions_offset().unwrap()));
+
+            let index = zerofiers
+                .iter()
+                .position(|zerofier| {
+                    (
+                        zerofier.period,
+                        zerofier.offset,
+                        zerofier.exemptions,
+                        zerofier.end_exemptions,
+                    ) == (period, offset, exemptions, end_exemptions)
+                })
+                .unwrap_or_else(|| {
+                    zerofiers.push(Zerofier {
+                        period,
+                        offset,
+                        exemptions,
+                        end_exemptions,
+                        end_exemptions_poly: constraint
+                            .end_exemptions_poly(&domain.trace_primitive_root, air.trace_length()),
+                    });
+                    zerofiers.len() - 1
+                });
+            constraint_zerofiers[constraint.constraint_idx()] = index;
+        }
+
+        Self {
+            zerofiers,
+            constraint_zerofiers,
+        }
+    }
+
+    /// Returns the evaluations of the distinct zerofiers over the points of `chunk`.
+    fn evaluate(
+        &self,
+        chunks: &LdeChunks<F>,
+        chunk: usize,
+    ) -> Result<Vec<Vec<FieldElement<F>>>, FFTError> {
+        self.zerofiers
+            .iter()
+            .map(|zerofier| zerofier.evaluate(chunks, chunk))
+            .collect()
+    }
+}
+
+impl<A, H> StreamingProver<A, H>
+where
+    A: AIR + Send + Sync,
+    A::FieldExtension: IsFFTField,
+    H: IsCommitmentHash<A::Field, A::FieldExtension>,
+    FieldElement<A::Field>: AsBytes + Send + Sync,
+    FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+{
+    /// Creates a prover that holds at most `memory_budget` bytes of evaluations over the LDE
+    /// domain at once. The chunks are never smaller than the trace, so a smaller budget is
+    /// rounded up to the size of a chunk of the length of the trace.
+    pub fn new(memory_budget: usize) -> Self {
+        Self {
+            memory_budget,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the number of rows of the LDE domain evaluated at once for `air`.
+    pub fn chunk_size(&self, air: &A) -> usize {
+        let (num_main_columns, num_aux_columns) = air.trace_layout();
+        let row_size = (num_main_columns + air.num_preprocessed_columns())
+            * core::mem::size_of::<FieldElement<A::Field>>()
+            + num_aux_columns * core::mem::size_of::<FieldElement<A::FieldExtension>>();
+        let lde_size = air.trace_length() * air.blowup_factor() as usize;
+
+        let mut chunk_size = air.trace_length();
+        while chunk_size < lde_size && 2 * chunk_size * row_size <= self.memory_budget {
+            chunk_size *= 2;
+        }
+        chunk_size
+    }
+
+    /// Returns the Merkle tree and the commitment to the evaluations of `polys` over the LDE
+    /// domain. Each leaf holds `rows_per_leaf` consecutive rows in bit-reversed order.
+    fn commit<E, B>(
+        chunks: &LdeChunks<A::Field>,
+        polys: &[Polynomial<FieldElement<E>>],
+        rows_per_leaf: usize,
+    ) -> Result<(MerkleTree<B>, Commitment), ProvingError>
+    where
+        E: IsField,
+        A::Field: IsSubFieldOf<E>,
+        B: IsMerkleTreeBackend<Node = Commitment, Data = Vec<FieldElement<E>>>,
+    {
+        let mut hashed_leaves = Vec::with_capacity(chunks.domain.lde_roots_of_unity_coset.len());
+        for chunk in 0..chunks.num_chunks() {
+            let rows = chunks.bit_reversed_rows(polys, chunk)?;
+            let leaves: Vec<_> = rows
+                .chunks(rows_per_leaf)
+                .map(|rows| rows.concat())
+                .collect();
+            hashed_leaves.extend(B::hash_leaves(&leaves));
+        }
+
+        let tree =
+            MerkleTree::from_hashed_leaves(hashed_leaves).ok_or(ProvingError::EmptyCommitment)?;
+        let root = tree.root;
+        Ok((tree, root))
+    }
+
+    /// Returns the evaluations of the composition polynomial over the first `size` rows of the
+    /// LDE domain in bit-reversed order, which form the coset of the subgroup of order `size`,
+    /// in natural order.
+    #[allow(clippy::too_many_arguments)]
+    fn evaluate_composition_polynomial(
+        air: &A,
+        chunks: &LdeChunks<A::Field>,
+        size: usize,
+        main_polys: &[Polynomial<FieldElement<A::Field>>],
+        aux_polys: &[Polynomial<FieldElement<A::FieldExtension>>],
+        rap_challenges: &[FieldElement<A::FieldExtension>],
+        transition_coefficients: &[FieldElement<A::FieldExtension>],
+        boundary_coefficients: &[FieldElement<A::FieldExtension>],
+    ) -> Result<Vec<FieldElement<A::FieldExtension>>, FFTError> {
+        let domain = chunks.domain;
+        let chunk_size = chunks.chunk_size;
+        let boundary_constraints = air.boundary_constraints(rap_challenges);
+        let periodic_polys = air.get_periodic_column_polynomials();
+        let zerofiers = TransitionZerofiers::new(air, domain);
+        let offsets = &air.context().transition_offsets;
+
+        let mut evaluations = Vec::with_capacity(size);
+        for chunk in 0..size / chunk_size {
+            let points = chunks.points(chunk);
+            let main_columns = main_polys
+                .iter()
+                .map(|poly| chunks.evaluate::<A::Field>(poly, chunk))
+                .collect::<Result<Vec<_>, _>>()?;
+            let aux_columns = aux_polys
+                .iter()
+                .map(|poly| chunks.evaluate(poly, chunk))
+                .collect::<Result<Vec<_>, _>>()?;
+            // The frames only read rows of this chunk, which are `chunk_size / trace_length`
+            // rows apart from one step of the trace to the next.
+            let lde_chunk = LDETraceTable::from_columns(
+                main_columns,
+                aux_columns,
+                A::STEP_SIZE,
+                chunk_size / domain.interpolation_domain_size,
+            );
+            let periodic_columns = periodic_polys
+                .iter()
+                .map(|poly| chunks.evaluate::<A::Field>(poly, chunk))
+                .collect::<Result<Vec<_>, _>>()?;
+            let zerofiers_evaluations = zerofiers.evaluate(chunks, chunk)?;
+            let boundary_zerofiers_inverses: Vec<_> = boundary_constraints
+                .constraints
+                .iter()
+                .map(|constraint| {
+                    let point = domain.trace_primitive_root.pow(constraint.step as u64);
+                    let mut evaluations: Vec<_> = points.iter().map(|x| x - &point).collect();
+                    FieldElement::inplace_batch_inverse(&mut evaluations).unwrap();
+                    evaluations
+                })
+                .collect();
+
+            #[cfg(feature = "parallel")]
+            let rows = (0..chunk_size).into_par_iter();
+            #[cfg(not(feature = "parallel"))]
+            let rows = 0..chunk_size;
+
+            let mut chunk_evaluations: Vec<_> = rows
+                .map(|row| {
+                    let frame = Frame::read_from_lde(&lde_chunk, row, offsets);
+                    let periodic_values: Vec<_> = periodic_columns
+                        .iter()
+                        .map(|column| column[row].clone())
+                        .collect();
+                    let context = TransitionEvaluationContext::new_prover(
+                        &frame,
+                        &periodic_values,
+                        rap_challenges,
+                    );
+
+                    let transition = izip!(
+                        air.compute_transition(&context),
+                        &zerofiers.constraint_zerofiers,
+                        transition_coefficients
+                    )
+                    .fold(
+                        FieldElement::zero(),
+                        |acc, (evaluation, zerofier, beta)| {
+                            acc + &zerofiers_evaluations[*zerofier][row] * evaluation * beta
+                        },
+                    );
+
+                    let boundary = izip!(
+                        &boundary_constraints.constraints,
+                        &boundary_zerofiers_inverses,
+                        boundary_coefficients
+                    )
+                    .fold(
+                        FieldElement::zero(),
+                        |acc, (constraint, inverses, beta)| {
+                            let difference = if constraint.is_aux {
+                                lde_chunk.get_aux(row, constraint.col) - &constraint.value
+                            } else {
+                                lde_chunk.get_main(row, constraint.col) - &constraint.value
+                            };
+                            acc + &inverses[row] * beta * difference
+                        },
+                    );
+
+                    transition + boundary
+                })
+                .collect();
+
+            in_place_bit_reverse_permute(&mut chunk_evaluations);
+            evaluations.extend(chunk_evaluations);
+        }
+
+        in_place_bit_reverse_permute(&mut evaluations);
+        Ok(evaluations)
+    }
+
+    /// Returns the openings of the evaluations of `polys` committed in `tree` at the point of
+    /// the LDE domain corresponding to the FRI query challenge `challenge` and its symmetric.
+    fn open_trace_polys<E, B>(
+        domain: &Domain<A::Field>,
+        tree: &MerkleTree<B>,
+        polys: &[Polynomial<FieldElement<E>>],
+        challenge: usize,
+    ) -> Result<PolynomialOpenings<E>, ProvingError>
+    where
+        E: IsField,
+        A::Field: IsSubFieldOf<E>,
+        B: IsMerkleTreeBackend<Node = Commitment>,
+    {
+        let domain_size = domain.lde_roots_of_unity_coset.len() as u64;
+        let evaluate = |index: usize| {
+            let x = domain.lde_roots_of_unity_coset[reverse_index(index, domain_size)]
+                .clone()
+                .to_extension::<E>();
+            polys.iter().map(|poly| poly.evaluate(&x)).collect()
+        };
+
+        let (index, index_sym) = (challenge * 2, challenge * 2 + 1);
+        Ok(PolynomialOpenings {
+            proof: merkle_proof(tree, index)?,
+            proof_sym: merkle_proof(tree, index_sym)?,
+            evaluations: evaluate(index),
+            evaluations_sym: evaluate(index_sym),
+        })
+    }
+
+    /// Generates a STARK proof for `trace` with public inputs `pub_inputs`, equal to the one of
+    /// [`IsStarkProver::prove`] for the commitment hash `H`.
+    /// Warning: the transcript must be safely initializated before passing it to this method.
+    /// Use `prove_statement` to have it initialized from the statement being proven.
+    pub fn prove(
+        &self,
+        trace: &mut TraceTable<A::Field, A::FieldExtension>,
+        pub_inputs: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        mut transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> Result<StarkProof<A::Field, A::FieldExtension>, ProvingError> {
+        info!("Started streaming proof generation...");
+
+        StarkProver::<A, H>::validate_proof_options(trace.num_rows(), proof_options)?;
+        let air = A::new(trace.num_rows(), pub_inputs, proof_options);
+        StarkProver::<A, H>::validate_trace_and_air(&air, trace)?;
+        let domain = Domain::new(&air);
+        let chunks = LdeChunks::new(&domain, self.chunk_size(&air));
+        info!(
+            "Evaluating the LDE domain in {} chunks of {} rows",
+            chunks.num_chunks(),
+            chunks.chunk_size
+        );
+
+        // ===================================
+        // ==========|   Round 1   |==========
+        // ===================================
+
+        let preprocessed = PreprocessedTrace::<A::Field, H::MainBackend>::new(&air, &domain)?;
+        if let Some(preprocessed) = &preprocessed {
+            transcript.append_bytes(&preprocessed.commitment());
+        }
+
+        let main_polys = trace.compute_trace_polys_main::<A::Field>()?;
+        let (main_merkle_tree, main_merkle_root) =
+            Self::commit::<A::Field, H::MainBackend>(&chunks, &main_polys, 1)?;
+        transcript.append_bytes(&main_merkle_root);
+        let main = Round1CommitmentData::<A::Field, H::MainBackend> {
+            trace_polys: main_polys,
+            lde_trace_merkle_tree: main_merkle_tree,
+            lde_trace_merkle_root: main_merkle_root,
+        };
+
+        let rap_challenges = air.build_rap_challenges(&mut transcript);
+        let aux = if air.has_trace_interaction() {
+            air.build_auxiliary_trace(trace, &rap_challenges);
+            let (num_main_columns, num_aux_columns) = air.trace_layout();
+            if trace.num_aux_columns != num_aux_columns {
+                return Err(ProvingError::TraceColumnsMismatch {
+                    expected: (num_main_columns, num_aux_columns),
+                    actual: (trace.num_main_columns, trace.num_aux_columns),
+                });
+            }
+            let aux_polys = trace.compute_trace_polys_aux::<A::Field>()?;
+            let (aux_merkle_tree, aux_merkle_root) = Self::commit(&chunks, &aux_polys, 1)?;
+            transcript.append_bytes(&aux_merkle_root);
+            Some(
+                Round1CommitmentData::<A::FieldExtension, H::ExtensionBackend> {
+                    trace_polys: aux_polys,
+                    lde_trace_merkle_tree: aux_merkle_tree,
+                    lde_trace_merkle_root: aux_merkle_root,
+                },
+            )
+        } else {
+            None
+        };
+        let aux_polys = aux
+            .as_ref()
+            .map(|aux| aux.trace_polys.clone())
+            .unwrap_or_default();
+
+        // The preprocessed columns are placed right after the main trace columns in the frames.
+        let main_and_preprocessed_polys: Vec<_> = main
+            .trace_polys
+            .iter()
+            .chain(
+                preprocessed
+                    .iter()
+                    .flat_map(|preprocessed| &preprocessed.polys),
+            )
+            .cloned()
+            .collect();
+
+        #[cfg(debug_assertions)]
+        validate_trace(
+            &air,
+            &main_and_preprocessed_polys,
+            &aux_polys,
+            &domain,
+            &rap_challenges,
+        );
+
+        // ===================================
+        // ==========|   Round 2   |==========
+        // ===================================
+
+        // <<<< Receive challenge: 𝛽
+        let beta = transcript.sample_field_element();
+        let num_boundary_constraints = air.boundary_constraints(&rap_challenges).constraints.len();
+        let num_transition_constraints = air.context().num_transition_constraints;
+
+        let mut coefficients: Vec<_> =
+            core::iter::successors(Some(FieldElement::one()), |x| Some(x * &beta))
+                .take(num_boundary_constraints + num_transition_constraints)
+                .collect();
+        let transition_coefficients: Vec<_> =
+            coefficients.drain(..num_transition_constraints).collect();
+        let boundary_coefficients = coefficients;
+
+        // The composition polynomial has degree smaller than its degree bound, so it is
+        // interpolated from its evaluations over a coset of that size rather than the whole LDE.
+        let degree_bound = air.composition_poly_degree_bound();
+        let interpolation_size = degree_bound.next_power_of_two();
+        let constraint_chunks = LdeChunks::new(&domain, chunks.chunk_size.min(interpolation_size));
+        let constraint_evaluations = Self::evaluate_composition_polynomial(
+            &air,
+            &constraint_chunks,
+            interpolation_size,
+            &main_and_preprocessed_polys,
+            &aux_polys,
+            &rap_challenges,
+            &transition_coefficients,
+            &boundary_coefficients,
+        )?;
+        let composition_poly =
+            Polynomial::interpolate_offset_fft(&constraint_evaluations, &domain.coset_offset)?;
+        drop(constraint_evaluations);
+
+        let number_of_parts = degree_bound / air.trace_length();
+        let composition_poly_parts = composition_poly.break_in_parts(number_of_parts);
+        drop(composition_poly);
+
+        // Each leaf holds two consecutive rows of the composition polynomial parts.
+        let (composition_poly_merkle_tree, composition_poly_root) =
+            Self::commit::<_, H::ExtensionBackend>(&chunks, &composition_poly_parts, 2)?;
+
+        // >>>> Send commitments: [H₁], [H₂]
+        transcript.append_bytes(&composition_poly_root);
+
+        // ===================================
+        // ==========|   Round 3   |==========
+        // ===================================
+
+        // <<<< Receive challenge: z
+        let z = transcript.sample_z_ood(
+            &domain.lde_roots_of_unity_coset,
+            &domain.trace_roots_of_unity,
+        );
+
+        let z_power = z.pow(composition_poly_parts.len());
+        let round_3_result = Round3 {
+            trace_ood_evaluations: get_trace_evaluations(
+                &main_and_preprocessed_polys,
+                &aux_polys,
+                &z,
+                &air.context().transition_offsets,
+                &domain.trace_primitive_root,
+                A::STEP_SIZE,
+            ),
+            composition_poly_parts_ood_evaluation: composition_poly_parts
+                .iter()
+                .map(|part| part.evaluate(&z_power))
+                .collect(),
+        };
+
+        // >>>> Send values: tⱼ(zgᵏ)
+        for col in round_3_result.trace_ood_evaluations.columns().iter() {
+            for elem in col.iter() {
+                transcript.append_field_element(elem);
+            }
+        }
+
+        // >>>> Send values: Hᵢ(z^N)
+        for element in round_3_result.composition_poly_parts_ood_evaluation.iter() {
+            transcript.append_field_element(element);
+        }
+
+        // ===================================
+        // ==========|   Round 4   |==========
+        // ===================================
+
+        let trace_polys: Vec<_> = main_and_preprocessed_polys
+            .iter()
+            .map(|poly| poly.clone().to_extension())
+            .chain(aux_polys.iter().cloned())
+            .collect();
+        let (fri_last_value, fri_layers, commit_phase_nonces, nonce, iotas) =
+            StarkProver::<A, H>::run_fri_on_the_deep_composition_polynomial(
+                &air,
+                &domain,
+                &trace_polys,
+                &composition_poly_parts,
+                &round_3_result,
+                &z,
+                &mut transcript,
+            )?;
+        drop(trace_polys);
+
+        let query_list = fri::query_phase(&fri_layers, &iotas)?;
+        let fri_layers_merkle_roots: Vec<_> = fri_layers
+            .iter()
+            .map(|layer| layer.merkle_tree.root)
+            .collect();
+        drop(fri_layers);
+
+        let domain_size = domain.lde_roots_of_unity_coset.len() as u64;
+        let deep_poly_openings = iotas
+            .iter()
+            .map(|iota| {
+                let composition_poly_proof = merkle_proof(&composition_poly_merkle_tree, *iota)?;
+                let evaluate_parts = |index: usize| {
+                    let x = &domain.lde_roots_of_unity_coset[reverse_index(index, domain_size)];
+                    composition_poly_parts
+                        .iter()
+                        .map(|part| part.evaluate(&x.clone().to_extension()))
+                        .collect()
+                };
+
+                Ok(DeepPolynomialOpening {
+                    composition_poly: PolynomialOpenings {
+                        proof: composition_poly_proof.clone(),
+                        proof_sym: composition_poly_proof,
+                        evaluations: evaluate_parts(iota * 2),
+                        evaluations_sym: evaluate_parts(iota * 2 + 1),
+                    },
+                    main_trace_polys: Self::open_trace_polys::<A::Field, _>(
+                        &domain,
+                        &main.lde_trace_merkle_tree,
+                        &main.trace_polys,
+                        *iota,
+                    )?,
+                    aux_trace_polys: aux
+                        .as_ref()
+                        .map(|aux| {
+                            Self::open_trace_polys(
+                                &domain,
+                                &aux.lde_trace_merkle_tree,
+                                &aux.trace_polys,
+                                *iota,
+                            )
+                        })
+                        .transpose()?,
+                    preprocessed_trace_polys: preprocessed
+                        .as_ref()
+                        .map(|preprocessed| {
+                            Self::open_trace_polys::<A::Field, _>(
+                                &domain,
+                                &preprocessed.merkle_tree,
+                                &preprocessed.polys,
+                                *iota,
+                            )
+                        })
+                        .transpose()?,
+                })
+            })
+            .collect::<Result<Vec<_>, ProvingError>>()?;
+
+        info!("End streaming proof generation");
+
+        Ok(StarkProof {
+            lde_trace_main_merkle_root: main.lde_trace_merkle_root,
+            lde_trace_aux_merkle_root: aux.map(|aux| aux.lde_trace_merkle_root),
+            trace_ood_evaluations: round_3_result.trace_ood_evaluations,
+            composition_poly_root,
+            composition_poly_parts_ood_evaluation: round_3_result
+                .composition_poly_parts_ood_evaluation,
+            fri_layers_merkle_roots,
+            fri_last_value,
+            query_list,
+            deep_poly_openings,
+            commit_phase_nonces,
+            nonce,
+            trace_length: air.trace_length(),
+        })
+    }
+
+    /// Generates a STARK proof for `trace` with public inputs `pub_inputs`, binding the
+    /// transcript to the statement being proven as [`IsStarkProver::prove_statement`] does.
+    pub fn prove_statement(
+        &self,
+        trace: &mut TraceTable<A::Field, A::FieldExtension>,
+        pub_inputs: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        mut transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> Result<StarkProof<A::Field, A::FieldExtension>, ProvingError>
+    where
+        A::PublicInputs: AsBytes,
+    {
+        bind_statement::<A>(&mut transcript, trace.num_rows(), pub_inputs, proof_options);
+        self.prove(trace, pub_inputs, proof_options, transcript)
+    }
+}
@@ -1,4 +1,5 @@
 use crate::frame::Frame;
+use alloc::vec::Vec;
 use lambdaworks_math::field::{
     element::FieldElement,
     traits::{IsField, IsSubFieldOf},
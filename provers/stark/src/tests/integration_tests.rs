@@ -1,26 +1,74 @@
-use lambdaworks_math::field::{
-    element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+use lambdaworks_math::{
+    field::{
+        element::FieldElement,
+        fields::{
+            fft_friendly::{
+                babybear::Babybear31PrimeField, stark_101_prime_field::Stark101PrimeField,
+                stark_252_prime_field::Stark252PrimeField,
+            },
+            mersenne31::field::Mersenne31Field,
+        },
+    },
+    traits::ByteConversion,
 };
 
 use crate::{
+    batch::{BatchProver, BatchVerifier},
+    cairo::{
+        air::{CairoAIR, CairoPublicInputs},
+        errors::CairoError,
+        execution::CairoExecution,
+        layout::{BITWISE_AND_SUFFIX, PEDERSEN_ACC_Y},
+        prove_cairo_execution, verify_cairo_execution,
+    },
+    circle::{
+        proof::CircleStarkProof, prover::CircleProver, transcript::CircleTranscript,
+        verifier::CircleVerifier,
+    },
+    config::Sha3_256Commitments,
     examples::{
         bit_flags::{self, BitFlagsAIR},
+        circle_fibonacci::{
+            circle_fibonacci_trace, CircleFibonacciAIR, CircleFibonacciPublicInputs,
+        },
         dummy_air::{self, DummyAIR},
         fibonacci_2_cols_shifted::{self, Fibonacci2ColsShifted},
         fibonacci_2_columns::{self, Fibonacci2ColsAIR},
         fibonacci_rap::{fibonacci_rap_trace, FibonacciRAP, FibonacciRAPPublicInputs},
         quadratic_air::{self, QuadraticAIR, QuadraticPublicInputs},
         read_only_memory::{sort_rap_trace, ReadOnlyPublicInputs, ReadOnlyRAP},
+        round_constants::{self, RoundConstantsAIR, RoundConstantsPublicInputs},
         simple_fibonacci::{self, FibonacciAIR, FibonacciPublicInputs},
         simple_periodic_cols::{self, SimplePeriodicAIR, SimplePeriodicPublicInputs}, //         simple_periodic_cols::{self, SimplePeriodicAIR, SimplePeriodicPublicInputs},
     },
-    proof::options::ProofOptions,
-    prover::{IsStarkProver, Prover},
-    transcript::StoneProverTranscript,
-    verifier::{IsStarkVerifier, Verifier},
+    padding::{
+        prove_padded, verify_padded, PaddedAIR, PaddedPublicInputs, PaddingError, PaddingMode,
+        PaddingRow,
+    },
+    preprocessed::VerifierKey,
+    proof::{
+        binary::{ProofEncodingError, PROOF_FORMAT_VERSION, PROOF_MAGIC},
+        options::ProofOptions,
+        stark::StarkProof,
+    },
+    prover::{IsStarkProver, Prover, ProvingError, StarkProver},
+    recursion::{
+        air::RecursionPublicInputs, errors::RecursionError, hash::PoseidonCommitments,
+        prove_recursive, transcript::PoseidonTranscript, verify_recursive,
+    },
+    streaming::StreamingProver,
+    trace::TraceTable,
+    traits::AIR,
+    transcript::{
+        algebraic::{Poseidon2BabyBearTranscript, PoseidonStark252Transcript},
+        StoneProverTranscript,
+    },
+    verifier::{IsStarkVerifier, StarkVerifier, VerificationError, Verifier},
     Felt252,
 };
 
+type FpE = FieldElement<Mersenne31Field>;
+
 #[test_log::test]
 fn test_prove_fib() {
     let mut trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
@@ -44,7 +92,8 @@ fn test_prove_fib() {
         &pub_inputs,
         &proof_options,
         StoneProverTranscript::new(&[]),
-    ));
+    )
+    .is_ok());
 }
 
 #[test_log::test]
@@ -70,7 +119,8 @@ fn test_prove_simple_periodic_8() {
         &pub_inputs,
         &proof_options,
         StoneProverTranscript::new(&[]),
-    ));
+    )
+    .is_ok());
 }
 
 #[test_log::test]
@@ -96,7 +146,97 @@ fn test_prove_simple_periodic_32() {
         &pub_inputs,
         &proof_options,
         StoneProverTranscript::new(&[]),
-    ));
+    )
+    .is_ok());
+}
+
+#[test_log::test]
+fn test_prove_round_constants_with_preprocessed_column() {
+    let mut trace =
+        round_constants::round_constants_trace::<Stark252PrimeField>(Felt252::from(3), 16);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = RoundConstantsPublicInputs {
+        input: Felt252::from(3),
+        output: *trace.get_main(15, 0),
+    };
+
+    let proof = Prover::<RoundConstantsAIR<Stark252PrimeField>>::prove(
+        &mut trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::<RoundConstantsAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .is_ok());
+
+    let air = RoundConstantsAIR::new(proof.trace_length, &pub_inputs, &proof_options);
+    let verifier_key = VerifierKey::new(&air).unwrap();
+    assert!(verifier_key.preprocessed_commitment.is_some());
+    assert!(
+        Verifier::<RoundConstantsAIR<Stark252PrimeField>>::verify_with_key(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            &verifier_key,
+            StoneProverTranscript::new(&[]),
+        )
+        .is_ok()
+    );
+}
+
+#[test_log::test]
+fn test_round_constants_proof_is_rejected_with_wrong_verifier_key() {
+    let mut trace =
+        round_constants::round_constants_trace::<Stark252PrimeField>(Felt252::from(3), 16);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = RoundConstantsPublicInputs {
+        input: Felt252::from(3),
+        output: *trace.get_main(15, 0),
+    };
+
+    let proof = Prover::<RoundConstantsAIR<Stark252PrimeField>>::prove(
+        &mut trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+
+    let missing_key = VerifierKey::default();
+    assert!(
+        Verifier::<RoundConstantsAIR<Stark252PrimeField>>::verify_with_key(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            &missing_key,
+            StoneProverTranscript::new(&[]),
+        )
+        .is_err()
+    );
+
+    let wrong_key = VerifierKey {
+        preprocessed_commitment: Some([0u8; 32]),
+    };
+    assert!(
+        Verifier::<RoundConstantsAIR<Stark252PrimeField>>::verify_with_key(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            &wrong_key,
+            StoneProverTranscript::new(&[]),
+        )
+        .is_err()
+    );
 }
 
 #[test_log::test]
@@ -122,7 +262,8 @@ fn test_prove_fib_2_cols() {
         &pub_inputs,
         &proof_options,
         StoneProverTranscript::new(&[])
-    ));
+    )
+    .is_ok());
 }
 
 #[test_log::test]
@@ -150,7 +291,8 @@ fn test_prove_fib_2_cols_shifted() {
         &pub_inputs,
         &proof_options,
         StoneProverTranscript::new(&[])
-    ));
+    )
+    .is_ok());
 }
 
 #[test_log::test]
@@ -175,7 +317,8 @@ fn test_prove_quadratic() {
         &pub_inputs,
         &proof_options,
         StoneProverTranscript::new(&[])
-    ));
+    )
+    .is_ok());
 }
 
 #[test_log::test]
@@ -203,7 +346,8 @@ fn test_prove_rap_fib() {
         &pub_inputs,
         &proof_options,
         StoneProverTranscript::new(&[])
-    ));
+    )
+    .is_ok());
 }
 
 #[test_log::test]
@@ -225,7 +369,8 @@ fn test_prove_dummy() {
         &(),
         &proof_options,
         StoneProverTranscript::new(&[])
-    ));
+    )
+    .is_ok());
 }
 
 #[test_log::test]
@@ -246,7 +391,8 @@ fn test_prove_bit_flags() {
         &(),
         &proof_options,
         StoneProverTranscript::new(&[]),
-    ));
+    )
+    .is_ok());
 }
 
 #[test_log::test]
@@ -292,5 +438,1699 @@ fn test_prove_read_only_memory() {
         &pub_inputs,
         &proof_options,
         StoneProverTranscript::new(&[])
+    )
+    .is_ok());
+}
+
+fn fibonacci_proof_and_inputs() -> (
+    StarkProof<Stark252PrimeField, Stark252PrimeField>,
+    FibonacciPublicInputs<Stark252PrimeField>,
+    ProofOptions,
+) {
+    let mut trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
+    let proof_options = ProofOptions::default_test_options();
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+    let proof = Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+        &mut trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    (proof, pub_inputs, proof_options)
+}
+
+fn verify_fibonacci(
+    proof: &StarkProof<Stark252PrimeField, Stark252PrimeField>,
+    pub_inputs: &FibonacciPublicInputs<Stark252PrimeField>,
+    proof_options: &ProofOptions,
+) -> Result<(), VerificationError> {
+    Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        proof,
+        pub_inputs,
+        proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+}
+
+#[test_log::test]
+fn test_verify_rejects_proof_with_insufficient_queries() {
+    let (mut proof, pub_inputs, proof_options) = fibonacci_proof_and_inputs();
+    proof.query_list.pop();
+
+    assert_eq!(
+        verify_fibonacci(&proof, &pub_inputs, &proof_options),
+        Err(VerificationError::InsufficientQueries {
+            expected: proof_options.fri_number_of_queries,
+            actual: proof_options.fri_number_of_queries - 1,
+        })
+    );
+    assert!(!Verifier::<FibonacciAIR<Stark252PrimeField>>::is_valid(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_verify_rejects_malformed_proofs_without_panicking() {
+    let (mut proof, pub_inputs, proof_options) = fibonacci_proof_and_inputs();
+    proof.trace_length = 6;
+    assert!(matches!(
+        verify_fibonacci(&proof, &pub_inputs, &proof_options),
+        Err(VerificationError::MalformedProof(_))
+    ));
+
+    let (mut proof, pub_inputs, proof_options) = fibonacci_proof_and_inputs();
+    proof.trace_ood_evaluations.width += 1;
+    assert!(matches!(
+        verify_fibonacci(&proof, &pub_inputs, &proof_options),
+        Err(VerificationError::MalformedProof(_))
+    ));
+
+    let (mut proof, pub_inputs, proof_options) = fibonacci_proof_and_inputs();
+    proof.composition_poly_parts_ood_evaluation.pop();
+    assert!(matches!(
+        verify_fibonacci(&proof, &pub_inputs, &proof_options),
+        Err(VerificationError::MalformedProof(_))
+    ));
+
+    let (mut proof, pub_inputs, proof_options) = fibonacci_proof_and_inputs();
+    proof.deep_poly_openings[1]
+        .main_trace_polys
+        .evaluations_sym
+        .pop();
+    assert!(matches!(
+        verify_fibonacci(&proof, &pub_inputs, &proof_options),
+        Err(VerificationError::MalformedProof(_))
+    ));
+
+    let (mut proof, pub_inputs, proof_options) = fibonacci_proof_and_inputs();
+    proof.fri_layers_merkle_roots.clear();
+    assert!(matches!(
+        verify_fibonacci(&proof, &pub_inputs, &proof_options),
+        Err(VerificationError::MalformedProof(_))
+    ));
+}
+
+#[test_log::test]
+fn test_verify_rejects_proof_with_invalid_grinding_nonce() {
+    let (mut proof, pub_inputs, proof_options) = fibonacci_proof_and_inputs();
+    proof.nonce = None;
+
+    assert_eq!(
+        verify_fibonacci(&proof, &pub_inputs, &proof_options),
+        Err(VerificationError::InvalidGrindingNonce)
+    );
+}
+
+fn fibonacci_proof_with_commit_phase_grinding() -> (
+    StarkProof<Stark252PrimeField, Stark252PrimeField>,
+    FibonacciPublicInputs<Stark252PrimeField>,
+    ProofOptions,
+) {
+    let (_, pub_inputs, proof_options) = fibonacci_proof_and_inputs();
+    let proof_options = ProofOptions {
+        commit_phase_grinding_factor: 8,
+        ..proof_options
+    };
+    let mut trace = simple_fibonacci::fibonacci_trace([pub_inputs.a0, pub_inputs.a1], 8);
+    let proof = Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+        &mut trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    (proof, pub_inputs, proof_options)
+}
+
+#[test_log::test]
+fn test_prove_fib_with_commit_phase_grinding() {
+    let (proof, pub_inputs, proof_options) = fibonacci_proof_with_commit_phase_grinding();
+
+    // One nonce before each folding challenge.
+    assert_eq!(
+        proof.commit_phase_nonces.len(),
+        proof.fri_layers_merkle_roots.len() + 1
+    );
+    assert_eq!(
+        verify_fibonacci(&proof, &pub_inputs, &proof_options),
+        Ok(())
+    );
+
+    let decoded = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+    assert_eq!(decoded.commit_phase_nonces, proof.commit_phase_nonces);
+    assert_eq!(
+        verify_fibonacci(&decoded, &pub_inputs, &proof_options),
+        Ok(())
+    );
+}
+
+#[test_log::test]
+fn test_verify_rejects_proof_with_invalid_commit_phase_grinding_nonce() {
+    let (mut proof, pub_inputs, proof_options) = fibonacci_proof_with_commit_phase_grinding();
+    proof.commit_phase_nonces.pop();
+    assert!(matches!(
+        verify_fibonacci(&proof, &pub_inputs, &proof_options),
+        Err(VerificationError::MalformedProof(_))
+    ));
+
+    // Any nonce below the first valid one fails the proof of work.
+    let (mut proof, pub_inputs, proof_options) = fibonacci_proof_with_commit_phase_grinding();
+    let layer = proof.commit_phase_nonces.len() - 1;
+    let nonce = proof.commit_phase_nonces[layer];
+    assert!(nonce > 0);
+    proof.commit_phase_nonces[layer] = nonce - 1;
+    assert_eq!(
+        verify_fibonacci(&proof, &pub_inputs, &proof_options),
+        Err(VerificationError::InvalidCommitPhaseGrindingNonce { layer })
+    );
+
+    // Proofs without commit phase grinding don't verify with options requiring it.
+    let (proof, pub_inputs, proof_options) = fibonacci_proof_and_inputs();
+    let proof_options = ProofOptions {
+        commit_phase_grinding_factor: 8,
+        ..proof_options
+    };
+    assert!(matches!(
+        verify_fibonacci(&proof, &pub_inputs, &proof_options),
+        Err(VerificationError::MalformedProof(_))
+    ));
+}
+
+#[test_log::test]
+fn test_prove_fib_with_sha3_commitments_and_grinding() {
+    let mut trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
+    let proof_options = ProofOptions {
+        grinding_factor: 8,
+        commit_phase_grinding_factor: 4,
+        ..ProofOptions::default_test_options()
+    };
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = StarkProver::<FibonacciAIR<Stark252PrimeField>, Sha3_256Commitments>::prove(
+        &mut trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+
+    assert!(
+        StarkVerifier::<FibonacciAIR<Stark252PrimeField>, Sha3_256Commitments>::verify(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .is_ok()
+    );
+    // The Merkle trees and the proofs of work use Sha3, so the Keccak verifier rejects the proof.
+    assert!(verify_fibonacci(&proof, &pub_inputs, &proof_options).is_err());
+}
+
+#[test_log::test]
+fn test_verify_rejects_proof_with_wrong_composition_polynomial_evaluation() {
+    let (proof, pub_inputs, proof_options) = fibonacci_proof_and_inputs();
+    // Changing a public input changes the boundary constraints, but not the claimed
+    // out-of-domain evaluations of the composition polynomial.
+    let pub_inputs = FibonacciPublicInputs {
+        a1: Felt252::from(2),
+        ..pub_inputs
+    };
+
+    assert_eq!(
+        verify_fibonacci(&proof, &pub_inputs, &proof_options),
+        Err(VerificationError::CompositionPolynomialMismatch)
+    );
+}
+
+#[test_log::test]
+fn test_verify_rejects_proof_with_wrong_fri_layer_evaluation() {
+    let (mut proof, pub_inputs, proof_options) = fibonacci_proof_and_inputs();
+    proof.query_list[1].layers_evaluations_sym[1] += Felt252::one();
+
+    assert_eq!(
+        verify_fibonacci(&proof, &pub_inputs, &proof_options),
+        Err(VerificationError::FriLayerOpening { query: 1, layer: 2 })
+    );
+}
+
+#[test_log::test]
+fn test_verify_rejects_proof_with_wrong_trace_opening() {
+    let (mut proof, pub_inputs, proof_options) = fibonacci_proof_and_inputs();
+    proof.deep_poly_openings[2]
+        .main_trace_polys
+        .proof
+        .merkle_path[0][0] ^= 1;
+
+    assert_eq!(
+        verify_fibonacci(&proof, &pub_inputs, &proof_options),
+        Err(VerificationError::TraceOpening { query: 2 })
+    );
+}
+
+#[test_log::test]
+fn test_verify_rejects_proof_with_wrong_composition_polynomial_opening() {
+    let (mut proof, pub_inputs, proof_options) = fibonacci_proof_and_inputs();
+    proof.deep_poly_openings[0]
+        .composition_poly
+        .proof
+        .merkle_path[0][0] ^= 1;
+
+    assert_eq!(
+        verify_fibonacci(&proof, &pub_inputs, &proof_options),
+        Err(VerificationError::CompositionPolynomialOpening { query: 0 })
+    );
+}
+
+fn prove_fibonacci(
+    trace: &mut TraceTable<Stark252PrimeField, Stark252PrimeField>,
+    proof_options: &ProofOptions,
+) -> Result<StarkProof<Stark252PrimeField, Stark252PrimeField>, ProvingError> {
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+    Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+        trace,
+        &pub_inputs,
+        proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+}
+
+#[test_log::test]
+fn test_prove_rejects_trace_with_length_not_power_of_two() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
+    let mut column = trace.columns_main().remove(0);
+    column.truncate(6);
+    let mut trace = TraceTable::from_columns_main(vec![column], 1);
+
+    assert!(matches!(
+        prove_fibonacci(&mut trace, &ProofOptions::default_test_options()),
+        Err(ProvingError::TraceLengthNotPowerOfTwo(6))
+    ));
+}
+
+#[test_log::test]
+fn test_prove_rejects_trace_with_wrong_number_of_columns() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
+    let column = trace.columns_main().remove(0);
+    let mut trace = TraceTable::from_columns_main(vec![column.clone(), column], 1);
+
+    assert!(matches!(
+        prove_fibonacci(&mut trace, &ProofOptions::default_test_options()),
+        Err(ProvingError::TraceColumnsMismatch {
+            expected: (1, 0),
+            actual: (2, 0)
+        })
     ));
 }
+
+#[test_log::test]
+fn test_prove_rejects_invalid_proof_options() {
+    let invalid_options = [
+        ProofOptions {
+            blowup_factor: 3,
+            ..ProofOptions::default_test_options()
+        },
+        ProofOptions {
+            coset_offset: 1,
+            ..ProofOptions::default_test_options()
+        },
+        ProofOptions {
+            grinding_factor: 64,
+            ..ProofOptions::default_test_options()
+        },
+        ProofOptions {
+            commit_phase_grinding_factor: 64,
+            ..ProofOptions::default_test_options()
+        },
+    ];
+
+    for proof_options in invalid_options {
+        let mut trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
+        assert!(matches!(
+            prove_fibonacci(&mut trace, &proof_options),
+            Err(ProvingError::InvalidProofOptions(_))
+        ));
+    }
+}
+
+#[test_log::test]
+fn test_prove_and_verify_statement_fib() {
+    let mut trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
+    let proof_options = ProofOptions::default_test_options();
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::<FibonacciAIR<Stark252PrimeField>>::prove_statement(
+        &mut trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+
+    assert!(
+        Verifier::<FibonacciAIR<Stark252PrimeField>>::verify_statement(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .is_ok()
+    );
+
+    // The proof options are bound to the proof, even the ones the verifier doesn't otherwise use.
+    let other_options = ProofOptions {
+        grinding_factor: 0,
+        ..proof_options
+    };
+    assert!(
+        Verifier::<FibonacciAIR<Stark252PrimeField>>::verify_statement(
+            &proof,
+            &pub_inputs,
+            &other_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .is_err()
+    );
+}
+
+#[test_log::test]
+fn test_verify_statement_rejects_proof_with_unbound_transcript() {
+    let (proof, pub_inputs, proof_options) = fibonacci_proof_and_inputs();
+
+    assert!(
+        Verifier::<FibonacciAIR<Stark252PrimeField>>::verify_statement(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .is_err()
+    );
+}
+
+fn circle_fibonacci_proof_and_inputs(
+) -> (CircleStarkProof, CircleFibonacciPublicInputs, ProofOptions) {
+    let trace = circle_fibonacci_trace([FpE::one(), FpE::one()], 32);
+    let proof_options = ProofOptions {
+        fri_number_of_queries: 30,
+        ..ProofOptions::default_test_options()
+    };
+    let pub_inputs = CircleFibonacciPublicInputs {
+        a0: FpE::one(),
+        a1: FpE::one(),
+    };
+
+    let proof = CircleProver::<CircleFibonacciAIR>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        CircleTranscript::new(&[]),
+    )
+    .unwrap();
+    (proof, pub_inputs, proof_options)
+}
+
+#[test_log::test]
+fn test_prove_circle_fib() {
+    let (proof, pub_inputs, proof_options) = circle_fibonacci_proof_and_inputs();
+
+    assert_eq!(
+        CircleVerifier::<CircleFibonacciAIR>::verify(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            CircleTranscript::new(&[]),
+        ),
+        Ok(())
+    );
+}
+
+#[test_log::test]
+fn test_circle_verifier_rejects_wrong_public_inputs() {
+    let (proof, _, proof_options) = circle_fibonacci_proof_and_inputs();
+    let pub_inputs = CircleFibonacciPublicInputs {
+        a0: FpE::one(),
+        a1: FpE::from(2),
+    };
+
+    assert!(CircleVerifier::<CircleFibonacciAIR>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        CircleTranscript::new(&[]),
+    )
+    .is_err());
+}
+
+#[test_log::test]
+fn test_circle_verifier_rejects_invalid_trace() {
+    let mut trace = circle_fibonacci_trace([FpE::one(), FpE::one()], 32);
+    trace.set(17, 0, FpE::from(5));
+    let proof_options = ProofOptions {
+        fri_number_of_queries: 30,
+        ..ProofOptions::default_test_options()
+    };
+    let pub_inputs = CircleFibonacciPublicInputs {
+        a0: FpE::one(),
+        a1: FpE::one(),
+    };
+
+    let proof = CircleProver::<CircleFibonacciAIR>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        CircleTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(CircleVerifier::<CircleFibonacciAIR>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        CircleTranscript::new(&[]),
+    )
+    .is_err());
+}
+
+#[test_log::test]
+fn test_circle_verifier_rejects_tampered_openings() {
+    let (proof, pub_inputs, proof_options) = circle_fibonacci_proof_and_inputs();
+
+    let mut tampered_trace = proof.clone();
+    tampered_trace.query_openings[0].trace.values[0] += FpE::one();
+    assert_eq!(
+        CircleVerifier::<CircleFibonacciAIR>::verify(
+            &tampered_trace,
+            &pub_inputs,
+            &proof_options,
+            CircleTranscript::new(&[]),
+        ),
+        Err(VerificationError::TraceOpening { query: 0 })
+    );
+
+    let mut tampered_fri = proof;
+    tampered_fri.query_openings[1].fri_layers[1].values[1] += FpE::one();
+    assert_eq!(
+        CircleVerifier::<CircleFibonacciAIR>::verify(
+            &tampered_fri,
+            &pub_inputs,
+            &proof_options,
+            CircleTranscript::new(&[]),
+        ),
+        Err(VerificationError::FriLayerOpening { query: 1, layer: 2 })
+    );
+}
+
+/// Encodes a Cairo instruction from its flags and its (unbiased) offsets.
+fn cairo_instruction(flags: u64, off_dst: i64, off_op0: i64, off_op1: i64) -> Felt252 {
+    let bias = |offset: i64| (offset + (1 << 15)) as u64;
+    Felt252::from(bias(off_dst) | bias(off_op0) << 16 | bias(off_op1) << 32 | flags << 48)
+}
+
+/// Writes the trace, memory and AIR public input files of a proof mode execution of
+/// ```text
+/// __start__:      call main
+/// __end__:        jmp rel 0
+/// main:           [ap] = V, ap++
+///                 [ap] = [ap - 1] * [ap - 1], ap++
+///                 [ap - 2] = [[fp - 3]]
+///                 ret
+/// ```
+/// where `[fp - 3]` points to a cell of the range check builtin, and returns the directory
+/// holding them. With `proof_mode` false the final infinite loop is left out of the trace.
+fn write_cairo_vm_files(name: &str, proof_mode: bool) -> std::path::PathBuf {
+    let value = Felt252::from_hex_unchecked("80008000800080008000800080008000");
+    let memory = [
+        cairo_instruction(0x1104, 0, 1, 1),
+        Felt252::from(4),
+        cairo_instruction(0x0107, -1, -1, 1),
+        Felt252::zero(),
+        cairo_instruction(0x4806, 0, -1, 1),
+        value,
+        cairo_instruction(0x4850, 0, -1, -1),
+        cairo_instruction(0x4002, -2, -3, 0),
+        cairo_instruction(0x208b, -2, -1, -1),
+        Felt252::from(15),
+        Felt252::from(11),
+        Felt252::from(3),
+        value,
+        value * value,
+        value,
+    ];
+    let mut states = vec![
+        (1, 11, 11),
+        (5, 13, 13),
+        (7, 14, 13),
+        (8, 15, 13),
+        (9, 15, 13),
+    ];
+    if proof_mode {
+        states.extend([(3, 15, 11); 3]);
+    }
+
+    let directory = std::env::temp_dir().join(format!("cairo_vm_files_{name}"));
+    std::fs::create_dir_all(&directory).unwrap();
+
+    let trace: Vec<u8> = states
+        .iter()
+        .flat_map(|(pc, ap, fp)| [ap, fp, pc])
+        .flat_map(|register: &u64| register.to_le_bytes())
+        .collect();
+    std::fs::write(directory.join("trace.bin"), trace).unwrap();
+
+    let memory: Vec<u8> = memory
+        .iter()
+        .enumerate()
+        .flat_map(|(i, value)| {
+            let mut entry = (i as u64 + 1).to_le_bytes().to_vec();
+            entry.extend(value.representative().to_bytes_le());
+            entry
+        })
+        .collect();
+    std::fs::write(directory.join("memory.bin"), memory).unwrap();
+
+    let air_public_input = r#"{
+        "memory_segments": {
+            "program": {"begin_addr": 1, "stop_ptr": 10},
+            "execution": {"begin_addr": 10, "stop_ptr": 15},
+            "output": {"begin_addr": 15, "stop_ptr": 15},
+            "range_check": {"begin_addr": 15, "stop_ptr": 16}
+        }
+    }"#;
+    std::fs::write(directory.join("air_public_input.json"), air_public_input).unwrap();
+
+    directory
+}
+
+fn load_cairo_execution(directory: &std::path::Path) -> Result<CairoExecution, CairoError> {
+    CairoExecution::from_files(
+        directory.join("trace.bin"),
+        directory.join("memory.bin"),
+        directory.join("air_public_input.json"),
+    )
+}
+
+#[test_log::test]
+fn test_prove_cairo_execution_from_vm_files() {
+    let directory = write_cairo_vm_files("prove", true);
+    let execution = load_cairo_execution(&directory).unwrap();
+    let proof_options = ProofOptions::default_test_options();
+
+    let (proof, public_inputs) =
+        prove_cairo_execution(&execution, &proof_options, StoneProverTranscript::new(&[])).unwrap();
+
+    assert_eq!(public_inputs.pc_final, 3);
+    assert_eq!(public_inputs.ap_final, 15);
+    assert_eq!(
+        (public_inputs.rc_min, public_inputs.rc_max),
+        (0x7ffd, 0x8001)
+    );
+    assert_eq!(public_inputs.public_memory.len(), 9);
+    assert_eq!(
+        verify_cairo_execution(
+            &proof,
+            &public_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[])
+        ),
+        Ok(())
+    );
+}
+
+#[test_log::test]
+fn test_cairo_verifier_rejects_wrong_public_inputs() {
+    let directory = write_cairo_vm_files("wrong_public_inputs", true);
+    let execution = load_cairo_execution(&directory).unwrap();
+    let proof_options = ProofOptions::default_test_options();
+    let (proof, public_inputs) =
+        prove_cairo_execution(&execution, &proof_options, StoneProverTranscript::new(&[])).unwrap();
+
+    let mut wrong_program = public_inputs.clone();
+    wrong_program.public_memory.insert(6, Felt252::from(7));
+    let wrong_final_ap = CairoPublicInputs {
+        ap_final: 16,
+        ..public_inputs.clone()
+    };
+    for wrong_public_inputs in [wrong_program, wrong_final_ap] {
+        assert!(verify_cairo_execution(
+            &proof,
+            &wrong_public_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[])
+        )
+        .is_err());
+    }
+
+    let missing_program = CairoPublicInputs {
+        public_memory: Default::default(),
+        ..public_inputs
+    };
+    assert!(matches!(
+        verify_cairo_execution(
+            &proof,
+            &missing_program,
+            &proof_options,
+            StoneProverTranscript::new(&[])
+        ),
+        Err(CairoError::InvalidPublicInputs(_))
+    ));
+}
+
+/// Adds to the files written by `write_cairo_vm_files` an instance of the pedersen builtin at the
+/// addresses 16 to 18 and one of the bitwise builtin at the addresses 19 to 23, holding `cells`.
+fn write_cairo_builtin_cells(directory: &std::path::Path, cells: [Felt252; 8]) {
+    let mut memory = std::fs::read(directory.join("memory.bin")).unwrap();
+    for (address, value) in (16u64..).zip(cells) {
+        memory.extend(address.to_le_bytes());
+        memory.extend(value.representative().to_bytes_le());
+    }
+    std::fs::write(directory.join("memory.bin"), memory).unwrap();
+
+    let air_public_input = r#"{
+        "memory_segments": {
+            "program": {"begin_addr": 1, "stop_ptr": 10},
+            "execution": {"begin_addr": 10, "stop_ptr": 15},
+            "output": {"begin_addr": 15, "stop_ptr": 15},
+            "range_check": {"begin_addr": 15, "stop_ptr": 16},
+            "pedersen": {"begin_addr": 16, "stop_ptr": 19},
+            "bitwise": {"begin_addr": 19, "stop_ptr": 24}
+        }
+    }"#;
+    std::fs::write(directory.join("air_public_input.json"), air_public_input).unwrap();
+}
+
+fn cairo_builtin_cells() -> [Felt252; 8] {
+    let x = Felt252::from_hex_unchecked(
+        "03d937c035c878245caf64531a5756109c53068da139362728feb561405371cb",
+    );
+    let y = Felt252::from_hex_unchecked(
+        "0208a0a10250e382e1e4bbe2880906c2791bf6275695e02fbbc6aeff9cd8b31a",
+    );
+    let hash = Felt252::from_hex_unchecked(
+        "030e480bed5fe53fa909cc0f8c4d99b8f9f2c016be4c41e13a4848797979c662",
+    );
+    let (a, b) = (0xf0f0_1234u64, 0x0ff0_4321u64);
+    [x, y, hash]
+        .into_iter()
+        .chain([a, b, a & b, a ^ b, a | b].map(Felt252::from))
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap()
+}
+
+#[test_log::test]
+fn test_prove_cairo_execution_with_pedersen_and_bitwise_builtins() {
+    let directory = write_cairo_vm_files("builtins", true);
+    write_cairo_builtin_cells(&directory, cairo_builtin_cells());
+    let execution = load_cairo_execution(&directory).unwrap();
+    let proof_options = ProofOptions::default_test_options();
+
+    let (proof, public_inputs) =
+        prove_cairo_execution(&execution, &proof_options, StoneProverTranscript::new(&[])).unwrap();
+
+    // A pedersen instance takes 512 rows.
+    assert_eq!(proof.trace_length, 512);
+    assert_eq!(public_inputs.pedersen_builtin, Some(16..19));
+    assert_eq!(public_inputs.bitwise_builtin, Some(19..24));
+    assert_eq!(
+        verify_cairo_execution(
+            &proof,
+            &public_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[])
+        ),
+        Ok(())
+    );
+
+    let more_instances = CairoPublicInputs {
+        bitwise_builtin: Some(19..29),
+        ..public_inputs.clone()
+    };
+    assert!(verify_cairo_execution(
+        &proof,
+        &more_instances,
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    )
+    .is_err());
+    let partial_instance = CairoPublicInputs {
+        pedersen_builtin: Some(16..18),
+        ..public_inputs
+    };
+    assert!(matches!(
+        verify_cairo_execution(
+            &proof,
+            &partial_instance,
+            &proof_options,
+            StoneProverTranscript::new(&[])
+        ),
+        Err(CairoError::InvalidPublicInputs(_))
+    ));
+}
+
+#[test_log::test]
+fn test_cairo_execution_rejects_wrong_builtin_outputs() {
+    let mut cells = cairo_builtin_cells();
+    cells[2] += Felt252::one();
+    let directory = write_cairo_vm_files("wrong_pedersen_output", true);
+    write_cairo_builtin_cells(&directory, cells);
+    assert!(matches!(
+        load_cairo_execution(&directory),
+        Err(CairoError::WrongBuiltinOutput(18))
+    ));
+
+    let mut cells = cairo_builtin_cells();
+    cells[6] = cells[7];
+    let directory = write_cairo_vm_files("wrong_bitwise_output", true);
+    write_cairo_builtin_cells(&directory, cells);
+    assert!(matches!(
+        load_cairo_execution(&directory),
+        Err(CairoError::WrongBuiltinOutput(22))
+    ));
+}
+
+#[test_log::test]
+fn test_cairo_verifier_rejects_wrong_builtin_traces() {
+    let directory = write_cairo_vm_files("wrong_builtin_traces", true);
+    write_cairo_builtin_cells(&directory, cairo_builtin_cells());
+    let execution = load_cairo_execution(&directory).unwrap();
+    let public_inputs = execution.public_inputs().clone();
+    let proof_options = ProofOptions::default_test_options();
+
+    // The pedersen accumulator doesn't add the constant point of a set bit, and a bit of the
+    // `and` of the bitwise inputs is not the `and` of their bits.
+    for (row, col) in [(300, PEDERSEN_ACC_Y), (2, BITWISE_AND_SUFFIX)] {
+        let mut trace = execution.build_trace();
+        let value = *trace.get_main(row, col) + Felt252::one();
+        trace.set_main(row, col, value);
+        let proof = Prover::<CairoAIR>::prove_statement(
+            &mut trace,
+            &public_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+
+        assert!(verify_cairo_execution(
+            &proof,
+            &public_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[])
+        )
+        .is_err());
+    }
+}
+
+#[test_log::test]
+fn test_cairo_execution_requires_proof_mode() {
+    let directory = write_cairo_vm_files("not_proof_mode", false);
+
+    assert!(matches!(
+        load_cairo_execution(&directory),
+        Err(CairoError::NotProofMode)
+    ));
+}
+
+/// Options of the inner proofs of the recursion tests, without grinding.
+fn recursion_inner_proof_options() -> ProofOptions {
+    ProofOptions {
+        blowup_factor: 4,
+        fri_number_of_queries: 2,
+        coset_offset: 3,
+        grinding_factor: 0,
+        commit_phase_grinding_factor: 0,
+    }
+}
+
+fn prove_fib_with_poseidon(
+    pub_inputs: &FibonacciPublicInputs<Stark252PrimeField>,
+    proof_options: &ProofOptions,
+) -> StarkProof<Stark252PrimeField, Stark252PrimeField> {
+    let mut trace = simple_fibonacci::fibonacci_trace([pub_inputs.a0, pub_inputs.a1], 8);
+    StarkProver::<FibonacciAIR<Stark252PrimeField>, PoseidonCommitments>::prove_statement(
+        &mut trace,
+        pub_inputs,
+        proof_options,
+        PoseidonTranscript::new(&[]),
+    )
+    .unwrap()
+}
+
+#[test_log::test]
+fn test_prove_fib_with_poseidon_commitments() {
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+    let proof_options = ProofOptions::default_test_options();
+    let proof = prove_fib_with_poseidon(&pub_inputs, &proof_options);
+
+    assert!(
+        StarkVerifier::<FibonacciAIR<Stark252PrimeField>, PoseidonCommitments>::verify_statement(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            PoseidonTranscript::new(&[]),
+        )
+        .is_ok()
+    );
+    // The Merkle trees of the proof are not Keccak256 trees.
+    assert!(
+        Verifier::<FibonacciAIR<Stark252PrimeField>>::verify_statement(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            PoseidonTranscript::new(&[]),
+        )
+        .is_err()
+    );
+}
+
+#[test_log::test]
+fn test_recursive_proof_of_fibonacci() {
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+    let inner_proof_options = recursion_inner_proof_options();
+    let inner_proof = prove_fib_with_poseidon(&pub_inputs, &inner_proof_options);
+
+    let proof_options = ProofOptions::default_test_options();
+    let (proof, recursion_pub_inputs) = prove_recursive::<FibonacciAIR<Stark252PrimeField>>(
+        &inner_proof,
+        &pub_inputs,
+        &inner_proof_options,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+
+    assert!(verify_recursive(
+        &proof,
+        &recursion_pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .is_ok());
+
+    let wrong_pub_inputs = RecursionPublicInputs::<FibonacciAIR<Stark252PrimeField>> {
+        inner_public_inputs: FibonacciPublicInputs {
+            a0: Felt252::one(),
+            a1: Felt252::from(2),
+        },
+        ..recursion_pub_inputs.clone()
+    };
+    assert!(matches!(
+        verify_recursive(
+            &proof,
+            &wrong_pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        ),
+        Err(RecursionError::Verification(_))
+    ));
+}
+
+#[test_log::test]
+fn test_recursion_rejects_invalid_inner_proofs() {
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+    let inner_proof_options = recursion_inner_proof_options();
+    let proof_options = ProofOptions::default_test_options();
+    let mut inner_proof = prove_fib_with_poseidon(&pub_inputs, &inner_proof_options);
+    inner_proof.fri_last_value += Felt252::one();
+
+    assert!(matches!(
+        prove_recursive::<FibonacciAIR<Stark252PrimeField>>(
+            &inner_proof,
+            &pub_inputs,
+            &inner_proof_options,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        ),
+        Err(RecursionError::InvalidInnerProof(_))
+    ));
+
+    let with_grinding = ProofOptions::default_test_options();
+    let inner_proof = prove_fib_with_poseidon(&pub_inputs, &with_grinding);
+    assert!(matches!(
+        prove_recursive::<FibonacciAIR<Stark252PrimeField>>(
+            &inner_proof,
+            &pub_inputs,
+            &with_grinding,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        ),
+        Err(RecursionError::UnsupportedProofOptions(_))
+    ));
+}
+
+/// Checks that the streaming prover generates the proof of the default prover for `trace`,
+/// with memory budgets ranging from a chunk of the length of the trace to the whole LDE.
+fn assert_streaming_proofs_match<A>(
+    trace: &TraceTable<Stark252PrimeField, Stark252PrimeField>,
+    pub_inputs: &A::PublicInputs,
+) where
+    A: AIR<Field = Stark252PrimeField, FieldExtension = Stark252PrimeField> + Send + Sync,
+{
+    let proof_options = ProofOptions::default_test_options();
+    let proof = Prover::<A>::prove(
+        &mut trace.clone(),
+        pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    let expected = serde_json::to_string(&proof).unwrap();
+
+    let air = A::new(trace.num_rows(), pub_inputs, &proof_options);
+    let lde_size = trace.num_rows() * proof_options.blowup_factor as usize;
+    assert_eq!(
+        StreamingProver::<A>::new(0).chunk_size(&air),
+        trace.num_rows()
+    );
+    assert_eq!(
+        StreamingProver::<A>::new(usize::MAX).chunk_size(&air),
+        lde_size
+    );
+
+    for memory_budget in [0, 1 << 11, 1 << 13, usize::MAX] {
+        let streaming_proof = StreamingProver::<A>::new(memory_budget)
+            .prove(
+                &mut trace.clone(),
+                pub_inputs,
+                &proof_options,
+                StoneProverTranscript::new(&[]),
+            )
+            .unwrap();
+        assert_eq!(serde_json::to_string(&streaming_proof).unwrap(), expected);
+        assert!(Verifier::<A>::verify(
+            &streaming_proof,
+            pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .is_ok());
+    }
+}
+
+#[test_log::test]
+fn test_streaming_prover_matches_prover_on_fib() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+    assert_streaming_proofs_match::<FibonacciAIR<Stark252PrimeField>>(&trace, &pub_inputs);
+}
+
+#[test_log::test]
+fn test_streaming_prover_matches_prover_on_fib_2_cols_shifted() {
+    let trace = fibonacci_2_cols_shifted::compute_trace(FieldElement::one(), 16);
+    let pub_inputs = fibonacci_2_cols_shifted::PublicInputs {
+        claimed_value: trace.main_table.get_row(14)[0],
+        claimed_index: 14,
+    };
+    assert_streaming_proofs_match::<Fibonacci2ColsShifted<_>>(&trace, &pub_inputs);
+}
+
+#[test_log::test]
+fn test_streaming_prover_matches_prover_on_quadratic() {
+    let trace = quadratic_air::quadratic_trace(Felt252::from(3), 32);
+    let pub_inputs = QuadraticPublicInputs {
+        a0: Felt252::from(3),
+    };
+    assert_streaming_proofs_match::<QuadraticAIR<Stark252PrimeField>>(&trace, &pub_inputs);
+}
+
+#[test_log::test]
+fn test_streaming_prover_matches_prover_with_periodic_columns() {
+    let trace = simple_periodic_cols::simple_periodic_trace::<Stark252PrimeField>(32);
+    let pub_inputs = SimplePeriodicPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::from(32768),
+    };
+    assert_streaming_proofs_match::<SimplePeriodicAIR<Stark252PrimeField>>(&trace, &pub_inputs);
+}
+
+#[test_log::test]
+fn test_streaming_prover_matches_prover_with_preprocessed_columns() {
+    let trace = round_constants::round_constants_trace::<Stark252PrimeField>(Felt252::from(3), 16);
+    let pub_inputs = RoundConstantsPublicInputs {
+        input: Felt252::from(3),
+        output: *trace.get_main(15, 0),
+    };
+    assert_streaming_proofs_match::<RoundConstantsAIR<Stark252PrimeField>>(&trace, &pub_inputs);
+}
+
+#[test_log::test]
+fn test_streaming_prover_matches_prover_with_auxiliary_trace() {
+    let steps = 16;
+    let trace = fibonacci_rap_trace([Felt252::from(1), Felt252::from(1)], steps);
+    let pub_inputs = FibonacciRAPPublicInputs {
+        steps,
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+    assert_streaming_proofs_match::<FibonacciRAP<Stark252PrimeField>>(&trace, &pub_inputs);
+}
+
+#[test_log::test]
+fn test_streaming_prover_matches_prover_with_exemptions() {
+    assert_streaming_proofs_match::<DummyAIR>(&dummy_air::dummy_trace(16), &());
+    assert_streaming_proofs_match::<BitFlagsAIR>(&bit_flags::bit_prefix_flag_trace(32), &());
+}
+
+#[test_log::test]
+fn test_binary_proof_round_trip() {
+    let (proof, pub_inputs, proof_options) = fibonacci_proof_and_inputs();
+
+    let bytes = proof.to_bytes();
+    let decoded = StarkProof::<Stark252PrimeField, Stark252PrimeField>::from_bytes(&bytes).unwrap();
+
+    assert_eq!(
+        serde_json::to_string(&decoded).unwrap(),
+        serde_json::to_string(&proof).unwrap()
+    );
+    assert_eq!(decoded.to_bytes(), bytes);
+    assert!(verify_fibonacci(&decoded, &pub_inputs, &proof_options).is_ok());
+}
+
+#[test_log::test]
+fn test_binary_proof_round_trip_with_auxiliary_trace() {
+    let steps = 16;
+    let mut trace = fibonacci_rap_trace([Felt252::from(1), Felt252::from(1)], steps);
+    let proof_options = ProofOptions::default_test_options();
+    let pub_inputs = FibonacciRAPPublicInputs {
+        steps,
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+    let proof = Prover::<FibonacciRAP<Stark252PrimeField>>::prove(
+        &mut trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+
+    let decoded =
+        StarkProof::<Stark252PrimeField, Stark252PrimeField>::from_bytes(&proof.to_bytes())
+            .unwrap();
+
+    assert!(decoded.lde_trace_aux_merkle_root.is_some());
+    assert!(Verifier::<FibonacciRAP<Stark252PrimeField>>::verify(
+        &decoded,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    )
+    .is_ok());
+}
+
+#[test_log::test]
+fn test_binary_proof_deduplicates_authentication_path_nodes() {
+    let (proof, _, _) = fibonacci_proof_and_inputs();
+
+    let breakdown = proof.proof_size_breakdown();
+    assert_eq!(breakdown.total(), proof.to_bytes().len());
+
+    // The composition polynomial openings have the same path for both evaluations, and every
+    // path has the nodes close to the root in common with the others.
+    let composition_paths_nodes: usize = proof
+        .deep_poly_openings
+        .iter()
+        .map(|opening| {
+            opening.composition_poly.proof.merkle_path.len()
+                + opening.composition_poly.proof_sym.merkle_path.len()
+        })
+        .sum();
+    assert!(breakdown.composition_poly_authentication_paths * 2 < composition_paths_nodes * 32);
+
+    let fri_paths_nodes: usize = proof
+        .query_list
+        .iter()
+        .flat_map(|decommitment| decommitment.layers_auth_paths.iter())
+        .map(|path| path.merkle_path.len())
+        .sum();
+    assert!(breakdown.fri_layers_authentication_paths < fri_paths_nodes * 32);
+}
+
+#[test_log::test]
+fn test_binary_proof_decoding_rejects_malformed_bytes() {
+    type Proof = StarkProof<Stark252PrimeField, Stark252PrimeField>;
+    let (proof, _, _) = fibonacci_proof_and_inputs();
+    let bytes = proof.to_bytes();
+
+    for length in 0..bytes.len() {
+        assert!(Proof::from_bytes(&bytes[..length]).is_err());
+    }
+
+    let mut with_trailing_bytes = bytes.clone();
+    with_trailing_bytes.push(0);
+    assert_eq!(
+        Proof::from_bytes(&with_trailing_bytes).err(),
+        Some(ProofEncodingError::TrailingBytes(1))
+    );
+
+    let mut with_wrong_magic = bytes.clone();
+    with_wrong_magic[0] ^= 1;
+    assert_eq!(
+        Proof::from_bytes(&with_wrong_magic).err(),
+        Some(ProofEncodingError::InvalidMagic)
+    );
+
+    let mut with_next_version = bytes.clone();
+    with_next_version[PROOF_MAGIC.len()] = PROOF_FORMAT_VERSION + 1;
+    assert_eq!(
+        Proof::from_bytes(&with_next_version).err(),
+        Some(ProofEncodingError::UnsupportedVersion(
+            PROOF_FORMAT_VERSION + 1
+        ))
+    );
+
+    assert_eq!(
+        StarkProof::<Stark101PrimeField, Stark101PrimeField>::from_bytes(&bytes).err(),
+        Some(ProofEncodingError::ElementSizeMismatch {
+            expected: 8,
+            found: 32
+        })
+    );
+}
+
+#[test_log::test]
+fn test_binary_proof_decoding_rejects_paths_longer_than_the_merkle_trees() {
+    type Proof = StarkProof<Stark252PrimeField, Stark252PrimeField>;
+    let (mut proof, _, _) = fibonacci_proof_and_inputs();
+
+    // A trace of length 8 has Merkle trees of at most 8 * 2^7 leaves, so paths of at most 10
+    // nodes.
+    let path = &mut proof.deep_poly_openings[0]
+        .main_trace_polys
+        .proof
+        .merkle_path;
+    let node = path[0];
+    path.resize(11, node);
+    assert_eq!(
+        Proof::from_bytes(&proof.to_bytes()).err(),
+        Some(ProofEncodingError::InvalidPathLength(11))
+    );
+
+    proof.deep_poly_openings[0]
+        .main_trace_polys
+        .proof
+        .merkle_path
+        .truncate(10);
+    assert!(Proof::from_bytes(&proof.to_bytes()).is_ok());
+}
+
+#[test_log::test]
+fn test_binary_proof_field_elements_are_canonical() {
+    type Proof = StarkProof<Stark252PrimeField, Stark252PrimeField>;
+    let (mut proof, _, _) = fibonacci_proof_and_inputs();
+    proof.fri_last_value = -Felt252::one();
+    let bytes = proof.to_bytes();
+
+    let canonical = (-Felt252::one()).to_bytes_be();
+    let positions: Vec<_> = bytes
+        .windows(canonical.len())
+        .enumerate()
+        .filter(|(_, window)| *window == &canonical[..])
+        .map(|(position, _)| position)
+        .collect();
+    assert_eq!(positions.len(), 1);
+    assert_eq!(
+        Proof::from_bytes(&bytes).unwrap().fri_last_value,
+        -Felt252::one()
+    );
+
+    // p - 1 ends with a zero byte, so adding one to it gives the modulus, which decodes to zero
+    // if its range is not checked.
+    let mut with_modulus = bytes.clone();
+    with_modulus[positions[0] + canonical.len() - 1] += 1;
+    assert_eq!(
+        Proof::from_bytes(&with_modulus).err(),
+        Some(ProofEncodingError::NonCanonicalFieldElement)
+    );
+}
+
+#[test_log::test]
+fn test_prove_fib_trace_imported_from_csv() {
+    // A trace generated outside of Rust, with a header line and a row per step.
+    let csv = "fib\n1\n1\n2\n3\n5\n8\n13\n0x15\n";
+    let mut trace = TraceTable::from_csv(csv, 1).unwrap();
+    assert_eq!(
+        trace.columns_main(),
+        simple_fibonacci::fibonacci_trace([Felt252::one(), Felt252::one()], 8).columns_main()
+    );
+
+    let proof_options = ProofOptions::default_test_options();
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+        &mut trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .is_ok());
+}
+
+#[test_log::test]
+fn test_prove_fib_rap_trace_dumped_to_bytes() {
+    let trace = fibonacci_rap_trace([Felt252::one(), Felt252::one()], 16);
+    let mut decoded = TraceTable::from_bytes(&trace.to_bytes()).unwrap();
+
+    let proof_options = ProofOptions::default_test_options();
+    let pub_inputs = FibonacciRAPPublicInputs {
+        steps: 16,
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::<FibonacciRAP<Stark252PrimeField>>::prove(
+        &mut decoded,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::<FibonacciRAP<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .is_ok());
+}
+
+#[test_log::test]
+fn test_batch_prove_fib_traces_of_different_lengths() {
+    let initial_values = [(1, 1), (2, 3), (5, 1)];
+    let mut traces: Vec<_> = initial_values
+        .iter()
+        .zip([8, 32, 16])
+        .map(|((a0, a1), trace_length)| {
+            simple_fibonacci::fibonacci_trace(
+                [Felt252::from(*a0), Felt252::from(*a1)],
+                trace_length,
+            )
+        })
+        .collect();
+    let pub_inputs: Vec<_> = initial_values
+        .iter()
+        .map(|(a0, a1)| FibonacciPublicInputs {
+            a0: Felt252::from(*a0),
+            a1: Felt252::from(*a1),
+        })
+        .collect();
+    let proof_options = ProofOptions::default_test_options();
+
+    let proof = BatchProver::<FibonacciAIR<Stark252PrimeField>>::prove_statement(
+        &mut traces,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert_eq!(proof.trace_lengths, vec![8, 32, 16]);
+    assert!(
+        BatchVerifier::<FibonacciAIR<Stark252PrimeField>>::verify_statement(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .is_ok()
+    );
+}
+
+#[test_log::test]
+fn test_batch_prove_with_commit_phase_grinding() {
+    let mut traces = vec![
+        simple_fibonacci::fibonacci_trace([Felt252::one(), Felt252::one()], 8),
+        simple_fibonacci::fibonacci_trace([Felt252::one(), Felt252::one()], 16),
+    ];
+    let pub_inputs = vec![
+        FibonacciPublicInputs {
+            a0: Felt252::one(),
+            a1: Felt252::one(),
+        };
+        2
+    ];
+    let proof_options = ProofOptions {
+        commit_phase_grinding_factor: 4,
+        ..ProofOptions::default_test_options()
+    };
+
+    let mut proof = BatchProver::<FibonacciAIR<Stark252PrimeField>>::prove(
+        &mut traces,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert_eq!(
+        proof.commit_phase_nonces.len(),
+        proof.fri_layers_merkle_roots.len() + 1
+    );
+    assert!(BatchVerifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .is_ok());
+
+    // The nonces are the smallest valid ones, so the previous value fails the proof of work.
+    proof.commit_phase_nonces[0] -= 1;
+    assert_eq!(
+        BatchVerifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        ),
+        Err(VerificationError::InvalidCommitPhaseGrindingNonce { layer: 0 })
+    );
+}
+
+#[test_log::test]
+fn test_batch_prove_rap_fib_traces() {
+    // The constraints of the RAP example are written for 16 steps starting from 1, 1.
+    let mut traces = vec![
+        fibonacci_rap_trace([Felt252::one(), Felt252::one()], 16),
+        fibonacci_rap_trace([Felt252::one(), Felt252::one()], 16),
+    ];
+    let pub_inputs = vec![
+        FibonacciRAPPublicInputs {
+            steps: 16,
+            a0: Felt252::one(),
+            a1: Felt252::one(),
+        };
+        2
+    ];
+    let proof_options = ProofOptions::default_test_options();
+
+    let proof = BatchProver::<FibonacciRAP<Stark252PrimeField>>::prove(
+        &mut traces,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(proof.lde_trace_aux_merkle_root.is_some());
+    assert!(BatchVerifier::<FibonacciRAP<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .is_ok());
+}
+
+#[test_log::test]
+fn test_batch_verifier_rejects_wrong_public_inputs() {
+    let mut traces = vec![
+        simple_fibonacci::fibonacci_trace([Felt252::one(), Felt252::one()], 8),
+        simple_fibonacci::fibonacci_trace([Felt252::one(), Felt252::from(2)], 16),
+    ];
+    let mut pub_inputs = vec![
+        FibonacciPublicInputs {
+            a0: Felt252::one(),
+            a1: Felt252::one(),
+        },
+        FibonacciPublicInputs {
+            a0: Felt252::one(),
+            a1: Felt252::from(2),
+        },
+    ];
+    let proof_options = ProofOptions::default_test_options();
+
+    let proof = BatchProver::<FibonacciAIR<Stark252PrimeField>>::prove(
+        &mut traces,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+
+    pub_inputs[1].a1 = Felt252::from(3);
+    assert_eq!(
+        BatchVerifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        ),
+        Err(VerificationError::CompositionPolynomialMismatch)
+    );
+    assert!(matches!(
+        BatchVerifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+            &proof,
+            &pub_inputs[..1],
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        ),
+        Err(VerificationError::MalformedProof(_))
+    ));
+}
+
+fn prove_padded_fib(
+    real_length: usize,
+    padding: &PaddingRow<Stark252PrimeField>,
+    mode: PaddingMode,
+) -> (
+    StarkProof<Stark252PrimeField, Stark252PrimeField>,
+    PaddedPublicInputs<FibonacciAIR<Stark252PrimeField>>,
+) {
+    let mut trace =
+        simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], real_length);
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    prove_padded::<FibonacciAIR<Stark252PrimeField>>(
+        &mut trace,
+        &pub_inputs,
+        padding,
+        mode,
+        &ProofOptions::default_test_options(),
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap()
+}
+
+#[test_log::test]
+fn test_prove_padded_fib_with_selector() {
+    let (proof, pub_inputs) = prove_padded_fib(11, &PaddingRow::RepeatLast, PaddingMode::Selector);
+    assert_eq!(proof.trace_length, 16);
+    assert_eq!(pub_inputs.real_length, 11);
+
+    assert!(verify_padded(
+        &proof,
+        &pub_inputs,
+        &ProofOptions::default_test_options(),
+        StoneProverTranscript::new(&[]),
+    )
+    .is_ok());
+}
+
+#[test_log::test]
+fn test_prove_padded_fib_with_end_exemptions() {
+    let padding = PaddingRow::Fixed(vec![Felt252::zero()]);
+    let (proof, pub_inputs) = prove_padded_fib(13, &padding, PaddingMode::EndExemptions);
+    assert_eq!(proof.trace_length, 16);
+
+    assert!(verify_padded(
+        &proof,
+        &pub_inputs,
+        &ProofOptions::default_test_options(),
+        StoneProverTranscript::new(&[]),
+    )
+    .is_ok());
+}
+
+#[test_log::test]
+fn test_padded_fib_rejects_wrong_real_length() {
+    for mode in [PaddingMode::Selector, PaddingMode::EndExemptions] {
+        let (proof, mut pub_inputs) = prove_padded_fib(11, &PaddingRow::RepeatLast, mode);
+        let verify = |pub_inputs: &PaddedPublicInputs<_>| {
+            verify_padded(
+                &proof,
+                pub_inputs,
+                &ProofOptions::default_test_options(),
+                StoneProverTranscript::new(&[]),
+            )
+        };
+
+        // The padding rows repeat the last row, so they don't continue the sequence.
+        pub_inputs.real_length = 13;
+        assert!(matches!(
+            verify(&pub_inputs),
+            Err(PaddingError::Verification(_))
+        ));
+
+        for real_length in [0, 17] {
+            pub_inputs.real_length = real_length;
+            assert!(matches!(
+                verify(&pub_inputs),
+                Err(PaddingError::InvalidRealLength {
+                    trace_length: 16,
+                    ..
+                })
+            ));
+        }
+    }
+}
+
+#[test_log::test]
+fn test_padded_fib_without_real_rows_has_no_selector_row_constraint() {
+    let pub_inputs = PaddedPublicInputs::<FibonacciAIR<Stark252PrimeField>> {
+        inner_public_inputs: FibonacciPublicInputs {
+            a0: Felt252::one(),
+            a1: Felt252::one(),
+        },
+        real_length: 0,
+        mode: PaddingMode::Selector,
+    };
+    let air = PaddedAIR::new(16, &pub_inputs, &ProofOptions::default_test_options());
+
+    // Only the zero at the first row is added to the two constraints of the inner AIR.
+    let constraints = air.boundary_constraints(&[]).constraints;
+    assert_eq!(constraints.len(), 3);
+    assert_eq!(constraints[2].step, 0);
+    assert_eq!(constraints[2].value, Felt252::zero());
+}
+
+#[test_log::test]
+fn test_padded_selector_mode_rejects_preprocessed_columns() {
+    let proof_options = ProofOptions::default_test_options();
+    let mut trace =
+        round_constants::round_constants_trace::<Stark252PrimeField>(Felt252::from(3), 16);
+    let inner_public_inputs = RoundConstantsPublicInputs {
+        input: Felt252::from(3),
+        output: *trace.get_main(15, 0),
+    };
+
+    let (proof, mut pub_inputs) = prove_padded::<RoundConstantsAIR<Stark252PrimeField>>(
+        &mut trace.clone(),
+        &inner_public_inputs,
+        &PaddingRow::RepeatLast,
+        PaddingMode::EndExemptions,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+
+    assert!(matches!(
+        prove_padded::<RoundConstantsAIR<Stark252PrimeField>>(
+            &mut trace,
+            &inner_public_inputs,
+            &PaddingRow::RepeatLast,
+            PaddingMode::Selector,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        ),
+        Err(PaddingError::PreprocessedColumns)
+    ));
+
+    pub_inputs.mode = PaddingMode::Selector;
+    assert!(matches!(
+        verify_padded(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        ),
+        Err(PaddingError::PreprocessedColumns)
+    ));
+}
+
+#[test_log::test]
+fn test_prove_fib_with_poseidon_transcript() {
+    let mut trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
+    let proof_options = ProofOptions::default_test_options();
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+        &mut trace,
+        &pub_inputs,
+        &proof_options,
+        PoseidonStark252Transcript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        PoseidonStark252Transcript::new(&[]),
+    )
+    .is_ok());
+    // The challenges of the proof were drawn from the Poseidon transcript.
+    assert!(Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .is_err());
+}
+
+#[test_log::test]
+fn test_prove_rap_fib_with_poseidon2_babybear_transcript() {
+    type F = Babybear31PrimeField;
+    let mut trace = fibonacci_rap_trace([FieldElement::<F>::one(), FieldElement::one()], 16);
+    let proof_options = ProofOptions::default_test_options();
+    let pub_inputs = FibonacciRAPPublicInputs {
+        steps: 16,
+        a0: FieldElement::one(),
+        a1: FieldElement::one(),
+    };
+
+    let proof = Prover::<FibonacciRAP<F>>::prove_statement(
+        &mut trace,
+        &pub_inputs,
+        &proof_options,
+        Poseidon2BabyBearTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::<FibonacciRAP<F>>::verify_statement(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        Poseidon2BabyBearTranscript::new(&[]),
+    )
+    .is_ok());
+}
@@ -1,4 +1,5 @@
 use crate::table::Table;
+use alloc::vec::Vec;
 use itertools::Itertools;
 use lambdaworks_math::fft::errors::FFTError;
 use lambdaworks_math::field::traits::{IsField, IsSubFieldOf};
@@ -180,7 +181,7 @@ where
         )
     }
 
-    pub fn compute_trace_polys_main<S>(&self) -> Vec<Polynomial<FieldElement<F>>>
+    pub fn compute_trace_polys_main<S>(&self) -> Result<Vec<Polynomial<FieldElement<F>>>, FFTError>
     where
         S: IsFFTField + IsSubFieldOf<F>,
         FieldElement<F>: Send + Sync,
@@ -193,10 +194,9 @@ where
 
         iter.map(|col| Polynomial::interpolate_fft::<S>(col))
             .collect::<Result<Vec<Polynomial<FieldElement<F>>>, FFTError>>()
-            .unwrap()
     }
 
-    pub fn compute_trace_polys_aux<S>(&self) -> Vec<Polynomial<FieldElement<E>>>
+    pub fn compute_trace_polys_aux<S>(&self) -> Result<Vec<Polynomial<FieldElement<E>>>, FFTError>
     where
         S: IsFFTField + IsSubFieldOf<F>,
         FieldElement<E>: Send + Sync,
@@ -209,7 +209,6 @@ where
 
         iter.map(|col| Polynomial::interpolate_fft::<F>(col))
             .collect::<Result<Vec<Polynomial<FieldElement<E>>>, FFTError>>()
-            .unwrap()
     }
 
     pub fn get_column_main(&self, col_idx: usize) -> Vec<FieldElement<F>> {
@@ -0,0 +1,564 @@
+use alloc::{string::String, vec::Vec};
+use core::marker::PhantomData;
+use lambdaworks_math::{
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsPrimeField, IsSubFieldOf},
+    },
+    traits::ByteConversion,
+};
+
+use crate::{table::Table, trace::TraceTable};
+
+/// Bytes at the start of every trace encoded with [`TraceTable::to_bytes`].
+pub const TRACE_MAGIC: [u8; 4] = *b"LWST";
+
+/// Version of the binary encoding written by [`TraceTable::to_bytes`].
+pub const TRACE_FORMAT_VERSION: u8 = 1;
+
+/// Errors found while decoding a trace encoded with [`TraceTable::to_bytes`] or
+/// [`TraceTable::to_csv`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum TraceEncodingError {
+    /// The bytes don't start with [`TRACE_MAGIC`].
+    InvalidMagic,
+    /// The trace was encoded with a version of the format this decoder doesn't know.
+    UnsupportedVersion(u8),
+    /// The size of the field elements of the trace is not the size of the elements of the
+    /// fields it is decoded into.
+    ElementSizeMismatch { expected: usize, found: usize },
+    /// The trace ended before all its elements were read.
+    UnexpectedEndOfTrace,
+    /// Bytes were left after reading the whole trace.
+    TrailingBytes(usize),
+    /// A dimension of the trace doesn't fit in a `usize`.
+    IntegerOverflow,
+    /// A field element can't be decoded from its bytes.
+    InvalidFieldElement,
+    /// The step size is zero or doesn't divide the number of rows.
+    InvalidStepSize { step_size: usize, num_rows: usize },
+    /// The CSV input has no header line.
+    MissingCsvHeader,
+    /// A CSV row doesn't have as many values as the header has columns.
+    /// Lines are numbered from 1, the header being line 1.
+    InvalidCsvRow {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A CSV value is not a field element.
+    InvalidCsvValue { line: usize, column: usize },
+    /// A CSV value is not smaller than the modulus of the field.
+    CsvValueOutOfRange { line: usize, column: usize },
+}
+
+fn element_size<F>() -> usize
+where
+    F: IsField,
+    FieldElement<F>: ByteConversion,
+{
+    FieldElement::<F>::zero().to_bytes_be().len()
+}
+
+fn check_step_size(step_size: usize, num_rows: usize) -> Result<(), TraceEncodingError> {
+    if step_size == 0 || num_rows % step_size != 0 {
+        return Err(TraceEncodingError::InvalidStepSize {
+            step_size,
+            num_rows,
+        });
+    }
+    Ok(())
+}
+
+impl<F, E> TraceTable<F, E>
+where
+    E: IsField,
+    F: IsSubFieldOf<E> + IsFFTField,
+    FieldElement<F>: ByteConversion,
+    FieldElement<E>: ByteConversion,
+{
+    /// Encodes the main and auxiliary columns of the trace in a columnar binary format.
+    ///
+    /// After a header with the format version, the size in bytes of the elements of each
+    /// field, the step size and the dimensions of the trace as big-endian `u64`s, every
+    /// column is written from its first to its last row, main columns first. Field elements
+    /// are written with the big-endian `ByteConversion` of their field, which for prime fields
+    /// is the canonical representative of the element.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut output = Vec::new();
+        output.extend_from_slice(&TRACE_MAGIC);
+        output.push(TRACE_FORMAT_VERSION);
+        for value in [
+            element_size::<F>(),
+            element_size::<E>(),
+            self.step_size,
+            self.num_rows(),
+            self.main_table.width,
+            self.aux_table.width,
+        ] {
+            output.extend_from_slice(&(value as u64).to_be_bytes());
+        }
+        for column in self.columns_main() {
+            for element in column {
+                output.extend_from_slice(&element.to_bytes_be());
+            }
+        }
+        for column in self.columns_aux() {
+            for element in column {
+                output.extend_from_slice(&element.to_bytes_be());
+            }
+        }
+        output
+    }
+
+    /// Decodes a trace encoded with [`TraceTable::to_bytes`]. The whole input must be consumed.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, TraceEncodingError> {
+        let mut reader = TraceReader { bytes };
+        if reader.read_bytes(TRACE_MAGIC.len())? != TRACE_MAGIC {
+            return Err(TraceEncodingError::InvalidMagic);
+        }
+        let version = reader.read_bytes(1)?[0];
+        if version != TRACE_FORMAT_VERSION {
+            return Err(TraceEncodingError::UnsupportedVersion(version));
+        }
+        reader.check_element_size::<F>()?;
+        reader.check_element_size::<E>()?;
+
+        let step_size = reader.read_usize()?;
+        let num_rows = reader.read_usize()?;
+        let num_main_columns = reader.read_usize()?;
+        let num_aux_columns = reader.read_usize()?;
+        check_step_size(step_size, num_rows)?;
+
+        let main_columns = (0..num_main_columns)
+            .map(|_| reader.read_column::<F>(num_rows))
+            .collect::<Result<Vec<_>, _>>()?;
+        let aux_columns = (0..num_aux_columns)
+            .map(|_| reader.read_column::<E>(num_rows))
+            .collect::<Result<Vec<_>, _>>()?;
+        if !reader.bytes.is_empty() {
+            return Err(TraceEncodingError::TrailingBytes(reader.bytes.len()));
+        }
+
+        Ok(Self {
+            main_table: Table::from_columns(main_columns),
+            aux_table: Table::from_columns(aux_columns),
+            num_main_columns,
+            num_aux_columns,
+            step_size,
+        })
+    }
+}
+
+impl<F, E> TraceTable<F, E>
+where
+    E: IsField,
+    F: IsSubFieldOf<E> + IsFFTField + IsPrimeField,
+{
+    /// Writes the main columns of the trace as CSV: a header line naming the columns
+    /// `main_0`, `main_1`, ..., followed by a line per row with the canonical representatives
+    /// of its elements, as they are displayed: `0x` prefixed hexadecimal numbers for fields
+    /// backed by big integers and decimal numbers for fields backed by machine words.
+    ///
+    /// Auxiliary columns are built by the prover from the RAP challenges and are not written,
+    /// use [`TraceTable::to_bytes`] to dump them too.
+    pub fn to_csv(&self) -> String {
+        let mut csv = (0..self.main_table.width)
+            .map(|column| format!("main_{column}"))
+            .collect::<Vec<_>>()
+            .join(",");
+        csv.push('\n');
+        for row in self.main_table.rows() {
+            let values = row
+                .iter()
+                .map(|element| format!("{}", element.representative()))
+                .collect::<Vec<_>>();
+            csv.push_str(&values.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Reads a main trace written as CSV, as in [`TraceTable::to_csv`]. The first line is a
+    /// header whose fields name the columns and is otherwise ignored. Values are hexadecimal
+    /// when they start with `0x` and decimal otherwise, and must be smaller than the modulus of
+    /// the field. Empty lines are skipped.
+    pub fn from_csv(csv: &str, step_size: usize) -> Result<Self, TraceEncodingError> {
+        let mut lines = csv
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+        let (_, header) = lines.next().ok_or(TraceEncodingError::MissingCsvHeader)?;
+        let num_columns = header.split(',').count();
+        let parser = CsvValueParser::<F>::new();
+
+        let mut data = Vec::new();
+        for (line, row) in lines {
+            let values = row.split(',').map(str::trim).collect::<Vec<_>>();
+            if values.len() != num_columns {
+                return Err(TraceEncodingError::InvalidCsvRow {
+                    line,
+                    expected: num_columns,
+                    found: values.len(),
+                });
+            }
+            for (column, value) in values.into_iter().enumerate() {
+                data.push(parser.parse(value, line, column)?);
+            }
+        }
+        check_step_size(step_size, data.len() / num_columns)?;
+
+        Ok(Self::new_main(data, num_columns, step_size))
+    }
+}
+
+/// Parses the values of a CSV trace, checking they are canonical against the digits of the
+/// largest element of the field, `p - 1`, in each base.
+struct CsvValueParser<F> {
+    max_decimal_digits: Vec<u32>,
+    max_hexadecimal_digits: Vec<u32>,
+    field: PhantomData<F>,
+}
+
+impl<F: IsPrimeField> CsvValueParser<F> {
+    fn new() -> Self {
+        Self {
+            max_decimal_digits: Self::modulus_minus_one_digits(10),
+            max_hexadecimal_digits: Self::modulus_minus_one_digits(16),
+            field: PhantomData,
+        }
+    }
+
+    /// Returns the digits of `p - 1` in base `radix`, most significant first.
+    fn modulus_minus_one_digits(radix: u32) -> Vec<u32> {
+        let modulus_minus_one = F::modulus_minus_one();
+        let one = F::RepresentativeType::from(1);
+        // Doubles the number and adds the next bit of `p - 1`, from the most significant one.
+        let mut digits: Vec<u32> = Vec::new();
+        for bit in (0..F::field_bit_size()).rev() {
+            let mut carry = u32::from((modulus_minus_one >> bit) & one == one);
+            for digit in digits.iter_mut() {
+                let doubled = *digit * 2 + carry;
+                *digit = doubled % radix;
+                carry = doubled / radix;
+            }
+            if carry > 0 {
+                digits.push(carry);
+            }
+        }
+        digits.reverse();
+        digits
+    }
+
+    fn parse(
+        &self,
+        value: &str,
+        line: usize,
+        column: usize,
+    ) -> Result<FieldElement<F>, TraceEncodingError> {
+        let (value, radix, max_digits) = match value.strip_prefix("0x") {
+            Some(value) => (value, 16, &self.max_hexadecimal_digits),
+            None => (value, 10, &self.max_decimal_digits),
+        };
+        let digits = value
+            .chars()
+            .map(|digit| digit.to_digit(radix))
+            .collect::<Option<Vec<_>>>()
+            .filter(|digits| !digits.is_empty())
+            .ok_or(TraceEncodingError::InvalidCsvValue { line, column })?;
+
+        let significant_digits = &digits[digits.iter().take_while(|digit| **digit == 0).count()..];
+        if (significant_digits.len(), significant_digits) > (max_digits.len(), &max_digits[..]) {
+            return Err(TraceEncodingError::CsvValueOutOfRange { line, column });
+        }
+        let radix = FieldElement::<F>::from(radix as u64);
+        Ok(digits.iter().fold(FieldElement::zero(), |acc, digit| {
+            acc * &radix + FieldElement::from(*digit as u64)
+        }))
+    }
+}
+
+struct TraceReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> TraceReader<'a> {
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], TraceEncodingError> {
+        if self.bytes.len() < len {
+            return Err(TraceEncodingError::UnexpectedEndOfTrace);
+        }
+        let (read, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(read)
+    }
+
+    fn read_usize(&mut self) -> Result<usize, TraceEncodingError> {
+        let mut value = [0; 8];
+        value.copy_from_slice(self.read_bytes(8)?);
+        usize::try_from(u64::from_be_bytes(value)).map_err(|_| TraceEncodingError::IntegerOverflow)
+    }
+
+    fn check_element_size<F>(&mut self) -> Result<(), TraceEncodingError>
+    where
+        F: IsField,
+        FieldElement<F>: ByteConversion,
+    {
+        let expected = element_size::<F>();
+        let found = self.read_usize()?;
+        if found != expected {
+            return Err(TraceEncodingError::ElementSizeMismatch { expected, found });
+        }
+        Ok(())
+    }
+
+    fn read_column<F>(
+        &mut self,
+        num_rows: usize,
+    ) -> Result<Vec<FieldElement<F>>, TraceEncodingError>
+    where
+        F: IsField,
+        FieldElement<F>: ByteConversion,
+    {
+        let size = element_size::<F>();
+        let bytes = self.read_bytes(
+            num_rows
+                .checked_mul(size)
+                .ok_or(TraceEncodingError::IntegerOverflow)?,
+        )?;
+        bytes
+            .chunks(size)
+            .map(|element| {
+                FieldElement::from_bytes_be(element)
+                    .map_err(|_| TraceEncodingError::InvalidFieldElement)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::field::fields::fft_friendly::{
+        babybear_u32::Babybear31PrimeField, stark_252_prime_field::Stark252PrimeField,
+    };
+
+    use super::*;
+    use crate::{examples::fibonacci_rap::fibonacci_rap_trace, Felt252};
+
+    type StarkTrace = TraceTable<Stark252PrimeField, Stark252PrimeField>;
+
+    fn trace_with_aux_column() -> StarkTrace {
+        let mut trace = fibonacci_rap_trace([Felt252::one(), Felt252::one()], 7);
+        for row in 0..trace.num_rows() {
+            trace.set_aux(row, 0, Felt252::from(row as u64) - Felt252::from(3));
+        }
+        trace
+    }
+
+    #[test]
+    fn binary_encoding_round_trips_main_and_aux_columns() {
+        let trace = trace_with_aux_column();
+        let bytes = trace.to_bytes();
+
+        assert_eq!(&bytes[..4], &TRACE_MAGIC);
+        assert_eq!(bytes.len(), 5 + 6 * 8 + 3 * trace.num_rows() * 32);
+        let decoded = StarkTrace::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.columns_main(), trace.columns_main());
+        assert_eq!(decoded.columns_aux(), trace.columns_aux());
+        assert_eq!(decoded.step_size, trace.step_size);
+    }
+
+    #[test]
+    fn binary_encoding_writes_canonical_elements_column_by_column() {
+        let trace = StarkTrace::from_columns_main(
+            vec![
+                vec![Felt252::from(1), Felt252::from(2)],
+                vec![Felt252::from(3), -Felt252::one()],
+            ],
+            1,
+        );
+        let elements = &trace.to_bytes()[5 + 6 * 8..];
+
+        assert_eq!(elements[31], 1);
+        assert_eq!(elements[63], 2);
+        assert_eq!(elements[95], 3);
+        assert_eq!(&elements[96..], &(-Felt252::one()).to_bytes_be()[..]);
+    }
+
+    #[test]
+    fn binary_decoding_rejects_malformed_traces() {
+        let bytes = trace_with_aux_column().to_bytes();
+
+        let mut wrong_magic = bytes.clone();
+        wrong_magic[0] = b'X';
+        assert_eq!(
+            StarkTrace::from_bytes(&wrong_magic).unwrap_err(),
+            TraceEncodingError::InvalidMagic
+        );
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[4] = 2;
+        assert_eq!(
+            StarkTrace::from_bytes(&wrong_version).unwrap_err(),
+            TraceEncodingError::UnsupportedVersion(2)
+        );
+
+        assert_eq!(
+            StarkTrace::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+            TraceEncodingError::UnexpectedEndOfTrace
+        );
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            StarkTrace::from_bytes(&trailing).unwrap_err(),
+            TraceEncodingError::TrailingBytes(1)
+        );
+
+        let mut zero_step_size = bytes;
+        zero_step_size[5 + 2 * 8..5 + 3 * 8].copy_from_slice(&0u64.to_be_bytes());
+        assert_eq!(
+            StarkTrace::from_bytes(&zero_step_size).unwrap_err(),
+            TraceEncodingError::InvalidStepSize {
+                step_size: 0,
+                num_rows: 8
+            }
+        );
+    }
+
+    #[test]
+    fn binary_decoding_rejects_traces_of_another_field() {
+        let trace = TraceTable::<Babybear31PrimeField, Babybear31PrimeField>::from_columns_main(
+            vec![vec![FieldElement::one(); 4]],
+            1,
+        );
+
+        assert_eq!(
+            StarkTrace::from_bytes(&trace.to_bytes()).unwrap_err(),
+            TraceEncodingError::ElementSizeMismatch {
+                expected: 32,
+                found: 4
+            }
+        );
+    }
+
+    #[test]
+    fn csv_encoding_round_trips_main_columns() {
+        let trace = trace_with_aux_column();
+        let csv = trace.to_csv();
+
+        assert!(csv.starts_with("main_0,main_1\n0x1,0xd\n"));
+        let decoded = StarkTrace::from_csv(&csv, 1).unwrap();
+        assert_eq!(decoded.columns_main(), trace.columns_main());
+        assert_eq!(decoded.num_aux_columns, 0);
+    }
+
+    #[test]
+    fn csv_encoding_writes_word_sized_fields_in_decimal() {
+        let trace = TraceTable::<Babybear31PrimeField, Babybear31PrimeField>::from_columns_main(
+            vec![vec![FieldElement::from(7), -FieldElement::one()]],
+            1,
+        );
+        let csv = trace.to_csv();
+
+        assert_eq!(csv, "main_0\n7\n2013265920\n");
+        assert_eq!(
+            TraceTable::<Babybear31PrimeField, Babybear31PrimeField>::from_csv(&csv, 1)
+                .unwrap()
+                .columns_main(),
+            trace.columns_main()
+        );
+    }
+
+    #[test]
+    fn csv_decoding_accepts_hexadecimal_and_decimal_values() {
+        let csv = "a, b\n0x10, 16\n\n 0x0 ,001\n";
+        let trace = StarkTrace::from_csv(csv, 2).unwrap();
+
+        assert_eq!(
+            trace.columns_main(),
+            vec![
+                vec![Felt252::from(16), Felt252::zero()],
+                vec![Felt252::from(16), Felt252::one()],
+            ]
+        );
+        assert_eq!(trace.num_steps(), 1);
+    }
+
+    #[test]
+    fn csv_decoding_rejects_values_not_smaller_than_the_modulus() {
+        // p - 1 and p of the Stark252 field, in decimal and in hexadecimal.
+        let max_decimal =
+            "3618502788666131213697322783095070105623107215331596699973092056135872020480";
+        let modulus_decimal =
+            "3618502788666131213697322783095070105623107215331596699973092056135872020481";
+        let max_hexadecimal = "0x800000000000011000000000000000000000000000000000000000000000000";
+        let modulus_hexadecimal =
+            "0x0800000000000011000000000000000000000000000000000000000000000001";
+
+        let csv = format!("a,b\n{max_decimal},{max_hexadecimal}\n");
+        assert_eq!(
+            StarkTrace::from_csv(&csv, 1).unwrap().columns_main(),
+            vec![vec![-Felt252::one()], vec![-Felt252::one()]]
+        );
+
+        for (column, value) in [(0, modulus_decimal), (1, modulus_hexadecimal)] {
+            let mut values = [max_decimal, max_hexadecimal];
+            values[column] = value;
+            let csv = format!("a,b\n0,0\n{}\n", values.join(","));
+            assert_eq!(
+                StarkTrace::from_csv(&csv, 1).unwrap_err(),
+                TraceEncodingError::CsvValueOutOfRange { line: 3, column }
+            );
+        }
+        assert_eq!(
+            StarkTrace::from_csv("a\n2013265921\n", 1)
+                .map(|trace| trace.columns_main())
+                .ok(),
+            Some(vec![vec![Felt252::from(2013265921)]])
+        );
+        assert_eq!(
+            TraceTable::<Babybear31PrimeField, Babybear31PrimeField>::from_csv(
+                "a\n2013265921\n",
+                1
+            )
+            .unwrap_err(),
+            TraceEncodingError::CsvValueOutOfRange { line: 2, column: 0 }
+        );
+    }
+
+    #[test]
+    fn csv_decoding_rejects_malformed_traces() {
+        assert_eq!(
+            StarkTrace::from_csv("\n\n", 1).unwrap_err(),
+            TraceEncodingError::MissingCsvHeader
+        );
+        assert_eq!(
+            StarkTrace::from_csv("a,b\n1,2\n3\n", 1).unwrap_err(),
+            TraceEncodingError::InvalidCsvRow {
+                line: 3,
+                expected: 2,
+                found: 1
+            }
+        );
+        assert_eq!(
+            StarkTrace::from_csv("a,b\n1,2\n3,0xzz\n", 1).unwrap_err(),
+            TraceEncodingError::InvalidCsvValue { line: 3, column: 1 }
+        );
+        assert_eq!(
+            StarkTrace::from_csv("a,b\n1,-2\n", 1).unwrap_err(),
+            TraceEncodingError::InvalidCsvValue { line: 2, column: 1 }
+        );
+        assert_eq!(
+            StarkTrace::from_csv("a,b\n1,0x\n", 1).unwrap_err(),
+            TraceEncodingError::InvalidCsvValue { line: 2, column: 1 }
+        );
+        assert_eq!(
+            StarkTrace::from_csv("a\n1\n2\n3\n", 2).unwrap_err(),
+            TraceEncodingError::InvalidStepSize {
+                step_size: 2,
+                num_rows: 3
+            }
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
 
 use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
 use lambdaworks_math::{
@@ -79,6 +79,16 @@ pub trait AIR {
 
     const STEP_SIZE: usize;
 
+    /// A name identifying the AIR. It is absorbed into the transcript by
+    /// [`IsStarkProver::prove_statement`](crate::prover::IsStarkProver::prove_statement), so
+    /// two different AIRs must not share it, and it must not change between the builds of the
+    /// prover and the verifier.
+    const AIR_ID: &'static str;
+
+    /// The degree of `FieldExtension` over `Field`. It is used to estimate the security of the
+    /// proof options for this AIR.
+    const EXTENSION_DEGREE: usize = 1;
+
     fn new(
         trace_length: usize,
         pub_inputs: &Self::PublicInputs,
@@ -113,6 +123,24 @@ pub trait AIR {
         self.trace_layout().1
     }
 
+    /// Returns the preprocessed (fixed) columns of the AIR. These columns do not depend on the
+    /// witness, are committed to once at setup time and are checked by the verifier against the
+    /// commitment in its `VerifierKey`.
+    /// In evaluation frames they are placed right after the main trace columns, so the column
+    /// `trace_layout().0 + i` refers to the i-th preprocessed column. The number of
+    /// `trace_columns` in the `AirContext` must account for them, and AIRs overriding this
+    /// method must also override `num_preprocessed_columns`.
+    fn preprocessed_columns(&self) -> Vec<Vec<FieldElement<Self::Field>>> {
+        vec![]
+    }
+
+    /// Returns the number of preprocessed columns without building them. The prover and the
+    /// verifier use it to lay out the frames, and the setup rejects AIRs whose
+    /// `preprocessed_columns` don't have this many columns.
+    fn num_preprocessed_columns(&self) -> usize {
+        0
+    }
+
     fn composition_poly_degree_bound(&self) -> usize;
 
     /// The method called by the prover to evaluate the transitions corresponding to an evaluation frame.
@@ -197,8 +225,8 @@ pub trait AIR {
     ) -> Vec<Vec<FieldElement<Self::Field>>> {
         let mut evals = vec![Vec::new(); self.num_transition_constraints()];
 
-        let mut zerofier_groups: HashMap<ZerofierGroupKey, Vec<FieldElement<Self::Field>>> =
-            HashMap::new();
+        let mut zerofier_groups: BTreeMap<ZerofierGroupKey, Vec<FieldElement<Self::Field>>> =
+            BTreeMap::new();
 
         self.transition_constraints().iter().for_each(|c| {
             let period = c.period();
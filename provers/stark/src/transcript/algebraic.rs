@@ -0,0 +1,427 @@
+//! Transcripts built on field-native sponges, which are cheap to replay inside an arithmetic
+//! circuit: field elements are absorbed as elements of the field of the sponge, and challenges
+//! and query indices are read from its state, without converting them to or from bytes.
+//!
+//! [`AlgebraicTranscript`] is a duplex sponge over any [`IsAlgebraicSponge`]. It is instantiated
+//! with Poseidon over `Stark252PrimeField` ([`PoseidonStark252Transcript`]), Rescue Prime
+//! Optimized over Goldilocks ([`RpoGoldilocksTranscript`]) and Poseidon2 over BabyBear
+//! ([`Poseidon2BabyBearTranscript`]). Recursive proofs use their own Poseidon transcript, whose
+//! encoding of byte strings is tailored to the verifier circuit.
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use lambdaworks_crypto::{
+    fiat_shamir::is_transcript::IsTranscript,
+    hash::{
+        poseidon::{starknet::PoseidonCairoStark252, Poseidon},
+        poseidon2::{self, Poseidon2BabyBear},
+        rescue_prime::RescuePrimeOptimized,
+    },
+};
+use lambdaworks_math::{
+    field::{
+        element::FieldElement,
+        extensions::quadratic::{HasQuadraticNonResidue, QuadraticExtensionField},
+        fields::{
+            fft_friendly::{
+                babybear::Babybear31PrimeField, quartic_babybear::Degree4BabyBearExtensionField,
+                stark_252_prime_field::Stark252PrimeField,
+            },
+            u64_goldilocks_field::Goldilocks64Field,
+        },
+        traits::IsField,
+    },
+    traits::ByteConversion,
+};
+
+/// A permutation over the field `Field` together with the layout of the sponge built on it.
+pub trait IsAlgebraicSponge: Clone + Default {
+    type Field: IsField;
+
+    /// Number of elements of the state.
+    const WIDTH: usize;
+    /// Positions of the state that are overwritten when absorbing and read when squeezing.
+    const RATE: Range<usize>;
+    /// Number of bytes packed into each element when absorbing a byte string.
+    const BYTES_PER_ELEMENT: usize;
+
+    fn permute(&self, state: &mut [FieldElement<Self::Field>]);
+
+    /// Returns the element whose canonical representative is `bytes`, read as a big-endian
+    /// integer of at most `BYTES_PER_ELEMENT` bytes.
+    fn element_from_bytes(bytes: &[u8]) -> FieldElement<Self::Field>;
+
+    /// Returns the 64 least significant bits of the canonical representative of `element`.
+    fn to_u64(element: &FieldElement<Self::Field>) -> u64;
+}
+
+/// A field whose elements are absorbed and sampled by a sponge over `F`, as a fixed number of
+/// elements of `F`.
+pub trait IsSpongeField<F: IsField>: IsField {
+    /// Number of elements of `F` of each element.
+    const NUM_ELEMENTS: usize;
+
+    fn to_sponge_elements(element: &FieldElement<Self>) -> Vec<FieldElement<F>>;
+
+    fn from_sponge_elements(elements: &[FieldElement<F>]) -> FieldElement<Self>;
+}
+
+impl<F: IsField> IsSpongeField<F> for F {
+    const NUM_ELEMENTS: usize = 1;
+
+    fn to_sponge_elements(element: &FieldElement<F>) -> Vec<FieldElement<F>> {
+        vec![element.clone()]
+    }
+
+    fn from_sponge_elements(elements: &[FieldElement<F>]) -> FieldElement<F> {
+        elements[0].clone()
+    }
+}
+
+impl<F, Q> IsSpongeField<F> for QuadraticExtensionField<F, Q>
+where
+    F: IsField,
+    Q: Clone + core::fmt::Debug + HasQuadraticNonResidue<F>,
+{
+    const NUM_ELEMENTS: usize = 2;
+
+    fn to_sponge_elements(element: &FieldElement<Self>) -> Vec<FieldElement<F>> {
+        element.value().to_vec()
+    }
+
+    fn from_sponge_elements(elements: &[FieldElement<F>]) -> FieldElement<Self> {
+        FieldElement::new([elements[0].clone(), elements[1].clone()])
+    }
+}
+
+impl IsSpongeField<Babybear31PrimeField> for Degree4BabyBearExtensionField {
+    const NUM_ELEMENTS: usize = 4;
+
+    fn to_sponge_elements(element: &FieldElement<Self>) -> Vec<FieldElement<Babybear31PrimeField>> {
+        element.value().to_vec()
+    }
+
+    fn from_sponge_elements(elements: &[FieldElement<Babybear31PrimeField>]) -> FieldElement<Self> {
+        FieldElement::new([
+            elements[0].clone(),
+            elements[1].clone(),
+            elements[2].clone(),
+            elements[3].clone(),
+        ])
+    }
+}
+
+/// A transcript implementing `IsTranscript` with a duplex sponge over the permutation `S`.
+///
+/// Absorbed elements are buffered and overwrite the rate of the state when the buffer is full,
+/// or before squeezing, after which the state is permuted and its rate becomes the output buffer.
+/// Samples are taken from the output buffer, and the state is permuted again when it is empty.
+/// Elements of extension fields are absorbed and sampled as their coefficients, and byte strings
+/// as their length followed by chunks of `S::BYTES_PER_ELEMENT` bytes. Indices are reduced from
+/// the 64 least significant bits of a sampled element, so the bound must be much smaller than the
+/// modulus for them to be close to uniform, as is the case for query indices.
+#[derive(Clone)]
+pub struct AlgebraicTranscript<S: IsAlgebraicSponge> {
+    sponge: S,
+    state: Vec<FieldElement<S::Field>>,
+    input: Vec<FieldElement<S::Field>>,
+    output: Vec<FieldElement<S::Field>>,
+}
+
+pub type PoseidonStark252Transcript = AlgebraicTranscript<PoseidonStark252Sponge>;
+pub type RpoGoldilocksTranscript = AlgebraicTranscript<RpoGoldilocksSponge>;
+pub type Poseidon2BabyBearTranscript = AlgebraicTranscript<Poseidon2BabyBearSponge>;
+
+impl<S: IsAlgebraicSponge> AlgebraicTranscript<S> {
+    pub fn new(data: &[u8]) -> Self {
+        let mut transcript = Self {
+            sponge: S::default(),
+            state: vec![FieldElement::zero(); S::WIDTH],
+            input: Vec::with_capacity(S::RATE.len()),
+            output: Vec::with_capacity(S::RATE.len()),
+        };
+        transcript.absorb_bytes(data);
+        transcript
+    }
+
+    fn absorb(&mut self, element: FieldElement<S::Field>) {
+        self.output.clear();
+        self.input.push(element);
+        if self.input.len() == S::RATE.len() {
+            self.duplex();
+        }
+    }
+
+    fn absorb_bytes(&mut self, bytes: &[u8]) {
+        self.absorb(FieldElement::from(bytes.len() as u64));
+        for chunk in bytes.chunks(S::BYTES_PER_ELEMENT) {
+            self.absorb(S::element_from_bytes(chunk));
+        }
+    }
+
+    fn squeeze(&mut self) -> FieldElement<S::Field> {
+        if !self.input.is_empty() || self.output.is_empty() {
+            self.duplex();
+        }
+        self.output.pop().unwrap()
+    }
+
+    fn duplex(&mut self) {
+        for (state, element) in self.state[S::RATE].iter_mut().zip(self.input.drain(..)) {
+            *state = element;
+        }
+        self.sponge.permute(&mut self.state);
+        self.output.clear();
+        self.output.extend_from_slice(&self.state[S::RATE]);
+    }
+}
+
+impl<S, E> IsTranscript<E> for AlgebraicTranscript<S>
+where
+    S: IsAlgebraicSponge,
+    E: IsSpongeField<S::Field>,
+{
+    fn append_field_element(&mut self, element: &FieldElement<E>) {
+        for element in E::to_sponge_elements(element) {
+            self.absorb(element);
+        }
+    }
+
+    fn append_bytes(&mut self, new_bytes: &[u8]) {
+        self.absorb_bytes(new_bytes);
+    }
+
+    /// Returns bytes squeezed from a copy of the transcript, which depend on everything absorbed
+    /// and sampled so far.
+    fn state(&self) -> [u8; 32] {
+        let bytes_per_element = S::BYTES_PER_ELEMENT.min(8);
+        let mut transcript = self.clone();
+        let mut state = Vec::with_capacity(32 + bytes_per_element);
+        while state.len() < 32 {
+            let bytes = S::to_u64(&transcript.squeeze()).to_be_bytes();
+            state.extend_from_slice(&bytes[8 - bytes_per_element..]);
+        }
+        state[..32].try_into().unwrap()
+    }
+
+    fn sample_field_element(&mut self) -> FieldElement<E> {
+        let elements: Vec<_> = (0..E::NUM_ELEMENTS).map(|_| self.squeeze()).collect();
+        E::from_sponge_elements(&elements)
+    }
+
+    fn sample_u64(&mut self, upper_bound: u64) -> u64 {
+        S::to_u64(&self.squeeze()) % upper_bound
+    }
+}
+
+/// Reads `bytes` as a big-endian integer of at most 8 bytes.
+fn u64_from_bytes(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(0, |value, byte| (value << 8) | u64::from(*byte))
+}
+
+/// The Poseidon permutation of Starknet, with a state of 3 elements and a rate of 2.
+#[derive(Clone, Default)]
+pub struct PoseidonStark252Sponge;
+
+impl IsAlgebraicSponge for PoseidonStark252Sponge {
+    type Field = Stark252PrimeField;
+
+    const WIDTH: usize = 3;
+    const RATE: Range<usize> = 0..2;
+    const BYTES_PER_ELEMENT: usize = 31;
+
+    fn permute(&self, state: &mut [FieldElement<Self::Field>]) {
+        PoseidonCairoStark252::hades_permutation(state);
+    }
+
+    fn element_from_bytes(bytes: &[u8]) -> FieldElement<Self::Field> {
+        let mut padded = [0u8; 32];
+        padded[32 - bytes.len()..].copy_from_slice(bytes);
+        FieldElement::from_bytes_be(&padded).unwrap()
+    }
+
+    fn to_u64(element: &FieldElement<Self::Field>) -> u64 {
+        element.representative().limbs[3]
+    }
+}
+
+/// Rescue Prime Optimized with 128 bits of security, with a state of 12 elements whose last 8
+/// are the rate.
+#[derive(Clone, Default)]
+pub struct RpoGoldilocksSponge(RescuePrimeOptimized);
+
+impl IsAlgebraicSponge for RpoGoldilocksSponge {
+    type Field = Goldilocks64Field;
+
+    const WIDTH: usize = 12;
+    const RATE: Range<usize> = 4..12;
+    const BYTES_PER_ELEMENT: usize = 7;
+
+    fn permute(&self, state: &mut [FieldElement<Self::Field>]) {
+        self.0.permutation(state);
+    }
+
+    fn element_from_bytes(bytes: &[u8]) -> FieldElement<Self::Field> {
+        FieldElement::from(u64_from_bytes(bytes))
+    }
+
+    fn to_u64(element: &FieldElement<Self::Field>) -> u64 {
+        element.representative()
+    }
+}
+
+/// Poseidon2 over BabyBear, with a state of 16 elements whose first 8 are the rate.
+#[derive(Clone, Default)]
+pub struct Poseidon2BabyBearSponge;
+
+impl IsAlgebraicSponge for Poseidon2BabyBearSponge {
+    type Field = Babybear31PrimeField;
+
+    const WIDTH: usize = poseidon2::WIDTH;
+    const RATE: Range<usize> = 0..8;
+    const BYTES_PER_ELEMENT: usize = 3;
+
+    fn permute(&self, state: &mut [FieldElement<Self::Field>]) {
+        Poseidon2BabyBear::permutation(state.try_into().unwrap());
+    }
+
+    fn element_from_bytes(bytes: &[u8]) -> FieldElement<Self::Field> {
+        FieldElement::from(u64_from_bytes(bytes))
+    }
+
+    fn to_u64(element: &FieldElement<Self::Field>) -> u64 {
+        element.representative().limbs[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lambdaworks_math::field::fields::u64_goldilocks_field::Goldilocks64ExtensionField;
+
+    fn transcripts_with_the_same_history_agree<S, E>(element: FieldElement<E>)
+    where
+        S: IsAlgebraicSponge,
+        E: IsSpongeField<S::Field>,
+    {
+        let mut transcript_1 = AlgebraicTranscript::<S>::new(b"statement");
+        let mut transcript_2 = AlgebraicTranscript::<S>::new(b"statement");
+        for transcript in [&mut transcript_1, &mut transcript_2] {
+            transcript.append_field_element(&element);
+            IsTranscript::<E>::append_bytes(transcript, &[1, 2, 3]);
+        }
+
+        assert_eq!(
+            IsTranscript::<E>::state(&transcript_1),
+            IsTranscript::<E>::state(&transcript_2)
+        );
+        assert_eq!(
+            IsTranscript::<E>::sample_field_element(&mut transcript_1),
+            IsTranscript::<E>::sample_field_element(&mut transcript_2)
+        );
+        assert_eq!(
+            IsTranscript::<E>::sample_u64(&mut transcript_1, 1 << 20),
+            IsTranscript::<E>::sample_u64(&mut transcript_2, 1 << 20)
+        );
+    }
+
+    fn samples_depend_on_the_history<S, E>(element: FieldElement<E>)
+    where
+        S: IsAlgebraicSponge,
+        E: IsSpongeField<S::Field>,
+    {
+        let mut transcript_1 = AlgebraicTranscript::<S>::new(b"statement");
+        let mut transcript_2 = AlgebraicTranscript::<S>::new(b"statement");
+        let mut transcript_3 = AlgebraicTranscript::<S>::new(b"other statement");
+        transcript_1.append_field_element(&element);
+        transcript_2.append_field_element(&(element.clone() + FieldElement::<E>::one()));
+        transcript_3.append_field_element(&element);
+
+        let state_1 = IsTranscript::<E>::state(&transcript_1);
+        assert_ne!(state_1, IsTranscript::<E>::state(&transcript_2));
+        assert_ne!(state_1, IsTranscript::<E>::state(&transcript_3));
+
+        let sample_1: FieldElement<E> = transcript_1.sample_field_element();
+        let sample_2: FieldElement<E> = transcript_2.sample_field_element();
+        let sample_3: FieldElement<E> = transcript_3.sample_field_element();
+        assert_ne!(sample_1, sample_2);
+        assert_ne!(sample_1, sample_3);
+
+        // Sampling changes the state, and so do the following samples.
+        assert_ne!(state_1, IsTranscript::<E>::state(&transcript_1));
+        let next_sample: FieldElement<E> = transcript_1.sample_field_element();
+        assert_ne!(sample_1, next_sample);
+    }
+
+    fn check_transcript<S, E>(element: FieldElement<E>)
+    where
+        S: IsAlgebraicSponge,
+        E: IsSpongeField<S::Field>,
+    {
+        transcripts_with_the_same_history_agree::<S, E>(element.clone());
+        samples_depend_on_the_history::<S, E>(element);
+    }
+
+    #[test]
+    fn poseidon_stark252_transcript() {
+        check_transcript::<PoseidonStark252Sponge, Stark252PrimeField>(FieldElement::from(7));
+    }
+
+    #[test]
+    fn rpo_goldilocks_transcript() {
+        check_transcript::<RpoGoldilocksSponge, Goldilocks64Field>(FieldElement::from(7));
+        check_transcript::<RpoGoldilocksSponge, Goldilocks64ExtensionField>(FieldElement::new([
+            FieldElement::from(7),
+            FieldElement::from(8),
+        ]));
+    }
+
+    #[test]
+    fn poseidon2_babybear_transcript() {
+        check_transcript::<Poseidon2BabyBearSponge, Babybear31PrimeField>(FieldElement::from(7));
+        check_transcript::<Poseidon2BabyBearSponge, Degree4BabyBearExtensionField>(
+            FieldElement::new([
+                FieldElement::from(7),
+                FieldElement::from(8),
+                FieldElement::from(9),
+                FieldElement::from(10),
+            ]),
+        );
+    }
+
+    #[test]
+    fn extension_elements_are_absorbed_as_their_coefficients() {
+        let coefficients = [7u64, 8, 9, 10].map(FieldElement::<Babybear31PrimeField>::from);
+        let mut transcript_1 = Poseidon2BabyBearTranscript::new(&[]);
+        let mut transcript_2 = Poseidon2BabyBearTranscript::new(&[]);
+        transcript_1.append_field_element(&FieldElement::<Degree4BabyBearExtensionField>::new(
+            coefficients.clone(),
+        ));
+        for coefficient in coefficients.iter() {
+            transcript_2.append_field_element(coefficient);
+        }
+
+        let sample: FieldElement<Degree4BabyBearExtensionField> =
+            transcript_1.sample_field_element();
+        let coefficients: Vec<FieldElement<Babybear31PrimeField>> = (0..4)
+            .map(|_| transcript_2.sample_field_element())
+            .collect();
+        assert_eq!(sample.value().to_vec(), coefficients);
+    }
+
+    #[test]
+    fn sampled_indices_are_below_the_bound() {
+        let mut transcript = Poseidon2BabyBearTranscript::new(&[]);
+        for bound in [1, 2, 3, 1000, 1 << 20] {
+            for _ in 0..20 {
+                assert!(
+                    IsTranscript::<Babybear31PrimeField>::sample_u64(&mut transcript, bound)
+                        < bound
+                );
+            }
+        }
+    }
+}
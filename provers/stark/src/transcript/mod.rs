@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
 use lambdaworks_math::{
     field::{
@@ -9,6 +10,13 @@ use lambdaworks_math::{
 };
 use sha3::{Digest, Keccak256};
 
+use crate::{proof::options::ProofOptions, traits::AIR};
+
+pub mod algebraic;
+
+/// Domain separator absorbed first by [`bind_statement`].
+pub const STARK_DOMAIN_SEPARATOR: &[u8] = b"lambdaworks-stark-platinum-prover";
+
 /// A transcript implementing `IsTranscript` and compatible with Stone (https://github.com/starkware-libs/stone-prover).
 pub struct StoneProverTranscript {
     state: [u8; 32],
@@ -144,13 +152,45 @@ where
         .collect()
 }
 
+/// Absorbs into `transcript` everything that identifies the statement being proven: the
+/// domain separator, the AIR identity, the proof options, the trace length and the public inputs.
+/// Variable-length items are prefixed with their length so that the encoding is unambiguous.
+///
+/// `transcript` must be freshly created, since prover and verifier must start from the same state.
+pub fn bind_statement<A: AIR>(
+    transcript: &mut impl IsTranscript<A::FieldExtension>,
+    trace_length: usize,
+    pub_inputs: &A::PublicInputs,
+    proof_options: &ProofOptions,
+) where
+    A::PublicInputs: AsBytes,
+{
+    let air_id = A::AIR_ID.as_bytes();
+    let pub_inputs = pub_inputs.as_bytes();
+
+    transcript.append_bytes(STARK_DOMAIN_SEPARATOR);
+    transcript.append_bytes(&(air_id.len() as u64).to_be_bytes());
+    transcript.append_bytes(air_id);
+    transcript.append_bytes(&proof_options.as_bytes());
+    transcript.append_bytes(&(trace_length as u64).to_be_bytes());
+    transcript.append_bytes(&(pub_inputs.len() as u64).to_be_bytes());
+    transcript.append_bytes(&pub_inputs);
+}
+
 #[cfg(test)]
 mod tests {
     use lambdaworks_math::field::{
         element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
     };
 
-    use crate::transcript::{IsTranscript, StoneProverTranscript};
+    use crate::{
+        examples::{
+            fibonacci_2_columns::Fibonacci2ColsAIR,
+            simple_fibonacci::{FibonacciAIR, FibonacciPublicInputs},
+        },
+        proof::options::ProofOptions,
+        transcript::{bind_statement, IsTranscript, StoneProverTranscript},
+    };
 
     use std::num::ParseIntError;
 
@@ -435,4 +475,38 @@ mod tests {
         assert_eq!(transcript.sample_u64(128), 28);
         assert_eq!(transcript.sample_u64(128), 31);
     }
+
+    #[test]
+    fn bind_statement_separates_airs_with_the_same_public_inputs() {
+        let pub_inputs = FibonacciPublicInputs {
+            a0: FE::one(),
+            a1: FE::one(),
+        };
+        let proof_options = ProofOptions::default_test_options();
+
+        let mut transcript_1 = StoneProverTranscript::new(&[]);
+        bind_statement::<FibonacciAIR<Stark252PrimeField>>(
+            &mut transcript_1,
+            8,
+            &pub_inputs,
+            &proof_options,
+        );
+        let mut transcript_2 = StoneProverTranscript::new(&[]);
+        bind_statement::<Fibonacci2ColsAIR<Stark252PrimeField>>(
+            &mut transcript_2,
+            8,
+            &pub_inputs,
+            &proof_options,
+        );
+        let mut transcript_3 = StoneProverTranscript::new(&[]);
+        bind_statement::<FibonacciAIR<Stark252PrimeField>>(
+            &mut transcript_3,
+            16,
+            &pub_inputs,
+            &proof_options,
+        );
+
+        assert_ne!(transcript_1.state(), transcript_2.state());
+        assert_ne!(transcript_1.state(), transcript_3.state());
+    }
 }
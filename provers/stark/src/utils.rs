@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 use lambdaworks_crypto::merkle_tree::proof::Proof;
 use lambdaworks_math::errors::DeserializationError;
 
@@ -1,13 +1,28 @@
 use super::{
-    config::BatchedMerkleTreeBackend,
+    config::{IsCommitmentHash, Keccak256Commitments},
     domain::Domain,
     fri::fri_decommit::FriDecommitment,
     grinding,
+    preprocessed::VerifierKey,
     proof::{options::ProofOptions, stark::StarkProof},
+    table::Table,
     traits::{TransitionEvaluationContext, AIR},
 };
-use crate::{config::Commitment, proof::stark::DeepPolynomialOpening};
-use lambdaworks_crypto::{fiat_shamir::is_transcript::IsTranscript, merkle_tree::proof::Proof};
+use crate::{
+    config::Commitment,
+    proof::stark::{DeepPolynomialOpening, PolynomialOpenings},
+    transcript::bind_statement,
+};
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::marker::PhantomData;
+use lambdaworks_crypto::{
+    fiat_shamir::is_transcript::IsTranscript,
+    merkle_tree::{proof::Proof, traits::IsMerkleTreeBackend},
+};
 use lambdaworks_math::{
     fft::cpu::bit_reversing::reverse_index,
     field::{
@@ -18,19 +33,54 @@ use lambdaworks_math::{
 };
 #[cfg(not(feature = "test_fiat_shamir"))]
 use log::error;
-use std::marker::PhantomData;
 #[cfg(feature = "instruments")]
 use std::time::Instant;
 
-/// A default STARK verifier implementing `IsStarkVerifier`.
-pub struct Verifier<A: AIR> {
-    phantom: PhantomData<A>,
+/// A default STARK verifier implementing `IsStarkVerifier`, for proofs committed with Keccak256.
+pub type Verifier<A> = StarkVerifier<A, Keccak256Commitments>;
+
+/// A STARK verifier implementing `IsStarkVerifier` for proofs whose Merkle trees use the hash `H`.
+pub struct StarkVerifier<A: AIR, H> {
+    phantom: PhantomData<(A, H)>,
 }
 
-impl<A: AIR> IsStarkVerifier<A> for Verifier<A> {}
+impl<A, H> IsStarkVerifier<A, H> for StarkVerifier<A, H>
+where
+    A: AIR,
+    H: IsCommitmentHash<A::Field, A::FieldExtension>,
+{
+}
+
+/// The reasons a STARK proof can be rejected, one per step of the verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationError {
+    /// The proof does not have the shape expected by the AIR and the proof options,
+    /// e.g. vectors of the wrong length or a mismatched number of columns.
+    MalformedProof(String),
+    /// The proof has fewer FRI queries than required by the proof options.
+    InsufficientQueries { expected: usize, actual: usize },
+    /// The proof-of-work nonce is missing or does not satisfy the grinding factor.
+    InvalidGrindingNonce,
+    /// The proof-of-work nonce before the FRI folding challenge `layer` is missing or does not
+    /// satisfy the commit phase grinding factor.
+    InvalidCommitPhaseGrindingNonce { layer: usize },
+    /// The claimed out-of-domain evaluation of the composition polynomial is not consistent
+    /// with the out-of-domain evaluations of the trace.
+    CompositionPolynomialMismatch,
+    /// The opening of the FRI layer `layer` (starting from 1) failed for the query `query`.
+    FriLayerOpening { query: usize, layer: usize },
+    /// The value obtained after folding the query `query` does not match the last FRI value.
+    FriLastValueMismatch { query: usize },
+    /// The opening of the trace polynomials failed for the query `query`.
+    TraceOpening { query: usize },
+    /// The opening of the composition polynomial parts failed for the query `query`.
+    CompositionPolynomialOpening { query: usize },
+    /// The setup of the AIR failed, e.g. its preprocessed columns don't have the declared shape.
+    InvalidSetup(String),
+}
 
-/// A container holding the complete list of challenges sent to the prover along with the seed used
-/// to validate the proof-of-work nonce.
+/// A container holding the complete list of challenges sent to the prover along with the seeds used
+/// to validate the proof-of-work nonces.
 pub struct Challenges<A>
 where
     A: AIR,
@@ -53,13 +103,20 @@ where
     pub rap_challenges: Vec<FieldElement<A::FieldExtension>>,
     /// The seed used to verify the proof-of-work nonce.
     pub grinding_seed: [u8; 32],
+    /// The seeds used to verify the proof-of-work nonces of the FRI commit phase, one per
+    /// folding challenge if the commit phase grinding factor is not zero.
+    pub commit_phase_grinding_seeds: Vec<[u8; 32]>,
 }
 
 pub type DeepPolynomialEvaluations<F> = (Vec<FieldElement<F>>, Vec<FieldElement<F>>);
 
 /// The functionality of a STARK verifier providing methods to run the STARK Verify protocol
 /// https://lambdaclass.github.io/lambdaworks/starks/protocol.html
-pub trait IsStarkVerifier<A: AIR> {
+pub trait IsStarkVerifier<A, H = Keccak256Commitments>
+where
+    A: AIR,
+    H: IsCommitmentHash<A::Field, A::FieldExtension>,
+{
     fn sample_query_indexes(
         number_of_queries: usize,
         domain: &Domain<A::Field>,
@@ -72,12 +129,40 @@ pub trait IsStarkVerifier<A: AIR> {
     }
 
     /// Returns the list of challenges sent to the prover.
+    /// If the AIR has preprocessed columns, this runs the setup to obtain their commitment. Use
+    /// `step_1_replay_rounds_and_recover_challenges_with_key` to take it from a precomputed
+    /// `VerifierKey` instead.
     fn step_1_replay_rounds_and_recover_challenges(
         air: &A,
         proof: &StarkProof<A::Field, A::FieldExtension>,
         domain: &Domain<A::Field>,
         transcript: &mut impl IsTranscript<A::FieldExtension>,
     ) -> Challenges<A>
+    where
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes,
+    {
+        // A failed setup leaves the commitment out of the transcript, so the challenges won't
+        // match the ones of any proof with preprocessed columns.
+        let verifier_key = VerifierKey::with_commitment_hash::<A, H>(air).unwrap_or_default();
+        Self::step_1_replay_rounds_and_recover_challenges_with_key(
+            air,
+            proof,
+            domain,
+            &verifier_key,
+            transcript,
+        )
+    }
+
+    /// Returns the list of challenges sent to the prover, taking the commitment to the
+    /// preprocessed columns from `verifier_key`.
+    fn step_1_replay_rounds_and_recover_challenges_with_key(
+        air: &A,
+        proof: &StarkProof<A::Field, A::FieldExtension>,
+        domain: &Domain<A::Field>,
+        verifier_key: &VerifierKey,
+        transcript: &mut impl IsTranscript<A::FieldExtension>,
+    ) -> Challenges<A>
     where
         FieldElement<A::Field>: AsBytes,
         FieldElement<A::FieldExtension>: AsBytes,
@@ -86,6 +171,11 @@ pub trait IsStarkVerifier<A: AIR> {
         // ==========|   Round 1   |==========
         // ===================================
 
+        // The commitment to the preprocessed columns is known in advance from the verifier key.
+        if let Some(root) = verifier_key.preprocessed_commitment {
+            transcript.append_bytes(&root);
+        }
+
         // <<<< Receive commitments:[tⱼ]
         transcript.append_bytes(&proof.lde_trace_main_merkle_root);
 
@@ -164,19 +254,24 @@ pub trait IsStarkVerifier<A: AIR> {
 
         // FRI commit phase
         let merkle_roots = &proof.fri_layers_merkle_roots;
-        let mut zetas = merkle_roots
-            .iter()
-            .map(|root| {
-                // >>>> Send challenge 𝜁ₖ
-                let element = transcript.sample_field_element();
-                // <<<< Receive commitment: [pₖ] (the first one is [p₀])
+        let commit_phase_grinding_factor = air.context().proof_options.commit_phase_grinding_factor;
+        let mut commit_phase_grinding_seeds = Vec::new();
+        let mut zetas = Vec::with_capacity(merkle_roots.len() + 1);
+        for k in 0..=merkle_roots.len() {
+            // Receive commit phase grinding value
+            if commit_phase_grinding_factor > 0 {
+                if let Some(nonce_value) = proof.commit_phase_nonces.get(k) {
+                    commit_phase_grinding_seeds.push(transcript.state());
+                    transcript.append_bytes(&nonce_value.to_be_bytes());
+                }
+            }
+            // >>>> Send challenge 𝜁ₖ
+            zetas.push(transcript.sample_field_element());
+            // <<<< Receive commitment: [pₖ] (the first one is [p₀])
+            if let Some(root) = merkle_roots.get(k) {
                 transcript.append_bytes(root);
-                element
-            })
-            .collect::<Vec<FieldElement<A::FieldExtension>>>();
-
-        // >>>> Send challenge 𝜁ₙ₋₁
-        zetas.push(transcript.sample_field_element());
+            }
+        }
 
         // <<<< Receive value: pₙ
         transcript.append_field_element(&proof.fri_last_value);
@@ -206,6 +301,7 @@ pub trait IsStarkVerifier<A: AIR> {
             iotas,
             rap_challenges,
             grinding_seed,
+            commit_phase_grinding_seeds,
         }
     }
 
@@ -217,7 +313,26 @@ pub trait IsStarkVerifier<A: AIR> {
         proof: &StarkProof<A::Field, A::FieldExtension>,
         domain: &Domain<A::Field>,
         challenges: &Challenges<A>,
-    ) -> bool {
+    ) -> Result<(), VerificationError> {
+        Self::verify_composition_poly_ood_evaluation(
+            air,
+            &proof.trace_ood_evaluations,
+            &proof.composition_poly_parts_ood_evaluation,
+            domain,
+            challenges,
+        )
+    }
+
+    /// Checks that the purported evaluations `composition_poly_parts_ood_evaluation` of the
+    /// composition polynomial parts at the out-of-domain challenge are consistent with the
+    /// evaluations `trace_ood_evaluations` of the trace polynomials.
+    fn verify_composition_poly_ood_evaluation(
+        air: &A,
+        trace_ood_evaluations: &Table<A::FieldExtension>,
+        composition_poly_parts_ood_evaluation: &[FieldElement<A::FieldExtension>],
+        domain: &Domain<A::Field>,
+        challenges: &Challenges<A>,
+    ) -> Result<(), VerificationError> {
         let boundary_constraints = air.boundary_constraints(&challenges.rap_challenges);
 
         let trace_length = air.trace_length();
@@ -234,10 +349,11 @@ pub trait IsStarkVerifier<A: AIR> {
                 let point = &domain.trace_primitive_root.pow(step as u64);
                 let column_idx = boundary_constraints.constraints[index].col;
                 let trace_evaluation = if is_aux {
-                    let column_idx = air.trace_layout().0 + column_idx;
-                    &proof.trace_ood_evaluations.get_row(0)[column_idx]
+                    let column_idx =
+                        air.trace_layout().0 + air.num_preprocessed_columns() + column_idx;
+                    &trace_ood_evaluations.get_row(0)[column_idx]
                 } else {
-                    &proof.trace_ood_evaluations.get_row(0)[column_idx]
+                    &trace_ood_evaluations.get_row(0)[column_idx]
                 };
                 let boundary_zerofier_challenges_z_den = -point + &challenges.z;
 
@@ -269,11 +385,9 @@ pub trait IsStarkVerifier<A: AIR> {
             .map(|poly| poly.evaluate(&challenges.z))
             .collect::<Vec<FieldElement<A::FieldExtension>>>();
 
-        let num_main_trace_columns =
-            proof.trace_ood_evaluations.width - air.num_auxiliary_rap_columns();
+        let num_main_trace_columns = trace_ood_evaluations.width - air.num_auxiliary_rap_columns();
 
-        let ood_frame =
-            (proof.trace_ood_evaluations).into_frame(num_main_trace_columns, A::STEP_SIZE);
+        let ood_frame = trace_ood_evaluations.into_frame(num_main_trace_columns, A::STEP_SIZE);
         let transition_evaluation_context = TransitionEvaluationContext::new_verifier(
             &ood_frame,
             &periodic_values,
@@ -301,15 +415,17 @@ pub trait IsStarkVerifier<A: AIR> {
         let composition_poly_ood_evaluation =
             &boundary_quotient_ood_evaluation + transition_c_i_evaluations_sum;
 
-        let composition_poly_claimed_ood_evaluation = proof
-            .composition_poly_parts_ood_evaluation
+        let composition_poly_claimed_ood_evaluation = composition_poly_parts_ood_evaluation
             .iter()
             .rev()
             .fold(FieldElement::zero(), |acc, coeff| {
                 acc * &challenges.z + coeff
             });
 
-        composition_poly_claimed_ood_evaluation == composition_poly_ood_evaluation
+        if composition_poly_claimed_ood_evaluation != composition_poly_ood_evaluation {
+            return Err(VerificationError::CompositionPolynomialMismatch);
+        }
+        Ok(())
     }
 
     /// Reconstructs the Deep composition polynomial evaluations at the challenge indices values using the provided
@@ -319,7 +435,7 @@ pub trait IsStarkVerifier<A: AIR> {
         proof: &StarkProof<A::Field, A::FieldExtension>,
         domain: &Domain<A::Field>,
         challenges: &Challenges<A>,
-    ) -> bool
+    ) -> Result<(), VerificationError>
     where
         FieldElement<A::Field>: AsBytes + Sync + Send,
         FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
@@ -343,17 +459,17 @@ pub trait IsStarkVerifier<A: AIR> {
             .zip(&challenges.iotas)
             .zip(evaluation_point_inverse)
             .enumerate()
-            .fold(true, |mut result, (i, ((proof_s, iota_s), eval))| {
-                result &= Self::verify_query_and_sym_openings(
+            .try_for_each(|(i, ((proof_s, iota_s), eval))| {
+                Self::verify_query_and_sym_openings(
                     proof,
                     &challenges.zetas,
+                    i,
                     *iota_s,
                     proof_s,
                     eval,
                     &deep_poly_evaluations[i],
                     &deep_poly_evaluations_sym[i],
-                );
-                result
+                )
             })
     }
 
@@ -377,8 +493,8 @@ pub trait IsStarkVerifier<A: AIR> {
         .clone()
     }
 
-    /// Verifies the validity of the opening proof.
-    fn verify_opening<E>(
+    /// Verifies the validity of the opening proof against a Merkle tree with backend `B`.
+    fn verify_opening<E, B>(
         proof: &Proof<Commitment>,
         root: &Commitment,
         index: usize,
@@ -389,14 +505,16 @@ pub trait IsStarkVerifier<A: AIR> {
         FieldElement<E>: AsBytes + Sync + Send,
         E: IsField,
         A::Field: IsSubFieldOf<E>,
+        B: IsMerkleTreeBackend<Node = Commitment, Data = Vec<FieldElement<E>>>,
     {
-        proof.verify::<BatchedMerkleTreeBackend<E>>(root, index, &value.to_owned())
+        proof.verify::<B>(root, index, &value.to_owned())
     }
 
     /// Verify opening Open(tⱼ(D_LDE), 𝜐) and Open(tⱼ(D_LDE), -𝜐) for all trace polynomials tⱼ,
     /// where 𝜐 and -𝜐 are the elements corresponding to the index challenge `iota`.
     fn verify_trace_openings(
         proof: &StarkProof<A::Field, A::FieldExtension>,
+        verifier_key: &VerifierKey,
         deep_poly_openings: &DeepPolynomialOpening<A::Field, A::FieldExtension>,
         iota: usize,
     ) -> bool
@@ -408,13 +526,13 @@ pub trait IsStarkVerifier<A: AIR> {
         let index_sym = iota * 2 + 1;
         let mut result = true;
 
-        result &= Self::verify_opening::<A::Field>(
+        result &= Self::verify_opening::<A::Field, H::MainBackend>(
             &deep_poly_openings.main_trace_polys.proof,
             &proof.lde_trace_main_merkle_root,
             index,
             &deep_poly_openings.main_trace_polys.evaluations,
         );
-        result &= Self::verify_opening::<A::Field>(
+        result &= Self::verify_opening::<A::Field, H::MainBackend>(
             &deep_poly_openings.main_trace_polys.proof_sym,
             &proof.lde_trace_main_merkle_root,
             index_sym,
@@ -428,13 +546,13 @@ pub trait IsStarkVerifier<A: AIR> {
             (None, Some(_)) => result = false,
             (Some(_), None) => result = false,
             (Some(aux_root), Some(aux_trace_polys_opening)) => {
-                result &= Self::verify_opening::<A::FieldExtension>(
+                result &= Self::verify_opening::<A::FieldExtension, H::ExtensionBackend>(
                     &aux_trace_polys_opening.proof,
                     &aux_root,
                     index,
                     &aux_trace_polys_opening.evaluations,
                 );
-                result &= Self::verify_opening::<A::FieldExtension>(
+                result &= Self::verify_opening::<A::FieldExtension, H::ExtensionBackend>(
                     &aux_trace_polys_opening.proof_sym,
                     &aux_root,
                     index_sym,
@@ -444,6 +562,30 @@ pub trait IsStarkVerifier<A: AIR> {
             _ => {}
         }
 
+        // The preprocessed columns are checked against the commitment of the verifier key.
+        match (
+            verifier_key.preprocessed_commitment,
+            &deep_poly_openings.preprocessed_trace_polys,
+        ) {
+            (None, Some(_)) => result = false,
+            (Some(_), None) => result = false,
+            (Some(preprocessed_root), Some(preprocessed_trace_polys_opening)) => {
+                result &= Self::verify_opening::<A::Field, H::MainBackend>(
+                    &preprocessed_trace_polys_opening.proof,
+                    &preprocessed_root,
+                    index,
+                    &preprocessed_trace_polys_opening.evaluations,
+                );
+                result &= Self::verify_opening::<A::Field, H::MainBackend>(
+                    &preprocessed_trace_polys_opening.proof_sym,
+                    &preprocessed_root,
+                    index_sym,
+                    &preprocessed_trace_polys_opening.evaluations_sym,
+                );
+            }
+            _ => {}
+        }
+
         result
     }
 
@@ -464,11 +606,7 @@ pub trait IsStarkVerifier<A: AIR> {
         deep_poly_openings
             .composition_poly
             .proof
-            .verify::<BatchedMerkleTreeBackend<A::FieldExtension>>(
-                composition_poly_merkle_root,
-                *iota,
-                &value,
-            )
+            .verify::<H::ExtensionBackend>(composition_poly_merkle_root, *iota, &value)
     }
 
     /// Verifies the validity of the purported values of the trace polynomials and the composition polynomial
@@ -476,25 +614,32 @@ pub trait IsStarkVerifier<A: AIR> {
     /// index challenges.
     fn step_4_verify_trace_and_composition_openings(
         proof: &StarkProof<A::Field, A::FieldExtension>,
+        verifier_key: &VerifierKey,
         challenges: &Challenges<A>,
-    ) -> bool
+    ) -> Result<(), VerificationError>
     where
         FieldElement<A::Field>: AsBytes + Sync + Send,
         FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
     {
-        challenges.iotas.iter().zip(&proof.deep_poly_openings).fold(
-            true,
-            |mut result, (iota_n, deep_poly_opening)| {
-                result &= Self::verify_composition_poly_opening(
+        challenges
+            .iotas
+            .iter()
+            .zip(&proof.deep_poly_openings)
+            .enumerate()
+            .try_for_each(|(query, (iota_n, deep_poly_opening))| {
+                if !Self::verify_composition_poly_opening(
                     deep_poly_opening,
                     &proof.composition_poly_root,
                     iota_n,
-                );
-
-                result &= Self::verify_trace_openings(proof, deep_poly_opening, *iota_n);
-                result
-            },
-        )
+                ) {
+                    return Err(VerificationError::CompositionPolynomialOpening { query });
+                }
+
+                if !Self::verify_trace_openings(proof, verifier_key, deep_poly_opening, *iota_n) {
+                    return Err(VerificationError::TraceOpening { query });
+                }
+                Ok(())
+            })
     }
 
     /// Verifies the openings of a fold polynomial of an inner layer of FRI.
@@ -515,35 +660,65 @@ pub trait IsStarkVerifier<A: AIR> {
             vec![evaluation.clone(), evaluation_sym.clone()]
         };
 
-        auth_path_sym.verify::<BatchedMerkleTreeBackend<A::FieldExtension>>(
-            merkle_root,
-            iota >> 1,
-            &evaluations,
-        )
+        auth_path_sym.verify::<H::ExtensionBackend>(merkle_root, iota >> 1, &evaluations)
     }
 
     /// Verify a single FRI query
     /// `zetas`: the vector of all challenges sent by the verifier to the prover at the commit
     /// phase to fold polynomials.
+    /// `query`: the position of this FRI query in the list of queries, used to report errors.
     /// `iota`: the index challenge of this FRI query. This index uniquely determines two elements 𝜐 and -𝜐
     /// of the evaluation domain of FRI layer 0.
     /// `evaluation_point_inv`: precomputed value of 𝜐⁻¹.
     /// `deep_composition_evaluation`: precomputed value of p₀(𝜐), where p₀ is the deep composition polynomial.
     /// `deep_composition_evaluation_sym`: precomputed value of p₀(-𝜐), where p₀ is the deep composition polynomial.
+    #[allow(clippy::too_many_arguments)]
     fn verify_query_and_sym_openings(
         proof: &StarkProof<A::Field, A::FieldExtension>,
         zetas: &[FieldElement<A::FieldExtension>],
+        query: usize,
         iota: usize,
         fri_decommitment: &FriDecommitment<A::FieldExtension>,
         evaluation_point_inv: FieldElement<A::Field>,
         deep_composition_evaluation: &FieldElement<A::FieldExtension>,
         deep_composition_evaluation_sym: &FieldElement<A::FieldExtension>,
-    ) -> bool
+    ) -> Result<(), VerificationError>
+    where
+        FieldElement<A::Field>: AsBytes + Sync + Send,
+        FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
+    {
+        Self::verify_fri_query(
+            &proof.fri_layers_merkle_roots,
+            &proof.fri_last_value,
+            zetas,
+            query,
+            iota,
+            fri_decommitment,
+            evaluation_point_inv,
+            deep_composition_evaluation,
+            deep_composition_evaluation_sym,
+        )
+    }
+
+    /// Verifies a single FRI query against the commitments `fri_layers_merkle_roots` of the inner
+    /// layers and the last value `fri_last_value`. See `verify_query_and_sym_openings` for the
+    /// rest of the arguments.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_fri_query(
+        fri_layers_merkle_roots: &[Commitment],
+        fri_last_value: &FieldElement<A::FieldExtension>,
+        zetas: &[FieldElement<A::FieldExtension>],
+        query: usize,
+        iota: usize,
+        fri_decommitment: &FriDecommitment<A::FieldExtension>,
+        evaluation_point_inv: FieldElement<A::Field>,
+        deep_composition_evaluation: &FieldElement<A::FieldExtension>,
+        deep_composition_evaluation_sym: &FieldElement<A::FieldExtension>,
+    ) -> Result<(), VerificationError>
     where
         FieldElement<A::Field>: AsBytes + Sync + Send,
         FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
     {
-        let fri_layers_merkle_roots = &proof.fri_layers_merkle_roots;
         let evaluation_point_vec: Vec<FieldElement<A::Field>> =
             core::iter::successors(Some(evaluation_point_inv.square()), |evaluation_point| {
                 Some(evaluation_point.square())
@@ -562,46 +737,45 @@ pub trait IsStarkVerifier<A: AIR> {
         // For each FRI layer, starting from the layer 1: use the proof to verify the validity of values pᵢ(−𝜐^(2ⁱ)) (given by the prover) and
         // pᵢ(𝜐^(2ⁱ)) (computed on the previous iteration by the verifier). Then use them to obtain pᵢ₊₁(𝜐^(2ⁱ⁺¹)).
         // Finally, check that the final value coincides with the given by the prover.
-        fri_layers_merkle_roots
-            .iter()
-            .enumerate()
-            .zip(&fri_decommitment.layers_auth_paths)
-            .zip(&fri_decommitment.layers_evaluations_sym)
-            .zip(evaluation_point_vec)
-            .fold(
-                true,
-                |result,
-                 (
-                    (((i, merkle_root), auth_path_sym), evaluation_sym),
-                    evaluation_point_inv,
-                )| {
-                    // Verify opening Open(pᵢ(Dₖ), −𝜐^(2ⁱ)) and Open(pᵢ(Dₖ), 𝜐^(2ⁱ)).
-                    // `v` is pᵢ(𝜐^(2ⁱ)).
-                    // `evaluation_sym` is pᵢ(−𝜐^(2ⁱ)).
-                    let openings_ok = Self::verify_fri_layer_openings(
-                        merkle_root,
-                        auth_path_sym,
-                        &v,
-                        evaluation_sym,
-                        index,
-                    );
-
-                    // Update `v` with next value pᵢ₊₁(𝜐^(2ⁱ⁺¹)).
-                    v = (&v + evaluation_sym) + evaluation_point_inv * &zetas[i + 1] * (&v - evaluation_sym);
-
-                    // Update index for next iteration. The index of the squares in the next layer
-                    // is obtained by halving the current index. This is due to the bit-reverse
-                    // ordering of the elements in the Merkle tree.
-                    index >>= 1;
-
-                    if i < fri_decommitment.layers_evaluations_sym.len() - 1 {
-                        result & openings_ok
-                    } else {
-                        // Check that final value is the given by the prover
-                        result & (v == proof.fri_last_value) & openings_ok
-                    }
-                },
-            )
+        for (i, (((merkle_root, auth_path_sym), evaluation_sym), evaluation_point_inv)) in
+            fri_layers_merkle_roots
+                .iter()
+                .zip(&fri_decommitment.layers_auth_paths)
+                .zip(&fri_decommitment.layers_evaluations_sym)
+                .zip(evaluation_point_vec)
+                .enumerate()
+        {
+            // Verify opening Open(pᵢ(Dₖ), −𝜐^(2ⁱ)) and Open(pᵢ(Dₖ), 𝜐^(2ⁱ)).
+            // `v` is pᵢ(𝜐^(2ⁱ)).
+            // `evaluation_sym` is pᵢ(−𝜐^(2ⁱ)).
+            if !Self::verify_fri_layer_openings(
+                merkle_root,
+                auth_path_sym,
+                &v,
+                evaluation_sym,
+                index,
+            ) {
+                return Err(VerificationError::FriLayerOpening {
+                    query,
+                    layer: i + 1,
+                });
+            }
+
+            // Update `v` with next value pᵢ₊₁(𝜐^(2ⁱ⁺¹)).
+            v = (&v + evaluation_sym)
+                + evaluation_point_inv * &zetas[i + 1] * (&v - evaluation_sym);
+
+            // Update index for next iteration. The index of the squares in the next layer
+            // is obtained by halving the current index. This is due to the bit-reverse
+            // ordering of the elements in the Merkle tree.
+            index >>= 1;
+        }
+
+        // Check that final value is the given by the prover
+        if &v != fri_last_value {
+            return Err(VerificationError::FriLastValueMismatch { query });
+        }
+        Ok(())
     }
 
     fn reconstruct_deep_composition_poly_evaluations_for_all_queries(
@@ -622,6 +796,16 @@ pub trait IsStarkVerifier<A: AIR> {
                 .into_iter()
                 .map(|x| x.to_extension())
                 .collect();
+            if let Some(preprocessed_trace_polys) =
+                &proof.deep_poly_openings[i].preprocessed_trace_polys
+            {
+                evaluations.extend(
+                    preprocessed_trace_polys
+                        .evaluations
+                        .iter()
+                        .map(|x| x.clone().to_extension()),
+                );
+            }
             if let Some(aux_trace_polys) = &proof.deep_poly_openings[i].aux_trace_polys {
                 evaluations.extend_from_slice(&aux_trace_polys.evaluations);
             }
@@ -644,6 +828,16 @@ pub trait IsStarkVerifier<A: AIR> {
                 .into_iter()
                 .map(|x| x.to_extension())
                 .collect();
+            if let Some(preprocessed_trace_polys) =
+                &proof.deep_poly_openings[i].preprocessed_trace_polys
+            {
+                evaluations_sym.extend(
+                    preprocessed_trace_polys
+                        .evaluations_sym
+                        .iter()
+                        .map(|x| x.clone().to_extension()),
+                );
+            }
             if let Some(aux_trace_polys) = &proof.deep_poly_openings[i].aux_trace_polys {
                 evaluations_sym.extend_from_slice(&aux_trace_polys.evaluations_sym);
             }
@@ -669,8 +863,32 @@ pub trait IsStarkVerifier<A: AIR> {
         lde_trace_evaluations: &[FieldElement<A::FieldExtension>],
         lde_composition_poly_parts_evaluation: &[FieldElement<A::FieldExtension>],
     ) -> FieldElement<A::FieldExtension> {
-        let ood_evaluations_table_height = proof.trace_ood_evaluations.height;
-        let ood_evaluations_table_width = proof.trace_ood_evaluations.width;
+        Self::evaluate_deep_composition_poly(
+            &proof.trace_ood_evaluations,
+            &proof.composition_poly_parts_ood_evaluation,
+            evaluation_point,
+            primitive_root,
+            challenges,
+            lde_trace_evaluations,
+            lde_composition_poly_parts_evaluation,
+        )
+    }
+
+    /// Returns the evaluation of the DEEP composition polynomial at `evaluation_point` from the
+    /// evaluations of the trace polynomials and the composition polynomial parts at that point and
+    /// at the out-of-domain challenge.
+    #[allow(clippy::too_many_arguments)]
+    fn evaluate_deep_composition_poly(
+        trace_ood_evaluations: &Table<A::FieldExtension>,
+        composition_poly_parts_ood_evaluation: &[FieldElement<A::FieldExtension>],
+        evaluation_point: &FieldElement<A::Field>,
+        primitive_root: &FieldElement<A::Field>,
+        challenges: &Challenges<A>,
+        lde_trace_evaluations: &[FieldElement<A::FieldExtension>],
+        lde_composition_poly_parts_evaluation: &[FieldElement<A::FieldExtension>],
+    ) -> FieldElement<A::FieldExtension> {
+        let ood_evaluations_table_height = trace_ood_evaluations.height;
+        let ood_evaluations_table_width = trace_ood_evaluations.width;
         let trace_term_coeffs = &challenges.trace_term_coeffs;
         debug_assert_eq!(
             ood_evaluations_table_height * ood_evaluations_table_width,
@@ -689,7 +907,7 @@ pub trait IsStarkVerifier<A: AIR> {
                     FieldElement::zero(),
                     |trace_t, (row_idx, coeff)| {
                         let poly_evaluation = (lde_trace_evaluations[col_idx].clone()
-                            - proof.trace_ood_evaluations.get_row(row_idx)[col_idx].clone())
+                            - trace_ood_evaluations.get_row(row_idx)[col_idx].clone())
                             * &denoms_trace[row_idx];
                         trace_t + &poly_evaluation * coeff
                     },
@@ -703,7 +921,7 @@ pub trait IsStarkVerifier<A: AIR> {
         let denom_composition = (evaluation_point - z_pow).inv().unwrap();
         let mut h_terms = FieldElement::zero();
         for (j, h_i_upsilon) in lde_composition_poly_parts_evaluation.iter().enumerate() {
-            let h_i_zpower = &proof.composition_poly_parts_ood_evaluation[j];
+            let h_i_zpower = &composition_poly_parts_ood_evaluation[j];
             let h_i_term = (h_i_upsilon - h_i_zpower) * &challenges.gammas[j];
             h_terms += h_i_term;
         }
@@ -712,21 +930,275 @@ pub trait IsStarkVerifier<A: AIR> {
         trace_term + h_terms
     }
 
+    /// Checks the proof-of-work nonces done before each FRI folding challenge, using the seeds
+    /// recovered when replaying the rounds.
+    fn verify_commit_phase_grinding(
+        air: &A,
+        proof: &StarkProof<A::Field, A::FieldExtension>,
+        challenges: &Challenges<A>,
+    ) -> Result<(), VerificationError> {
+        let grinding_factor = air.context().proof_options.commit_phase_grinding_factor;
+        if grinding_factor == 0 {
+            return Ok(());
+        }
+        for layer in 0..challenges.zetas.len() {
+            let nonce_is_valid = proof
+                .commit_phase_nonces
+                .get(layer)
+                .zip(challenges.commit_phase_grinding_seeds.get(layer))
+                .map_or(false, |(nonce_value, seed)| {
+                    grinding::is_valid_nonce::<H::GrindingHash>(seed, *nonce_value, grinding_factor)
+                });
+            if !nonce_is_valid {
+                error!("Commit phase grinding factor not satisfied");
+                return Err(VerificationError::InvalidCommitPhaseGrindingNonce { layer });
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that `trace_length` is a valid trace length for the field of the AIR and the
+    /// blowup factor of `proof_options`. This must hold before building the AIR and its domain.
+    fn validate_trace_length(
+        trace_length: usize,
+        proof_options: &ProofOptions,
+    ) -> Result<(), VerificationError> {
+        if !trace_length.is_power_of_two() {
+            return Err(VerificationError::MalformedProof(format!(
+                "trace length {trace_length} is not a power of two"
+            )));
+        }
+        let blowup_factor = proof_options.blowup_factor as usize;
+        if !blowup_factor.is_power_of_two() {
+            return Err(VerificationError::MalformedProof(format!(
+                "blowup factor {blowup_factor} is not a power of two"
+            )));
+        }
+        match trace_length.checked_mul(blowup_factor) {
+            Some(lde_size) if u64::from(lde_size.trailing_zeros()) <= A::Field::TWO_ADICITY => {
+                Ok(())
+            }
+            _ => Err(VerificationError::MalformedProof(format!(
+                "an LDE domain of size {trace_length} * {blowup_factor} exceeds the two-adicity of the field"
+            ))),
+        }
+    }
+
+    /// Checks that every part of the proof read by the verifier has the length and number of
+    /// columns expected by `air`, so that malformed proofs are rejected before being indexed.
+    fn validate_proof_shape(
+        air: &A,
+        proof: &StarkProof<A::Field, A::FieldExtension>,
+        domain: &Domain<A::Field>,
+        verifier_key: &VerifierKey,
+    ) -> Result<(), VerificationError> {
+        let malformed = |message: String| Err(VerificationError::MalformedProof(message));
+
+        let number_of_queries = air.options().fri_number_of_queries;
+        if proof.deep_poly_openings.len() < number_of_queries {
+            return malformed(format!(
+                "expected {number_of_queries} trace and composition polynomial openings, got {}",
+                proof.deep_poly_openings.len()
+            ));
+        }
+
+        let ood_table = &proof.trace_ood_evaluations;
+        let expected_width = air.context().trace_columns;
+        let expected_height = air.context().transition_offsets.len() * A::STEP_SIZE;
+        if ood_table.width != expected_width
+            || ood_table.height != expected_height
+            || ood_table.data.len() != expected_width * expected_height
+        {
+            return malformed(format!(
+                "expected {expected_height} x {expected_width} out-of-domain trace evaluations, got {} x {} with {} elements",
+                ood_table.height,
+                ood_table.width,
+                ood_table.data.len()
+            ));
+        }
+
+        let number_of_parts = air.composition_poly_degree_bound() / air.trace_length();
+        if proof.composition_poly_parts_ood_evaluation.len() != number_of_parts {
+            return malformed(format!(
+                "expected {number_of_parts} composition polynomial parts, got {}",
+                proof.composition_poly_parts_ood_evaluation.len()
+            ));
+        }
+
+        let number_of_fri_layers = (domain.root_order as usize).saturating_sub(1);
+        if proof.fri_layers_merkle_roots.len() != number_of_fri_layers {
+            return malformed(format!(
+                "expected {number_of_fri_layers} FRI layers, got {}",
+                proof.fri_layers_merkle_roots.len()
+            ));
+        }
+        let number_of_commit_phase_nonces =
+            if air.context().proof_options.commit_phase_grinding_factor > 0 {
+                number_of_fri_layers + 1
+            } else {
+                0
+            };
+        if proof.commit_phase_nonces.len() != number_of_commit_phase_nonces {
+            return malformed(format!(
+                "expected {number_of_commit_phase_nonces} commit phase grinding nonces, got {}",
+                proof.commit_phase_nonces.len()
+            ));
+        }
+        for (query, decommitment) in proof.query_list.iter().take(number_of_queries).enumerate() {
+            if decommitment.layers_auth_paths.len() != number_of_fri_layers
+                || decommitment.layers_evaluations_sym.len() != number_of_fri_layers
+            {
+                return malformed(format!(
+                    "FRI decommitment of query {query} does not have {number_of_fri_layers} layers"
+                ));
+            }
+        }
+
+        if proof.lde_trace_aux_merkle_root.is_some() != air.has_trace_interaction() {
+            return malformed("unexpected presence of the auxiliary trace commitment".to_string());
+        }
+
+        // The number of main columns is derived from the context, as the verifier does when
+        // splitting the out-of-domain frame.
+        let num_aux_columns = air.num_auxiliary_rap_columns();
+        let num_preprocessed_columns = air.num_preprocessed_columns();
+        let num_main_columns = expected_width
+            .checked_sub(num_aux_columns + num_preprocessed_columns)
+            .ok_or_else(|| {
+                VerificationError::MalformedProof(
+                    "the AIR declares fewer trace columns than its layout".to_string(),
+                )
+            })?;
+        let has_expected_length = |openings: &PolynomialOpenings<_>, expected: usize| {
+            openings.evaluations.len() == expected && openings.evaluations_sym.len() == expected
+        };
+        for (query, opening) in proof
+            .deep_poly_openings
+            .iter()
+            .take(number_of_queries)
+            .enumerate()
+        {
+            let main_is_valid = has_expected_length(&opening.main_trace_polys, num_main_columns);
+            let aux_is_valid = match &opening.aux_trace_polys {
+                Some(aux) => {
+                    air.has_trace_interaction()
+                        && aux.evaluations.len() == num_aux_columns
+                        && aux.evaluations_sym.len() == num_aux_columns
+                }
+                None => !air.has_trace_interaction(),
+            };
+            let preprocessed_is_valid = match &opening.preprocessed_trace_polys {
+                Some(preprocessed) => {
+                    verifier_key.preprocessed_commitment.is_some()
+                        && has_expected_length(preprocessed, num_preprocessed_columns)
+                }
+                None => verifier_key.preprocessed_commitment.is_none(),
+            };
+            let composition_is_valid = opening.composition_poly.evaluations.len()
+                == number_of_parts
+                && opening.composition_poly.evaluations_sym.len() == number_of_parts;
+
+            if !(main_is_valid && aux_is_valid && preprocessed_is_valid && composition_is_valid) {
+                return malformed(format!(
+                    "openings of query {query} do not match the layout of the AIR"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verifies a STARK proof generated with
+    /// [`IsStarkProver::prove_statement`](crate::prover::IsStarkProver::prove_statement).
+    /// The AIR identity, the proof options, the trace length and the public inputs are absorbed
+    /// into `transcript`, which must be freshly created, before verifying the proof.
+    fn verify_statement(
+        proof: &StarkProof<A::Field, A::FieldExtension>,
+        pub_input: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        mut transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> Result<(), VerificationError>
+    where
+        A::PublicInputs: AsBytes,
+        FieldElement<A::Field>: AsBytes + Sync + Send,
+        FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
+    {
+        bind_statement::<A>(
+            &mut transcript,
+            proof.trace_length,
+            pub_input,
+            proof_options,
+        );
+        Self::verify(proof, pub_input, proof_options, transcript)
+    }
+
     /// Verifies a STARK proof with public inputs `pub_inputs`.
+    /// If the AIR has preprocessed columns, this runs the setup to obtain their commitment. Use
+    /// `verify_with_key` to check them against a precomputed `VerifierKey` instead.
     /// Warning: the transcript must be safely initializated before passing it to this method.
+    /// Use `verify_statement` to have it initialized from the statement being verified.
     fn verify(
         proof: &StarkProof<A::Field, A::FieldExtension>,
         pub_input: &A::PublicInputs,
         proof_options: &ProofOptions,
-        mut transcript: impl IsTranscript<A::FieldExtension>,
+        transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> Result<(), VerificationError>
+    where
+        FieldElement<A::Field>: AsBytes + Sync + Send,
+        FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
+    {
+        Self::validate_trace_length(proof.trace_length, proof_options)?;
+        let air = A::new(proof.trace_length, pub_input, proof_options);
+        let verifier_key = VerifierKey::with_commitment_hash::<A, H>(&air)?;
+        Self::verify_with_key(proof, pub_input, proof_options, &verifier_key, transcript)
+    }
+
+    /// Returns whether the STARK proof is valid. Convenience wrapper around `verify` for callers
+    /// that do not need to know why a proof was rejected.
+    /// Warning: the transcript must be safely initializated before passing it to this method.
+    fn is_valid(
+        proof: &StarkProof<A::Field, A::FieldExtension>,
+        pub_input: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        transcript: impl IsTranscript<A::FieldExtension>,
     ) -> bool
+    where
+        FieldElement<A::Field>: AsBytes + Sync + Send,
+        FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
+    {
+        Self::verify(proof, pub_input, proof_options, transcript).is_ok()
+    }
+
+    /// Verifies a STARK proof with public inputs `pub_inputs`, checking the openings of the
+    /// preprocessed columns against the commitment in `verifier_key`.
+    /// Warning: the transcript must be safely initializated before passing it to this method.
+    fn verify_with_key(
+        proof: &StarkProof<A::Field, A::FieldExtension>,
+        pub_input: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        verifier_key: &VerifierKey,
+        mut transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> Result<(), VerificationError>
     where
         FieldElement<A::Field>: AsBytes + Sync + Send,
         FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
     {
         // Verify there are enough queries
         if proof.query_list.len() < proof_options.fri_number_of_queries {
-            return false;
+            let error = VerificationError::InsufficientQueries {
+                expected: proof_options.fri_number_of_queries,
+                actual: proof.query_list.len(),
+            };
+            error!("{:?}", error);
+            return Err(error);
+        }
+
+        Self::validate_trace_length(proof.trace_length, proof_options)?;
+        let air = A::new(proof.trace_length, pub_input, proof_options);
+        let domain = Domain::new(&air);
+        if let Err(error) = Self::validate_proof_shape(&air, proof, &domain, verifier_key) {
+            error!("Malformed proof: {:?}", error);
+            return Err(error);
         }
 
         #[cfg(feature = "instruments")]
@@ -734,13 +1206,11 @@ pub trait IsStarkVerifier<A: AIR> {
         #[cfg(feature = "instruments")]
         let timer1 = Instant::now();
 
-        let air = A::new(proof.trace_length, pub_input, proof_options);
-        let domain = Domain::new(&air);
-
-        let challenges = Self::step_1_replay_rounds_and_recover_challenges(
+        let challenges = Self::step_1_replay_rounds_and_recover_challenges_with_key(
             &air,
             proof,
             &domain,
+            verifier_key,
             &mut transcript,
         );
 
@@ -748,14 +1218,19 @@ pub trait IsStarkVerifier<A: AIR> {
         let security_bits = air.context().proof_options.grinding_factor;
         if security_bits > 0 {
             let nonce_is_valid = proof.nonce.map_or(false, |nonce_value| {
-                grinding::is_valid_nonce(&challenges.grinding_seed, nonce_value, security_bits)
+                grinding::is_valid_nonce::<H::GrindingHash>(
+                    &challenges.grinding_seed,
+                    nonce_value,
+                    security_bits,
+                )
             });
 
             if !nonce_is_valid {
                 error!("Grinding factor not satisfied");
-                return false;
+                return Err(VerificationError::InvalidGrindingNonce);
             }
         }
+        Self::verify_commit_phase_grinding(&air, proof, &challenges)?;
 
         #[cfg(feature = "instruments")]
         let elapsed1 = timer1.elapsed();
@@ -767,9 +1242,11 @@ pub trait IsStarkVerifier<A: AIR> {
         #[cfg(feature = "instruments")]
         let timer2 = Instant::now();
 
-        if !Self::step_2_verify_claimed_composition_polynomial(&air, proof, &domain, &challenges) {
+        if let Err(error) =
+            Self::step_2_verify_claimed_composition_polynomial(&air, proof, &domain, &challenges)
+        {
             error!("Composition Polynomial verification failed");
-            return false;
+            return Err(error);
         }
 
         #[cfg(feature = "instruments")]
@@ -782,9 +1259,9 @@ pub trait IsStarkVerifier<A: AIR> {
         #[cfg(feature = "instruments")]
         let timer3 = Instant::now();
 
-        if !Self::step_3_verify_fri(proof, &domain, &challenges) {
-            error!("FRI verification failed");
-            return false;
+        if let Err(error) = Self::step_3_verify_fri(proof, &domain, &challenges) {
+            error!("FRI verification failed: {:?}", error);
+            return Err(error);
         }
 
         #[cfg(feature = "instruments")]
@@ -797,10 +1274,14 @@ pub trait IsStarkVerifier<A: AIR> {
         #[cfg(feature = "instruments")]
         let timer4 = Instant::now();
 
-        #[allow(clippy::let_and_return)]
-        if !Self::step_4_verify_trace_and_composition_openings(proof, &challenges) {
-            error!("DEEP Composition Polynomial verification failed");
-            return false;
+        if let Err(error) =
+            Self::step_4_verify_trace_and_composition_openings(proof, verifier_key, &challenges)
+        {
+            error!(
+                "DEEP Composition Polynomial verification failed: {:?}",
+                error
+            );
+            return Err(error);
         }
 
         #[cfg(feature = "instruments")]
@@ -820,6 +1301,6 @@ pub trait IsStarkVerifier<A: AIR> {
             );
         }
 
-        true
+        Ok(())
     }
 }
@@ -0,0 +1,42 @@
+use crate::{parse_field_elements, VerificationResult};
+use lambdaworks_groth16::{common::FrField, verify, Proof, VerifyingKey};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Verifies a Groth16 proof over BLS12-381.
+///
+/// `proof` and `verifying_key` are encoded with `Proof::serialize` and
+/// `VerifyingKey::serialize`. `public_inputs` are the hex encoded public
+/// inputs, starting with the constant `0x1`.
+#[wasm_bindgen]
+pub fn verify_groth16_proof(
+    proof: &[u8],
+    verifying_key: &[u8],
+    public_inputs: Vec<String>,
+) -> VerificationResult {
+    verify_serialized(proof, verifying_key, &public_inputs).into()
+}
+
+fn verify_serialized(
+    proof: &[u8],
+    verifying_key: &[u8],
+    public_inputs: &[String],
+) -> Result<(), String> {
+    let proof =
+        Proof::deserialize(proof).map_err(|error| format!("Invalid proof encoding: {error:?}"))?;
+    let verifying_key = VerifyingKey::deserialize(verifying_key)
+        .map_err(|error| format!("Invalid verifying key encoding: {error:?}"))?;
+    let public_inputs = parse_field_elements::<FrField>(public_inputs)?;
+
+    if public_inputs.len() != verifying_key.verifier_k_tau_g1.len() {
+        return Err(format!(
+            "Expected {} public inputs, got {}",
+            verifying_key.verifier_k_tau_g1.len(),
+            public_inputs.len()
+        ));
+    }
+
+    match verify(&verifying_key, &proof, &public_inputs) {
+        true => Ok(()),
+        false => Err("Proof rejected".to_string()),
+    }
+}
@@ -0,0 +1,59 @@
+use lambdaworks_math::field::{element::FieldElement, traits::IsPrimeField};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+pub mod groth16;
+pub mod plonk;
+pub mod stark;
+
+/// The outcome of a verification, returned to JS.
+///
+/// `verified` is only true when the proof was decoded and accepted by the verifier.
+/// Otherwise `error` describes why the inputs could not be decoded or why the proof
+/// was rejected.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerificationResult {
+    verified: bool,
+    error: Option<String>,
+}
+
+#[wasm_bindgen]
+impl VerificationResult {
+    #[wasm_bindgen(getter)]
+    pub fn verified(&self) -> bool {
+        self.verified
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn error(&self) -> Option<String> {
+        self.error.clone()
+    }
+}
+
+impl From<Result<(), String>> for VerificationResult {
+    fn from(result: Result<(), String>) -> Self {
+        match result {
+            Ok(()) => Self {
+                verified: true,
+                error: None,
+            },
+            Err(error) => Self {
+                verified: false,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+/// Parses public inputs given as hex strings, as they are passed from JS.
+pub(crate) fn parse_field_elements<F: IsPrimeField>(
+    values: &[String],
+) -> Result<Vec<FieldElement<F>>, String> {
+    values
+        .iter()
+        .map(|value| {
+            FieldElement::<F>::from_hex(value)
+                .map_err(|_| format!("Invalid field element in public inputs: {value}"))
+        })
+        .collect()
+}
@@ -0,0 +1,71 @@
+use crate::{parse_field_elements, VerificationResult};
+use lambdaworks_crypto::commitments::kzg::{KateZaveruchaGoldberg, StructuredReferenceString};
+use lambdaworks_math::{
+    elliptic_curve::{
+        short_weierstrass::curves::bls12_381::{
+            curve::BLS12381Curve, default_types::FrField, pairing::BLS12381AtePairing,
+            twist::BLS12381TwistCurve,
+        },
+        traits::IsEllipticCurve,
+    },
+    traits::Deserializable,
+};
+use lambdaworks_plonk::{
+    prover::Proof,
+    setup::{CommonPreprocessedInput, VerificationKey},
+    verifier::Verifier,
+};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+type G1Point = <BLS12381Curve as IsEllipticCurve>::PointRepresentation;
+type G2Point = <BLS12381TwistCurve as IsEllipticCurve>::PointRepresentation;
+type Kzg = KateZaveruchaGoldberg<FrField, BLS12381AtePairing>;
+
+/// Verifies a PLONK proof over BLS12-381 with KZG commitments.
+///
+/// `proof`, `verification_key`, `common_preprocessed_input` and `srs` are encoded with
+/// `AsBytes`: the proof, the verification key and the common preprocessed input of the circuit
+/// given by its setup, and the structured reference string of the KZG commitments.
+/// `public_inputs` are the hex encoded public inputs.
+#[wasm_bindgen]
+pub fn verify_plonk_proof(
+    proof: &[u8],
+    public_inputs: Vec<String>,
+    verification_key: &[u8],
+    common_preprocessed_input: &[u8],
+    srs: &[u8],
+) -> VerificationResult {
+    verify_serialized(
+        proof,
+        &public_inputs,
+        verification_key,
+        common_preprocessed_input,
+        srs,
+    )
+    .into()
+}
+
+fn verify_serialized(
+    proof: &[u8],
+    public_inputs: &[String],
+    verification_key: &[u8],
+    common_preprocessed_input: &[u8],
+    srs: &[u8],
+) -> Result<(), String> {
+    let proof = Proof::<FrField, Kzg>::deserialize(proof)
+        .map_err(|error| format!("Invalid proof encoding: {error:?}"))?;
+    let verification_key = VerificationKey::<G1Point>::deserialize(verification_key)
+        .map_err(|error| format!("Invalid verification key encoding: {error:?}"))?;
+    let common_input =
+        CommonPreprocessedInput::<FrField>::deserialize(common_preprocessed_input)
+            .map_err(|error| format!("Invalid common preprocessed input encoding: {error:?}"))?;
+    let srs = StructuredReferenceString::<G1Point, G2Point>::deserialize(srs)
+        .map_err(|error| format!("Invalid SRS encoding: {error:?}"))?;
+    let public_inputs = parse_field_elements::<FrField>(public_inputs)?;
+
+    let verifier = Verifier::new(Kzg::new(srs));
+    match verifier.verify(&proof, &public_inputs, &common_input, &verification_key) {
+        true => Ok(()),
+        false => Err("Proof rejected".to_string()),
+    }
+}
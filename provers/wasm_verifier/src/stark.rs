@@ -0,0 +1,124 @@
+use crate::{parse_field_elements, VerificationResult};
+use lambdaworks_math::{
+    field::{
+        element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+    },
+    traits::AsBytes,
+};
+use stark_platinum_prover::{
+    examples::{
+        fibonacci_2_columns::Fibonacci2ColsAIR,
+        fibonacci_rap::{FibonacciRAP, FibonacciRAPPublicInputs},
+        quadratic_air::{QuadraticAIR, QuadraticPublicInputs},
+        simple_fibonacci::{FibonacciAIR, FibonacciPublicInputs},
+    },
+    proof::{options::ProofOptions, stark::StarkProof},
+    traits::AIR,
+    transcript::StoneProverTranscript,
+    verifier::{IsStarkVerifier, Verifier},
+};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+type Felt252 = FieldElement<Stark252PrimeField>;
+
+/// Verifies a STARK proof of one of the example AIRs bundled with the prover.
+///
+/// `proof` is a proof encoded with `StarkProof::to_bytes` and `public_inputs` the hex
+/// encoded public inputs of the AIR, in order:
+/// - `simple_fibonacci` and `fibonacci_2_columns`: `a0`, `a1`.
+/// - `fibonacci_rap`: `steps`, `a0`, `a1`.
+/// - `quadratic`: `a0`.
+///
+/// The proof must have been generated with `IsStarkProver::prove_statement`, which binds the
+/// AIR, the proof options and the public inputs into the transcript. The proof options must be
+/// the ones the proof was generated with.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn verify_stark_proof(
+    air: &str,
+    proof: &[u8],
+    public_inputs: Vec<String>,
+    blowup_factor: u8,
+    fri_number_of_queries: usize,
+    coset_offset: u64,
+    grinding_factor: u8,
+    commit_phase_grinding_factor: u8,
+) -> VerificationResult {
+    let proof_options = ProofOptions {
+        blowup_factor,
+        fri_number_of_queries,
+        coset_offset,
+        grinding_factor,
+        commit_phase_grinding_factor,
+    };
+
+    let result =
+        parse_field_elements::<Stark252PrimeField>(&public_inputs).and_then(|inputs| {
+            match (air, inputs.as_slice()) {
+                ("simple_fibonacci", [a0, a1]) => verify::<FibonacciAIR<Stark252PrimeField>>(
+                    proof,
+                    &FibonacciPublicInputs { a0: *a0, a1: *a1 },
+                    &proof_options,
+                ),
+                ("fibonacci_2_columns", [a0, a1]) => {
+                    verify::<Fibonacci2ColsAIR<Stark252PrimeField>>(
+                        proof,
+                        &FibonacciPublicInputs { a0: *a0, a1: *a1 },
+                        &proof_options,
+                    )
+                }
+                ("fibonacci_rap", [steps, a0, a1]) => verify::<FibonacciRAP<Stark252PrimeField>>(
+                    proof,
+                    &FibonacciRAPPublicInputs {
+                        steps: to_usize(steps)?,
+                        a0: *a0,
+                        a1: *a1,
+                    },
+                    &proof_options,
+                ),
+                ("quadratic", [a0]) => verify::<QuadraticAIR<Stark252PrimeField>>(
+                    proof,
+                    &QuadraticPublicInputs { a0: *a0 },
+                    &proof_options,
+                ),
+                ("simple_fibonacci" | "fibonacci_2_columns" | "fibonacci_rap" | "quadratic", _) => {
+                    Err(format!(
+                        "Wrong number of public inputs for {air}: {}",
+                        inputs.len()
+                    ))
+                }
+                _ => Err(format!("Unsupported AIR: {air}")),
+            }
+        });
+
+    result.into()
+}
+
+fn verify<A>(
+    proof: &[u8],
+    pub_inputs: &A::PublicInputs,
+    proof_options: &ProofOptions,
+) -> Result<(), String>
+where
+    A: AIR<Field = Stark252PrimeField, FieldExtension = Stark252PrimeField>,
+    A::PublicInputs: AsBytes,
+{
+    let proof = StarkProof::<Stark252PrimeField, Stark252PrimeField>::from_bytes(proof)
+        .map_err(|error| format!("Invalid proof encoding: {error:?}"))?;
+
+    Verifier::<A>::verify_statement(
+        &proof,
+        pub_inputs,
+        proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .map_err(|error| format!("Proof rejected: {error:?}"))
+}
+
+fn to_usize(value: &Felt252) -> Result<usize, String> {
+    let limbs = value.representative().limbs;
+    if limbs[..3].iter().any(|limb| *limb != 0) {
+        return Err("Public input does not fit in a usize".to_string());
+    }
+    usize::try_from(limbs[3]).map_err(|_| "Public input does not fit in a usize".to_string())
+}
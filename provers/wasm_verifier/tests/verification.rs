@@ -0,0 +1,237 @@
+use lambdaworks_groth16::{
+    common::FrElement as Groth16FrElement, setup as groth16_setup, Prover as Groth16Prover,
+    QuadraticArithmeticProgram,
+};
+use lambdaworks_math::{
+    elliptic_curve::short_weierstrass::curves::bls12_381::default_types::FrElement,
+    field::{
+        element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+    },
+    traits::AsBytes,
+};
+use lambdaworks_plonk::{
+    prover::Prover as PlonkProver,
+    setup::setup as plonk_setup,
+    test_utils::{
+        circuit_1::{test_common_preprocessed_input_1, test_witness_1},
+        utils::{test_srs, TestRandomFieldGenerator, KZG},
+    },
+};
+use lambdaworks_wasm_verifier::{
+    groth16::verify_groth16_proof, plonk::verify_plonk_proof, stark::verify_stark_proof,
+};
+use stark_platinum_prover::{
+    examples::simple_fibonacci::{self, FibonacciAIR, FibonacciPublicInputs},
+    proof::options::ProofOptions,
+    prover::{IsStarkProver, Prover},
+    transcript::StoneProverTranscript,
+};
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+type Felt252 = FieldElement<Stark252PrimeField>;
+
+fn simple_fibonacci_proof(proof_options: &ProofOptions) -> Vec<u8> {
+    let mut trace = simple_fibonacci::fibonacci_trace([Felt252::one(), Felt252::one()], 8);
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    Prover::<FibonacciAIR<Stark252PrimeField>>::prove_statement(
+        &mut trace,
+        &pub_inputs,
+        proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap()
+    .to_bytes()
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn stark_proof_of_a_bundled_air_verifies() {
+    let options = ProofOptions::default_test_options();
+    let proof = simple_fibonacci_proof(&options);
+
+    let result = verify_stark_proof(
+        "simple_fibonacci",
+        &proof,
+        vec!["0x1".to_string(), "0x1".to_string()],
+        options.blowup_factor,
+        options.fri_number_of_queries,
+        options.coset_offset,
+        options.grinding_factor,
+        options.commit_phase_grinding_factor,
+    );
+
+    assert!(result.verified());
+    assert_eq!(result.error(), None);
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn stark_proof_with_wrong_public_inputs_is_rejected() {
+    let options = ProofOptions::default_test_options();
+    let proof = simple_fibonacci_proof(&options);
+
+    let result = verify_stark_proof(
+        "simple_fibonacci",
+        &proof,
+        vec!["0x1".to_string(), "0x2".to_string()],
+        options.blowup_factor,
+        options.fri_number_of_queries,
+        options.coset_offset,
+        options.grinding_factor,
+        options.commit_phase_grinding_factor,
+    );
+
+    assert!(!result.verified());
+    assert!(result.error().unwrap().starts_with("Proof rejected"));
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn stark_verification_reports_unsupported_airs_and_malformed_inputs() {
+    let options = ProofOptions::default_test_options();
+    let proof = simple_fibonacci_proof(&options);
+    let verify = |air: &str, proof: &[u8], public_inputs: &[&str]| {
+        verify_stark_proof(
+            air,
+            proof,
+            public_inputs
+                .iter()
+                .map(|value| value.to_string())
+                .collect(),
+            options.blowup_factor,
+            options.fri_number_of_queries,
+            options.coset_offset,
+            options.grinding_factor,
+            options.commit_phase_grinding_factor,
+        )
+    };
+
+    let result = verify("cairo", &proof, &["0x1", "0x1"]);
+    assert_eq!(result.error(), Some("Unsupported AIR: cairo".to_string()));
+
+    let result = verify("simple_fibonacci", &proof, &["0x1"]);
+    assert_eq!(
+        result.error(),
+        Some("Wrong number of public inputs for simple_fibonacci: 1".to_string())
+    );
+
+    let result = verify("simple_fibonacci", &proof, &["0x1", "not hex"]);
+    assert!(!result.verified());
+
+    let result = verify(
+        "simple_fibonacci",
+        &proof[..proof.len() - 1],
+        &["0x1", "0x1"],
+    );
+    assert!(result
+        .error()
+        .unwrap()
+        .starts_with("Invalid proof encoding"));
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn plonk_proof_verifies_and_wrong_public_inputs_are_rejected() {
+    // This is the circuit for x * e == y
+    let common_preprocessed_input = test_common_preprocessed_input_1();
+    let srs = test_srs(common_preprocessed_input.n);
+    let serialized_srs = srs.as_bytes();
+    let kzg = KZG::new(srs);
+    let verification_key = plonk_setup(&common_preprocessed_input, &kzg);
+
+    let x = FrElement::from(4_u64);
+    let y = FrElement::from(12_u64);
+    let e = FrElement::from(3_u64);
+    let public_input = vec![x.clone(), y];
+    let witness = test_witness_1(x, e);
+
+    let proof = PlonkProver::new(kzg, TestRandomFieldGenerator {}).prove(
+        &witness,
+        &public_input,
+        &common_preprocessed_input,
+        &verification_key,
+    );
+
+    let result = verify_plonk_proof(
+        &proof.as_bytes(),
+        vec!["0x4".to_string(), "0xc".to_string()],
+        &verification_key.as_bytes(),
+        &common_preprocessed_input.as_bytes(),
+        &serialized_srs,
+    );
+    assert!(result.verified());
+
+    let result = verify_plonk_proof(
+        &proof.as_bytes(),
+        vec!["0x4".to_string(), "0xd".to_string()],
+        &verification_key.as_bytes(),
+        &common_preprocessed_input.as_bytes(),
+        &serialized_srs,
+    );
+    assert_eq!(result.error(), Some("Proof rejected".to_string()));
+}
+
+/// Represents x^3 + x + 5 = 35, based on https://vitalik.ca/general/2016/12/10/qap.html
+fn vitalik_qap() -> QuadraticArithmeticProgram {
+    let num_of_public_inputs = 1;
+    let [l, r, o] = [
+        [
+            ["0", "0", "0", "5"],
+            ["1", "0", "1", "0"],
+            ["0", "0", "0", "0"],
+            ["0", "1", "0", "0"],
+            ["0", "0", "1", "0"],
+            ["0", "0", "0", "1"],
+        ],
+        [
+            ["0", "0", "1", "1"],
+            ["1", "1", "0", "0"],
+            ["0", "0", "0", "0"],
+            ["0", "0", "0", "0"],
+            ["0", "0", "0", "0"],
+            ["0", "0", "0", "0"],
+        ],
+        [
+            ["0", "0", "0", "0"],
+            ["0", "0", "0", "0"],
+            ["0", "0", "0", "1"],
+            ["1", "0", "0", "0"],
+            ["0", "1", "0", "0"],
+            ["0", "0", "1", "0"],
+        ],
+    ]
+    .map(|matrix| matrix.map(|row| row.map(Groth16FrElement::from_hex_unchecked).to_vec()));
+    QuadraticArithmeticProgram::from_variable_matrices(num_of_public_inputs, &l, &r, &o)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn groth16_proof_verifies_and_wrong_inputs_are_reported() {
+    let qap = vitalik_qap();
+    let (pk, vk) = groth16_setup(&qap);
+    let w = ["0x1", "0x3", "0x23", "0x9", "0x1b", "0x1e"]
+        .map(Groth16FrElement::from_hex_unchecked)
+        .to_vec();
+    let proof = Groth16Prover::prove(&w, &qap, &pk).serialize();
+
+    let result = verify_groth16_proof(&proof, &vk.serialize(), vec!["0x1".to_string()]);
+    assert!(result.verified());
+
+    let result = verify_groth16_proof(&proof, &vk.serialize(), vec!["0x2".to_string()]);
+    assert_eq!(result.error(), Some("Proof rejected".to_string()));
+
+    let result = verify_groth16_proof(
+        &proof,
+        &vk.serialize(),
+        vec!["0x1".to_string(), "0x3".to_string()],
+    );
+    assert_eq!(
+        result.error(),
+        Some("Expected 1 public inputs, got 2".to_string())
+    );
+}
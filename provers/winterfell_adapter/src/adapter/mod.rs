@@ -6,6 +6,7 @@ use winter_math::StarkField;
 
 pub mod air;
 pub mod public_inputs;
+pub mod verifier;
 
 pub struct FeltTranscript {
     hasher: Keccak256,
@@ -0,0 +1,645 @@
+use super::public_inputs::AirAdapterPublicInputs;
+use miden_core::Felt;
+use std::marker::PhantomData;
+use winter_air::{
+    proof::{StarkProof, Table},
+    Air, AuxTraceRandElements, ConstraintCompositionCoefficients, DeepCompositionCoefficients,
+    EvaluationFrame, FieldExtension,
+};
+use winter_crypto::{BatchMerkleProof, ElementHasher, MerkleTree, RandomCoin};
+use winter_fri::{FriVerifier, VerifierChannel};
+use winter_math::{fields::QuadExtension, polynom, FieldElement, ToElements};
+
+/// The reasons a proof generated by the Winterfell prover can be rejected.
+#[derive(Debug)]
+pub enum WinterfellVerificationError {
+    /// The trace shape committed in the proof context differs from the one in the adapter
+    /// public inputs.
+    TraceInfoMismatch,
+    /// The proof uses a field extension the adapter doesn't support.
+    UnsupportedFieldExtension(usize),
+    /// The AIR has more than one auxiliary trace segment.
+    UnsupportedAuxSegments(usize),
+    /// A part of the proof can't be parsed with the parameters of the AIR.
+    ProofDeserialization(String),
+    /// The public coin failed to draw a challenge.
+    RandomCoin,
+    /// The out-of-domain evaluation of the constraints is not consistent with the out-of-domain
+    /// evaluations of the constraint composition columns.
+    InconsistentOodConstraintEvaluations,
+    /// The proof-of-work nonce doesn't have the leading zeros required by the grinding factor.
+    InvalidGrindingNonce,
+    /// The queried trace states don't match the commitment to the trace.
+    TraceQueryDoesNotMatchCommitment,
+    /// The queried constraint evaluations don't match the commitment to the constraints.
+    ConstraintQueryDoesNotMatchCommitment,
+    /// The DEEP composition polynomial is not of low degree.
+    Fri(winter_fri::VerifierError),
+}
+
+fn deserialization_error(error: impl ToString) -> WinterfellVerificationError {
+    WinterfellVerificationError::ProofDeserialization(error.to_string())
+}
+
+/// The elements of a Winterfell proof, parsed in the order the Winterfell prover sends them.
+/// FRI layers are read through `VerifierChannel` by `winter_fri::FriVerifier`.
+struct WinterfellVerifierChannel<E, H>
+where
+    E: FieldElement<BaseField = Felt>,
+    H: ElementHasher<BaseField = Felt>,
+{
+    trace_roots: Vec<H::Digest>,
+    constraint_root: H::Digest,
+    main_trace_query_proof: BatchMerkleProof<H>,
+    main_trace_states: Table<Felt>,
+    aux_trace_queries: Option<(BatchMerkleProof<H>, Table<E>)>,
+    constraint_query_proof: BatchMerkleProof<H>,
+    constraint_evaluations: Table<E>,
+    ood_main_trace_frame: EvaluationFrame<E>,
+    ood_aux_trace_frame: Option<EvaluationFrame<E>>,
+    ood_constraint_evaluations: Vec<E>,
+    fri_roots: Vec<H::Digest>,
+    fri_layer_proofs: Vec<BatchMerkleProof<H>>,
+    fri_layer_queries: Vec<Vec<E>>,
+    fri_remainder: Vec<E>,
+    fri_num_partitions: usize,
+    pow_nonce: u64,
+}
+
+impl<E, H> WinterfellVerifierChannel<E, H>
+where
+    E: FieldElement<BaseField = Felt>,
+    H: ElementHasher<BaseField = Felt>,
+{
+    fn new<A: Air<BaseField = Felt>>(
+        air: &A,
+        proof: StarkProof,
+    ) -> Result<Self, WinterfellVerificationError> {
+        let StarkProof {
+            commitments,
+            mut trace_queries,
+            constraint_queries,
+            ood_frame,
+            fri_proof,
+            pow_nonce,
+            ..
+        } = proof;
+        let trace_layout = air.trace_layout();
+        let num_aux_segments = trace_layout.num_aux_segments();
+        if num_aux_segments > 1 {
+            return Err(WinterfellVerificationError::UnsupportedAuxSegments(
+                num_aux_segments,
+            ));
+        }
+        if trace_queries.len() != num_aux_segments + 1 {
+            return Err(deserialization_error(format!(
+                "expected queries of {} trace segments, found {}",
+                num_aux_segments + 1,
+                trace_queries.len()
+            )));
+        }
+        let lde_domain_size = air.lde_domain_size();
+        let num_queries = air.options().num_queries();
+        let fri_options = air.options().to_fri_options();
+
+        let (trace_roots, constraint_root, fri_roots) = commitments
+            .parse::<H>(
+                num_aux_segments + 1,
+                fri_options.num_fri_layers(lde_domain_size),
+            )
+            .map_err(deserialization_error)?;
+
+        // Parsing the queries also checks that the hash of each queried row is the
+        // corresponding leaf of the batched Merkle proof.
+        let (main_trace_query_proof, main_trace_states) = trace_queries
+            .remove(0)
+            .parse::<H, Felt>(
+                lde_domain_size,
+                num_queries,
+                trace_layout.main_trace_width(),
+            )
+            .map_err(deserialization_error)?;
+        let aux_trace_queries = trace_queries
+            .pop()
+            .map(|queries| {
+                queries.parse::<H, E>(
+                    lde_domain_size,
+                    num_queries,
+                    trace_layout.get_aux_segment_width(0),
+                )
+            })
+            .transpose()
+            .map_err(deserialization_error)?;
+        let (constraint_query_proof, constraint_evaluations) = constraint_queries
+            .parse::<H, E>(
+                lde_domain_size,
+                num_queries,
+                air.context().num_constraint_composition_columns(),
+            )
+            .map_err(deserialization_error)?;
+
+        let (ood_main_trace_frame, ood_aux_trace_frame, ood_constraint_evaluations) = ood_frame
+            .parse(
+                trace_layout.main_trace_width(),
+                trace_layout.aux_trace_width(),
+                air.context().num_constraint_composition_columns(),
+            )
+            .map_err(deserialization_error)?;
+
+        let fri_num_partitions = fri_proof.num_partitions();
+        let fri_remainder = fri_proof.parse_remainder().map_err(deserialization_error)?;
+        let (fri_layer_queries, fri_layer_proofs) = fri_proof
+            .parse_layers::<H, E>(lde_domain_size, fri_options.folding_factor())
+            .map_err(deserialization_error)?;
+
+        Ok(Self {
+            trace_roots,
+            constraint_root,
+            main_trace_query_proof,
+            main_trace_states,
+            aux_trace_queries,
+            constraint_query_proof,
+            constraint_evaluations,
+            ood_main_trace_frame,
+            ood_aux_trace_frame,
+            ood_constraint_evaluations,
+            fri_roots,
+            fri_layer_proofs,
+            fri_layer_queries,
+            fri_remainder,
+            fri_num_partitions,
+            pow_nonce,
+        })
+    }
+
+    /// Checks that the queried trace states and constraint evaluations are the leaves at
+    /// `positions` of the committed Merkle trees.
+    fn verify_queries(&self, positions: &[usize]) -> Result<(), WinterfellVerificationError> {
+        MerkleTree::verify_batch(
+            &self.trace_roots[0],
+            positions,
+            &self.main_trace_query_proof,
+        )
+        .map_err(|_| WinterfellVerificationError::TraceQueryDoesNotMatchCommitment)?;
+        if let Some((aux_trace_query_proof, _)) = &self.aux_trace_queries {
+            MerkleTree::verify_batch(&self.trace_roots[1], positions, aux_trace_query_proof)
+                .map_err(|_| WinterfellVerificationError::TraceQueryDoesNotMatchCommitment)?;
+        }
+        MerkleTree::verify_batch(
+            &self.constraint_root,
+            positions,
+            &self.constraint_query_proof,
+        )
+        .map_err(|_| WinterfellVerificationError::ConstraintQueryDoesNotMatchCommitment)
+    }
+}
+
+impl<E, H> VerifierChannel<E> for WinterfellVerifierChannel<E, H>
+where
+    E: FieldElement<BaseField = Felt>,
+    H: ElementHasher<BaseField = Felt>,
+{
+    type Hasher = H;
+
+    fn read_fri_num_partitions(&self) -> usize {
+        self.fri_num_partitions
+    }
+
+    fn read_fri_layer_commitments(&mut self) -> Vec<H::Digest> {
+        self.fri_roots.drain(..).collect()
+    }
+
+    fn take_next_fri_layer_proof(&mut self) -> BatchMerkleProof<H> {
+        self.fri_layer_proofs.remove(0)
+    }
+
+    fn take_next_fri_layer_queries(&mut self) -> Vec<E> {
+        self.fri_layer_queries.remove(0)
+    }
+
+    fn take_fri_remainder(&mut self) -> Vec<E> {
+        core::mem::take(&mut self.fri_remainder)
+    }
+}
+
+/// A verifier of proofs generated by the Winterfell prover (for example the `StarkProof` of a
+/// Miden `ExecutionProof`) for the AIR `A` wrapped by the `AirAdapter`.
+///
+/// The proof is checked following Winterfell's conventions rather than the ones of the
+/// lambdaworks prover: the public coin `R` is seeded with the proof context and the public
+/// inputs and reseeded with the commitments, the queries are authenticated with batched Merkle
+/// proofs of the hash `H`, the constraints are composed with Winterfell's coefficients and
+/// degree adjustments, and FRI uses the folding factor and remainder of the proof options.
+/// `H` and `R` must be the ones the proof was generated with.
+pub struct WinterfellVerifier<A, H, R> {
+    phantom: PhantomData<(A, H, R)>,
+}
+
+impl<A, H, R> WinterfellVerifier<A, H, R>
+where
+    A: Air<BaseField = Felt>,
+    A::PublicInputs: Clone + ToElements<Felt>,
+    H: ElementHasher<BaseField = Felt>,
+    R: RandomCoin<BaseField = Felt, Hasher = H>,
+{
+    pub fn verify<M: Clone>(
+        proof: StarkProof,
+        pub_inputs: &AirAdapterPublicInputs<A, M>,
+    ) -> Result<(), WinterfellVerificationError> {
+        if proof.get_trace_info() != pub_inputs.trace_info {
+            return Err(WinterfellVerificationError::TraceInfoMismatch);
+        }
+
+        let mut public_coin_seed = proof.context.to_elements();
+        public_coin_seed.append(&mut pub_inputs.winterfell_public_inputs.to_elements());
+        let public_coin = R::new(&public_coin_seed);
+
+        let air = A::new(
+            proof.get_trace_info(),
+            pub_inputs.winterfell_public_inputs.clone(),
+            proof.options().clone(),
+        );
+        match air.options().field_extension() {
+            FieldExtension::None => {
+                let channel = WinterfellVerifierChannel::<Felt, H>::new(&air, proof)?;
+                Self::perform_verification(&air, channel, public_coin)
+            }
+            FieldExtension::Quadratic => {
+                let channel =
+                    WinterfellVerifierChannel::<QuadExtension<Felt>, H>::new(&air, proof)?;
+                Self::perform_verification(&air, channel, public_coin)
+            }
+            FieldExtension::Cubic => Err(WinterfellVerificationError::UnsupportedFieldExtension(3)),
+        }
+    }
+
+    fn perform_verification<E>(
+        air: &A,
+        mut channel: WinterfellVerifierChannel<E, H>,
+        mut public_coin: R,
+    ) -> Result<(), WinterfellVerificationError>
+    where
+        E: FieldElement<BaseField = Felt>,
+    {
+        // Trace commitments
+        public_coin.reseed(channel.trace_roots[0]);
+        let mut aux_trace_rand_elements = AuxTraceRandElements::<E>::new();
+        if let Some(aux_trace_root) = channel.trace_roots.get(1) {
+            let rand_elements = air
+                .get_aux_trace_segment_random_elements(0, &mut public_coin)
+                .map_err(|_| WinterfellVerificationError::RandomCoin)?;
+            aux_trace_rand_elements.add_segment_elements(rand_elements);
+            public_coin.reseed(*aux_trace_root);
+        }
+
+        // Constraint commitment
+        let constraint_coeffs = air
+            .get_constraint_composition_coefficients::<E, R>(&mut public_coin)
+            .map_err(|_| WinterfellVerificationError::RandomCoin)?;
+        public_coin.reseed(channel.constraint_root);
+
+        // Out-of-domain consistency check
+        let z = public_coin
+            .draw::<E>()
+            .map_err(|_| WinterfellVerificationError::RandomCoin)?;
+        let ood_constraint_evaluation = Self::evaluate_constraints(
+            air,
+            constraint_coeffs,
+            &channel.ood_main_trace_frame,
+            &channel.ood_aux_trace_frame,
+            &aux_trace_rand_elements,
+            z,
+        );
+        // The prover sends the auxiliary trace elements after the main ones, for both rows of
+        // the frame.
+        let mut current = channel.ood_main_trace_frame.current().to_vec();
+        let mut next = channel.ood_main_trace_frame.next().to_vec();
+        if let Some(ood_aux_trace_frame) = &channel.ood_aux_trace_frame {
+            current.extend_from_slice(ood_aux_trace_frame.current());
+            next.extend_from_slice(ood_aux_trace_frame.next());
+        }
+        public_coin.reseed(H::hash_elements(&current));
+        public_coin.reseed(H::hash_elements(&next));
+
+        let trace_length = air.trace_length() as u64;
+        let ood_composition_evaluation = channel
+            .ood_constraint_evaluations
+            .iter()
+            .enumerate()
+            .fold(E::ZERO, |result, (i, value)| {
+                result + z.exp((i as u64 * trace_length).into()) * *value
+            });
+        public_coin.reseed(H::hash_elements(&channel.ood_constraint_evaluations));
+        if ood_constraint_evaluation != ood_composition_evaluation {
+            return Err(WinterfellVerificationError::InconsistentOodConstraintEvaluations);
+        }
+
+        // FRI commitments
+        let deep_coefficients = air
+            .get_deep_composition_coefficients::<E, R>(&mut public_coin)
+            .map_err(|_| WinterfellVerificationError::RandomCoin)?;
+        let fri_verifier = FriVerifier::new(
+            &mut channel,
+            &mut public_coin,
+            air.options().to_fri_options(),
+            air.trace_poly_degree(),
+        )
+        .map_err(WinterfellVerificationError::Fri)?;
+
+        // Trace and constraint queries
+        let pow_nonce = channel.pow_nonce;
+        if public_coin.check_leading_zeros(pow_nonce) < air.options().grinding_factor() {
+            return Err(WinterfellVerificationError::InvalidGrindingNonce);
+        }
+        let query_positions = public_coin
+            .draw_integers(
+                air.options().num_queries(),
+                air.lde_domain_size(),
+                pow_nonce,
+            )
+            .map_err(|_| WinterfellVerificationError::RandomCoin)?;
+        channel.verify_queries(&query_positions)?;
+
+        // DEEP composition and FRI
+        let deep_evaluations = Self::deep_composition_evaluations(
+            air,
+            &channel,
+            &query_positions,
+            z,
+            &deep_coefficients,
+        );
+        fri_verifier
+            .verify(&mut channel, &deep_evaluations, &query_positions)
+            .map_err(WinterfellVerificationError::Fri)
+    }
+
+    /// Evaluates the composition of the transition and boundary constraints at `z` from the
+    /// out-of-domain evaluations of the trace.
+    fn evaluate_constraints<E>(
+        air: &A,
+        coefficients: ConstraintCompositionCoefficients<E>,
+        ood_main_trace_frame: &EvaluationFrame<E>,
+        ood_aux_trace_frame: &Option<EvaluationFrame<E>>,
+        aux_trace_rand_elements: &AuxTraceRandElements<E>,
+        z: E,
+    ) -> E
+    where
+        E: FieldElement<BaseField = Felt>,
+    {
+        let transition_constraints = air.get_transition_constraints(&coefficients.transition);
+        let periodic_values: Vec<E> = air
+            .get_periodic_column_polys()
+            .iter()
+            .map(|poly| {
+                let num_cycles = (air.trace_length() / poly.len()) as u64;
+                polynom::eval(poly, z.exp(num_cycles.into()))
+            })
+            .collect();
+
+        let mut main_evaluations = E::zeroed_vector(transition_constraints.num_main_constraints());
+        air.evaluate_transition(
+            ood_main_trace_frame,
+            &periodic_values,
+            &mut main_evaluations,
+        );
+        let mut aux_evaluations = E::zeroed_vector(transition_constraints.num_aux_constraints());
+        if let Some(ood_aux_trace_frame) = ood_aux_trace_frame {
+            air.evaluate_aux_transition(
+                ood_main_trace_frame,
+                ood_aux_trace_frame,
+                &periodic_values,
+                aux_trace_rand_elements,
+                &mut aux_evaluations,
+            );
+        }
+        let mut result =
+            transition_constraints.combine_evaluations::<E>(&main_evaluations, &aux_evaluations, z);
+
+        let boundary_constraints =
+            air.get_boundary_constraints(aux_trace_rand_elements, &coefficients.boundary);
+        for group in boundary_constraints.main_constraints() {
+            result += group.evaluate_at(ood_main_trace_frame.current(), z);
+        }
+        if let Some(ood_aux_trace_frame) = ood_aux_trace_frame {
+            for group in boundary_constraints.aux_constraints() {
+                result += group.evaluate_at(ood_aux_trace_frame.current(), z);
+            }
+        }
+        result
+    }
+
+    /// Evaluates the DEEP composition polynomial at the queried positions of the LDE domain from
+    /// the queried trace states and constraint evaluations.
+    fn deep_composition_evaluations<E>(
+        air: &A,
+        channel: &WinterfellVerifierChannel<E, H>,
+        positions: &[usize],
+        z: E,
+        coefficients: &DeepCompositionCoefficients<E>,
+    ) -> Vec<E>
+    where
+        E: FieldElement<BaseField = Felt>,
+    {
+        let z_next = z * E::from(air.trace_domain_generator());
+        let lde_domain_generator = air.lde_domain_generator();
+        let domain_offset = air.domain_offset();
+        let main_trace_width = channel.main_trace_states.num_columns();
+
+        positions
+            .iter()
+            .enumerate()
+            .map(|(query, position)| {
+                let x =
+                    E::from(lde_domain_generator.exp((*position as u64).into()) * domain_offset);
+
+                // Trace columns: T_i(x) - T_i(z) and T_i(x) - T_i(z * g)
+                let mut trace_terms = (E::ZERO, E::ZERO);
+                let main_row = channel.main_trace_states.get_row(query);
+                let ood_main = &channel.ood_main_trace_frame;
+                for (i, value) in main_row.iter().enumerate() {
+                    let value = E::from(*value);
+                    trace_terms.0 += (value - ood_main.current()[i]) * coefficients.trace[i].0;
+                    trace_terms.1 += (value - ood_main.next()[i]) * coefficients.trace[i].1;
+                }
+                if let (Some((_, aux_trace_states)), Some(ood_aux)) =
+                    (&channel.aux_trace_queries, &channel.ood_aux_trace_frame)
+                {
+                    let aux_row = aux_trace_states.get_row(query);
+                    for (i, value) in aux_row.iter().enumerate() {
+                        let coefficient = coefficients.trace[main_trace_width + i];
+                        trace_terms.0 += (*value - ood_aux.current()[i]) * coefficient.0;
+                        trace_terms.1 += (*value - ood_aux.next()[i]) * coefficient.1;
+                    }
+                }
+                let trace_composition = trace_terms.0 / (x - z) + trace_terms.1 / (x - z_next);
+
+                // Constraint composition columns: H_i(x) - H_i(z)
+                let constraint_row = channel.constraint_evaluations.get_row(query);
+                let constraint_composition = constraint_row
+                    .iter()
+                    .zip(&channel.ood_constraint_evaluations)
+                    .zip(&coefficients.constraints)
+                    .fold(E::ZERO, |result, ((value, ood_value), coefficient)| {
+                        result + (*value - *ood_value) * *coefficient
+                    })
+                    / (x - z);
+
+                (trace_composition + constraint_composition)
+                    * (coefficients.degree.0 + x * coefficients.degree.1)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WinterfellVerificationError, WinterfellVerifier};
+    use crate::{
+        adapter::public_inputs::AirAdapterPublicInputs,
+        examples::{
+            fibonacci_2_terms::{self, FibAir2Terms},
+            miden_vm::ExecutionTraceMetadata,
+        },
+    };
+    use miden_air::{ProcessorAir, ProvingOptions, PublicInputs};
+    use miden_assembly::Assembler;
+    use miden_core::{Felt, StackInputs};
+    use miden_processor::{self as processor, DefaultHost};
+    use winter_air::{proof::StarkProof, FieldExtension, ProofOptions, TraceInfo};
+    use winter_crypto::{
+        hashers::{Blake3_192, Blake3_256},
+        DefaultRandomCoin,
+    };
+    use winter_math::FieldElement;
+    use winter_prover::{Prover, Trace, TraceTable};
+
+    type FibVerifier2Terms =
+        WinterfellVerifier<FibAir2Terms, Blake3_256<Felt>, DefaultRandomCoin<Blake3_256<Felt>>>;
+
+    struct FibProver2Terms {
+        options: ProofOptions,
+    }
+
+    impl Prover for FibProver2Terms {
+        type BaseField = Felt;
+        type Air = FibAir2Terms;
+        type Trace = TraceTable<Felt>;
+        type HashFn = Blake3_256<Felt>;
+        type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+
+        fn get_pub_inputs(&self, trace: &Self::Trace) -> Felt {
+            trace.get(1, trace.length() - 1)
+        }
+
+        fn options(&self) -> &ProofOptions {
+            &self.options
+        }
+    }
+
+    fn fibonacci_proof(field_extension: FieldExtension) -> (StarkProof, Felt) {
+        let winter_trace = fibonacci_2_terms::build_trace(16);
+        let result = winter_trace.get(1, winter_trace.length() - 1);
+        let prover = FibProver2Terms {
+            options: ProofOptions::new(28, 8, 0, field_extension, 4, 7),
+        };
+        (prover.prove(winter_trace).unwrap(), result)
+    }
+
+    fn fibonacci_public_inputs(result: Felt) -> AirAdapterPublicInputs<FibAir2Terms, ()> {
+        AirAdapterPublicInputs::new(result, vec![1, 1], vec![0, 1], TraceInfo::new(2, 8), ())
+    }
+
+    #[test]
+    fn verify_a_winterfell_fibonacci_2_terms_proof() {
+        let (proof, result) = fibonacci_proof(FieldExtension::None);
+
+        assert!(FibVerifier2Terms::verify(proof, &fibonacci_public_inputs(result)).is_ok());
+    }
+
+    #[test]
+    fn verify_a_winterfell_fibonacci_2_terms_proof_over_the_quadratic_extension() {
+        let (proof, result) = fibonacci_proof(FieldExtension::Quadratic);
+
+        assert!(FibVerifier2Terms::verify(proof, &fibonacci_public_inputs(result)).is_ok());
+    }
+
+    #[test]
+    fn a_winterfell_fibonacci_2_terms_proof_with_a_wrong_result_is_rejected() {
+        let (proof, result) = fibonacci_proof(FieldExtension::None);
+
+        assert!(matches!(
+            FibVerifier2Terms::verify(proof, &fibonacci_public_inputs(result + Felt::ONE)),
+            Err(WinterfellVerificationError::InconsistentOodConstraintEvaluations)
+        ));
+    }
+
+    #[test]
+    fn a_tampered_winterfell_fibonacci_2_terms_proof_is_rejected() {
+        let (mut proof, result) = fibonacci_proof(FieldExtension::None);
+        // The nonce is mixed into the seed of the query positions, which are then not the ones
+        // the prover opened.
+        proof.pow_nonce += 1;
+
+        assert!(FibVerifier2Terms::verify(proof, &fibonacci_public_inputs(result)).is_err());
+    }
+
+    #[test]
+    fn a_winterfell_proof_with_a_different_trace_shape_is_rejected() {
+        let (proof, result) = fibonacci_proof(FieldExtension::None);
+        let pub_inputs =
+            AirAdapterPublicInputs::new(result, vec![1, 1], vec![0, 1], TraceInfo::new(2, 16), ());
+
+        assert!(matches!(
+            FibVerifier2Terms::verify(proof, &pub_inputs),
+            Err(WinterfellVerificationError::TraceInfoMismatch)
+        ));
+    }
+
+    #[test]
+    fn verify_a_miden_fibonacci_execution_proof() {
+        let program = Assembler::default()
+            .compile(
+                "begin
+                    repeat.15
+                        swap dup.1 add
+                    end
+                end",
+            )
+            .unwrap();
+        let stack_inputs = StackInputs::try_from_values([0, 1]).unwrap();
+
+        let winter_trace = processor::execute(
+            &program,
+            stack_inputs.clone(),
+            DefaultHost::default(),
+            *ProvingOptions::default().execution_options(),
+        )
+        .unwrap();
+        let (stack_outputs, proof) = miden_prover::prove(
+            &program,
+            stack_inputs.clone(),
+            DefaultHost::default(),
+            ProvingOptions::default(),
+        )
+        .unwrap();
+
+        let pub_inputs = AirAdapterPublicInputs::<ProcessorAir, ExecutionTraceMetadata>::new(
+            PublicInputs::new(
+                winter_trace.program_info().clone(),
+                stack_inputs,
+                stack_outputs,
+            ),
+            vec![2; 182],
+            vec![0, 1],
+            winter_trace.get_info(),
+            winter_trace.clone().into(),
+        );
+
+        assert!(WinterfellVerifier::<
+            ProcessorAir,
+            Blake3_192<Felt>,
+            DefaultRandomCoin<Blake3_192<Felt>>,
+        >::verify(proof.stark_proof().clone(), &pub_inputs)
+        .is_ok());
+    }
+}
@@ -3,13 +3,19 @@ use miden_air::ProcessorAir;
 use miden_core::{Felt, ProgramInfo, StackOutputs};
 use miden_processor::{AuxTraceHints, ExecutionTrace, TraceLenSummary};
 use winter_air::TraceLayout;
-use winter_prover::ColMatrix;
+use winter_prover::{ColMatrix, TraceTable};
 
 use crate::adapter::air::{AirAdapter, FromColumns};
 
 pub type MidenVMQuadFeltAir =
     AirAdapter<ProcessorAir, ExecutionTrace, Felt, QuadFelt, ExecutionTraceMetadata>;
 
+/// The Miden VM AIR as seen by a verifier, which only knows the public inputs
+/// of the execution. It never rebuilds the auxiliary trace, so it takes a plain
+/// `TraceTable` instead of an `ExecutionTrace` and can't be used for proving.
+pub type MidenVMQuadFeltVerifierAir =
+    AirAdapter<ProcessorAir, TraceTable<Felt>, Felt, QuadFelt, ()>;
+
 #[derive(Clone)]
 pub struct ExecutionTraceMetadata {
     meta: Vec<u8>,
@@ -51,7 +57,7 @@ impl FromColumns<Felt, ExecutionTraceMetadata> for ExecutionTrace {
 mod tests {
     use crate::adapter::public_inputs::AirAdapterPublicInputs;
     use crate::adapter::QuadFeltTranscript;
-    use crate::examples::miden_vm::MidenVMQuadFeltAir;
+    use crate::examples::miden_vm::{MidenVMQuadFeltAir, MidenVMQuadFeltVerifierAir};
     use miden_air::{ProvingOptions, PublicInputs};
     use miden_assembly::Assembler;
     use miden_core::{Felt, StackInputs};
@@ -185,4 +191,59 @@ mod tests {
             QuadFeltTranscript::new(&[]),
         ));
     }
+
+    #[test]
+    fn verify_miden_proof_with_public_inputs_only() {
+        let program = Assembler::default()
+            .compile("begin push.3 push.5 add end")
+            .unwrap();
+        let mut lambda_proof_options = ProofOptions::default_test_options();
+        lambda_proof_options.blowup_factor = 8;
+
+        let winter_trace = processor::execute(
+            &program,
+            StackInputs::default(),
+            DefaultHost::default(),
+            *ProvingOptions::default().execution_options(),
+        )
+        .unwrap();
+        let pub_inputs = PublicInputs::new(
+            winter_trace.program_info().clone(),
+            StackInputs::default(),
+            winter_trace.stack_outputs().clone(),
+        );
+
+        let prover_pub_inputs = AirAdapterPublicInputs {
+            winterfell_public_inputs: pub_inputs.clone(),
+            transition_exemptions: vec![2; 182],
+            transition_offsets: vec![0, 1],
+            trace_info: winter_trace.get_info(),
+            metadata: winter_trace.clone().into(),
+        };
+        let verifier_pub_inputs = AirAdapterPublicInputs {
+            winterfell_public_inputs: pub_inputs,
+            transition_exemptions: vec![2; 182],
+            transition_offsets: vec![0, 1],
+            trace_info: winter_trace.get_info(),
+            metadata: (),
+        };
+
+        let trace =
+            MidenVMQuadFeltAir::convert_winterfell_trace_table(winter_trace.main_segment().clone());
+
+        let proof = Prover::<MidenVMQuadFeltAir>::prove(
+            &trace,
+            &prover_pub_inputs,
+            &lambda_proof_options,
+            QuadFeltTranscript::new(&[]),
+        )
+        .unwrap();
+
+        assert!(Verifier::<MidenVMQuadFeltVerifierAir>::verify(
+            &proof,
+            &verifier_pub_inputs,
+            &lambda_proof_options,
+            QuadFeltTranscript::new(&[]),
+        ));
+    }
 }